@@ -13,6 +13,7 @@
 use core::mem;
 use liquid_macro::seq;
 use liquid_prelude::{
+    collections::BTreeMap,
     string::String,
     vec::{from_elem, Vec},
 };
@@ -368,6 +369,12 @@ impl MediateEncode for String {
     }
 }
 
+impl MediateEncode for str {
+    fn encode(&self) -> Mediate {
+        Mediate::Prefixed(encode_bytes(self.as_bytes()))
+    }
+}
+
 impl MediateDecode for String {
     fn decode(slices: &[Word], offset: usize) -> Result<DecodeResult<Self>, Error> {
         let offset_slice = peek(slices, offset)?;
@@ -377,8 +384,13 @@ impl MediateDecode for String {
         let len = as_u32(len_slice)? as usize;
 
         let taken = take(slices, len_offset + 1, len)?;
+        #[cfg(feature = "min-size")]
+        let value = String::from_utf8(taken.bytes)
+            .map_err(|_| Error::from("Invalid UTF-8 string"))?;
+        #[cfg(not(feature = "min-size"))]
+        let value = String::from_utf8_lossy(taken.bytes.as_slice()).into_owned();
         Ok(DecodeResult {
-            value: String::from_utf8_lossy(taken.bytes.as_slice()).into_owned(),
+            value,
             new_offset: offset + 1,
         })
     }
@@ -688,14 +700,7 @@ impl TypeInfo for i256 {}
 
 impl MediateEncode for i256 {
     fn encode(&self) -> Mediate {
-        let be = self.0.to_signed_bytes_be();
-        let mut buf = match self.0.sign() {
-            num_bigint::Sign::Plus | num_bigint::Sign::NoSign => [0x00; WORD_SIZE],
-            _ => [0xff; WORD_SIZE],
-        };
-
-        buf[(WORD_SIZE - be.len())..].copy_from_slice(&be);
-        Mediate::Raw([buf].to_vec())
+        Mediate::Raw([self.to_be_bytes()].to_vec())
     }
 }
 
@@ -725,11 +730,7 @@ impl TypeInfo for u256 {}
 
 impl MediateEncode for u256 {
     fn encode(&self) -> Mediate {
-        let be = self.0.to_bytes_be();
-        let mut buf: Word = [0x00; WORD_SIZE];
-
-        buf[(WORD_SIZE - be.len())..].copy_from_slice(&be);
-        Mediate::Raw([buf].to_vec())
+        Mediate::Raw([self.to_be_bytes()].to_vec())
     }
 }
 
@@ -823,6 +824,14 @@ impl MediateEncode for Bytes {
     }
 }
 
+/// Unlike `Vec<u8>`, which is `uint8[]`, a slice of bytes is encoded as
+/// `bytes`, the same as `Bytes`.
+impl MediateEncode for [u8] {
+    fn encode(&self) -> Mediate {
+        Mediate::Prefixed(encode_bytes(self))
+    }
+}
+
 impl MediateDecode for Bytes {
     fn decode(slices: &[Word], offset: usize) -> Result<DecodeResult<Self>, Error> {
         let offset_slice = peek(slices, offset)?;
@@ -837,3 +846,245 @@ impl MediateDecode for Bytes {
         })
     }
 }
+
+/// Splits the words of a tuple into the part where its fields start and the
+/// offset of the first field, following the head-tail layout.
+pub fn tuple_tail(
+    slices: &[Word],
+    offset: usize,
+    is_dynamic: bool,
+) -> Result<(&[Word], usize), Error> {
+    if is_dynamic {
+        let tail_offset = as_u32(peek(slices, offset)?)? as usize / WORD_SIZE;
+        match slices.get(tail_offset..) {
+            Some(tail) => Ok((tail, 0)),
+            None => Err("Invalid tuple offset".into()),
+        }
+    } else {
+        Ok((slices, offset))
+    }
+}
+
+// `Option<T>` is encoded as a tagged tuple `(bool, T)`, in which the slot of
+// an absent value is filled with the default value of `T`.
+impl<T> TypeInfo for Option<T>
+where
+    T: TypeInfo,
+{
+    #[inline(always)]
+    fn is_dynamic() -> bool {
+        <T as TypeInfo>::is_dynamic()
+    }
+
+    #[inline]
+    fn size_hint() -> u32 {
+        if Self::is_dynamic() {
+            unreachable!();
+        } else {
+            <bool as TypeInfo>::size_hint() + <T as TypeInfo>::size_hint()
+        }
+    }
+}
+
+impl<T> MediateEncode for Option<T>
+where
+    T: MediateEncode + TypeInfo + Default,
+{
+    fn encode(&self) -> Mediate {
+        let mut mediates = Vec::with_capacity(2);
+        mediates.push(MediateEncode::encode(&self.is_some()));
+        match self {
+            Some(value) => mediates.push(MediateEncode::encode(value)),
+            None => mediates.push(MediateEncode::encode(&T::default())),
+        }
+
+        if Self::is_dynamic() {
+            Mediate::PrefixedTuple(mediates)
+        } else {
+            Mediate::RawTuple(mediates)
+        }
+    }
+}
+
+impl<T> MediateDecode for Option<T>
+where
+    T: MediateDecode + TypeInfo,
+{
+    fn decode(slices: &[Word], offset: usize) -> Result<DecodeResult<Self>, Error> {
+        let is_dynamic = Self::is_dynamic();
+        let (tail, new_offset) = tuple_tail(slices, offset, is_dynamic)?;
+
+        let is_some = <bool as MediateDecode>::decode(tail, new_offset)?;
+        let value = <T as MediateDecode>::decode(tail, is_some.new_offset)?;
+        Ok(DecodeResult {
+            value: if is_some.value {
+                Some(value.value)
+            } else {
+                None
+            },
+            new_offset: if is_dynamic {
+                offset + 1
+            } else {
+                value.new_offset
+            },
+        })
+    }
+}
+
+// `Result<T, E>` is encoded as a tagged tuple `(bool, T, E)`, in which the
+// flag tells whether the result is `Ok`, and the slot of the other variant is
+// filled with its default value.
+impl<T, E> TypeInfo for Result<T, E>
+where
+    T: TypeInfo,
+    E: TypeInfo,
+{
+    #[inline(always)]
+    fn is_dynamic() -> bool {
+        <T as TypeInfo>::is_dynamic() || <E as TypeInfo>::is_dynamic()
+    }
+
+    #[inline]
+    fn size_hint() -> u32 {
+        if Self::is_dynamic() {
+            unreachable!();
+        } else {
+            <bool as TypeInfo>::size_hint()
+                + <T as TypeInfo>::size_hint()
+                + <E as TypeInfo>::size_hint()
+        }
+    }
+}
+
+impl<T, E> MediateEncode for Result<T, E>
+where
+    T: MediateEncode + TypeInfo + Default,
+    E: MediateEncode + TypeInfo + Default,
+{
+    fn encode(&self) -> Mediate {
+        let mut mediates = Vec::with_capacity(3);
+        mediates.push(MediateEncode::encode(&self.is_ok()));
+        match self {
+            Ok(value) => {
+                mediates.push(MediateEncode::encode(value));
+                mediates.push(MediateEncode::encode(&E::default()));
+            }
+            Err(err) => {
+                mediates.push(MediateEncode::encode(&T::default()));
+                mediates.push(MediateEncode::encode(err));
+            }
+        }
+
+        if Self::is_dynamic() {
+            Mediate::PrefixedTuple(mediates)
+        } else {
+            Mediate::RawTuple(mediates)
+        }
+    }
+}
+
+impl<T, E> MediateDecode for Result<T, E>
+where
+    T: MediateDecode + TypeInfo,
+    E: MediateDecode + TypeInfo,
+{
+    fn decode(slices: &[Word], offset: usize) -> Result<DecodeResult<Self>, Error> {
+        let is_dynamic = Self::is_dynamic();
+        let (tail, new_offset) = tuple_tail(slices, offset, is_dynamic)?;
+
+        let is_ok = <bool as MediateDecode>::decode(tail, new_offset)?;
+        let ok = <T as MediateDecode>::decode(tail, is_ok.new_offset)?;
+        let err = <E as MediateDecode>::decode(tail, ok.new_offset)?;
+        Ok(DecodeResult {
+            value: if is_ok.value {
+                Ok(ok.value)
+            } else {
+                Err(err.value)
+            },
+            new_offset: if is_dynamic {
+                offset + 1
+            } else {
+                err.new_offset
+            },
+        })
+    }
+}
+
+// `BTreeMap<K, V>` is encoded as a dynamic array of `(K, V)` tuples in
+// ascending order of keys.
+impl<K, V> TypeInfo for BTreeMap<K, V> {
+    #[inline(always)]
+    fn is_dynamic() -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn size_hint() -> u32 {
+        unreachable!()
+    }
+}
+
+impl<K, V> MediateEncode for BTreeMap<K, V>
+where
+    K: MediateEncode + TypeInfo,
+    V: MediateEncode + TypeInfo,
+{
+    fn encode(&self) -> Mediate {
+        let is_dynamic = K::is_dynamic() || V::is_dynamic();
+        let mediates = self
+            .iter()
+            .map(|(key, value)| {
+                let mut pair = Vec::with_capacity(2);
+                pair.push(MediateEncode::encode(key));
+                pair.push(MediateEncode::encode(value));
+                if is_dynamic {
+                    Mediate::PrefixedTuple(pair)
+                } else {
+                    Mediate::RawTuple(pair)
+                }
+            })
+            .collect();
+        Mediate::PrefixedArrayWithLength(mediates)
+    }
+}
+
+impl<K, V> MediateDecode for BTreeMap<K, V>
+where
+    K: MediateDecode + TypeInfo + Ord,
+    V: MediateDecode + TypeInfo,
+{
+    fn decode(slices: &[Word], offset: usize) -> Result<DecodeResult<Self>, Error> {
+        let offset_slice = peek(slices, offset)?;
+        let len_offset = (as_u32(offset_slice)? / (WORD_SIZE as u32)) as usize;
+        let len_slice = peek(slices, len_offset)?;
+        let len = as_u32(len_slice)? as usize;
+
+        let tail = match slices.get(len_offset + 1..) {
+            Some(tail) => tail,
+            None => return Err("Invalid map offset".into()),
+        };
+        let is_dynamic = K::is_dynamic() || V::is_dynamic();
+        let mut ret = BTreeMap::new();
+        let mut new_offset = 0;
+
+        for _ in 0..len {
+            let (pair, pair_offset) = tuple_tail(tail, new_offset, is_dynamic)?;
+            let key = <K as MediateDecode>::decode(pair, pair_offset)?;
+            let value = <V as MediateDecode>::decode(pair, key.new_offset)?;
+            new_offset = if is_dynamic {
+                new_offset + 1
+            } else {
+                value.new_offset
+            };
+
+            if ret.insert(key.value, value.value).is_some() {
+                return Err("Duplicate keys in map".into());
+            }
+        }
+
+        Ok(DecodeResult {
+            value: ret,
+            new_offset: offset + 1,
+        })
+    }
+}
@@ -16,8 +16,8 @@
 mod codec;
 
 pub use codec::{
-    as_u32, encode_head_tail, peek, Codec, Decode, DecodeResult, Encode, Input, Mediate,
-    MediateDecode, MediateEncode, Output, TypeInfo, Word, WORD_SIZE,
+    as_u32, encode_head_tail, peek, tuple_tail, Codec, Decode, DecodeResult, Encode,
+    Input, Mediate, MediateDecode, MediateEncode, Output, TypeInfo, Word, WORD_SIZE,
 };
 
 #[cfg(test)]
@@ -12,6 +12,7 @@
 
 use crate::codec::{Decode, Encode};
 use hex_literal::hex;
+use liquid_prelude::collections::BTreeMap;
 use liquid_primitives::types::*;
 
 macro_rules! test_encode_decode {
@@ -506,3 +507,136 @@ test_encode_decode! {
     0000000000000000000000000000000000000000000000000000000000000002
     1234000000000000000000000000000000000000000000000000000000000000"
 }
+
+test_encode_decode! {
+    name: option_some,
+    type: (Option<u32>,),
+    value: (Some(7),),
+    data: "0000000000000000000000000000000000000000000000000000000000000001
+    0000000000000000000000000000000000000000000000000000000000000007"
+}
+
+test_encode_decode! {
+    name: option_none,
+    type: (Option<u32>,),
+    value: (None,),
+    data: "0000000000000000000000000000000000000000000000000000000000000000
+    0000000000000000000000000000000000000000000000000000000000000000"
+}
+
+test_encode_decode! {
+    name: option_string_bool,
+    type: (Option<String>, bool),
+    value: (Some(String::from("foo")), true),
+    data: "0000000000000000000000000000000000000000000000000000000000000040
+    0000000000000000000000000000000000000000000000000000000000000001
+    0000000000000000000000000000000000000000000000000000000000000001
+    0000000000000000000000000000000000000000000000000000000000000040
+    0000000000000000000000000000000000000000000000000000000000000003
+    666f6f0000000000000000000000000000000000000000000000000000000000"
+}
+
+test_encode_decode! {
+    name: result_ok,
+    type: (Result<u32, String>,),
+    value: (Ok(5),),
+    data: "0000000000000000000000000000000000000000000000000000000000000020
+    0000000000000000000000000000000000000000000000000000000000000001
+    0000000000000000000000000000000000000000000000000000000000000005
+    0000000000000000000000000000000000000000000000000000000000000060
+    0000000000000000000000000000000000000000000000000000000000000000"
+}
+
+test_encode_decode! {
+    name: result_err,
+    type: (Result<u32, String>,),
+    value: (Err(String::from("bar")),),
+    data: "0000000000000000000000000000000000000000000000000000000000000020
+    0000000000000000000000000000000000000000000000000000000000000000
+    0000000000000000000000000000000000000000000000000000000000000000
+    0000000000000000000000000000000000000000000000000000000000000060
+    0000000000000000000000000000000000000000000000000000000000000003
+    6261720000000000000000000000000000000000000000000000000000000000"
+}
+
+test_encode_decode! {
+    name: vec_of_options,
+    type: (Vec<Option<bool>>,),
+    value: (vec![Some(true), None],),
+    data: "0000000000000000000000000000000000000000000000000000000000000020
+    0000000000000000000000000000000000000000000000000000000000000002
+    0000000000000000000000000000000000000000000000000000000000000001
+    0000000000000000000000000000000000000000000000000000000000000001
+    0000000000000000000000000000000000000000000000000000000000000000
+    0000000000000000000000000000000000000000000000000000000000000000"
+}
+
+test_decode_fail! {
+    name: option_invalid_flag,
+    type: (Option<u32>,),
+    data: "0000000000000000000000000000000000000000000000000000000000000002
+    0000000000000000000000000000000000000000000000000000000000000007"
+}
+
+test_encode_decode! {
+    name: map_of_static_pairs,
+    type: (BTreeMap<u8, bool>,),
+    value: ([(2, false), (1, true)].iter().cloned().collect(),),
+    data: "0000000000000000000000000000000000000000000000000000000000000020
+    0000000000000000000000000000000000000000000000000000000000000002
+    0000000000000000000000000000000000000000000000000000000000000001
+    0000000000000000000000000000000000000000000000000000000000000001
+    0000000000000000000000000000000000000000000000000000000000000002
+    0000000000000000000000000000000000000000000000000000000000000000"
+}
+
+test_encode_decode! {
+    name: map_of_dynamic_pairs,
+    type: (BTreeMap<u8, String>,),
+    value: ([(1, String::from("foo"))].iter().cloned().collect(),),
+    data: "0000000000000000000000000000000000000000000000000000000000000020
+    0000000000000000000000000000000000000000000000000000000000000001
+    0000000000000000000000000000000000000000000000000000000000000020
+    0000000000000000000000000000000000000000000000000000000000000001
+    0000000000000000000000000000000000000000000000000000000000000040
+    0000000000000000000000000000000000000000000000000000000000000003
+    666f6f0000000000000000000000000000000000000000000000000000000000"
+}
+
+test_decode_fail! {
+    name: map_with_duplicate_keys,
+    type: (BTreeMap<u8, bool>,),
+    data: "0000000000000000000000000000000000000000000000000000000000000020
+    0000000000000000000000000000000000000000000000000000000000000002
+    0000000000000000000000000000000000000000000000000000000000000001
+    0000000000000000000000000000000000000000000000000000000000000001
+    0000000000000000000000000000000000000000000000000000000000000001
+    0000000000000000000000000000000000000000000000000000000000000000"
+}
+
+#[cfg(feature = "min-size")]
+test_decode_fail! {
+    name: invalid_utf8_string,
+    type: (String,),
+    data: "0000000000000000000000000000000000000000000000000000000000000020
+    0000000000000000000000000000000000000000000000000000000000000002
+    c328000000000000000000000000000000000000000000000000000000000000"
+}
+
+#[test]
+fn encode_borrowed_strings_and_bytes() {
+    use crate::codec::{encode_head_tail, MediateEncode};
+
+    let string = String::from("foo");
+    let bytes = Bytes::from(&[0x12, 0x34][..]);
+    assert_eq!(
+        encode_head_tail(&[
+            MediateEncode::encode(string.as_str()),
+            MediateEncode::encode(bytes.as_slice())
+        ]),
+        encode_head_tail(&[
+            MediateEncode::encode(&string),
+            MediateEncode::encode(&bytes)
+        ]),
+    );
+}
@@ -23,6 +23,11 @@ pub struct ContractAbi {
     pub name: String,
     pub data: Vec<ParamAbi>,
     pub rights: Vec<RightAbi>,
+    pub signers: Vec<String>,
+    #[serde(skip_serializing_if = "::std::vec::Vec::is_empty")]
+    pub observers: Vec<String>,
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub key: Option<String>,
 }
 
 #[derive(Serialize)]
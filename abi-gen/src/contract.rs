@@ -15,11 +15,16 @@ use crate::traits::*;
 use cfg_if::cfg_if;
 use derive_more::From;
 use serde::Serialize;
+use std::collections::BTreeMap;
 
 pub struct ContractAbi {
     pub constructor_abi: ConstructorAbi,
     pub external_fn_abis: Vec<ExternalFnAbi>,
     pub event_abis: Vec<EventAbi>,
+    pub error_abis: Vec<ErrorAbi>,
+    pub storage_layout: StorageLayout,
+    pub userdoc: UserDoc,
+    pub devdoc: DevDoc,
 }
 
 cfg_if! {
@@ -73,6 +78,8 @@ cfg_if! {
             stateMutability: String,
             #[serde(rename = "type")]
             ty: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            selector: Option<String>,
         }
 
         impl ExternalFnAbi {
@@ -90,6 +97,7 @@ cfg_if! {
                         payable: false,
                         stateMutability: state_mutability,
                         ty: "function".to_owned(),
+                        selector: None,
                     },
                 }
             }
@@ -154,6 +162,8 @@ cfg_if! {
             outputs: Vec<ParamAbi>,
             #[serde(rename = "type")]
             ty: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            selector: Option<String>,
         }
 
         impl ExternalFnAbi {
@@ -168,6 +178,7 @@ cfg_if! {
                         name,
                         outputs: Vec::new(),
                         ty: "function".to_owned(),
+                        selector: None,
                     },
                 }
             }
@@ -229,6 +240,12 @@ impl ExternalFnAbiBuilder {
         }
     }
 
+    /// Records the selector pinned via `#[liquid(selector = "...")]`, as it
+    /// can't be derived from the signature of the function.
+    pub fn selector(&mut self, selector: String) {
+        self.abi.selector = Some(selector);
+    }
+
     pub fn done(self) -> ExternalFnAbi {
         self.abi
     }
@@ -276,3 +293,158 @@ impl EventAbiBuilder {
         self.abi
     }
 }
+
+/// An entry of the error registry, which maps the code embedded in revert
+/// data back to where the error is raised or defined.
+#[derive(Serialize)]
+pub struct ErrorAbi {
+    pub code: u32,
+    /// `Enum::Variant` for variants of `#[liquid(error)]` enums, empty for
+    /// `require!` sites.
+    #[serde(skip_serializing_if = "::std::string::String::is_empty")]
+    pub name: String,
+    pub message: String,
+    pub file: String,
+    pub line: u32,
+}
+
+/// The layout of the contract storage. Committing it as `storage_layout.json`
+/// in the root of the contract crate makes the compiler reject later changes
+/// of the layout which are incompatible with the stored data, unless the
+/// storage version is increased.
+#[derive(Serialize)]
+pub struct StorageLayout {
+    /// Specified by `#[liquid(storage_version = N)]`, 0 by default.
+    pub version: u32,
+    pub fields: Vec<FieldLayout>,
+}
+
+#[derive(Serialize)]
+pub struct FieldLayout {
+    pub name: String,
+    /// Prefix of the storage keys of the field.
+    pub key: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// Documentation of the contract for its users, made of the first paragraph
+/// of the doc comments on the contract module, its methods and its events.
+/// Methods and events are keyed by their names.
+#[derive(Serialize)]
+pub struct UserDoc {
+    kind: String,
+    #[serde(skip_serializing_if = "::std::string::String::is_empty")]
+    notice: String,
+    methods: BTreeMap<String, UserItemDoc>,
+    events: BTreeMap<String, UserItemDoc>,
+}
+
+#[derive(Serialize)]
+pub struct UserItemDoc {
+    notice: String,
+}
+
+/// Documentation of the contract for its developers, made of the remaining
+/// paragraphs of the doc comments, along with those on the parameters of
+/// methods and the fields of events.
+#[derive(Serialize)]
+pub struct DevDoc {
+    kind: String,
+    #[serde(skip_serializing_if = "::std::string::String::is_empty")]
+    details: String,
+    methods: BTreeMap<String, DevItemDoc>,
+    events: BTreeMap<String, DevItemDoc>,
+}
+
+#[derive(Serialize)]
+pub struct DevItemDoc {
+    #[serde(skip_serializing_if = "::std::string::String::is_empty")]
+    details: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    params: BTreeMap<String, String>,
+}
+
+pub struct DocsBuilder {
+    userdoc: UserDoc,
+    devdoc: DevDoc,
+}
+
+impl DocsBuilder {
+    pub fn new(notice: String, details: String) -> Self {
+        Self {
+            userdoc: UserDoc {
+                kind: "user".to_owned(),
+                notice,
+                methods: BTreeMap::new(),
+                events: BTreeMap::new(),
+            },
+            devdoc: DevDoc {
+                kind: "dev".to_owned(),
+                details,
+                methods: BTreeMap::new(),
+                events: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// Records the docs of a method, the constructor is named `constructor`.
+    pub fn method(
+        &mut self,
+        name: String,
+        notice: String,
+        details: String,
+        params: Vec<(String, String)>,
+    ) {
+        Self::record(
+            &mut self.userdoc.methods,
+            &mut self.devdoc.methods,
+            name,
+            notice,
+            details,
+            params,
+        );
+    }
+
+    /// Records the docs of an event, whose parameters are its fields.
+    pub fn event(
+        &mut self,
+        name: String,
+        notice: String,
+        details: String,
+        params: Vec<(String, String)>,
+    ) {
+        Self::record(
+            &mut self.userdoc.events,
+            &mut self.devdoc.events,
+            name,
+            notice,
+            details,
+            params,
+        );
+    }
+
+    fn record(
+        user_items: &mut BTreeMap<String, UserItemDoc>,
+        dev_items: &mut BTreeMap<String, DevItemDoc>,
+        name: String,
+        notice: String,
+        details: String,
+        params: Vec<(String, String)>,
+    ) {
+        let params = params
+            .into_iter()
+            .filter(|(_, doc)| !doc.is_empty())
+            .collect::<BTreeMap<_, _>>();
+        if !details.is_empty() || !params.is_empty() {
+            dev_items.insert(name.clone(), DevItemDoc { details, params });
+        }
+        if !notice.is_empty() {
+            user_items.insert(name, UserItemDoc { notice });
+        }
+    }
+
+    pub fn done(self) -> (UserDoc, DevDoc) {
+        (self.userdoc, self.devdoc)
+    }
+}
@@ -13,7 +13,7 @@
 use crate::*;
 use cfg_if::cfg_if;
 use liquid_macro::seq;
-use liquid_prelude::{string::String, vec::Vec};
+use liquid_prelude::{collections::BTreeMap, string::String, vec::Vec};
 #[cfg(feature = "contract")]
 use liquid_primitives::__Liquid_Getter_Index_Placeholder;
 use liquid_primitives::types::*;
@@ -157,6 +157,41 @@ where
     }
 }
 
+impl<K, V> GenerateParamAbi for BTreeMap<K, V>
+where
+    K: GenerateParamAbi,
+    V: GenerateParamAbi,
+{
+    fn generate_ty_name() -> String {
+        String::from("tuple[]")
+    }
+
+    fn generate_param_abi(name: String) -> ParamAbi {
+        CompositeAbi {
+            trivial: TrivialAbi::new(Self::generate_ty_name(), name),
+            components: vec![
+                <K as GenerateParamAbi>::generate_param_abi("key".into()),
+                <V as GenerateParamAbi>::generate_param_abi("value".into()),
+            ],
+        }
+        .into()
+    }
+}
+
+impl<K, V> GenerateOutputs for BTreeMap<K, V>
+where
+    K: GenerateParamAbi,
+    V: GenerateParamAbi,
+{
+    fn generate_outputs<B>(builder: &mut B)
+    where
+        B: FnOutputBuilder,
+    {
+        let param_abi = <Self as GenerateParamAbi>::generate_param_abi("".into());
+        builder.output(param_abi);
+    }
+}
+
 #[cfg(feature = "contract")]
 impl GenerateParamAbi for __Liquid_Getter_Index_Placeholder {
     fn generate_ty_name() -> String {
@@ -312,5 +347,73 @@ cfg_if! {
         seq!(N in 0..16 {
             impl_generate_param_abi_for_tuple!(#(T#N,)*);
         });
+    } else {
+        impl<T> GenerateParamAbi for Option<T>
+        where
+            T: GenerateParamAbi
+        {
+            fn generate_ty_name() -> String {
+                String::from("tuple")
+            }
+
+            fn generate_param_abi(name: String) -> ParamAbi {
+                CompositeAbi {
+                    trivial: TrivialAbi::new(Self::generate_ty_name(), name),
+                    components: vec![
+                        <bool as GenerateParamAbi>::generate_param_abi("isSome".into()),
+                        <T as GenerateParamAbi>::generate_param_abi("value".into()),
+                    ],
+                }
+                .into()
+            }
+        }
+
+        impl<T, E> GenerateParamAbi for Result<T, E>
+        where
+            T: GenerateParamAbi,
+            E: GenerateParamAbi,
+        {
+            fn generate_ty_name() -> String {
+                String::from("tuple")
+            }
+
+            fn generate_param_abi(name: String) -> ParamAbi {
+                CompositeAbi {
+                    trivial: TrivialAbi::new(Self::generate_ty_name(), name),
+                    components: vec![
+                        <bool as GenerateParamAbi>::generate_param_abi("isOk".into()),
+                        <T as GenerateParamAbi>::generate_param_abi("ok".into()),
+                        <E as GenerateParamAbi>::generate_param_abi("err".into()),
+                    ],
+                }
+                .into()
+            }
+        }
+    }
+}
+
+impl<T> GenerateOutputs for Option<T>
+where
+    Self: GenerateParamAbi,
+{
+    fn generate_outputs<B>(builder: &mut B)
+    where
+        B: FnOutputBuilder,
+    {
+        let param_abi = <Self as GenerateParamAbi>::generate_param_abi("".into());
+        builder.output(param_abi);
+    }
+}
+
+impl<T, E> GenerateOutputs for Result<T, E>
+where
+    Self: GenerateParamAbi,
+{
+    fn generate_outputs<B>(builder: &mut B)
+    where
+        B: FnOutputBuilder,
+    {
+        let param_abi = <Self as GenerateParamAbi>::generate_param_abi("".into());
+        builder.output(param_abi);
     }
 }
@@ -25,5 +25,21 @@ fn main() -> Result<(), std::io::Error> {
     let contents = format!("[{}]", contents);
     std::fs::create_dir("target").ok();
     std::fs::write("target/erc20.abi", contents)?;
+    std::fs::write(
+        "target/erc20.errors.json",
+        serde_json::to_string(&contract_abi.error_abis)?,
+    )?;
+    std::fs::write(
+        "target/erc20.storage.json",
+        serde_json::to_string(&contract_abi.storage_layout)?,
+    )?;
+    std::fs::write(
+        "target/erc20.userdoc.json",
+        serde_json::to_string(&contract_abi.userdoc)?,
+    )?;
+    std::fs::write(
+        "target/erc20.devdoc.json",
+        serde_json::to_string(&contract_abi.devdoc)?,
+    )?;
     Ok(())
 }
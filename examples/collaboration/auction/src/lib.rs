@@ -52,9 +52,9 @@ mod auction {
     #[liquid(rights)]
     impl AuctionInvitation {
         #[liquid(belongs_to = "buyer")]
+        #[liquid(expires_after = "self.auction.end")]
         pub fn submit_bid(self, price: u64, quantity: u64) -> ContractId<Bid> {
-            let now = self.env().now();
-            assert!(now < self.auction.end && now >= self.auction.start);
+            assert!(self.env().now() >= self.auction.start);
 
             sign! { Bid =>
                 allocation: Allocation {
@@ -122,4 +122,60 @@ mod auction {
 
         (rem_qty - allocation.quantity, allocation)
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use liquid_lang::env::test;
+
+        fn invite_bidder(
+            seller: address,
+            buyer: address,
+        ) -> ContractId<AuctionInvitation> {
+            test::set_caller(seller);
+            let auction_id = sign! { Auction =>
+                security: String::from("LIQUID"),
+                quantity: 100,
+                seller,
+                start: 1000,
+                end: 2000,
+            };
+            let invitation_id = auction_id.invite_bidder(buyer);
+            test::pop_execution_context();
+            invitation_id
+        }
+
+        #[test]
+        fn submit_bid() {
+            let default_accounts = test::default_accounts();
+            let alice = default_accounts.alice;
+            let bob = default_accounts.bob;
+
+            let invitation_id = invite_bidder(alice, bob);
+            test::set_block_timestamp(1500);
+            test::set_caller(bob);
+            let bid_id = invitation_id.submit_bid(10, 50);
+            test::pop_execution_context();
+
+            let bid = bid_id.fetch();
+            assert_eq!(bid.allocation.party, bob);
+            assert_eq!(bid.allocation.price, 10);
+            assert_eq!(bid.allocation.quantity, 50);
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "right `submit_bid` of contract `AuctionInvitation` has expired"
+        )]
+        fn expired_bid() {
+            let default_accounts = test::default_accounts();
+            let alice = default_accounts.alice;
+            let bob = default_accounts.bob;
+
+            let invitation_id = invite_bidder(alice, bob);
+            test::set_block_timestamp(2000);
+            test::set_caller(bob);
+            let _ = invitation_id.submit_bid(10, 50);
+        }
+    }
 }
@@ -16,6 +16,7 @@ use liquid_lang as liquid;
 #[liquid::collaboration]
 mod iou {
     #[liquid(contract)]
+    #[liquid(ensure = "self.cash > 0")]
     pub struct Iou {
         #[liquid(signers)]
         issuer: address,
@@ -145,5 +146,335 @@ mod iou {
             let _ = iou_sender_id.send_iou(iou_id);
             test::pop_execution_context();
         }
+
+        #[test]
+        fn propose_iou() {
+            let default_accounts = test::default_accounts();
+            let alice = default_accounts.alice;
+            let bob = default_accounts.bob;
+
+            test::set_caller(alice);
+            let proposal_id = propose! { Iou =>
+                issuer: alice,
+                owner: bob,
+                cash: 100,
+            };
+            test::pop_execution_context();
+            assert_eq!(proposal_id.pending_signers(), vec![bob]);
+
+            test::set_caller(bob);
+            let iou_id = proposal_id.accept().unwrap();
+            test::pop_execution_context();
+
+            let iou = iou_id.fetch();
+            assert_eq!(iou.issuer, alice);
+            assert_eq!(iou.owner, bob);
+            assert_eq!(iou.cash, 100);
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "accepting of proposal of contract `Iou` is not permitted"
+        )]
+        fn accept_iou_twice() {
+            let default_accounts = test::default_accounts();
+            let alice = default_accounts.alice;
+
+            test::set_caller(alice);
+            let proposal_id = propose! { Iou =>
+                issuer: alice,
+                owner: default_accounts.bob,
+                cash: 100,
+            };
+            proposal_id.accept();
+        }
+
+        #[test]
+        #[should_panic(expected = "the proposal of contract `Iou` with id `0` is \
+                                   closed or inexistent")]
+        fn withdraw_iou_proposal() {
+            let default_accounts = test::default_accounts();
+            let alice = default_accounts.alice;
+            let bob = default_accounts.bob;
+
+            test::set_caller(alice);
+            let proposal_id = propose! { Iou =>
+                issuer: alice,
+                owner: bob,
+                cash: 100,
+            };
+            proposal_id.withdraw();
+            test::pop_execution_context();
+
+            test::set_caller(bob);
+            proposal_id.accept();
+        }
+
+        #[test]
+        fn archive_iou() {
+            let default_accounts = test::default_accounts();
+            let alice = default_accounts.alice;
+
+            let iou_ids = (1..=3)
+                .map(|cash| create_iou(alice, cash))
+                .collect::<Vec<_>>();
+            assert_eq!(ContractId::<Iou>::active(0, 10), iou_ids);
+
+            test::set_caller(alice);
+            iou_ids[1].archive();
+            test::pop_execution_context();
+
+            assert_eq!(
+                ContractId::<Iou>::active(0, 10),
+                vec![iou_ids[0], iou_ids[2]]
+            );
+            assert_eq!(ContractId::<Iou>::active(1, 1), vec![iou_ids[2]]);
+            assert!(ContractId::<Iou>::active(1, 2).is_empty());
+        }
+
+        #[test]
+        fn iou_client() {
+            use liquid_primitives::hash::hash;
+            use scale::{Decode, Encode};
+
+            let default_accounts = test::default_accounts();
+            let alice = default_accounts.alice;
+            let bob = default_accounts.bob;
+            let remote = default_accounts.frank;
+
+            test::register_callee(remote, move |data| {
+                let (selector, mut input) = data.split_at(4);
+                if selector == &hash(b"Iou")[..4] {
+                    let fields = <(address, address, u32)>::decode(&mut input).unwrap();
+                    assert_eq!(fields, (alice, alice, 100));
+                    7u32.encode()
+                } else if selector == &hash(b"Iou(mutual_transfer)")[..4] {
+                    let (id, (new_owner,)) =
+                        <(u32, (address,))>::decode(&mut input).unwrap();
+                    assert_eq!((id, new_owner), (7, bob));
+                    8u32.encode()
+                } else if selector == &hash(b"!Iou")[..4] {
+                    assert_eq!(u32::decode(&mut input).unwrap(), 8);
+                    Vec::new()
+                } else {
+                    panic!("unexpected selector");
+                }
+            });
+
+            let client = IouClient::at(remote);
+            let iou_id = client.sign(alice, alice, 100).unwrap();
+            assert_eq!(iou_id.id(), 7);
+            let iou_id = client.mutual_transfer(iou_id, bob).unwrap();
+            assert_eq!(iou_id.id(), 8);
+            assert_eq!(client.archive(iou_id), Some(()));
+        }
+
+        #[test]
+        fn try_fetch_iou() {
+            let alice = test::default_accounts().alice;
+            let iou_id = create_iou(alice, 1);
+            assert_eq!(iou_id.try_fetch().map(|iou| iou.cash), Some(1));
+
+            let missing_id = "Iou#1".parse::<ContractId<Iou>>().unwrap();
+            assert!(missing_id.try_fetch().is_none());
+
+            test::set_caller(alice);
+            iou_id.archive();
+            test::pop_execution_context();
+            assert!(iou_id.try_fetch().is_none());
+        }
+
+        #[test]
+        fn iou_id_repr() {
+            let alice = test::default_accounts().alice;
+            let iou_id = create_iou(alice, 1);
+            let raw: u32 = iou_id.into();
+            assert_eq!(raw, iou_id.id());
+            assert_eq!(ContractId::<Iou>::from_raw(raw), Some(iou_id));
+            assert_eq!(ContractId::<Iou>::from_raw(raw + 1), None);
+
+            let repr = iou_id.to_string();
+            assert_eq!(repr, format!("Iou#{}", raw));
+            assert_eq!(repr.parse::<ContractId<Iou>>(), Ok(iou_id));
+            assert!("Iou".parse::<ContractId<Iou>>().is_err());
+            assert!("Iou#x".parse::<ContractId<Iou>>().is_err());
+            assert!(format!("Cash#{}", raw).parse::<ContractId<Iou>>().is_err());
+        }
+
+        #[test]
+        fn iou_by_signer() {
+            let default_accounts = test::default_accounts();
+            let alice = default_accounts.alice;
+            let bob = default_accounts.bob;
+
+            let alice_ious = (1..=3)
+                .map(|cash| create_iou(alice, cash))
+                .collect::<Vec<_>>();
+            let bob_iou = create_iou(bob, 4);
+            assert_eq!(ContractId::<Iou>::by_signer(&alice, 0, 10), alice_ious);
+            assert_eq!(ContractId::<Iou>::by_signer(&bob, 0, 10), vec![bob_iou]);
+            assert_eq!(
+                ContractId::<Iou>::by_signer(&alice, 1, 1),
+                vec![alice_ious[1]]
+            );
+
+            test::set_caller(alice);
+            alice_ious[0].archive();
+            test::pop_execution_context();
+
+            assert_eq!(
+                ContractId::<Iou>::by_signer(&alice, 0, 10),
+                vec![alice_ious[1], alice_ious[2]]
+            );
+            assert!(ContractId::<Iou>::by_signer(&alice, 2, 10).is_empty());
+        }
+
+        #[test]
+        fn lifecycle_events() {
+            use liquid_lang::lifecycle::{LifecycleEvent, Operation};
+
+            let default_accounts = test::default_accounts();
+            let alice = default_accounts.alice;
+            let bob = default_accounts.bob;
+
+            let iou_id = create_iou(alice, 100);
+            test::set_caller(alice);
+            ContractId::<Iou>::delegate(bob, "mutual_transfer");
+            test::pop_execution_context();
+            test::set_caller(bob);
+            let new_iou_id = iou_id.mutual_transfer(bob);
+            test::pop_execution_context();
+            let other_iou_id = create_iou(alice, 1);
+            test::set_caller(alice);
+            other_iou_id.archive();
+            test::pop_execution_context();
+
+            let events = test::get_events()
+                .iter()
+                .filter_map(|event| event.downcast::<LifecycleEvent>().cloned())
+                .map(|event| {
+                    (
+                        event.operation,
+                        event.contract_id,
+                        event.right,
+                        event.parties,
+                    )
+                })
+                .collect::<Vec<_>>();
+            // Parties are ordered by their addresses.
+            assert_eq!(
+                events,
+                vec![
+                    (Operation::Created, iou_id.id(), String::new(), vec![alice]),
+                    (Operation::Archived, iou_id.id(), String::new(), vec![alice]),
+                    (
+                        Operation::Exercised,
+                        iou_id.id(),
+                        String::from("mutual_transfer"),
+                        vec![bob, alice]
+                    ),
+                    (
+                        Operation::Created,
+                        new_iou_id.id(),
+                        String::new(),
+                        vec![bob, alice]
+                    ),
+                    (
+                        Operation::Created,
+                        other_iou_id.id(),
+                        String::new(),
+                        vec![alice]
+                    ),
+                    (
+                        Operation::Archived,
+                        other_iou_id.id(),
+                        String::new(),
+                        vec![alice]
+                    ),
+                ]
+            );
+
+            let event = &test::get_events()[0];
+            assert_eq!(
+                LifecycleEvent::try_from_log(&event.topics, &event.data)
+                    .map(|event| event.template),
+                Some(String::from("Iou"))
+            );
+        }
+
+        #[test]
+        fn delegated_transfer() {
+            let default_accounts = test::default_accounts();
+            let alice = default_accounts.alice;
+            let bob = default_accounts.bob;
+
+            let iou_id = create_iou(alice, 100);
+            test::set_caller(alice);
+            ContractId::<Iou>::delegate(bob, "mutual_transfer");
+            test::pop_execution_context();
+            assert!(ContractId::<Iou>::is_delegated(
+                &alice,
+                &bob,
+                "mutual_transfer"
+            ));
+            assert!(!ContractId::<Iou>::is_delegated(
+                &bob,
+                &alice,
+                "mutual_transfer"
+            ));
+
+            test::set_caller(bob);
+            let iou_id = iou_id.mutual_transfer(bob);
+            test::pop_execution_context();
+
+            let iou = iou_id.fetch();
+            assert_eq!(iou.issuer, alice);
+            assert_eq!(iou.owner, bob);
+        }
+
+        #[test]
+        #[should_panic(expected = "exercising right `mutual_transfer` of contract \
+                                   `Iou` is not permitted")]
+        fn revoked_transfer() {
+            let default_accounts = test::default_accounts();
+            let alice = default_accounts.alice;
+            let bob = default_accounts.bob;
+
+            let iou_id = create_iou(alice, 100);
+            test::set_caller(alice);
+            ContractId::<Iou>::delegate(bob, "mutual_transfer");
+            ContractId::<Iou>::revoke(bob, "mutual_transfer");
+            test::pop_execution_context();
+
+            test::set_caller(bob);
+            iou_id.mutual_transfer(bob);
+        }
+
+        #[test]
+        #[should_panic(expected = "contract `Iou` has no such right")]
+        fn delegate_unknown_right() {
+            let default_accounts = test::default_accounts();
+            test::set_caller(default_accounts.alice);
+            ContractId::<Iou>::delegate(default_accounts.bob, "burn");
+        }
+
+        #[test]
+        #[should_panic(expected = "archiving of contract `Iou` is not permitted")]
+        fn unauthorized_archive() {
+            let default_accounts = test::default_accounts();
+            let iou_id = create_iou(default_accounts.alice, 100);
+
+            test::set_caller(default_accounts.bob);
+            iou_id.archive();
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "precondition `self.cash > 0` of contract `Iou` is violated"
+        )]
+        fn zero_cash_iou() {
+            let default_accounts = test::default_accounts();
+            create_iou(default_accounts.alice, 0);
+        }
     }
 }
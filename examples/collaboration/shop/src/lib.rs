@@ -21,6 +21,7 @@ mod shop {
     pub struct Iou {
         #[liquid(signers)]
         issuer: address,
+        #[liquid(observers)]
         owner: address,
         amount: u64,
         currency: String,
@@ -49,12 +49,16 @@ mod voting {
         #[liquid(signers)]
         government: address,
         #[liquid(signers = "$[..](?@.voted).addr")]
+        #[liquid(observers = "$[..].addr")]
         voters: Vec<Voter>,
+        #[liquid(observers = "$.proposer")]
+        #[liquid(key)]
         proposal: Proposal,
     }
 
     #[liquid(rights_belong_to = "government")]
     impl Ballot {
+        #[liquid(consuming)]
         pub fn add(mut self, voter_addr: address) -> ContractId<Ballot> {
             assert!(self
                 .voters
@@ -75,6 +79,7 @@ mod voting {
             }
         }
 
+        #[liquid(consuming)]
         pub fn decide(self) -> ContractId<Decision> {
             require(
                 self.voters.iter().all(|voter| voter.voted),
@@ -99,6 +104,7 @@ mod voting {
     #[liquid(rights)]
     impl Ballot {
         #[liquid(belongs_to = "")]
+        #[liquid(non_consuming)]
         pub fn vote(&mut self, choice: bool) {
             let voter_addr = self.env().get_caller();
 
@@ -166,6 +172,66 @@ mod voting {
             assert_eq!(proposal.content, "take a holiday");
         }
 
+        #[test]
+        fn contracts_of_party() {
+            let default_accounts = test::default_accounts();
+            let government = default_accounts.alice;
+            let bob = default_accounts.bob;
+            let charlie = default_accounts.charlie;
+            let david = default_accounts.david;
+
+            test::set_caller(government);
+            let ballot_id = sign! { Ballot =>
+                government,
+                voters: Vec::new(),
+                proposal: Proposal {
+                    proposer: david,
+                    content: String::from("take a holiday"),
+                },
+            };
+            let ballot_id = ballot_id.add(bob);
+            test::pop_execution_context();
+
+            assert_eq!(
+                ContractId::<Ballot>::contracts_of(&government),
+                vec![ballot_id]
+            );
+            assert_eq!(ContractId::<Ballot>::contracts_of(&bob), vec![ballot_id]);
+            assert_eq!(ContractId::<Ballot>::contracts_of(&david), vec![ballot_id]);
+            assert!(ContractId::<Ballot>::contracts_of(&charlie).is_empty());
+
+            test::set_caller(bob);
+            ballot_id.vote(true);
+            test::pop_execution_context();
+
+            test::set_caller(government);
+            let decision_id = ballot_id.decide();
+            test::pop_execution_context();
+
+            assert!(ContractId::<Ballot>::contracts_of(&bob).is_empty());
+            assert_eq!(
+                ContractId::<Decision>::contracts_of(&bob),
+                vec![decision_id]
+            );
+            assert!(ContractId::<Decision>::contracts_of(&david).is_empty());
+
+            test::set_caller(government);
+            let ballot_id = sign! { Ballot =>
+                government,
+                voters: Vec::new(),
+                proposal: Proposal {
+                    proposer: david,
+                    content: String::from("take another holiday"),
+                },
+            };
+            assert_eq!(ContractId::<Ballot>::contracts_of(&david), vec![ballot_id]);
+            ballot_id.archive();
+            test::pop_execution_context();
+
+            assert!(ContractId::<Ballot>::contracts_of(&government).is_empty());
+            assert!(ContractId::<Ballot>::contracts_of(&david).is_empty());
+        }
+
         #[test]
         fn add_voter_after_voting() {
             let default_accounts = test::default_accounts();
@@ -285,6 +351,111 @@ mod voting {
             test::pop_execution_context();
         }
 
+        #[test]
+        fn ballot_by_key() {
+            let default_accounts = test::default_accounts();
+            let government = default_accounts.alice;
+            let bob = default_accounts.bob;
+            let proposal = || Proposal {
+                proposer: government,
+                content: String::from("take a holiday"),
+            };
+
+            test::set_caller(government);
+            let ballot_id = sign! { Ballot =>
+                government,
+                voters: Vec::new(),
+                proposal: proposal(),
+            };
+            assert_eq!(Ballot::lookup_by_key(&proposal()), Some(ballot_id));
+
+            let ballot_id = Ballot::exercise_by_key(&proposal()).add(bob);
+            let (fetched_id, ballot) = Ballot::fetch_by_key(&proposal());
+            assert_eq!(fetched_id, ballot_id);
+            assert_eq!(ballot.voters.len(), 1);
+            test::pop_execution_context();
+
+            test::set_caller(bob);
+            Ballot::exercise_by_key(&proposal()).vote(true);
+            test::pop_execution_context();
+
+            test::set_caller(government);
+            Ballot::exercise_by_key(&proposal()).decide();
+            test::pop_execution_context();
+
+            assert_eq!(Ballot::lookup_by_key(&proposal()), None);
+        }
+
+        #[test]
+        #[should_panic(expected = "an active `Ballot` contract with the same key exists")]
+        fn duplicated_key() {
+            let default_accounts = test::default_accounts();
+            let government = default_accounts.alice;
+
+            test::set_caller(government);
+            for _ in 0..2 {
+                sign! { Ballot =>
+                    government,
+                    voters: Vec::new(),
+                    proposal: Proposal {
+                        proposer: government,
+                        content: String::from("take a holiday"),
+                    },
+                };
+            }
+        }
+
+        #[test]
+        fn observer_fetching() {
+            let default_accounts = test::default_accounts();
+            let government = default_accounts.alice;
+            let bob = default_accounts.bob;
+            let david = default_accounts.david;
+
+            test::set_caller(government);
+            let ballot_id = sign! { Ballot =>
+                government,
+                voters: Vec::new(),
+                proposal: Proposal {
+                    proposer: david,
+                    content: String::from("take a holiday"),
+                },
+            };
+            let ballot_id = ballot_id.add(bob);
+            test::pop_execution_context();
+
+            for observer in [bob, david].iter() {
+                test::set_caller(*observer);
+                let ballot = ballot_id.fetch();
+                assert_eq!(ballot.government, government);
+                test::pop_execution_context();
+            }
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "fetching of contract `Ballot` with id `0` is not permitted"
+        )]
+        fn unauthorized_fetching() {
+            let default_accounts = test::default_accounts();
+            let government = default_accounts.alice;
+            let charlie = default_accounts.charlie;
+
+            test::set_caller(government);
+            let ballot_id = sign! { Ballot =>
+                government,
+                voters: Vec::new(),
+                proposal: Proposal {
+                    proposer: government,
+                    content: String::from("take a holiday"),
+                },
+            };
+            test::pop_execution_context();
+
+            test::set_caller(charlie);
+            ballot_id.fetch();
+        }
+
         #[test]
         #[should_panic(expected = "cannot decide on tie")]
         fn decide_on_tie() {
@@ -0,0 +1,97 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use liquid::{
+    acl::{AccessControl, Ownable},
+    storage,
+};
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod access_control {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Vault {
+        ownable: Ownable,
+        roles: AccessControl,
+        balance: storage::Value<u128>,
+    }
+
+    #[liquid(methods)]
+    impl Vault {
+        pub fn new(&mut self) {
+            let caller = self.env().get_caller();
+            self.ownable.initialize(caller);
+            self.roles.initialize(caller);
+            self.balance.initialize(0);
+        }
+
+        #[liquid(only_owner)]
+        pub fn grant_minter(&mut self, account: address) {
+            self.roles.grant_role("MINTER", account);
+        }
+
+        #[liquid(only_role = "MINTER")]
+        pub fn mint(&mut self, amount: u128) {
+            self.balance += amount;
+        }
+
+        #[liquid(only_owner)]
+        pub fn reset(&mut self) {
+            self.balance.set(0);
+        }
+
+        pub fn balance(&self) -> u128 {
+            *self.balance
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use liquid::env::test;
+
+        #[liquid::test]
+        fn owner_and_minter_succeed() {
+            let accounts = test::default_accounts();
+            let mut contract = Vault::new();
+
+            contract.grant_minter(accounts.bob);
+            test::set_caller(accounts.bob);
+            contract.mint(42);
+            test::pop_execution_context();
+            assert_eq!(contract.balance(), 42);
+
+            contract.reset();
+            assert_eq!(contract.balance(), 0);
+        }
+
+        #[liquid::test]
+        #[should_panic(expected = "Ownable: caller is not the owner")]
+        fn non_owner_reverts() {
+            let accounts = test::default_accounts();
+            let mut contract = Vault::new();
+
+            test::set_caller(accounts.bob);
+            contract.grant_minter(accounts.bob);
+        }
+
+        #[liquid::test]
+        #[should_panic(expected = "AccessControl: caller is missing role `MINTER`")]
+        fn caller_without_role_reverts() {
+            let accounts = test::default_accounts();
+            let mut contract = Vault::new();
+
+            contract.grant_minter(accounts.bob);
+            test::set_caller(accounts.charlie);
+            contract.mint(42);
+        }
+
+        #[liquid::test]
+        #[should_panic(expected = "AccessControl: caller is missing role `MINTER`")]
+        fn owner_without_role_reverts() {
+            let mut contract = Vault::new();
+            contract.mint(42);
+        }
+    }
+}
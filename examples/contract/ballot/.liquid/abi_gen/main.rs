@@ -25,5 +25,21 @@ fn main() -> Result<(), std::io::Error> {
     let contents = format!("[{}]", contents);
     std::fs::create_dir("target").ok();
     std::fs::write("target/ballot.abi", contents)?;
+    std::fs::write(
+        "target/ballot.errors.json",
+        serde_json::to_string(&contract_abi.error_abis)?,
+    )?;
+    std::fs::write(
+        "target/ballot.storage.json",
+        serde_json::to_string(&contract_abi.storage_layout)?,
+    )?;
+    std::fs::write(
+        "target/ballot.userdoc.json",
+        serde_json::to_string(&contract_abi.userdoc)?,
+    )?;
+    std::fs::write(
+        "target/ballot.devdoc.json",
+        serde_json::to_string(&contract_abi.devdoc)?,
+    )?;
     Ok(())
 }
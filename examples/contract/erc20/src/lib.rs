@@ -115,7 +115,7 @@ mod erc20 {
         use super::*;
         use liquid::env::test;
 
-        #[test]
+        #[liquid::test]
         fn new_works() {
             let accounts = test::default_accounts();
             let alice = accounts.alice;
@@ -127,7 +127,7 @@ mod erc20 {
             assert_eq!(contract.allowances.len(), 0);
         }
 
-        #[test]
+        #[liquid::test]
         fn balance_of_works() {
             let accounts = test::default_accounts();
             let alice = accounts.alice;
@@ -139,7 +139,7 @@ mod erc20 {
             assert_eq!(contract.balance_of(bob), 0);
         }
 
-        #[test]
+        #[liquid::test]
         fn transfer_works() {
             let accounts = test::default_accounts();
             let alice = accounts.alice;
@@ -179,7 +179,7 @@ mod erc20 {
             assert_eq!(contract.balance_of(alice), 90);
         }
 
-        #[test]
+        #[liquid::test]
         fn not_enough_balance() {
             let accounts = test::default_accounts();
             let alice = accounts.alice;
@@ -190,11 +190,36 @@ mod erc20 {
 
             assert_eq!(contract.balance_of(bob), 0);
             assert_eq!(contract.transfer(bob, 1000), false);
+            assert!(test::last_events().is_empty());
             assert_eq!(contract.balance_of(bob), 0);
             assert_eq!(contract.balance_of(alice), 100);
         }
 
-        #[test]
+        #[liquid::test]
+        fn revert_transfer() {
+            let accounts = test::default_accounts();
+            let alice = accounts.alice;
+            let bob = accounts.bob;
+
+            test::set_caller(alice);
+            let mut contract = Erc20::new(100);
+            let before_transfer = test::snapshot();
+
+            assert_eq!(contract.transfer(bob, 10), true);
+            assert_eq!(contract.balance_of(bob), 10);
+            assert_eq!(test::get_events().len(), 1);
+
+            test::revert_to(before_transfer);
+            assert_eq!(contract.balance_of(bob), 0);
+            assert_eq!(contract.balance_of(alice), 100);
+            assert!(test::get_events().is_empty());
+
+            assert_eq!(contract.transfer(bob, 30), true);
+            assert_eq!(contract.balance_of(bob), 30);
+            assert_eq!(contract.balance_of(alice), 70);
+        }
+
+        #[liquid::test]
         fn transfer_from_works() {
             let accounts = test::default_accounts();
             let alice = accounts.alice;
@@ -239,6 +264,10 @@ mod erc20 {
 
             test::set_caller(bob);
             assert_eq!(contract.transfer_from(alice, charlie, 10), true);
+            assert_eq!(test::last_events().len(), 1);
+            test::assert_emitted::<Transfer>(|e| {
+                e.from == alice && e.to == charlie && e.value == 10
+            });
             assert_eq!(contract.balance_of(alice), 90);
             assert_eq!(contract.balance_of(charlie), 10);
             assert_eq!(contract.balance_of(bob), 0);
@@ -3,7 +3,7 @@
 use liquid::storage;
 use liquid_lang as liquid;
 
-#[liquid::contract]
+#[liquid::contract(version = "1.0.0")]
 mod hello_world {
     use super::*;
 
@@ -45,5 +45,30 @@ mod hello_world {
             contract.set(new_name.clone());
             assert_eq!(contract.get(), "Bob");
         }
+
+        #[test]
+        fn metadata_works() {
+            use liquid_lang::metadata::{ContractMetadata, SCHEMA_VERSION};
+
+            let metadata = <HelloWorld as ContractMetadata>::metadata();
+            assert_eq!(metadata.schema_version, SCHEMA_VERSION);
+            assert_eq!(metadata.name, "HelloWorld");
+            assert_eq!(metadata.version.as_deref(), Some("1.0.0"));
+        }
+
+        #[test]
+        fn version_works() {
+            let contract = HelloWorld::new();
+            assert_eq!(contract.version(), "1.0.0");
+        }
+
+        #[test]
+        #[cfg(feature = "liquid-fuzz")]
+        fn fuzz_works() {
+            let report = liquid_lang::fuzz::fuzz(HelloWorld::new, 100);
+            report.assert_ok();
+            assert!(report.calls > 0);
+            assert_eq!(report.reverts, 0);
+        }
     }
 }
@@ -25,5 +25,21 @@ fn main() -> Result<(), std::io::Error> {
     let contents = format!("[{}]", contents);
     std::fs::create_dir("target").ok();
     std::fs::write("target/incrementer.abi", contents)?;
+    std::fs::write(
+        "target/incrementer.errors.json",
+        serde_json::to_string(&contract_abi.error_abis)?,
+    )?;
+    std::fs::write(
+        "target/incrementer.storage.json",
+        serde_json::to_string(&contract_abi.storage_layout)?,
+    )?;
+    std::fs::write(
+        "target/incrementer.userdoc.json",
+        serde_json::to_string(&contract_abi.userdoc)?,
+    )?;
+    std::fs::write(
+        "target/incrementer.devdoc.json",
+        serde_json::to_string(&contract_abi.devdoc)?,
+    )?;
     Ok(())
 }
@@ -113,29 +113,33 @@ mod kv_table_test {
         use crate::{entry::*, kv_table::*};
         use predicates::prelude::*;
 
-        #[test]
+        #[liquid::test]
         fn get_works() {
             // EXPECTATIONS SETUP
             let create_table_ctx = KvTableFactory::createTable_context();
-            create_table_ctx.expect().returns(0);
+            create_table_ctx.expect().times(1).returns(0);
 
             let open_table_ctx = KvTableFactory::openTable_context();
             open_table_ctx
                 .expect()
+                .times(2)
                 .returns(KvTable::at(Default::default()));
 
+            let sequence = liquid::mock::Sequence::new();
             let get_ctx = KvTable::get_context();
             get_ctx
                 .expect()
                 .when(predicate::eq(String::from("cat")))
+                .in_sequence(&sequence)
                 .returns((true, Entry::at(Default::default())));
             get_ctx
                 .expect()
                 .when_fn(|primary_key| primary_key == "dog")
+                .in_sequence(&sequence)
                 .throws();
 
             let get_int_ctx = Entry::getInt_context();
-            get_int_ctx.expect().returns(2500);
+            get_int_ctx.expect().at_least(1).returns(2500);
 
             let get_string_ctx = Entry::getString_context();
             get_string_ctx.expect().returns("dounai");
@@ -154,18 +158,65 @@ mod kv_table_test {
             assert_eq!(name, "");
         }
 
-        #[test]
-        fn set_works() {
-            use std::collections::HashMap;
+        #[liquid::test]
+        #[should_panic(
+            expected = "`KvTableFactory::openTable` is called more times than expected"
+        )]
+        fn unexpected_call() {
+            let create_table_ctx = KvTableFactory::createTable_context();
+            create_table_ctx.expect().returns(0);
 
-            fn entries() -> &'static mut HashMap<String, Vec<u8>> {
-                thread_local!(
-                    static ENTRIES: ::core::cell::RefCell<HashMap<String, Vec<u8>>> =
-                        std::cell::RefCell::new(HashMap::new());
-                );
+            let open_table_ctx = KvTableFactory::openTable_context();
+            open_table_ctx.expect().never();
 
-                ENTRIES.with(|entries| unsafe { &mut (*entries.as_ptr()) })
-            }
+            let contract = KvTableTest::new();
+            contract.get(String::from("cat"));
+        }
+
+        #[liquid::test]
+        fn default_behaviors() {
+            // EXPECTATIONS SETUP
+            let _lenient = liquid::mock::lenient();
+
+            let create_table_ctx = KvTableFactory::createTable_context();
+            create_table_ctx.expect().returns(0);
+
+            let open_table_ctx = KvTableFactory::openTable_context();
+            open_table_ctx
+                .expect()
+                .returns(KvTable::at(Default::default()));
+
+            let get_ctx = KvTable::get_context();
+            get_ctx
+                .expect()
+                .by_default()
+                .returns((false, Entry::at(Default::default())));
+            get_ctx
+                .expect()
+                .when(predicate::eq(String::from("cat")))
+                .returns((true, Entry::at(Default::default())));
+
+            let get_int_ctx = Entry::getInt_context();
+            get_int_ctx.expect().returns(2500);
+
+            // TESTS BEGIN
+            let contract = KvTableTest::new();
+
+            let (success, price, name) = contract.get(String::from("cat"));
+            assert_eq!(success, true);
+            assert_eq!(price, 2500.into());
+            assert_eq!(name, "");
+
+            let (success, _, _) = contract.get(String::from("dog"));
+            assert_eq!(success, false);
+        }
+
+        #[liquid::test]
+        fn set_works() {
+            use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+            // Fields set to the entry, shared by the mocked methods of `Entry`.
+            let entries = Rc::new(RefCell::new(HashMap::<String, Vec<u8>>::new()));
 
             // EXPECTATIONS SETUP
             let create_table_ctx = KvTableFactory::createTable_context();
@@ -182,15 +233,19 @@ mod kv_table_test {
                 .returns(Entry::at(Default::default()));
 
             let entry_set_ctx = Entry::set_context();
+            let string_entries = entries.clone();
             entry_set_ctx
                 .expect::<(String, String)>()
-                .returns_fn(|key, value| {
-                    entries().insert(key, value.into_bytes());
+                .returns_fn(move |key, value| {
+                    string_entries.borrow_mut().insert(key, value.into_bytes());
                 });
+            let int_entries = entries.clone();
             entry_set_ctx
                 .expect::<(String, i256)>()
-                .returns_fn(|key, value| {
-                    entries().insert(key, value.to_be_bytes().to_vec());
+                .returns_fn(move |key, value| {
+                    int_entries
+                        .borrow_mut()
+                        .insert(key, value.to_be_bytes().to_vec());
                 });
 
             let get_ctx = KvTable::get_context();
@@ -199,14 +254,15 @@ mod kv_table_test {
                 .when(predicate::eq(String::from("dog")))
                 .returns((true, Entry::at(Default::default())));
 
+            let int_entries = entries.clone();
             let get_int_ctx = Entry::getInt_context();
-            get_int_ctx.expect().returns_fn(|key| {
-                i256::from_signed_be_bytes(entries().get(&key).unwrap())
+            get_int_ctx.expect().returns_fn(move |key| {
+                i256::from_signed_be_bytes(&int_entries.borrow()[&key])
             });
 
             let get_string_ctx = Entry::getString_context();
-            get_string_ctx.expect().returns_fn(|key| {
-                String::from_utf8(entries().get(&key).unwrap().clone()).unwrap()
+            get_string_ctx.expect().returns_fn(move |key| {
+                String::from_utf8(entries.borrow()[&key].clone()).unwrap()
             });
 
             let kv_table_set_ctx = KvTable::liquid_is_fun();
@@ -216,6 +272,8 @@ mod kv_table_test {
             let mut contract = KvTableTest::new();
 
             contract.set(String::from("dog"), 2000.into(), String::from("baicai"));
+            liquid::env::test::assert_emitted::<SetResult>(|e| e.count == 0.into());
+
             let (success, price, name) = contract.get(String::from("dog"));
             assert_eq!(success, true);
             assert_eq!(price, 2000.into());
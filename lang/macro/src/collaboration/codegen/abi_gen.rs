@@ -11,7 +11,7 @@
 // limitations under the License.
 
 use crate::{
-    collaboration::ir::{Collaboration, FnArg, Right, Signature},
+    collaboration::ir::{Collaboration, FnArg, Right, SelectFrom, Selector, Signature},
     common::GenerateCode,
 };
 use derive_more::From;
@@ -88,6 +88,19 @@ fn generate_right_abis(rights: &[Right]) -> impl Iterator<Item = TokenStream2> +
     })
 }
 
+fn generate_party_fields(selectors: &[Selector]) -> Vec<String> {
+    let mut fields = Vec::new();
+    for selector in selectors {
+        if let SelectFrom::This(ident) = &selector.from {
+            let field = ident.to_string();
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+    }
+    fields
+}
+
 impl<'a> AbiGen<'a> {
     fn generate_contract_abis(&'a self) -> impl Iterator<Item = TokenStream2> + 'a {
         let contracts = &self.collaboration.contracts;
@@ -114,6 +127,15 @@ impl<'a> AbiGen<'a> {
                 let rights = &item_rights.rights;
                 generate_right_abis(rights.as_slice())
             }).flatten();
+            let signers = generate_party_fields(&contract.field_signers);
+            let observers = generate_party_fields(&contract.field_observers);
+            let key = match &contract.field_key {
+                Some(key) => {
+                    let key_ident = key.ident.to_string();
+                    quote! { Some(String::from(#key_ident)) }
+                }
+                None => quote! { None },
+            };
             quote! {
                 liquid_abi_gen::ContractAbi {
                     name: String::from(#contract_ident),
@@ -123,6 +145,9 @@ impl<'a> AbiGen<'a> {
                         #(rights.push(#right_abis);)*
                         rights
                     },
+                    signers: vec![#(String::from(#signers),)*],
+                    observers: vec![#(String::from(#observers),)*],
+                    key: #key,
                 }
             }
         })
@@ -0,0 +1,146 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    collaboration::{
+        codegen::{dispatch::Dispatch, utils},
+        ir::*,
+    },
+    common::GenerateCode,
+};
+use derive_more::From;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::quote;
+
+/// Generates a client for every contract template, through which ordinary
+/// contracts can sign contracts and exercise rights of a collaboration
+/// deployed at another address, just like calling it via an interface.
+#[derive(From)]
+pub struct Client<'a> {
+    collaboration: &'a Collaboration,
+}
+
+impl<'a> GenerateCode for Client<'a> {
+    fn generate_code(&self) -> TokenStream2 {
+        let clients = self
+            .collaboration
+            .contracts
+            .iter()
+            .map(|contract| self.generate_client(contract));
+
+        quote! {
+            #(#clients)*
+        }
+    }
+}
+
+impl<'a> Client<'a> {
+    fn generate_call(selector: [u8; 4], input: TokenStream2) -> TokenStream2 {
+        quote! {
+            let mut encoded = [#(#selector,)*].to_vec();
+            encoded.extend(scale::Encode::encode(&#input));
+            liquid_lang::env::call(&self.address, &encoded).ok()
+        }
+    }
+
+    fn generate_client(&self, contract: &ItemContract) -> TokenStream2 {
+        let ident = &contract.ident;
+        let client_ident = Ident::new(&format!("{}Client", ident), Span::call_site());
+        let doc = format!(
+            " Calls `{}` contracts of the collaboration deployed at another address.",
+            ident
+        );
+
+        let (field_idents, field_tys): (Vec<_>, Vec<_>) = contract
+            .fields
+            .named
+            .iter()
+            .map(|field| (&field.ident, &field.ty))
+            .unzip();
+        let sign = Self::generate_call(
+            Dispatch::generate_contract_selector(contract, ""),
+            quote! { (#(#field_idents,)*) },
+        );
+        let fetch = Self::generate_call(
+            Dispatch::generate_contract_selector(contract, "$"),
+            quote! { contract_id },
+        );
+        let archive = Self::generate_call(
+            Dispatch::generate_contract_selector(contract, "!"),
+            quote! { contract_id },
+        );
+
+        let rights = self
+            .collaboration
+            .all_item_rights
+            .iter()
+            .filter(|item_rights| item_rights.ident == *ident)
+            .flat_map(|item_rights| item_rights.rights.iter())
+            .map(|right| {
+                let sig = &right.sig;
+                let fn_ident = &sig.ident;
+                let input_idents = utils::generate_input_idents(&sig.inputs);
+                let input_tys = utils::generate_input_tys(sig);
+                let output = match &sig.output {
+                    syn::ReturnType::Default => quote! { () },
+                    syn::ReturnType::Type(_, ty) => quote! { #ty },
+                };
+                let call = Self::generate_call(
+                    Dispatch::generate_right_selector(right),
+                    quote! { (contract_id, (#(#input_idents,)*)) },
+                );
+
+                quote! {
+                    pub fn #fn_ident(
+                        &self,
+                        contract_id: ContractId<#ident>,
+                        #(#input_idents: #input_tys,)*
+                    ) -> Option<#output> {
+                        #call
+                    }
+                }
+            });
+
+        quote! {
+            #[doc = #doc]
+            #[allow(dead_code)]
+            pub struct #client_ident {
+                address: address,
+            }
+
+            #[allow(dead_code)]
+            impl #client_ident {
+                /// Creates a client of the collaboration deployed at `address`.
+                pub fn at(address: address) -> Self {
+                    Self { address }
+                }
+
+                /// Signs a new contract, returns `None` if the call fails.
+                pub fn sign(&self, #(#field_idents: #field_tys,)*) -> Option<ContractId<#ident>> {
+                    #sign
+                }
+
+                /// Fetches the content of a contract, returns `None` if the call fails.
+                pub fn fetch(&self, contract_id: ContractId<#ident>) -> Option<#ident> {
+                    #fetch
+                }
+
+                /// Archives a contract, returns `None` if the call fails.
+                pub fn archive(&self, contract_id: ContractId<#ident>) -> Option<()> {
+                    #archive
+                }
+
+                #(#rights)*
+            }
+        }
+    }
+}
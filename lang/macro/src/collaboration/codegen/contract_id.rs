@@ -26,9 +26,12 @@ use syn::spanned::Spanned;
 pub struct ContractId;
 
 impl ContractId {
-    fn generate_contract_id() -> TokenStream2 {
+    fn generate_id(id_name: &str) -> TokenStream2 {
+        let ident = Ident::new(id_name, Span::call_site());
+        let debug_format = format!("{}({{}})", id_name);
+
         quote! {
-            pub struct ContractId<T>
+            pub struct #ident<T>
             where
                 T: liquid_lang::You_Should_Use_An_Valid_Contract_Type,
             {
@@ -37,17 +40,17 @@ impl ContractId {
             }
 
             #[cfg(test)]
-            impl<T> ::core::fmt::Debug for ContractId<T>
+            impl<T> ::core::fmt::Debug for #ident<T>
             where
                 T: liquid_lang::You_Should_Use_An_Valid_Contract_Type,
             {
                 fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                    write!(f, "ContractId({})", self.__liquid_id)
+                    write!(f, #debug_format, self.__liquid_id)
                 }
             }
 
             // https://github.com/rust-lang/rust/issues/41481
-            impl<T> ::core::clone::Clone for ContractId<T>
+            impl<T> ::core::clone::Clone for #ident<T>
             where
                 T: liquid_lang::You_Should_Use_An_Valid_Contract_Type,
             {
@@ -59,13 +62,13 @@ impl ContractId {
                 }
             }
 
-            impl<T> ::core::marker::Copy for ContractId<T>
+            impl<T> ::core::marker::Copy for #ident<T>
             where
                 T: liquid_lang::You_Should_Use_An_Valid_Contract_Type,
             {
             }
 
-            impl<T> ::core::cmp::PartialEq for ContractId<T>
+            impl<T> ::core::cmp::PartialEq for #ident<T>
             where
                 T: liquid_lang::You_Should_Use_An_Valid_Contract_Type,
             {
@@ -74,7 +77,28 @@ impl ContractId {
                 }
             }
 
-            impl<T> scale::Encode for ContractId<T>
+            impl<T> #ident<T>
+            where
+                T: liquid_lang::You_Should_Use_An_Valid_Contract_Type,
+            {
+                /// Returns the numeric representation of this id, which is
+                /// stable across calls and unique among ids of the same
+                /// contract template.
+                pub fn id(&self) -> u32 {
+                    self.__liquid_id
+                }
+            }
+
+            impl<T> ::core::convert::From<#ident<T>> for u32
+            where
+                T: liquid_lang::You_Should_Use_An_Valid_Contract_Type,
+            {
+                fn from(id: #ident<T>) -> u32 {
+                    id.__liquid_id
+                }
+            }
+
+            impl<T> scale::Encode for #ident<T>
             where
                 T: liquid_lang::You_Should_Use_An_Valid_Contract_Type,
             {
@@ -83,26 +107,26 @@ impl ContractId {
                 }
             }
 
-            impl<T> liquid_lang::You_Should_Use_An_Valid_Field_Type for ContractId<T>
+            impl<T> liquid_lang::You_Should_Use_An_Valid_Field_Type for #ident<T>
             where
                 T: liquid_lang::You_Should_Use_An_Valid_Contract_Type,
             {
             }
 
-            impl<T> liquid_lang::You_Should_Use_An_Valid_Input_Type for ContractId<T>
+            impl<T> liquid_lang::You_Should_Use_An_Valid_Input_Type for #ident<T>
             where
                 T: liquid_lang::You_Should_Use_An_Valid_Contract_Type,
             {
             }
 
-            impl<T> liquid_lang::You_Should_Use_An_Valid_Return_Type for ContractId<T>
+            impl<T> liquid_lang::You_Should_Use_An_Valid_Return_Type for #ident<T>
             where
                 T: liquid_lang::You_Should_Use_An_Valid_Contract_Type,
             {
             }
 
             #[cfg(feature = "liquid-abi-gen")]
-            impl<T> liquid_abi_gen::traits::GenerateParamAbi for ContractId<T>
+            impl<T> liquid_abi_gen::traits::GenerateParamAbi for #ident<T>
             where
                 T: liquid_lang::You_Should_Use_An_Valid_Contract_Type,
             {
@@ -116,7 +140,7 @@ impl ContractId {
             }
 
             #[cfg(feature = "liquid-abi-gen")]
-            impl<T> liquid_abi_gen::traits::GenerateOutputs for ContractId<T>
+            impl<T> liquid_abi_gen::traits::GenerateOutputs for #ident<T>
             where
                 T: liquid_lang::You_Should_Use_An_Valid_Contract_Type,
             {
@@ -131,17 +155,61 @@ impl ContractId {
         }
     }
 
+    /// Contract ids can be rendered as `<template>#<id>` and be parsed back,
+    /// so that off-chain applications can't mix up ids of different templates
+    /// which share the same numeric representation.
+    fn generate_string_repr() -> TokenStream2 {
+        quote! {
+            impl<T> ::core::fmt::Display for ContractId<T>
+            where
+                T: liquid_lang::You_Should_Use_An_Valid_Contract_Type + liquid_lang::ContractName,
+            {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    write!(f, "{}#{}", T::CONTRACT_NAME, self.__liquid_id)
+                }
+            }
+
+            impl<T> ::core::str::FromStr for ContractId<T>
+            where
+                T: liquid_lang::You_Should_Use_An_Valid_Contract_Type + liquid_lang::ContractName,
+            {
+                type Err = &'static str;
+
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    let (name, id) = s
+                        .split_once('#')
+                        .ok_or("a contract id should be in form of `<template>#<id>`")?;
+                    if name != T::CONTRACT_NAME {
+                        return Err("the contract id belongs to another contract template");
+                    }
+
+                    let __liquid_id = id
+                        .parse::<u32>()
+                        .map_err(|_| "the contract id should be an unsigned 32-bit integer")?;
+                    Ok(Self {
+                        __liquid_id,
+                        __liquid_marker: Default::default(),
+                    })
+                }
+            }
+        }
+    }
+
     pub fn generate_code() -> TokenStream2 {
-        let contract_id = Self::generate_contract_id();
+        let contract_id = Self::generate_id("ContractId");
+        let proposal_id = Self::generate_id("ProposalId");
+        let string_repr = Self::generate_string_repr();
 
         quote! {
             mod __liquid_contract_id {
                 #[allow(unused_imports)]
                 use super::*;
                 #contract_id
+                #proposal_id
+                #string_repr
             }
 
-            pub use __liquid_contract_id::ContractId;
+            pub use __liquid_contract_id::{ContractId, ProposalId};
         }
     }
 }
@@ -36,6 +36,9 @@ impl<'a> GenerateCode for Contracts<'a> {
         let codecs = self.generate_codecs();
         let contract_visitors = self.generate_contract_visitors();
         let fns = self.generate_fns();
+        let key_fns = self.generate_key_fns();
+        let ensures = self.generate_ensures();
+        let proposals = self.generate_proposals();
         let constants = self.generate_constants();
 
         quote! {
@@ -44,6 +47,9 @@ impl<'a> GenerateCode for Contracts<'a> {
             #(#codecs)*
             #(#contract_visitors)*
             #(#fns)*
+            #(#key_fns)*
+            #(#ensures)*
+            #(#proposals)*
             #(#constants)*
         }
     }
@@ -117,56 +123,74 @@ impl<'a> Contracts<'a> {
         })
     }
 
-    fn generate_acquire_signers(&self) -> impl Iterator<Item = TokenStream2> + '_ {
-        let contracts = &self.collaboration.contracts;
-        contracts.iter().map(|contract| {
-            let span = contract.span;
-            let ident = &contract.ident;
-            let field_signers = &contract.field_signers;
-            let mated_name = &contract.mated_name;
-            let signers = field_signers.iter().map(|selector| {
-                let from = &selector.from;
-                let with = &selector.with;
-                let field_ident = match from {
-                    SelectFrom::This(ident) => ident,
-                    _ => unreachable!(),
-                };
+    fn generate_acquire_parties(
+        selectors: &[Selector],
+        acquire_trait: TokenStream2,
+        acquire_fn: Ident,
+        span: Span,
+    ) -> TokenStream2 {
+        let parties = selectors.iter().map(|selector| {
+            let from = &selector.from;
+            let with = &selector.with;
+            let field_ident = match from {
+                SelectFrom::This(ident) => ident,
+                _ => unreachable!(),
+            };
 
-                match with {
-                    None => {
-                        quote_spanned! { field_ident.span() =>
-                            &self.#field_ident
-                        }
+            match with {
+                None => {
+                    quote_spanned! { field_ident.span() =>
+                        &self.#field_ident
                     }
-                    Some(SelectWith::Func(path)) => {
-                        quote_spanned! { path.span() =>
-                            #path(&self.#field_ident)
-                        }
+                }
+                Some(SelectWith::Func(path)) => {
+                    quote_spanned! { path.span() =>
+                        #path(&self.#field_ident)
                     }
-                    Some(SelectWith::Obj(ast)) => {
-                        let mut path_visitor =
-                            PathVisitor::new(Some(quote! { &self.#field_ident }), &ast.arena);
-                        let stmts = path_visitor.eval(ast.root);
-                        quote_spanned! { field_ident.span() =>
-                            #stmts
-                        }
+                }
+                Some(SelectWith::Obj(ast)) => {
+                    let mut path_visitor =
+                        PathVisitor::new(Some(quote! { &self.#field_ident }), &ast.arena);
+                    let stmts = path_visitor.eval(ast.root);
+                    quote_spanned! { field_ident.span() =>
+                        #stmts
                     }
-                    Some(SelectWith::Inherited(field_ty)) => {
-                        quote_spanned! { field_ident.span() =>
-                            <#field_ty as liquid_lang::AcquireSigners>::acquire_signers(&self.#field_ident)
-                        }
+                }
+                Some(SelectWith::Inherited(field_ty)) => {
+                    quote_spanned! { field_ident.span() =>
+                        <#field_ty as #acquire_trait>::#acquire_fn(&self.#field_ident)
                     }
                 }
-            });
+            }
+        });
+
+        quote_spanned! { span =>
+            fn #acquire_fn(&self) -> liquid_prelude::collections::BTreeSet::<&address> {
+                #[allow(unused_imports)]
+                let mut parties = liquid_prelude::collections::BTreeSet::new();
+                #(parties.extend(liquid_lang::acquire_addrs(#parties));)*
+                parties
+            }
+        }
+    }
 
-            let acquire_signers = quote_spanned! { span =>
-                fn acquire_signers(&self) -> liquid_prelude::collections::BTreeSet::<&address> {
-                    #[allow(unused_imports)]
-                    let mut signers = liquid_prelude::collections::BTreeSet::new();
-                    #(signers.extend(liquid_lang::acquire_addrs(#signers));)*
-                    signers
-                }
-            };
+    fn generate_acquire_signers(&self) -> impl Iterator<Item = TokenStream2> + '_ {
+        let contracts = &self.collaboration.contracts;
+        contracts.iter().map(|contract| {
+            let ident = &contract.ident;
+            let mated_name = &contract.mated_name;
+            let acquire_signers = Self::generate_acquire_parties(
+                &contract.field_signers,
+                quote! { liquid_lang::AcquireSigners },
+                Ident::new("acquire_signers", Span::call_site()),
+                contract.span,
+            );
+            let acquire_observers = Self::generate_acquire_parties(
+                &contract.field_observers,
+                quote! { liquid_lang::AcquireObservers },
+                Ident::new("acquire_observers", Span::call_site()),
+                contract.span,
+            );
 
             quote! {
                 impl liquid_lang::AcquireSigners for #ident {
@@ -176,6 +200,14 @@ impl<'a> Contracts<'a> {
                 impl liquid_lang::AcquireSigners for #mated_name {
                     #acquire_signers
                 }
+
+                impl liquid_lang::AcquireObservers for #ident {
+                    #acquire_observers
+                }
+
+                impl liquid_lang::AcquireObservers for #mated_name {
+                    #acquire_observers
+                }
             }
         })
     }
@@ -194,6 +226,16 @@ impl<'a> Contracts<'a> {
                         })
                     }
                 }
+
+                impl scale::Decode for ProposalId<#ident> {
+                    fn decode<I: scale::Input>(input: &mut I) -> ::core::result::Result<Self, scale::Error> {
+                        let __liquid_id = <u32 as scale::Decode>::decode(input)?;
+                        Ok(Self {
+                            __liquid_id,
+                            __liquid_marker: Default::default(),
+                        })
+                    }
+                }
             }
         })
     }
@@ -204,25 +246,70 @@ impl<'a> Contracts<'a> {
             let ident = &contract.ident;
             let mated_name = &contract.mated_name;
             let state_name = &contract.state_name;
+            let index_key = match &contract.field_key {
+                Some(key) => {
+                    let key_ident = &key.ident;
+                    let index_name = &key.index_name;
+                    quote! {
+                        let keys = &mut storage.#index_name;
+                        if let Some(id) = keys.get(&contract.#key_ident) {
+                            if let Some((_, false)) = contracts.get(id) {
+                                liquid_lang::env::revert(&String::from(Self::DUPLICATED_KEY_ERROR));
+                            }
+                        }
+                        keys.insert(&contract.#key_ident, len);
+                    }
+                }
+                None => quote! {},
+            };
+            let ensure = if contract.ensures.is_empty() {
+                quote! {}
+            } else {
+                quote! {
+                    if let Some(error) = contract.__liquid_ensure() {
+                        liquid_lang::env::revert(&String::from(error));
+                    }
+                }
+            };
             quote! {
+                impl ContractId<#ident> {
+                    fn __liquid_visible_contract(&self, contract: &#mated_name) -> #ident {
+                        let parties = Self::__liquid_parties(contract);
+                        if !__liquid_visibility_check(&parties) {
+                            <Self as liquid_lang::ContractVisitor>::unauthorized_fetching_error(self.__liquid_id);
+                        }
+
+                        let encoded = <#mated_name as scale::Encode>::encode(contract);
+                        <#ident as scale::Decode>::decode(&mut encoded.as_slice()).unwrap()
+                    }
+                }
+
                 impl liquid_lang::ContractVisitor for ContractId<#ident> {
                     type Contract = #ident;
                     type ContractId = Self;
 
                     fn fetch(&self) -> #ident {
                         let storage = __liquid_acquire_storage_instance();
-                        let contracts = &mut storage.#state_name;
+                        let contracts = &storage.#state_name;
 
                         if let Some((contract, _)) = contracts.get(&self.__liquid_id) {
-                            let encoded = <#mated_name as scale::Encode>::encode(contract);
-                            let decoded = <#ident as scale::Decode>::decode(&mut encoded.as_slice()).unwrap();
-                            decoded
+                            self.__liquid_visible_contract(contract)
                         } else {
                             Self::inexistent_error(self.__liquid_id);
                             unreachable!();
                         }
                     }
 
+                    fn try_fetch(&self) -> Option<#ident> {
+                        let storage = __liquid_acquire_storage_instance();
+                        let contracts = &storage.#state_name;
+
+                        match contracts.get(&self.__liquid_id) {
+                            Some((contract, false)) => Some(self.__liquid_visible_contract(contract)),
+                            _ => None,
+                        }
+                    }
+
                     fn sign_new_contract(contract: #ident) -> Self {
                         let storage = __liquid_acquire_storage_instance();
                         let contracts = &mut storage.#state_name;
@@ -234,11 +321,22 @@ impl<'a> Contracts<'a> {
                         if !__liquid_authorization_check(&signers) {
                             liquid_lang::env::revert(&String::from(Self::UNAUTHORIZED_SIGNING_ERROR));
                         }
+                        #ensure
+                        let parties = signers.into_iter().copied().collect();
                         let len = contracts.len();
+                        #index_key
                         let mated = unsafe {
                             core::mem::transmute::<#ident, #mated_name>(contract)
                         };
+                        Self::__liquid_index_parties(len, &mated);
                         contracts.insert(&len, (mated, false));
+                        liquid_lang::lifecycle::emit(
+                            liquid_lang::lifecycle::Operation::Created,
+                            <#ident as liquid_lang::ContractName>::CONTRACT_NAME,
+                            len,
+                            "",
+                            parties,
+                        );
                         Self {
                             __liquid_id: len,
                             __liquid_marker: Default::default(),
@@ -255,6 +353,7 @@ impl<'a> Contracts<'a> {
             let ident = &contract.ident;
             let mated_name = &contract.mated_name;
             let state_name = &contract.state_name;
+            let parties_name = &contract.parties_name;
 
             let rights = self
                 .collaboration
@@ -262,9 +361,17 @@ impl<'a> Contracts<'a> {
                 .iter()
                 .filter(|item_rights| item_rights.ident == *ident)
                 .map(|item_rights| item_rights.rights.iter())
-                .flatten();
+                .flatten()
+                .collect::<Vec<_>>();
+            let ident_str = ident.to_string();
+            let right_names = rights
+                .iter()
+                .map(|right| format!("{}::{}", ident_str, right.sig.ident))
+                .collect::<Vec<_>>();
+            let unknown_right_error =
+                format!("contract `{}` has no such right", ident_str);
 
-            let fns = rights.map(|right| {
+            let fns = rights.iter().map(|right| {
                 let sig = &right.sig;
                 let fn_name = &sig.ident;
                 let inputs = &sig.inputs.iter().skip(1).collect::<Vec<_>>();
@@ -277,6 +384,13 @@ impl<'a> Contracts<'a> {
                         let decoded = <#mated_name as scale::Decode>::decode(&mut encoded.as_slice()).unwrap();
                         decoded.#fn_name(#(#input_idents,)*)
                     }
+                } else if sig.self_arg().mutability.is_some() {
+                    // The right may involve new parties in the contract.
+                    quote! {
+                        let output = contract.#fn_name(#(#input_idents,)*);
+                        Self::__liquid_index_parties(self.__liquid_id, contract);
+                        output
+                    }
                 } else {
                     quote! {
                         contract.#fn_name(#(#input_idents,)*)
@@ -286,6 +400,7 @@ impl<'a> Contracts<'a> {
                 quote! {
                     pub fn #fn_name(&self, #(#inputs,)*) #output {
                         let contract = self.__liquid_validity_check(#need_abolish);
+                        __liquid_acquire_storage_instance().__liquid_exercising = Some(self.__liquid_id);
                         #execute
                     }
                 }
@@ -303,6 +418,18 @@ impl<'a> Contracts<'a> {
                             }
                             if need_abolish {
                                 *abolished = true;
+                                Self::__liquid_unindex_parties(self.__liquid_id, contract);
+                                let parties = <#mated_name as liquid_lang::AcquireSigners>::acquire_signers(contract)
+                                    .into_iter()
+                                    .copied()
+                                    .collect();
+                                liquid_lang::lifecycle::emit(
+                                    liquid_lang::lifecycle::Operation::Archived,
+                                    <#ident as liquid_lang::ContractName>::CONTRACT_NAME,
+                                    self.__liquid_id,
+                                    "",
+                                    parties,
+                                );
                             }
                             contract
                         } else {
@@ -311,12 +438,418 @@ impl<'a> Contracts<'a> {
                         }
                     }
 
+                    /// Converts a numeric id received from outside into a
+                    /// `ContractId`, returning `None` if no contract of this
+                    /// kind has been signed with such id.
+                    pub fn from_raw(id: u32) -> Option<Self> {
+                        let storage = __liquid_acquire_storage_instance();
+                        if id < storage.#state_name.len() {
+                            Some(Self {
+                                __liquid_id: id,
+                                __liquid_marker: Default::default(),
+                            })
+                        } else {
+                            None
+                        }
+                    }
+
+                    fn __liquid_parties(contract: &#mated_name) -> liquid_prelude::collections::BTreeSet<&address> {
+                        let mut parties = <#mated_name as liquid_lang::AcquireSigners>::acquire_signers(contract);
+                        parties.extend(<#mated_name as liquid_lang::AcquireObservers>::acquire_observers(contract));
+                        parties
+                    }
+
+                    fn __liquid_index_parties(id: u32, contract: &#mated_name) {
+                        let index = &mut __liquid_acquire_storage_instance().#parties_name;
+                        for party in Self::__liquid_parties(contract) {
+                            match index.get_mut(party) {
+                                Some(ids) => {
+                                    if let Err(pos) = ids.binary_search(&id) {
+                                        ids.insert(pos, id);
+                                    }
+                                }
+                                None => {
+                                    let mut ids = liquid_prelude::vec::Vec::new();
+                                    ids.push(id);
+                                    index.insert(party, ids);
+                                }
+                            }
+                        }
+                    }
+
+                    fn __liquid_unindex_parties(id: u32, contract: &#mated_name) {
+                        let index = &mut __liquid_acquire_storage_instance().#parties_name;
+                        for party in Self::__liquid_parties(contract) {
+                            if let Some(ids) = index.get_mut(party) {
+                                if let Ok(pos) = ids.binary_search(&id) {
+                                    ids.remove(pos);
+                                }
+                            }
+                        }
+                    }
+
+                    /// Returns ids of active contracts of this kind which have
+                    /// ever involved `party`, keeping those which still do and
+                    /// satisfy `filter`.
+                    fn __liquid_indexed<'a, F>(party: &'a address, filter: F) -> impl Iterator<Item = Self> + 'a
+                    where
+                        F: Fn(&#mated_name) -> bool + 'a,
+                    {
+                        let storage: &'static _ = __liquid_acquire_storage_instance();
+                        let contracts = &storage.#state_name;
+                        let ids = match storage.#parties_name.get(party) {
+                            Some(ids) => ids.as_slice(),
+                            None => &[],
+                        };
+
+                        ids.iter()
+                            .filter(move |id| match contracts.get(*id) {
+                                // Rights may have removed the party from the
+                                // contract since it was indexed.
+                                Some((contract, false)) => {
+                                    Self::__liquid_parties(contract).contains(&party) && filter(contract)
+                                }
+                                _ => false,
+                            })
+                            .map(|id| Self {
+                                __liquid_id: *id,
+                                __liquid_marker: Default::default(),
+                            })
+                    }
+
+                    /// Returns ids of all active contracts of this kind in which
+                    /// `party` is involved as a signer or an observer.
+                    pub fn contracts_of(party: &address) -> liquid_prelude::vec::Vec<Self> {
+                        Self::__liquid_indexed(party, |_| true).collect()
+                    }
+
+                    /// Returns ids of active contracts of this kind signed by
+                    /// `signer` in the order they were signed, skipping the
+                    /// first `offset` ones and returning `limit` ids at most.
+                    pub fn by_signer(signer: &address, offset: u32, limit: u32) -> liquid_prelude::vec::Vec<Self> {
+                        Self::__liquid_indexed(signer, |contract| {
+                            <#mated_name as liquid_lang::AcquireSigners>::acquire_signers(contract).contains(&signer)
+                        })
+                        .skip(offset as usize)
+                        .take(limit as usize)
+                        .collect()
+                    }
+
+                    /// Returns ids of active contracts of this kind, `size` ids
+                    /// per page at most.
+                    pub fn active(page: u32, size: u32) -> liquid_prelude::vec::Vec<Self> {
+                        let storage = __liquid_acquire_storage_instance();
+                        let contracts = &storage.#state_name;
+
+                        (0..contracts.len())
+                            .filter(|id| matches!(contracts.get(id), Some((_, false))))
+                            .skip(page.saturating_mul(size) as usize)
+                            .take(size as usize)
+                            .map(|id| Self {
+                                __liquid_id: id,
+                                __liquid_marker: Default::default(),
+                            })
+                            .collect()
+                    }
+
+                    /// Archives the contract without exercising any right on it,
+                    /// which needs authorization of all its signers.
+                    /// Allows `operator` to exercise `right` of contracts of this
+                    /// kind on behalf of the caller, until being revoked.
+                    pub fn delegate(operator: address, right: &str) {
+                        let right = Self::__liquid_qualified_right(right);
+                        let grantor = liquid_lang::env::get_caller();
+                        let delegations = &mut __liquid_acquire_storage_instance().__liquid_delegations;
+                        let key = (grantor, operator);
+                        match delegations.get_mut(&key) {
+                            Some(rights) => {
+                                if !rights.contains(&right) {
+                                    rights.push(right);
+                                }
+                            }
+                            None => {
+                                let mut rights = liquid_prelude::vec::Vec::new();
+                                rights.push(right);
+                                delegations.insert(&key, rights);
+                            }
+                        }
+                    }
+
+                    /// Revokes `right` delegated to `operator` by the caller.
+                    pub fn revoke(operator: address, right: &str) {
+                        let right = Self::__liquid_qualified_right(right);
+                        let grantor = liquid_lang::env::get_caller();
+                        let delegations = &mut __liquid_acquire_storage_instance().__liquid_delegations;
+                        if let Some(rights) = delegations.get_mut(&(grantor, operator)) {
+                            rights.retain(|granted| *granted != right);
+                        }
+                    }
+
+                    /// Checks whether `grantor` has delegated `right` to
+                    /// `operator`.
+                    pub fn is_delegated(grantor: &address, operator: &address, right: &str) -> bool {
+                        let right = Self::__liquid_qualified_right(right);
+                        let delegations = &__liquid_acquire_storage_instance().__liquid_delegations;
+                        delegations
+                            .get(&(*grantor, *operator))
+                            .map_or(false, |rights| rights.contains(&right))
+                    }
+
+                    fn __liquid_qualified_right(right: &str) -> String {
+                        const RIGHT_NAMES: &[&str] = &[#(#right_names,)*];
+                        let mut qualified = String::from(#ident_str);
+                        qualified.push_str("::");
+                        qualified.push_str(right);
+                        if !RIGHT_NAMES.contains(&qualified.as_str()) {
+                            liquid_lang::env::revert(&String::from(#unknown_right_error));
+                        }
+                        qualified
+                    }
+
+                    pub fn archive(&self) {
+                        let contract = self.__liquid_validity_check(false);
+                        let signers = <#mated_name as liquid_lang::AcquireSigners>::acquire_signers(contract);
+                        if !__liquid_authorization_check(&signers) {
+                            liquid_lang::env::revert(&String::from(Self::UNAUTHORIZED_ARCHIVING_ERROR));
+                        }
+                        self.__liquid_validity_check(true);
+                    }
+
                     #(#fns)*
                 }
             }
         })
     }
 
+    fn generate_ensures(&self) -> impl Iterator<Item = TokenStream2> + '_ {
+        let contracts = &self.collaboration.contracts;
+        contracts
+            .iter()
+            .filter(|contract| !contract.ensures.is_empty())
+            .map(|contract| {
+                let ident = &contract.ident;
+                let checks = contract.ensures.iter().map(|ensure| {
+                    let cond = &ensure.cond;
+                    let error = format!(
+                        "precondition `{}` of contract `{}` is violated",
+                        ensure.text, ident
+                    );
+                    quote_spanned! { cond.span() =>
+                        if !(#cond) {
+                            return Some(#error);
+                        }
+                    }
+                });
+
+                quote! {
+                    impl #ident {
+                        fn __liquid_ensure(&self) -> Option<&'static str> {
+                            #(#checks)*
+                            None
+                        }
+                    }
+                }
+            })
+    }
+
+    fn generate_proposals(&self) -> impl Iterator<Item = TokenStream2> + '_ {
+        let contracts = &self.collaboration.contracts;
+        contracts.iter().map(|contract| {
+            let ident = &contract.ident;
+            let ident_str = ident.to_string();
+            let mated_name = &contract.mated_name;
+            let proposals_name = &contract.proposals_name;
+            let unauthorized_proposing_error =
+                format!("proposing of contract `{}` is not permitted", ident_str);
+            let unauthorized_accepting_error = format!(
+                "accepting of proposal of contract `{}` is not permitted",
+                ident_str
+            );
+            let unauthorized_withdrawing_error = format!(
+                "withdrawing of proposal of contract `{}` is not permitted",
+                ident_str
+            );
+            let closed_proposal_error =
+                format!("the proposal of contract `{}` with id `", ident_str);
+
+            quote! {
+                impl ProposalId<#ident> {
+                    const UNAUTHORIZED_PROPOSING_ERROR: &'static str = #unauthorized_proposing_error;
+                    const UNAUTHORIZED_ACCEPTING_ERROR: &'static str = #unauthorized_accepting_error;
+                    const UNAUTHORIZED_WITHDRAWING_ERROR: &'static str = #unauthorized_withdrawing_error;
+
+                    /// Returns parties in `signers` who authorize current call.
+                    fn __liquid_authorized_signers(
+                        signers: &liquid_prelude::collections::BTreeSet<&address>,
+                    ) -> liquid_prelude::vec::Vec<address> {
+                        signers
+                            .iter()
+                            .filter(|signer| {
+                                let mut party = liquid_prelude::collections::BTreeSet::new();
+                                party.insert(**signer);
+                                __liquid_authorization_check(&party)
+                            })
+                            .map(|signer| **signer)
+                            .collect()
+                    }
+
+                    fn __liquid_open_proposal(
+                        &self,
+                    ) -> &'static mut (#mated_name, liquid_prelude::vec::Vec<address>, bool) {
+                        let storage = __liquid_acquire_storage_instance();
+                        match storage.#proposals_name.get_mut(&self.__liquid_id) {
+                            Some(proposal) if !proposal.2 => proposal,
+                            _ => {
+                                let mut error_info = String::from(#closed_proposal_error);
+                                use liquid_prelude::string::ToString;
+                                error_info.push_str(&self.__liquid_id.to_string());
+                                error_info.push_str("` is closed or inexistent");
+                                liquid_lang::env::revert(&error_info);
+                                unreachable!();
+                            }
+                        }
+                    }
+
+                    /// Proposes `contract` on behalf of those of its signers who
+                    /// authorize current call, the contract will be signed once
+                    /// all other signers have accepted the proposal.
+                    pub fn propose(contract: #ident) -> Self {
+                        let accepted = {
+                            let signers = <#ident as liquid_lang::AcquireSigners>::acquire_signers(&contract);
+                            Self::__liquid_authorized_signers(&signers)
+                        };
+                        if accepted.is_empty() {
+                            liquid_lang::env::revert(&String::from(Self::UNAUTHORIZED_PROPOSING_ERROR));
+                        }
+
+                        let storage = __liquid_acquire_storage_instance();
+                        let proposals = &mut storage.#proposals_name;
+                        let len = proposals.len();
+                        let mated = unsafe {
+                            core::mem::transmute::<#ident, #mated_name>(contract)
+                        };
+                        proposals.insert(&len, (mated, accepted, false));
+                        Self {
+                            __liquid_id: len,
+                            __liquid_marker: Default::default(),
+                        }
+                    }
+
+                    /// Accepts the proposal on behalf of signers who authorize
+                    /// current call, returns id of the signed contract if no more
+                    /// signer needs to accept it.
+                    pub fn accept(&self) -> Option<ContractId<#ident>> {
+                        let (contract, accepted, closed) = self.__liquid_open_proposal();
+                        let signers = <#mated_name as liquid_lang::AcquireSigners>::acquire_signers(contract);
+                        let accepting = Self::__liquid_authorized_signers(&signers)
+                            .into_iter()
+                            .filter(|signer| !accepted.contains(signer))
+                            .collect::<liquid_prelude::vec::Vec<_>>();
+                        if accepting.is_empty() {
+                            liquid_lang::env::revert(&String::from(Self::UNAUTHORIZED_ACCEPTING_ERROR));
+                        }
+                        accepted.extend(accepting);
+
+                        if signers.iter().any(|signer| !accepted.contains(signer)) {
+                            return None;
+                        }
+
+                        *closed = true;
+                        let mut __liquid_guard = __liquid_acquire_authorizers_guard();
+                        __liquid_guard.authorizers().extend(signers.into_iter().cloned());
+                        let encoded = <#mated_name as scale::Encode>::encode(contract);
+                        let decoded = <#ident as scale::Decode>::decode(&mut encoded.as_slice()).unwrap();
+                        Some(<ContractId<#ident> as liquid_lang::ContractVisitor>::sign_new_contract(decoded))
+                    }
+
+                    /// Withdraws the proposal, which can be done by any of the
+                    /// signers of the proposed contract.
+                    pub fn withdraw(&self) {
+                        let (contract, _, closed) = self.__liquid_open_proposal();
+                        let signers = <#mated_name as liquid_lang::AcquireSigners>::acquire_signers(contract);
+                        if Self::__liquid_authorized_signers(&signers).is_empty() {
+                            liquid_lang::env::revert(&String::from(Self::UNAUTHORIZED_WITHDRAWING_ERROR));
+                        }
+                        *closed = true;
+                    }
+
+                    /// Returns signers who have not accepted the proposal yet.
+                    pub fn pending_signers(&self) -> liquid_prelude::vec::Vec<address> {
+                        let (contract, accepted, _) = self.__liquid_open_proposal();
+                        <#mated_name as liquid_lang::AcquireSigners>::acquire_signers(contract)
+                            .into_iter()
+                            .filter(|signer| !accepted.contains(signer))
+                            .cloned()
+                            .collect()
+                    }
+                }
+            }
+        })
+    }
+
+    fn generate_key_fns(&self) -> impl Iterator<Item = TokenStream2> + '_ {
+        let contracts = &self.collaboration.contracts;
+        contracts
+            .iter()
+            .filter_map(|contract| contract.field_key.as_ref().map(|key| (contract, key)))
+            .map(|(contract, key)| {
+                let ident = &contract.ident;
+                let ident_str = ident.to_string();
+                let state_name = &contract.state_name;
+                let index_name = &key.index_name;
+                let key_ty = &key.ty;
+                let duplicated_key_error =
+                    format!("an active `{}` contract with the same key exists", ident_str);
+                let missing_key_error =
+                    format!("no active `{}` contract is found with the key", ident_str);
+
+                quote! {
+                    impl ContractId<#ident> {
+                        const DUPLICATED_KEY_ERROR: &'static str = #duplicated_key_error;
+                        const MISSING_KEY_ERROR: &'static str = #missing_key_error;
+                    }
+
+                    impl #ident {
+                        /// Returns the id of the active contract identified by `key`.
+                        pub fn lookup_by_key(key: &#key_ty) -> Option<ContractId<Self>> {
+                            let storage = __liquid_acquire_storage_instance();
+                            let id = *storage.#index_name.get(key)?;
+                            match storage.#state_name.get(&id) {
+                                Some((_, false)) => Some(ContractId {
+                                    __liquid_id: id,
+                                    __liquid_marker: Default::default(),
+                                }),
+                                _ => None,
+                            }
+                        }
+
+                        /// Returns the id of the active contract identified by `key`
+                        /// for exercising its rights, reverts if there is no such
+                        /// contract.
+                        pub fn exercise_by_key(key: &#key_ty) -> ContractId<Self> {
+                            match Self::lookup_by_key(key) {
+                                Some(contract_id) => contract_id,
+                                None => {
+                                    liquid_lang::env::revert(&String::from(
+                                        ContractId::<Self>::MISSING_KEY_ERROR,
+                                    ));
+                                    unreachable!();
+                                }
+                            }
+                        }
+
+                        /// Fetches the active contract identified by `key` along
+                        /// with its id, reverts if there is no such contract.
+                        pub fn fetch_by_key(key: &#key_ty) -> (ContractId<Self>, Self) {
+                            let contract_id = Self::exercise_by_key(key);
+                            let contract = <ContractId<Self> as liquid_lang::ContractVisitor>::fetch(&contract_id);
+                            (contract_id, contract)
+                        }
+                    }
+                }
+            })
+    }
+
     fn generate_constants(&self) -> impl Iterator<Item = TokenStream2> + '_ {
         let contracts = &self.collaboration.contracts;
         contracts.iter().map(|contract| {
@@ -324,6 +857,8 @@ impl<'a> Contracts<'a> {
             let ident_str = ident.to_string();
             let unauthorized_signing_error =
                 format!("signing of contract `{}` is not permitted", ident_str);
+            let unauthorized_archiving_error =
+                format!("archiving of contract `{}` is not permitted", ident_str);
             let no_available_signers_error =
                 format!("no available signers to sign this `{}` contract", ident_str);
             quote! {
@@ -335,6 +870,7 @@ impl<'a> Contracts<'a> {
 
                 impl ContractId<#ident> {
                     const UNAUTHORIZED_SIGNING_ERROR: &'static str = #unauthorized_signing_error;
+                    const UNAUTHORIZED_ARCHIVING_ERROR: &'static str = #unauthorized_archiving_error;
                     const NO_AVAILABLE_SIGNERS_ERROR: &'static str = #no_available_signers_error;
                 }
             }
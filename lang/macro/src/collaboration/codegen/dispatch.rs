@@ -55,23 +55,23 @@ impl<'a> Dispatch<'a> {
         }
     }
 
-    fn generate_right_selector(right: &Right) -> [u8; 4] {
+    pub(super) fn generate_right_selector(right: &Right) -> [u8; 4] {
         let sig = &right.sig;
         let right_name = &sig.ident;
         let from = &right.from;
-        let hash = liquid_primitives::hash::hash(
+        liquid_primitives::selector(
             format!("{}({})", from.to_string(), right_name.to_string()).as_bytes(),
-        );
-        [hash[0], hash[1], hash[2], hash[3]]
+        )
     }
 
-    fn generate_contract_selector(contract: &ItemContract, for_fetch: bool) -> [u8; 4] {
+    pub(super) fn generate_contract_selector(
+        contract: &ItemContract,
+        prefix: &str,
+    ) -> [u8; 4] {
         let contract_name = &contract.ident;
-        let prefix = if for_fetch { "$" } else { "" };
-        let hash = liquid_primitives::hash::hash(
+        liquid_primitives::selector(
             format!("{}{}", prefix, contract_name.to_string()).as_bytes(),
-        );
-        [hash[0], hash[1], hash[2], hash[3]]
+        )
     }
 
     fn generate_right_id(right: &Right) -> u32 {
@@ -82,7 +82,7 @@ impl<'a> Dispatch<'a> {
     }
 
     fn generate_contract_id(contract: &ItemContract) -> u32 {
-        let selector = Self::generate_contract_selector(contract, false);
+        let selector = Self::generate_contract_selector(contract, "");
         let mut buf = [0u8; 4];
         buf.copy_from_slice(&selector[..4]);
         u32::from_be_bytes(buf)
@@ -184,7 +184,7 @@ impl<'a> Dispatch<'a> {
             }
         };
 
-        let selector = Self::generate_contract_selector(contract, false);
+        let selector = Self::generate_contract_selector(contract, "");
         let contract_selector = {
             quote! {
                 impl liquid_lang::FnSelector for #contract_marker {
@@ -264,7 +264,34 @@ impl<'a> Dispatch<'a> {
             .iter()
             .map(|field| &field.ident)
             .collect::<Vec<_>>();
-        let fetch_selector = Self::generate_contract_selector(item_contract, true);
+        let fetch_selector = Self::generate_contract_selector(item_contract, "$");
+        let contracts_of_selector = Self::generate_contract_selector(item_contract, "@");
+        let propose_selector = Self::generate_contract_selector(item_contract, "?");
+        let accept_selector = Self::generate_contract_selector(item_contract, "+");
+        let withdraw_selector = Self::generate_contract_selector(item_contract, "-");
+        let active_selector = Self::generate_contract_selector(item_contract, "*");
+        let by_signer_selector = Self::generate_contract_selector(item_contract, "#");
+        let archive_selector = Self::generate_contract_selector(item_contract, "!");
+        let delegate_selector = Self::generate_contract_selector(item_contract, "&");
+        let revoke_selector = Self::generate_contract_selector(item_contract, "~");
+        let fetch_by_key = match &item_contract.field_key {
+            Some(key) => {
+                let key_ty = &key.ty;
+                let fetch_by_key_selector =
+                    Self::generate_contract_selector(item_contract, "%");
+                quote! {
+                    if selector == [#(#fetch_by_key_selector,)*] {
+                        let key = <#key_ty as scale::Decode>::decode(&mut data.as_slice())
+                            .map_err(|_| liquid_lang::DispatchError::InvalidParams)?;
+
+                        let result = #contract_ident::fetch_by_key(&key);
+                        liquid_lang::env::finish(&result);
+                        return Ok(());
+                    }
+                }
+            }
+            None => quote! {},
+        };
         quote! {
             if selector == <#contract_marker as liquid_lang::FnSelector>::SELECTOR {
                 let (#(#input_idents,)*) = <<#contract_marker as liquid_lang::FnInput>::Input as scale::Decode>::decode(&mut data.as_slice())
@@ -284,6 +311,90 @@ impl<'a> Dispatch<'a> {
                 liquid_lang::env::finish(&contract);
                 return Ok(());
             }
+
+            if selector == [#(#contracts_of_selector,)*] {
+                let party = <address as scale::Decode>::decode(&mut data.as_slice())
+                    .map_err(|_| liquid_lang::DispatchError::InvalidParams)?;
+
+                let contract_ids = ContractId::<#contract_ident>::contracts_of(&party);
+                liquid_lang::env::finish(&contract_ids);
+                return Ok(());
+            }
+
+            if selector == [#(#propose_selector,)*] {
+                let (#(#input_idents,)*) = <<#contract_marker as liquid_lang::FnInput>::Input as scale::Decode>::decode(&mut data.as_slice())
+                    .map_err(|_| liquid_lang::DispatchError::InvalidParams)?;
+                let proposal_id = liquid_macro::propose! (#contract_ident => #(#input_idents,)*);
+                <Storage as liquid_lang::storage::Flush>::flush(storage);
+                liquid_lang::env::finish(&proposal_id);
+                return Ok(());
+            }
+
+            if selector == [#(#accept_selector,)*] {
+                let proposal_id = <ProposalId<#contract_ident> as scale::Decode>::decode(&mut data.as_slice())
+                    .map_err(|_| liquid_lang::DispatchError::InvalidParams)?;
+
+                let contract_id = proposal_id.accept();
+                <Storage as liquid_lang::storage::Flush>::flush(storage);
+                liquid_lang::env::finish(&contract_id);
+                return Ok(());
+            }
+
+            if selector == [#(#withdraw_selector,)*] {
+                let proposal_id = <ProposalId<#contract_ident> as scale::Decode>::decode(&mut data.as_slice())
+                    .map_err(|_| liquid_lang::DispatchError::InvalidParams)?;
+
+                proposal_id.withdraw();
+                <Storage as liquid_lang::storage::Flush>::flush(storage);
+                return Ok(());
+            }
+
+            if selector == [#(#active_selector,)*] {
+                let (page, size) = <(u32, u32) as scale::Decode>::decode(&mut data.as_slice())
+                    .map_err(|_| liquid_lang::DispatchError::InvalidParams)?;
+
+                let contract_ids = ContractId::<#contract_ident>::active(page, size);
+                liquid_lang::env::finish(&contract_ids);
+                return Ok(());
+            }
+
+            if selector == [#(#by_signer_selector,)*] {
+                let (signer, offset, limit) = <(address, u32, u32) as scale::Decode>::decode(&mut data.as_slice())
+                    .map_err(|_| liquid_lang::DispatchError::InvalidParams)?;
+
+                let contract_ids = ContractId::<#contract_ident>::by_signer(&signer, offset, limit);
+                liquid_lang::env::finish(&contract_ids);
+                return Ok(());
+            }
+
+            if selector == [#(#archive_selector,)*] {
+                let contract_id = <ContractId<#contract_ident> as scale::Decode>::decode(&mut data.as_slice())
+                    .map_err(|_| liquid_lang::DispatchError::InvalidParams)?;
+
+                contract_id.archive();
+                <Storage as liquid_lang::storage::Flush>::flush(storage);
+                return Ok(());
+            }
+
+            if selector == [#(#delegate_selector,)*] {
+                let (operator, right) = <(address, String) as scale::Decode>::decode(&mut data.as_slice())
+                    .map_err(|_| liquid_lang::DispatchError::InvalidParams)?;
+
+                ContractId::<#contract_ident>::delegate(operator, &right);
+                <Storage as liquid_lang::storage::Flush>::flush(storage);
+                return Ok(());
+            }
+
+            if selector == [#(#revoke_selector,)*] {
+                let (operator, right) = <(address, String) as scale::Decode>::decode(&mut data.as_slice())
+                    .map_err(|_| liquid_lang::DispatchError::InvalidParams)?;
+
+                ContractId::<#contract_ident>::revoke(operator, &right);
+                <Storage as liquid_lang::storage::Flush>::flush(storage);
+                return Ok(());
+            }
+
+            #fetch_by_key
         }
     }
 
@@ -11,6 +11,7 @@
 // limitations under the License.
 
 mod abi_gen;
+mod client;
 mod contract_id;
 mod contracts;
 mod dispatch;
@@ -26,6 +27,7 @@ use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::quote;
 
 use abi_gen::AbiGen;
+use client::Client;
 use contract_id::ContractId;
 use contracts::Contracts;
 use dispatch::Dispatch;
@@ -44,13 +46,14 @@ impl GenerateCode for Collaboration {
         let rights = Rights::from(self).generate_code();
         let contract_id = ContractId::generate_code();
         let abi_gen = AbiGen::from(self).generate_code();
+        let client = Client::from(self).generate_code();
 
         quote! {
             mod #mod_ident {
                 #[allow(unused_imports)]
                 use liquid_lang::intrinsics::*;
                 #[allow(unused_imports)]
-                use liquid_macro::sign;
+                use liquid_macro::{propose, sign};
                 #[allow(unused_imports)]
                 use liquid_lang::Env;
                 #[allow(unused_imports)]
@@ -60,6 +63,7 @@ impl GenerateCode for Collaboration {
 
                 #contracts
                 #rights
+                #client
                 mod __liquid_private {
                     use super::*;
 
@@ -70,6 +74,8 @@ impl GenerateCode for Collaboration {
                 use __liquid_private::__liquid_acquire_storage_instance;
                 use __liquid_private::__liquid_acquire_authorizers_guard;
                 use __liquid_private::__liquid_authorization_check;
+                use __liquid_private::__liquid_right_authorization_check;
+                use __liquid_private::__liquid_visibility_check;
 
                 #abi_gen
                 #(#rust_items)*
@@ -76,10 +76,25 @@ impl<'a> GenerateCode for Rights<'a> {
                 let sig = &right.sig;
                 let fn_ident = &sig.ident;
                 let fn_ident_str = fn_ident.to_string();
+                let delegated_right = format!("{}::{}", contract_ident_str, fn_ident_str);
                 let inputs = &sig.inputs;
                 let output = &sig.output;
                 let body = &right.body;
                 let stmts = &body.stmts;
+                let expiry_check = right.expires_after.as_ref().map(|deadline| {
+                    quote_spanned! { deadline.span() =>
+                        let __liquid_deadline: timestamp = #deadline;
+                        if liquid_lang::env::now() >= __liquid_deadline {
+                            let mut error_info = String::from("right `");
+                            error_info.push_str(#fn_ident_str);
+                            error_info.push_str("` of contract `");
+                            error_info.push_str(#contract_ident_str);
+                            error_info.push_str("` has expired");
+                            liquid_lang::env::revert(&error_info);
+                            unreachable!();
+                        }
+                    }
+                });
                 let self_ref = if sig.is_self_ref() {
                     quote! { self }
                 } else {
@@ -98,7 +113,7 @@ impl<'a> GenerateCode for Rights<'a> {
                             #[allow(unused_mut)]
                             let mut owners = liquid_prelude::collections::BTreeSet::<&'_ address>::new();
                             #(owners.extend(liquid_lang::acquire_addrs(#selectors));)*
-                            if !__liquid_authorization_check(&owners) {
+                            if !__liquid_right_authorization_check(&owners, #delegated_right) {
                                 let mut error_info = String::from("exercising right `");
                                 error_info.push_str(#fn_ident_str);
                                 error_info.push_str("` of contract `");
@@ -107,6 +122,15 @@ impl<'a> GenerateCode for Rights<'a> {
                                 liquid_lang::env::revert(&error_info);
                                 unreachable!();
                             }
+                            if let Some(id) = __liquid_acquire_storage_instance().__liquid_exercising.take() {
+                                liquid_lang::lifecycle::emit(
+                                    liquid_lang::lifecycle::Operation::Exercised,
+                                    #contract_ident_str,
+                                    id,
+                                    #fn_ident_str,
+                                    owners.iter().copied().copied().collect(),
+                                );
+                            }
                             let signers = <#mated_name as AcquireSigners>::acquire_signers(#self_ref);
                             let authorizers = __liquid_guard.authorizers();
                             authorizers.extend(signers);
@@ -114,6 +138,7 @@ impl<'a> GenerateCode for Rights<'a> {
                             authorizers.sort();
                             authorizers.dedup();
                         }
+                        #expiry_check
                         #(#stmts)*
                     }
                 }
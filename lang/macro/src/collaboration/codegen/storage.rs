@@ -39,19 +39,65 @@ impl<'a> GenerateCode for Storage<'a> {
 impl<'a> Storage<'a> {
     fn generate_storage_struct(&self) -> TokenStream2 {
         let contracts = &self.collaboration.contracts;
-        let (field_idents, fields): (Vec<_>, Vec<_>) = contracts
-            .iter()
-            .map(|contract| {
+        let delegations_name = Ident::new("__liquid_delegations", Span::call_site());
+        let delegations = quote! {
+            // Rights which a grantor (the 1st address in key) allows an
+            // operator (the 2nd address in key) to exercise on behalf of it,
+            // in form of `<contract>::<right>`.
+            pub #delegations_name: liquid_lang::storage::Mapping<
+                (address, address),
+                liquid_prelude::vec::Vec<String>,
+            >,
+        };
+        let (field_idents, fields): (Vec<_>, Vec<_>) = ::core::iter::once((&delegations_name, delegations))
+            .chain(contracts.iter().flat_map(|contract| {
                 let mated_name = &contract.mated_name;
                 let state_name = &contract.state_name;
-                (
-                    state_name,
-                    quote! {
-                        // The 2nd field in value is used to mark whether the contract is abolished.
-                        pub #state_name: liquid_lang::storage::Mapping<u32, (#mated_name, bool)>,
-                    },
-                )
-            })
+                let proposals_name = &contract.proposals_name;
+                let parties_name = &contract.parties_name;
+                let mut fields = vec![
+                    (
+                        state_name,
+                        quote! {
+                            // The 2nd field in value is used to mark whether the contract is abolished.
+                            pub #state_name: liquid_lang::storage::Mapping<u32, (#mated_name, bool)>,
+                        },
+                    ),
+                    (
+                        proposals_name,
+                        quote! {
+                            // The value consists of the proposed contract, signers who have
+                            // accepted it and whether the proposal is closed.
+                            pub #proposals_name: liquid_lang::storage::Mapping<
+                                u32,
+                                (#mated_name, liquid_prelude::vec::Vec<address>, bool),
+                            >,
+                        },
+                    ),
+                    (
+                        parties_name,
+                        quote! {
+                            // Ids of contracts in which a party is involved as a signer
+                            // or an observer, in ascending order.
+                            pub #parties_name: liquid_lang::storage::Mapping<
+                                address,
+                                liquid_prelude::vec::Vec<u32>,
+                            >,
+                        },
+                    ),
+                ];
+                if let Some(key) = &contract.field_key {
+                    let index_name = &key.index_name;
+                    let key_ty = &key.ty;
+                    fields.push((
+                        index_name,
+                        quote! {
+                            pub #index_name: liquid_lang::storage::Mapping<#key_ty, u32>,
+                        },
+                    ));
+                }
+                fields
+            }))
             .unzip();
 
         let keys = field_idents
@@ -69,12 +115,17 @@ impl<'a> Storage<'a> {
         quote! {
             pub struct Storage {
                 pub __liquid_authorizers: liquid_prelude::vec::Vec<address>,
+                // Id of the contract whose right is about to be exercised, taken
+                // by the right to report the exercise.
+                pub __liquid_exercising: Option<u32>,
                 #(#fields)*
             }
 
             impl liquid_lang::storage::Flush for Storage {
                 fn flush(&mut self) {
-                    #(liquid_lang::storage::Flush::flush(&mut self.#field_idents);)*
+                    liquid_lang::env::batch_storage_writes(|| {
+                        #(liquid_lang::storage::Flush::flush(&mut self.#field_idents);)*
+                    })
                 }
             }
 
@@ -87,6 +138,7 @@ impl<'a> Storage<'a> {
                 fn new() -> Self {
                     let mut storage = Self {
                         __liquid_authorizers: liquid_prelude::vec::Vec::new(),
+                        __liquid_exercising: None,
                         #(#bind_stats)*
                     };
                     #(storage.#field_idents.initialize();)*
@@ -145,6 +197,41 @@ impl<'a> Storage<'a> {
                 }
             }
 
+            /// Besides the parties themselves, operators which have been
+            /// delegated `right` by every one of the parties are permitted to
+            /// exercise it when being called directly.
+            pub fn __liquid_right_authorization_check(
+                parties: &liquid_prelude::collections::BTreeSet<&address>,
+                right: &str,
+            ) -> bool {
+                if !acquire_authorizers().is_empty() {
+                    return __liquid_authorization_check(parties);
+                }
+
+                let caller = liquid_lang::env::get_caller();
+                let delegations = &__liquid_acquire_storage_instance().__liquid_delegations;
+                parties.iter().all(|party| {
+                    **party == caller
+                        || delegations
+                            .get(&(**party, caller))
+                            .map_or(false, |rights| rights.iter().any(|granted| granted == right))
+                })
+            }
+
+            pub fn __liquid_visibility_check(parties: &liquid_prelude::collections::BTreeSet<&address>) -> bool {
+                // Tests may inspect contracts from outside of any call.
+                #[cfg(test)]
+                let caller = match liquid_lang::env::test::current_caller() {
+                    Some(caller) => caller,
+                    None => return true,
+                };
+                #[cfg(not(test))]
+                let caller = liquid_lang::env::get_caller();
+
+                let authorizers = acquire_authorizers();
+                parties.contains(&caller) || authorizers.iter().any(|authorizer| parties.contains(authorizer))
+            }
+
             #[cfg(not(test))]
             pub fn __liquid_acquire_storage_instance() -> &'static mut Storage {
                 use liquid_lang::storage::New;
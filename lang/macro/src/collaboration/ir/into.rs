@@ -28,8 +28,10 @@ use syn::{
 
 impl Parse for ir::Marker {
     fn parse(input: ParseStream) -> Result<Self> {
-        const SINGLE_MARKER: [&str; 2] = ["contract", "rights"];
-        const VALUED_MARKER: [&str; 2] = ["belongs_to", "rights_belong_to"];
+        const SINGLE_MARKER: [&str; 5] =
+            ["contract", "rights", "key", "consuming", "non_consuming"];
+        const VALUED_MARKER: [&str; 4] =
+            ["belongs_to", "rights_belong_to", "ensure", "expires_after"];
 
         let content;
         let paren_token = syn::parenthesized!(content in input);
@@ -174,44 +176,40 @@ impl TryFrom<syn::ItemStruct> for ir::ItemContract {
         };
 
         let mut field_signers = Vec::new();
+        let mut field_observers = Vec::new();
+        let mut field_key = None;
         for field in &fields.named {
             let markers = filter_map_liquid_attributes(&field.attrs)?;
-            let signers = markers
+            if let Some(selector) = parse_parties(field, &markers, "signers")? {
+                field_signers.push(selector);
+            }
+            if let Some(selector) = parse_parties(field, &markers, "observers")? {
+                field_observers.push(selector);
+            }
+
+            let keys = markers
                 .iter()
-                .filter(|marker| marker.ident == "signers")
+                .filter(|marker| marker.ident == "key")
                 .collect::<Vec<_>>();
-            if signers.is_empty() {
+            if keys.is_empty() {
                 continue;
             }
-
-            let name = field.ident.as_ref().unwrap();
-            if signers.len() > 1 {
+            if keys.len() > 1 {
                 bail!(
                     field,
-                    "duplicated `#[liquid(signers)]` attributes defined for this field"
+                    "duplicated `#[liquid(key)]` attributes defined for this field"
                 )
             }
-
-            field_signers.push(ir::Selector {
-                from: ir::SelectFrom::This(name.clone()),
-                with: match &signers[0].value {
-                    (AttrValue::None, _) => None,
-                    (AttrValue::LitStr(path), span) => {
-                        let select_path = parse_select_path(&path.value(), *span)?;
-                        Some(select_path)
-                    }
-                    (AttrValue::Ident(ident), span) => {
-                        if ident == "inherited" {
-                            Some(ir::SelectWith::Inherited(field.ty.clone()))
-                        } else {
-                            bail_span!(
-                                *span,
-                                "invalid indicators of signers: `{}`",
-                                ident
-                            )
-                        }
-                    }
-                },
+            if field_key.is_some() {
+                bail!(
+                    field,
+                    "only one field can be marked as `#[liquid(key)]` in a contract"
+                )
+            }
+            field_key = Some(ir::ContractKey {
+                ident: field.ident.clone().unwrap(),
+                ty: field.ty.clone(),
+                index_name: generate_key_index_name(&item_struct.ident),
             });
         }
 
@@ -219,8 +217,28 @@ impl TryFrom<syn::ItemStruct> for ir::ItemContract {
             bail!(item_struct, "this contract has no signers")
         }
 
+        let mut ensures = Vec::new();
+        for marker in filter_map_liquid_attributes(&item_struct.attrs)?
+            .iter()
+            .filter(|marker| marker.ident == "ensure")
+        {
+            match &marker.value {
+                (AttrValue::LitStr(cond), _) => ensures.push(ir::Ensure {
+                    cond: cond.parse::<syn::Expr>().map_err(|error| {
+                        format_err_span!(cond.span(), "invalid precondition: {}", error)
+                    })?,
+                    text: cond.value(),
+                }),
+                (_, span) => {
+                    bail_span!(*span, "the precondition should be a string literal")
+                }
+            }
+        }
+
         let ident = item_struct.ident;
         let state_name = generate_state_name(&ident);
+        let proposals_name = generate_proposals_name(&ident);
+        let parties_name = generate_parties_name(&ident);
         let mated_name = generate_mated_name(&ident);
 
         Ok(ir::ItemContract {
@@ -229,13 +247,61 @@ impl TryFrom<syn::ItemStruct> for ir::ItemContract {
             ident,
             fields: fields.clone(),
             field_signers,
+            field_observers,
+            field_key,
+            ensures,
             state_name,
+            proposals_name,
+            parties_name,
             mated_name,
             span,
         })
     }
 }
 
+/// Parses the `#[liquid(signers)]` or `#[liquid(observers)]` marker of a
+/// contract field, returning `None` if the field carries no such marker.
+fn parse_parties(
+    field: &syn::Field,
+    markers: &[ir::Marker],
+    role: &str,
+) -> Result<Option<ir::Selector>> {
+    let parties = markers
+        .iter()
+        .filter(|marker| marker.ident == role)
+        .collect::<Vec<_>>();
+    if parties.is_empty() {
+        return Ok(None);
+    }
+
+    let name = field.ident.as_ref().unwrap();
+    if parties.len() > 1 {
+        bail!(
+            field,
+            "duplicated `#[liquid({})]` attributes defined for this field",
+            role
+        )
+    }
+
+    Ok(Some(ir::Selector {
+        from: ir::SelectFrom::This(name.clone()),
+        with: match &parties[0].value {
+            (AttrValue::None, _) => None,
+            (AttrValue::LitStr(path), span) => {
+                let select_path = parse_select_path(&path.value(), *span)?;
+                Some(select_path)
+            }
+            (AttrValue::Ident(ident), span) => {
+                if ident == "inherited" {
+                    Some(ir::SelectWith::Inherited(field.ty.clone()))
+                } else {
+                    bail_span!(*span, "invalid indicators of {}: `{}`", role, ident)
+                }
+            }
+        },
+    }))
+}
+
 impl TryFrom<syn::FnArg> for ir::FnArg {
     type Error = Error;
 
@@ -443,13 +509,41 @@ impl TryFrom<(syn::ImplItemMethod, Selectors, Ident)> for ir::Right {
             }
         };
 
+        // Names of functions generated for `ContractId` and clients of contracts,
+        // which rights should not shadow.
+        const RESERVED_NAMES: &[&str] = &[
+            "contracts_of",
+            "active",
+            "archive",
+            "by_signer",
+            "id",
+            "from_raw",
+            "delegate",
+            "revoke",
+            "is_delegated",
+            "at",
+            "sign",
+            "fetch",
+        ];
+        let ident = &method.sig.ident;
+        if RESERVED_NAMES.iter().any(|name| ident == name) {
+            bail!(
+                ident,
+                "`{}` is reserved by liquid and can not be used as the name of a right",
+                ident
+            )
+        }
+
         let span = method.span();
         let sig = ir::Signature::try_from(&method.sig)?;
+        check_consumption(&markers, &sig, span)?;
+        let expires_after = parse_deadline(&markers)?;
 
         Ok(Self {
             attrs: method.attrs,
             owners,
             sig,
+            expires_after,
             body: method.block,
             from,
             span,
@@ -457,6 +551,69 @@ impl TryFrom<(syn::ImplItemMethod, Selectors, Ident)> for ir::Right {
     }
 }
 
+/// Parses the `#[liquid(expires_after = "...")]` marker of a right, if any.
+fn parse_deadline(markers: &[ir::Marker]) -> Result<Option<syn::Expr>> {
+    let deadlines = markers
+        .iter()
+        .filter(|marker| marker.ident == "expires_after")
+        .collect::<Vec<_>>();
+    if deadlines.len() > 1 {
+        bail!(
+            &deadlines[1].ident,
+            "duplicated `#[liquid(expires_after)]` attributes defined for this right"
+        )
+    }
+
+    match deadlines.first().map(|marker| &marker.value) {
+        None => Ok(None),
+        Some((AttrValue::LitStr(deadline), _)) => {
+            deadline.parse::<syn::Expr>().map(Some).map_err(|error| {
+                format_err_span!(deadline.span(), "invalid deadline: {}", error)
+            })
+        }
+        Some((_, span)) => bail_span!(*span, "the deadline should be a string literal"),
+    }
+}
+
+/// Checks that the receiver of a right agrees with its
+/// `#[liquid(consuming)]` or `#[liquid(non_consuming)]` marker, if any.
+fn check_consumption(
+    markers: &[ir::Marker],
+    sig: &ir::Signature,
+    span: Span,
+) -> Result<()> {
+    let consuming = markers
+        .iter()
+        .filter(|marker| marker.ident == "consuming")
+        .count();
+    let non_consuming = markers
+        .iter()
+        .filter(|marker| marker.ident == "non_consuming")
+        .count();
+    if consuming + non_consuming > 1 {
+        bail_span!(
+            span,
+            "at most one of `#[liquid(consuming)]` or `#[liquid(non_consuming)]` can be \
+             defined for this right"
+        )
+    }
+
+    let receiver = sig.self_arg();
+    if consuming > 0 && sig.is_self_ref() {
+        bail!(
+            receiver,
+            "a consuming right should take `self` or `mut self` as receiver"
+        )
+    }
+    if non_consuming > 0 && !sig.is_self_ref() {
+        bail!(
+            receiver,
+            "a non-consuming right should take `&self` or `&mut self` as receiver"
+        )
+    }
+    Ok(())
+}
+
 impl TryFrom<(syn::ItemImpl, Selectors)> for ir::ItemRights {
     type Error = Error;
 
@@ -15,8 +15,8 @@ mod syn_def;
 mod utils;
 
 pub use syn_def::{
-    Collaboration, FnArg, IdentType, Item, ItemContract, ItemRights, LiquidItem, Marker,
-    Right, RustItem, SelectFrom, SelectWith, Selector, Signature,
+    Collaboration, ContractKey, Ensure, FnArg, IdentType, Item, ItemContract, ItemRights,
+    LiquidItem, Marker, Right, RustItem, SelectFrom, SelectWith, Selector, Signature,
 };
 
 use proc_macro2::Span;
@@ -61,6 +61,24 @@ pub struct Selector {
     pub with: Option<SelectWith>,
 }
 
+/// The field marked by `#[liquid(key)]` in a contract.
+pub struct ContractKey {
+    /// Name of the key field.
+    pub ident: Ident,
+    /// Type of the key field.
+    pub ty: syn::Type,
+    /// Name of the field in storage which maps keys to ids of contracts.
+    pub index_name: Ident,
+}
+
+/// A precondition declared by `#[liquid(ensure = "...")]` on a contract.
+pub struct Ensure {
+    /// The condition, in which `self` refers to the contract being signed.
+    pub cond: syn::Expr,
+    /// The condition as written by user.
+    pub text: String,
+}
+
 /// The description of contract.
 pub struct ItemContract {
     /// Outer attributes of the contract.
@@ -73,6 +91,12 @@ pub struct ItemContract {
     pub fields: syn::FieldsNamed,
     /// Signers of the contract.
     pub field_signers: Vec<Selector>,
+    /// Parties who may look up the contract without having to sign it.
+    pub field_observers: Vec<Selector>,
+    /// The key by which the contract can be looked up, if any.
+    pub field_key: Option<ContractKey>,
+    /// Preconditions checked before the contract is signed.
+    pub ensures: Vec<Ensure>,
     /// A contract will be mapped to a `Mapping` in storage.
     /// For example, the contract `#[liquid(contract)] struct Foo { ... }`
     /// is mapped to a field with type `Mapping<u32, Foo>` in storage,
@@ -80,6 +104,11 @@ pub struct ItemContract {
     /// with contract name in snake case followed. In the example, the
     /// field name will be "__liquid_foo".
     pub state_name: Ident,
+    /// Name of the field in storage which keeps proposals of the contract.
+    pub proposals_name: Ident,
+    /// Name of the field in storage which maps parties to ids of contracts
+    /// they are involved in.
+    pub parties_name: Ident,
     pub mated_name: Ident,
     /// Span of the contract.
     pub span: Span,
@@ -119,6 +148,9 @@ pub struct Right {
     pub owners: Vec<Selector>,
     /// The signature of the right.
     pub sig: Signature,
+    /// The deadline declared by `#[liquid(expires_after = "...")]`, after
+    /// which the right can not be exercised any more.
+    pub expires_after: Option<syn::Expr>,
     /// The body of the right.
     pub body: syn::Block,
     /// In which contract the right is declared.
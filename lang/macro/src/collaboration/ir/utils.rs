@@ -324,6 +324,30 @@ pub fn generate_state_name(ident: &Ident) -> Ident {
     )
 }
 
+pub fn generate_key_index_name(ident: &Ident) -> Ident {
+    use heck::SnakeCase;
+    Ident::new(
+        &format!("__liquid_{}_keys", ident.to_string().to_snake_case()),
+        ident.span(),
+    )
+}
+
+pub fn generate_proposals_name(ident: &Ident) -> Ident {
+    use heck::SnakeCase;
+    Ident::new(
+        &format!("__liquid_{}_proposals", ident.to_string().to_snake_case()),
+        ident.span(),
+    )
+}
+
+pub fn generate_parties_name(ident: &Ident) -> Ident {
+    use heck::SnakeCase;
+    Ident::new(
+        &format!("__liquid_{}_parties", ident.to_string().to_snake_case()),
+        ident.span(),
+    )
+}
+
 pub fn generate_mated_name(ident: &Ident) -> Ident {
     use heck::CamelCase;
     Ident::new(
@@ -15,7 +15,7 @@ mod ir;
 mod obj_path;
 
 use crate::{common::GenerateCode, utils::check_idents};
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use std::convert::TryFrom;
 use syn::Result;
 
@@ -27,6 +27,14 @@ pub fn generate(attr: TokenStream2, input: TokenStream2) -> TokenStream2 {
 }
 
 fn generate_impl(_attr: TokenStream2, input: TokenStream2) -> Result<TokenStream2> {
+    if cfg!(feature = "solidity-compatible") {
+        bail_span!(
+            Span::call_site(),
+            "`#[liquid::collaboration]` can not be used when compilation feature \
+             `solidity-compatible` is enabled"
+        )
+    }
+
     check_idents(input.clone())?;
 
     let item_mod = syn::parse2::<syn::ItemMod>(input)?;
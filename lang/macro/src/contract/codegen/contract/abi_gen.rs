@@ -10,13 +10,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::errors::generate_error_abis;
 use crate::{
     common::GenerateCode,
-    contract::ir::{Contract, FnArg, Signature},
+    contract::{
+        codegen::utils,
+        ir::{
+            docs::{collect_docs, Docs},
+            layout::StorageLayout,
+            Contract, FnArg, FunctionKind,
+        },
+    },
 };
 use derive_more::From;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
+use std::iter;
 
 #[derive(From)]
 pub struct AbiGen<'a> {
@@ -28,6 +37,9 @@ impl<'a> GenerateCode for AbiGen<'a> {
         let constructor_abi = self.generate_constructor_abi();
         let external_fn_abis = self.generate_external_fn_abis();
         let event_abis = self.generate_event_abis();
+        let error_abis = generate_error_abis(self.contract);
+        let storage_layout = self.generate_storage_layout();
+        let docs = self.generate_docs();
 
         quote! {
             #[cfg(feature = "liquid-abi-gen")]
@@ -41,11 +53,18 @@ impl<'a> GenerateCode for AbiGen<'a> {
                         let constructor_abi = #constructor_abi;
                         let external_fn_abis = #external_fn_abis;
                         let event_abis = #event_abis;
+                        let error_abis = #error_abis;
+                        let storage_layout = #storage_layout;
+                        let (userdoc, devdoc) = #docs;
 
                         liquid_abi_gen::ContractAbi {
                             constructor_abi,
                             external_fn_abis,
                             event_abis,
+                            error_abis,
+                            storage_layout,
+                            userdoc,
+                            devdoc,
                         }
                     }
                 }
@@ -54,24 +73,103 @@ impl<'a> GenerateCode for AbiGen<'a> {
     }
 }
 
-fn generate_fn_inputs(sig: &Signature) -> impl Iterator<Item = TokenStream2> + '_ {
-    sig.inputs.iter().skip(1).map(|arg| match arg {
-        FnArg::Typed(ident_type) => {
-            let ident = &ident_type.ident.to_string();
-            let ty = &ident_type.ty;
+impl<'a> AbiGen<'a> {
+    fn generate_docs(&self) -> TokenStream2 {
+        let contract = self.contract;
+        let notice = &contract.docs.notice;
+        let details = &contract.docs.details;
+        let methods = iter::once(&contract.constructor)
+            .chain(
+                contract
+                    .functions
+                    .iter()
+                    .filter(|func| func.is_external_fn() && !func.is_internal_fn()),
+            )
+            .map(|func| {
+                let name = if matches!(func.kind, FunctionKind::Constructor) {
+                    String::from("constructor")
+                } else {
+                    func.sig.ident.to_string()
+                };
+                let notice = &func.docs.notice;
+                let details = &func.docs.details;
+                let params = func.sig.inputs.iter().filter_map(|arg| match arg {
+                    FnArg::Typed(ident_type) => {
+                        let name = ident_type.ident.to_string();
+                        let docs = &ident_type.docs;
+                        Some(quote! { (String::from(#name), String::from(#docs)) })
+                    }
+                    _ => None,
+                });
+
+                quote! {
+                    docs.method(
+                        String::from(#name),
+                        String::from(#notice),
+                        String::from(#details),
+                        vec![#(#params,)*],
+                    );
+                }
+            });
+        let events = contract.events.iter().map(|event| {
+            let name = event.ident.to_string();
+            let Docs { notice, details } = Docs::from_attrs(&event.attrs);
+            let params = event.fields.iter().filter_map(|field| {
+                let name = field.ident.as_ref()?.to_string();
+                let docs = collect_docs(&field.attrs);
+                Some(quote! { (String::from(#name), String::from(#docs)) })
+            });
 
             quote! {
-                <#ty as liquid_abi_gen::traits::GenerateParamAbi>::generate_param_abi(#ident.to_owned())
+                docs.event(
+                    String::from(#name),
+                    String::from(#notice),
+                    String::from(#details),
+                    vec![#(#params,)*],
+                );
+            }
+        });
+
+        quote! {
+            {
+                let mut docs = liquid_abi_gen::DocsBuilder::new(
+                    String::from(#notice),
+                    String::from(#details),
+                );
+                #(#methods)*
+                #(#events)*
+                docs.done()
             }
         }
-        _ => unreachable!(),
-    })
-}
+    }
+
+    fn generate_storage_layout(&self) -> TokenStream2 {
+        let layout = StorageLayout::from(&self.contract.storage);
+        let version = layout.version;
+        let fields = layout.fields.iter().map(|field| {
+            let name = &field.name;
+            let key = &field.key;
+            let ty = &field.ty;
+            quote! {
+                liquid_abi_gen::FieldLayout {
+                    name: #name.to_owned(),
+                    key: #key.to_owned(),
+                    ty: #ty.to_owned(),
+                }
+            }
+        });
+
+        quote! {
+            liquid_abi_gen::StorageLayout {
+                version: #version,
+                fields: vec![#(#fields,)*],
+            }
+        }
+    }
 
-impl<'a> AbiGen<'a> {
     fn generate_constructor_abi(&self) -> TokenStream2 {
         let constructor = &self.contract.constructor;
-        let input_args = generate_fn_inputs(&constructor.sig);
+        let input_args = utils::generate_fn_inputs(&constructor.sig);
 
         quote! {
             liquid_abi_gen::ConstructorAbi::new_builder()
@@ -82,45 +180,21 @@ impl<'a> AbiGen<'a> {
 
     fn generate_external_fn_abis(&self) -> TokenStream2 {
         let external_fns = &self.contract.functions;
-        let fn_abis = external_fns.iter().filter(|func| func.is_external_fn() && !func.is_internal_fn()).map(|external_fn| {
-            let ident = external_fn.sig.ident.to_string();
-            let input_args = generate_fn_inputs(&external_fn.sig);
-            let output = &external_fn.sig.output;
-            let output_args = match output {
-                syn::ReturnType::Default => quote! {},
-                syn::ReturnType::Type(_, ty) => {
-                    quote! {
-                        <#ty as liquid_abi_gen::traits::GenerateOutputs>::generate_outputs(&mut builder);
-                    }
-                }
-            };
-
-            let constant = !external_fn.sig.is_mut();
-            let build_args = if cfg!(feature = "solidity-compatible") {
-                let state_mutability = if constant { "view" } else { "nonpayable" };
-                quote! {
-                    String::from(#ident), String::from(#state_mutability), #constant
-                }
-            } else {
-                quote! {
-                    String::from(#ident), #constant
-                }
-            };
-
-            quote! {
-                {
-                    let mut builder = liquid_abi_gen::ExternalFnAbi::new_builder(#build_args);
-                    #(builder.input(#input_args);)*
-                    #output_args
-                    builder.done()
-                }
-            }
-        });
+        let fn_abis = external_fns
+            .iter()
+            .filter(|func| func.is_external_fn() && !func.is_internal_fn())
+            .map(|external_fn| utils::generate_fn_abi(&external_fn.sig, external_fn.selector));
+        let mixins = self
+            .contract
+            .mixins
+            .iter()
+            .map(|mixin| mixin.mixin.as_ref().unwrap());
 
         quote! {
             {
                 let mut external_fn_abis = Vec::new();
                 #(external_fn_abis.push(#fn_abis);)*
+                #(external_fn_abis.extend(<Storage as #mixins>::__liquid_external_fn_abis());)*
                 external_fn_abis
             }
         }
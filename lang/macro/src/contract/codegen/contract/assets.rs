@@ -12,7 +12,7 @@
 
 use crate::{
     common::GenerateCode,
-    contract::{ir::Contract, SUPPORTS_ASSET_SIGNATURE},
+    contract::{ir::Contract, ASSET_RECEIVED_SIGNATURE, SUPPORTS_ASSET_SIGNATURE},
     utils as lang_utils,
 };
 use cfg_if::cfg_if;
@@ -67,10 +67,8 @@ impl<'a> Assets<'a> {
                     };
                     if is_contract {
                         type Input = (String,);
-                        const SUPPORTS_ASSET: liquid_primitives::Selector = {
-                            let hash = liquid_primitives::hash::hash(&#supports_asset_signature.as_bytes());
-                            [hash[0], hash[1], hash[2], hash[3]]
-                        };
+                        const SUPPORTS_ASSET: liquid_primitives::Selector =
+                            liquid_primitives::selector(#supports_asset_signature.as_bytes());
                         let mut encoded = SUPPORTS_ASSET.to_vec();
                         encoded.extend(<Input as liquid_abi_codec::Encode>::encode(&(String::from(Self::ASSET_NAME),)));
                         match liquid_lang::env::call::<bool>(&to, &encoded) {
@@ -80,8 +78,58 @@ impl<'a> Assets<'a> {
                     }
                 }
             };
+            let asset_received_signature = ASSET_RECEIVED_SIGNATURE;
+            // Expects the sender of the asset to be bound to `from`.
+            let notify_receiver = |amount_or_id: TokenStream2| {
+                if cfg!(feature = "std") {
+                    quote! {}
+                } else {
+                    quote_spanned! {span =>
+                        if liquid_lang::env::get_external_code_size(to) != 0 {
+                            type Input = (String, address, u64);
+                            const ASSET_RECEIVED: liquid_primitives::Selector =
+                                liquid_primitives::selector(#asset_received_signature.as_bytes());
+                            let mut encoded = ASSET_RECEIVED.to_vec();
+                            encoded.extend(<Input as liquid_abi_codec::Encode>::encode(&(
+                                String::from(Self::ASSET_NAME),
+                                from,
+                                #amount_or_id,
+                            )));
+                            if liquid_lang::env::call::<()>(&to, &encoded).is_err() {
+                                require(false, String::from("the contract rejected ") + Self::ASSET_NAME)
+                            }
+                        }
+                    }
+                }
+            };
+
+            let event_helpers = quote! {
+                fn sender(from_self: bool) -> address {
+                    if from_self {
+                        liquid_lang::env::get_address()
+                    } else {
+                        liquid_lang::env::get_caller()
+                    }
+                }
+                fn emit_issued(to: &address, amount_or_id: u64) {
+                    liquid_lang::env::emit(AssetIssued {
+                        asset: String::from(Self::ASSET_NAME),
+                        to: *to,
+                        amount_or_id,
+                    });
+                }
+                fn emit_transferred(from: address, to: &address, amount_or_id: u64) {
+                    liquid_lang::env::emit(AssetTransferred {
+                        asset: String::from(Self::ASSET_NAME),
+                        from,
+                        to: *to,
+                        amount_or_id,
+                    });
+                }
+            };
 
             if asset.fungible {
+                let notify_receiver = notify_receiver(quote! { self.value });
                 quote_spanned! {span =>
                     #[cfg_attr(test, derive(Debug))]
                     pub struct #ident{
@@ -102,6 +150,7 @@ impl<'a> Assets<'a> {
                         const ISSUER : &'a str = #issuer;
                         const ASSET_NAME : &'a str = #asset_name;
                         const DESCRIPTION: &'a str = #description;
+                        #event_helpers
                         pub fn env(&self) -> liquid_lang::EnvAccess {
                             liquid_lang::EnvAccess {}
                         }
@@ -117,9 +166,24 @@ impl<'a> Assets<'a> {
                                 Self::DESCRIPTION.as_bytes(),
                             )
                         }
+                        pub fn is_registered() -> bool {
+                            liquid_lang::env::is_asset_registered(Self::ASSET_NAME.as_bytes())
+                        }
+                        pub fn ensure_registered() {
+                            if !Self::is_registered() {
+                                require(
+                                    Self::register(),
+                                    String::from("failed to register ") + Self::ASSET_NAME,
+                                );
+                            }
+                        }
                         pub fn total_supply() -> u64 {
                             Self::TOTAL_SUPPLY
                         }
+                        pub fn circulating_supply() -> u64 {
+                            Self::TOTAL_SUPPLY
+                                - liquid_lang::env::get_burnt_asset(Self::ASSET_NAME.as_bytes())
+                        }
                         pub fn issuer() -> address {
                             Self::ISSUER.parse().unwrap()
                         }
@@ -133,11 +197,15 @@ impl<'a> Assets<'a> {
                             )
                         }
                         pub fn issue_to(to: &address, amount: u64) -> bool {
-                            liquid_lang::env::issue_fungible_asset(
+                            let issued = liquid_lang::env::issue_fungible_asset(
                                 to,
                                 Self::ASSET_NAME.as_bytes(),
                                 amount,
-                            )
+                            );
+                            if issued {
+                                Self::emit_issued(to, amount);
+                            }
+                            issued
                         }
                         pub fn withdraw_from_caller(amount: u64) -> Option<Self> {
                             let caller = liquid_lang::env::get_caller();
@@ -163,6 +231,46 @@ impl<'a> Assets<'a> {
                                 from_self: true,
                             })
                         }
+                        pub fn approve(spender: &address, amount: u64) -> bool {
+                            let owner = liquid_lang::env::get_caller();
+                            let current = Self::allowance(&owner, spender);
+                            let approved = liquid_lang::env::approve_asset(
+                                spender,
+                                Self::ASSET_NAME.as_bytes(),
+                                amount,
+                            );
+                            if approved {
+                                let custodian = liquid_lang::env::get_address();
+                                if amount > current {
+                                    Self::emit_transferred(owner, &custodian, amount - current);
+                                } else if amount < current {
+                                    Self::emit_transferred(custodian, &owner, current - amount);
+                                }
+                            }
+                            approved
+                        }
+                        pub fn allowance(owner: &address, spender: &address) -> u64 {
+                            liquid_lang::env::get_asset_allowance(
+                                owner,
+                                spender,
+                                Self::ASSET_NAME.as_bytes(),
+                            )
+                        }
+                        pub fn withdraw_from(owner: &address, amount: u64) -> Option<Self> {
+                            if !liquid_lang::env::spend_asset_allowance(
+                                owner,
+                                Self::ASSET_NAME.as_bytes(),
+                                amount,
+                            ) {
+                                return None;
+                            }
+                            // Approved assets are kept in custody of this contract.
+                            Some(#ident {
+                                value: amount,
+                                stored: false,
+                                from_self: true,
+                            })
+                        }
                         pub fn deposit(mut self, to: &address) {
                             #call_supports_asset
                             self.stored = liquid_lang::env::transfer_asset(
@@ -171,11 +279,29 @@ impl<'a> Assets<'a> {
                                 self.value,
                                 self.from_self,
                             );
+                            if self.stored {
+                                let from = Self::sender(self.from_self);
+                                Self::emit_transferred(from, to, self.value);
+                                #notify_receiver
+                            }
+                        }
+                        pub fn burn(mut self) {
+                            self.stored = liquid_lang::env::burn_asset(
+                                Self::ASSET_NAME.as_bytes(),
+                                self.value,
+                                self.from_self,
+                            );
+                            if self.stored {
+                                let from = Self::sender(self.from_self);
+                                Self::emit_transferred(from, &address::zero(), self.value);
+                            }
                         }
                     }
                 }
             } else {
                 // not fungible token
+                let notify_receiver_in_batch = notify_receiver(quote! { *id });
+                let notify_receiver = notify_receiver(quote! { self.id });
                 quote_spanned! {span =>
                     #[cfg_attr(test, derive(Debug))]
                     pub struct #ident{
@@ -195,6 +321,7 @@ impl<'a> Assets<'a> {
                         const ISSUER : &'a str = #issuer;
                         const ASSET_NAME : &'a str = #asset_name;
                         const DESCRIPTION: &'a str = #description;
+                        #event_helpers
                         #[allow(unused)]
                         pub fn env(&self) -> liquid_lang::EnvAccess {
                             liquid_lang::EnvAccess {}
@@ -214,9 +341,24 @@ impl<'a> Assets<'a> {
                                 Self::DESCRIPTION.as_bytes(),
                             )
                         }
+                        pub fn is_registered() -> bool {
+                            liquid_lang::env::is_asset_registered(Self::ASSET_NAME.as_bytes())
+                        }
+                        pub fn ensure_registered() {
+                            if !Self::is_registered() {
+                                require(
+                                    Self::register(),
+                                    String::from("failed to register ") + Self::ASSET_NAME,
+                                );
+                            }
+                        }
                         pub fn total_supply() -> u64 {
                             Self::TOTAL_SUPPLY
                         }
+                        pub fn circulating_supply() -> u64 {
+                            Self::TOTAL_SUPPLY
+                                - liquid_lang::env::get_burnt_asset(Self::ASSET_NAME.as_bytes())
+                        }
                         pub fn issuer() -> address {
                             Self::ISSUER.parse().unwrap()
                         }
@@ -242,8 +384,23 @@ impl<'a> Assets<'a> {
                                 uri.as_bytes(),
                             ){
                                 0 => None,
-                                v => Some(v),
+                                v => {
+                                    Self::emit_issued(to, v);
+                                    Some(v)
+                                }
+                            }
+                        }
+                        pub fn issue_batch_to(to: &address, uris: &[&str]) -> Vec<u64> {
+                            let uris = uris.iter().map(|uri| uri.as_bytes()).collect::<Vec<_>>();
+                            let ids = liquid_lang::env::issue_not_fungible_assets(
+                                to,
+                                Self::ASSET_NAME.as_bytes(),
+                                &uris,
+                            );
+                            for id in ids.iter() {
+                                Self::emit_issued(to, *id);
                             }
+                            ids
                         }
                         pub fn withdraw_from_caller(id: u64) -> Option<Self> {
                             let caller = liquid_lang::env::get_caller();
@@ -287,6 +444,49 @@ impl<'a> Assets<'a> {
                                 self.id,
                                 self.from_self,
                             );
+                            if self.stored {
+                                let from = Self::sender(self.from_self);
+                                Self::emit_transferred(from, to, self.id);
+                                #notify_receiver
+                            }
+                        }
+                        pub fn transfer_batch(tokens: Vec<Self>, to: &address) {
+                            #call_supports_asset
+                            let (from_self, from_caller): (Vec<Self>, Vec<Self>) =
+                                tokens.into_iter().partition(|token| token.from_self);
+                            for (mut tokens, from_self) in vec![(from_caller, false), (from_self, true)] {
+                                if tokens.is_empty() {
+                                    continue;
+                                }
+                                let ids = tokens.iter().map(|token| token.id).collect::<Vec<_>>();
+                                let stored = liquid_lang::env::transfer_assets(
+                                    to,
+                                    Self::ASSET_NAME.as_bytes(),
+                                    &ids,
+                                    from_self,
+                                );
+                                for token in tokens.iter_mut() {
+                                    token.stored = stored;
+                                }
+                                if stored {
+                                    let from = Self::sender(from_self);
+                                    for id in ids.iter() {
+                                        Self::emit_transferred(from, to, *id);
+                                        #notify_receiver_in_batch
+                                    }
+                                }
+                            }
+                        }
+                        pub fn burn(mut self) {
+                            self.stored = liquid_lang::env::burn_asset(
+                                Self::ASSET_NAME.as_bytes(),
+                                self.id,
+                                self.from_self,
+                            );
+                            if self.stored {
+                                let from = Self::sender(self.from_self);
+                                Self::emit_transferred(from, &address::zero(), self.id);
+                            }
                         }
                     }
                 }
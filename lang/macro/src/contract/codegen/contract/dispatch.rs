@@ -15,7 +15,9 @@ use crate::{
     contract::{
         codegen::utils,
         ir::{Contract, Function, FunctionKind},
+        UPGRADE_TO_NAME,
     },
+    utils as lang_utils,
 };
 
 use derive_more::From;
@@ -32,15 +34,20 @@ impl<'a> GenerateCode for Dispatch<'a> {
     fn generate_code(&self) -> TokenStream2 {
         let marker = self.generate_external_fn_marker();
         let traits = self.generate_external_fn_traits();
+        let selector_checker = self.generate_selector_checker();
         let dispatch = self.generate_dispatch();
+        let fuzzable = self.generate_fuzzable();
         let entry_point = self.generate_entry_point();
 
+        // The dispatcher is kept in test mode as well, which is how contracts
+        // deployed via `test::deploy_at` are called.
         quote! {
-            #[cfg(not(test))]
             const _: () = {
                 #marker
                 #traits
+                #selector_checker
                 #dispatch
+                #fuzzable
                 #entry_point
             };
         }
@@ -61,20 +68,26 @@ impl<'a> Dispatch<'a> {
             .contract
             .functions
             .iter()
-            .filter(|func| matches!(&func.kind, FunctionKind::External(..)))
-            .map(|func| self.generate_external_fn_trait(func));
+            .filter_map(|func| match &func.kind {
+                FunctionKind::External(fn_id, _) => Some((func, *fn_id)),
+                _ => None,
+            })
+            .chain(self.contract.alt_constructors.iter().map(|constructor| {
+                (
+                    constructor,
+                    lang_utils::calculate_fn_id(&constructor.sig.ident),
+                )
+            }))
+            .map(|(func, fn_id)| self.generate_external_fn_trait(func, fn_id));
 
         quote! {
             #(#traits)*
         }
     }
 
-    fn generate_external_fn_trait(&self, func: &Function) -> TokenStream2 {
-        let fn_id = match &func.kind {
-            FunctionKind::External(fn_id, _) => fn_id,
-            _ => unreachable!(),
-        };
-
+    /// Alternative constructors have the traits as well, as they are selected
+    /// by selectors just like external functions.
+    fn generate_external_fn_trait(&self, func: &Function, fn_id: usize) -> TokenStream2 {
         let fn_marker = quote! { FnMarker::<[(); #fn_id]> };
         let sig = &func.sig;
 
@@ -109,8 +122,23 @@ impl<'a> Dispatch<'a> {
         let fn_name_bytes = fn_name.as_bytes();
         let fn_name_len = fn_name.len();
 
-        let selector = if cfg!(feature = "solidity-compatible") {
-            quote! {
+        let input_checker = Ident::new(
+            &format!("__LIQUID_EXTERNAL_INPUT_CHECKER_{}", fn_id),
+            func.span(),
+        );
+        let selector = match &func.selector {
+            Some(selector) => {
+                let selector = selector.iter();
+                quote! {
+                    #[allow(non_camel_case_types)]
+                    struct #input_checker #input_ty_checker;
+
+                    impl liquid_lang::FnSelector for #fn_marker {
+                        const SELECTOR: liquid_primitives::Selector = [#(#selector),*];
+                    }
+                }
+            }
+            None if cfg!(feature = "solidity-compatible") => quote! {
                 impl liquid_lang::FnSelector for #fn_marker {
                     const SELECTOR: liquid_primitives::Selector = {
                         const SIG_LEN: usize =
@@ -118,28 +146,19 @@ impl<'a> Dispatch<'a> {
                             + #fn_name_len
                             + 2;
                         const SIG: [u8; SIG_LEN] = liquid_ty_mapping::composite::<(#(#input_tys,)*), SIG_LEN>(&[#(#fn_name_bytes),*]);
-                        let hash = liquid_primitives::hash::hash(&SIG);
-                        [hash[0], hash[1], hash[2], hash[3]]
+                        liquid_primitives::selector(&SIG)
                     };
                 }
-            }
-        } else {
-            let input_checker = Ident::new(
-                &format!("__LIQUID_EXTERNAL_INPUT_CHECKER_{}", fn_id),
-                func.span(),
-            );
-
-            quote! {
+            },
+            None => quote! {
                 #[allow(non_camel_case_types)]
                 struct #input_checker #input_ty_checker;
 
                 impl liquid_lang::FnSelector for #fn_marker {
-                    const SELECTOR: liquid_primitives::Selector = {
-                        let hash = liquid_primitives::hash::hash(&[#(#fn_name_bytes),*]);
-                        [hash[0], hash[1], hash[2], hash[3]]
-                    };
+                    const SELECTOR: liquid_primitives::Selector =
+                        liquid_primitives::selector(&[#(#fn_name_bytes),*]);
                 }
-            }
+            },
         };
 
         let is_mut = sig.is_mut();
@@ -195,22 +214,112 @@ impl<'a> Dispatch<'a> {
         };
 
         quote! {
-            if selector == <#namespace as liquid_lang::FnSelector>::SELECTOR {
-                #pat_idents_init
+            #pat_idents_init
 
-                #attr
-                let result = storage.#fn_name(#(#input_idents,)*);
+            #attr
+            let result = storage.#fn_name(#(#input_idents,)*);
 
-                if <#namespace as liquid_lang::FnMutability>::IS_MUT {
-                    <Storage as liquid_lang::storage::Flush>::flush(&mut storage);
-                }
+            if <#namespace as liquid_lang::FnMutability>::IS_MUT {
+                <Storage as liquid_lang::storage::Flush>::flush(&mut storage);
+            }
+
+            if core::any::TypeId::of::<<#namespace as liquid_lang::FnOutput>::Output>() != core::any::TypeId::of::<()>() {
+                liquid_lang::env::finish(&result);
+            }
+
+            return Ok(());
+        }
+    }
 
-                if core::any::TypeId::of::<<#namespace as liquid_lang::FnOutput>::Output>() != core::any::TypeId::of::<()>() {
-                    liquid_lang::env::finish(&result);
+    /// Rejects any two external functions sharing a selector, including those
+    /// exported by mixins, otherwise one of them would be unreachable.
+    fn generate_selector_checker(&self) -> TokenStream2 {
+        let (names, fn_ids): (Vec<_>, Vec<_>) = self
+            .contract
+            .functions
+            .iter()
+            .filter_map(|func| match &func.kind {
+                FunctionKind::External(fn_id, _) => {
+                    Some((func.sig.ident.to_string(), *fn_id))
                 }
+                _ => None,
+            })
+            .unzip();
+        let len = fn_ids.len();
+        let mixins = self
+            .contract
+            .mixins
+            .iter()
+            .map(|mixin| mixin.mixin.as_ref().unwrap())
+            .collect::<Vec<_>>();
 
-                return Ok(());
+        let fn_checkers = names.iter().enumerate().map(|(i, name)| {
+            let msg = format!(
+                "the selector of `{}` collides with that of another external function",
+                name
+            );
+            let mixin_checkers = mixins.iter().map(|mixin| {
+                let msg = format!(
+                    "the selector of `{}` collides with that of a method exported by \
+                     mixin `{}`",
+                    name,
+                    quote!(#mixin).to_string().replace(' ', "")
+                );
+                quote! {
+                    assert!(
+                        !liquid_lang::contains_selector(<Storage as #mixin>::__LIQUID_SELECTORS, 0, &SELECTORS[#i]),
+                        #msg
+                    );
+                }
+            });
+            quote! {
+                assert!(
+                    !liquid_lang::contains_selector(&SELECTORS, #i + 1, &SELECTORS[#i]),
+                    #msg
+                );
+                #(#mixin_checkers)*
+            }
+        });
+        let mixin_checkers = mixins.iter().enumerate().map(|(i, mixin)| {
+            let others = &mixins[i + 1..];
+            let msgs = others.iter().map(|other| {
+                format!(
+                    "mixin `{}` and mixin `{}` export methods with the same selector",
+                    quote!(#mixin).to_string().replace(' ', ""),
+                    quote!(#other).to_string().replace(' ', "")
+                )
+            });
+            let msg = format!(
+                "mixin `{}` exports methods with the same selector",
+                quote!(#mixin).to_string().replace(' ', "")
+            );
+            quote! {
+                let selectors = <Storage as #mixin>::__LIQUID_SELECTORS;
+                let mut i = 0;
+                while i < selectors.len() {
+                    assert!(
+                        !liquid_lang::contains_selector(selectors, i + 1, &selectors[i]),
+                        #msg
+                    );
+                    #(
+                        assert!(
+                            !liquid_lang::contains_selector(<Storage as #others>::__LIQUID_SELECTORS, 0, &selectors[i]),
+                            #msgs
+                        );
+                    )*
+                    i += 1;
+                }
             }
+        });
+
+        quote! {
+            const _: () = {
+                const SELECTORS: [liquid_primitives::Selector; #len] = [
+                    #(<FnMarker<[(); #fn_ids]> as liquid_lang::FnSelector>::SELECTOR,)*
+                ];
+                #(#fn_checkers)*
+                #(#mixin_checkers)*
+            };
         }
     }
 
@@ -227,13 +336,50 @@ impl<'a> Dispatch<'a> {
         }
     }
 
+    fn generate_upgrade_route(&self) -> TokenStream2 {
+        if !self.contract.storage.upgradeable {
+            return quote! {};
+        }
+
+        let fn_id = lang_utils::calculate_fn_id(&UPGRADE_TO_NAME);
+        quote! {
+            const _: () = assert!(
+                liquid_lang::upgrade::DELEGATE_CALL_ENABLED,
+                "upgradeable contracts require the `ext-delegate-call` feature of liquid_lang"
+            );
+            let call_data = match liquid_lang::upgrade::route(
+                call_data,
+                <FnMarker<[(); #fn_id]> as liquid_lang::FnSelector>::SELECTOR,
+            )? {
+                Some(call_data) => call_data,
+                None => return Ok(()),
+            };
+        }
+    }
+
     fn generate_dispatch(&self) -> TokenStream2 {
-        let fragments = self.contract.functions.iter().map(|func| {
-            let is_getter = matches!(func.kind, FunctionKind::External(_, true));
-            self.generate_dispatch_fragment(func, is_getter)
-        });
+        let (fn_ids, fragments): (Vec<_>, Vec<_>) = self
+            .contract
+            .functions
+            .iter()
+            .filter_map(|func| match &func.kind {
+                FunctionKind::External(fn_id, is_getter) => {
+                    Some((*fn_id, self.generate_dispatch_fragment(func, *is_getter)))
+                }
+                _ => None,
+            })
+            .unzip();
+        let len = fn_ids.len();
+        let indices = 0..len;
+
+        let mixins = self
+            .contract
+            .mixins
+            .iter()
+            .map(|mixin| mixin.mixin.as_ref().unwrap());
 
         let constr_input_ty_checker = self.generate_constr_input_ty_checker();
+        let route = self.generate_upgrade_route();
 
         quote! {
             #constr_input_ty_checker
@@ -243,10 +389,30 @@ impl<'a> Dispatch<'a> {
                     let mut storage = <Storage as liquid_lang::storage::New>::new();
                     let call_data = liquid_lang::env::get_call_data(liquid_lang::env::CallMode::Call)
                         .map_err(|_| liquid_lang::DispatchError::CouldNotReadInput)?;
+                    #route
                     let selector = call_data.selector;
                     let data = call_data.data;
 
-                    #(#fragments)*
+                    // Selectors are sorted at compile time, so that finding the
+                    // method to call takes logarithmic comparisons.
+                    const SELECTOR_TABLE: [(liquid_primitives::Selector, usize); #len] =
+                        liquid_lang::sort_selectors([
+                            #(<FnMarker<[(); #fn_ids]> as liquid_lang::FnSelector>::SELECTOR,)*
+                        ]);
+                    match liquid_lang::search_selector(&SELECTOR_TABLE, &selector) {
+                        #(
+                            Some(#indices) => {
+                                #fragments
+                            }
+                        )*
+                        _ => (),
+                    }
+
+                    #(
+                        if let Some(result) = <Storage as #mixins>::__liquid_dispatch(&mut storage, &selector, &data) {
+                            return result;
+                        }
+                    )*
 
                     Err(liquid_lang::DispatchError::UnknownSelector)
                 }
@@ -254,6 +420,35 @@ impl<'a> Dispatch<'a> {
         }
     }
 
+    /// Lists external functions for `liquid_lang::fuzz`, leaving out those
+    /// generated by the macro itself and `upgrade_to`, which would make the
+    /// contract forward later calls to an arbitrary address.
+    fn generate_fuzzable(&self) -> TokenStream2 {
+        let (names, fn_ids): (Vec<_>, Vec<_>) = self
+            .contract
+            .functions
+            .iter()
+            .filter_map(|func| match &func.kind {
+                FunctionKind::External(fn_id, _) => {
+                    Some((func.sig.ident.to_string(), *fn_id))
+                }
+                _ => None,
+            })
+            .filter(|(name, _)| !name.starts_with("__liquid") && name != UPGRADE_TO_NAME)
+            .unzip();
+
+        quote! {
+            #[cfg(all(test, feature = "liquid-fuzz"))]
+            impl liquid_lang::fuzz::Fuzzable for TestableStorage {
+                fn methods() -> Vec<liquid_lang::fuzz::Method> {
+                    vec![
+                        #(liquid_lang::fuzz::Method::new::<FnMarker<[(); #fn_ids]>>(#names),)*
+                    ]
+                }
+            }
+        }
+    }
+
     #[cfg(feature = "std")]
     fn generate_entry_point(&self) -> TokenStream2 {
         quote!()
@@ -293,6 +488,33 @@ impl<'a> Dispatch<'a> {
                 let result = <(#(#input_tys,)*) as scale::Decode>::decode(&mut data.as_slice());
             }
         };
+        let construct = quote! {
+            #decode_result
+
+            if let Ok(data) = result {
+                let #pat_idents = data;
+                storage.#ident(#(#input_idents,)*);
+                <Storage as liquid_lang::storage::Flush>::flush(&mut storage);
+            } else {
+                liquid_lang::env::revert(&String::from("invalid params"));
+            }
+        };
+        let construct = if self.contract.alt_constructors.is_empty() {
+            construct
+        } else {
+            let alt_constructs = self
+                .contract
+                .alt_constructors
+                .iter()
+                .map(|constructor| self.generate_alt_construct(constructor));
+            quote! {
+                let mut constructed = false;
+                #(#alt_constructs)*
+                if !constructed {
+                    #construct
+                }
+            }
+        };
 
         quote! {
             #[no_mangle]
@@ -310,15 +532,7 @@ impl<'a> Dispatch<'a> {
                 let result = liquid_lang::env::get_call_data(liquid_lang::env::CallMode::Deploy);
                 if let Ok(call_data) = result {
                     let data = call_data.data;
-                    #decode_result
-
-                    if let Ok(data) = result {
-                        let #pat_idents = data;
-                        storage.#ident(#(#input_idents,)*);
-                        <Storage as liquid_lang::storage::Flush>::flush(&mut storage);
-                    } else {
-                        liquid_lang::env::revert(&String::from("invalid params"));
-                    }
+                    #construct
                 } else {
                     liquid_lang::env::revert(&String::from("could not read input"));
                 }
@@ -334,4 +548,41 @@ impl<'a> Dispatch<'a> {
             }
         }
     }
+
+    /// Runs the alternative constructor if the deployment data starts with
+    /// its selector, and the remaining data are valid arguments of it.
+    #[cfg(not(feature = "std"))]
+    fn generate_alt_construct(&self, constructor: &Function) -> TokenStream2 {
+        let fn_id = lang_utils::calculate_fn_id(&constructor.sig.ident);
+        let namespace = quote! { FnMarker<[(); #fn_id]> };
+        let ident = &constructor.sig.ident;
+        let input_idents = utils::generate_input_idents(&constructor.sig.inputs);
+        let pat_idents = if input_idents.is_empty() {
+            quote! { _ }
+        } else {
+            quote! { (#(#input_idents,)*) }
+        };
+        let decode_result = if cfg!(feature = "solidity-compatible") {
+            quote! {
+                <<#namespace as liquid_lang::FnInput>::Input as liquid_abi_codec::Decode>::decode(&mut &data[4..])
+            }
+        } else {
+            quote! {
+                <<#namespace as liquid_lang::FnInput>::Input as scale::Decode>::decode(&mut &data[4..])
+            }
+        };
+
+        quote! {
+            if !constructed
+                && data.len() >= 4
+                && data[..4] == <#namespace as liquid_lang::FnSelector>::SELECTOR[..]
+            {
+                if let Ok(#pat_idents) = #decode_result {
+                    storage.#ident(#(#input_idents,)*);
+                    <Storage as liquid_lang::storage::Flush>::flush(&mut storage);
+                    constructed = true;
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,174 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    common::GenerateCode,
+    contract::ir::{Contract, Function, ItemError},
+    utils as lang_utils,
+};
+use derive_more::From;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{
+    spanned::Spanned,
+    visit::{self, Visit},
+};
+
+#[derive(From)]
+pub struct Errors<'a> {
+    contract: &'a Contract,
+}
+
+impl<'a> GenerateCode for Errors<'a> {
+    fn generate_code(&self) -> TokenStream2 {
+        let errors = self.contract.errors.iter().map(generate_error);
+        quote! {
+            #(#errors)*
+        }
+    }
+}
+
+fn generate_error(error: &ItemError) -> TokenStream2 {
+    let span = error.span();
+    let attrs = lang_utils::filter_non_liquid_attributes(&error.attrs);
+    let ident = &error.ident;
+    let variant_attrs = error
+        .variants
+        .iter()
+        .map(|variant| lang_utils::filter_non_liquid_attributes(&variant.attrs))
+        .map(|attrs| quote! { #(#attrs)* });
+    let variant_idents = error
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+    let keys = variant_idents
+        .iter()
+        .map(|variant_ident| format!("{}::{}", ident, variant_ident));
+    let messages = error.variants.iter().map(|variant| &variant.message);
+
+    quote_spanned! { span =>
+        #(#attrs)*
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        #[cfg_attr(test, derive(Debug))]
+        pub enum #ident {
+            #(#variant_attrs #variant_idents,)*
+        }
+
+        impl liquid_lang::intrinsics::ErrorCode for #ident {
+            fn code(&self) -> u32 {
+                match self {
+                    #(Self::#variant_idents => {
+                        const CODE: u32 = liquid_lang::intrinsics::error_code(#keys.as_bytes());
+                        CODE
+                    })*
+                }
+            }
+
+            fn message(&self) -> &'static str {
+                match self {
+                    #(Self::#variant_idents => #messages,)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates the entries of the error registry for all `#[liquid(error)]`
+/// variants and all `require!` sites with a literal message.
+pub fn generate_error_abis(contract: &Contract) -> TokenStream2 {
+    let variant_abis = contract.errors.iter().flat_map(|error| {
+        let ident = &error.ident;
+        error.variants.iter().map(move |variant| {
+            let span = variant.ident.span();
+            let name = format!("{}::{}", ident, variant.ident);
+            let message = &variant.message;
+            quote_spanned! { span =>
+                liquid_abi_gen::ErrorAbi {
+                    code: liquid_lang::intrinsics::error_code(#name.as_bytes()),
+                    name: String::from(#name),
+                    message: String::from(#message),
+                    file: String::from(file!()),
+                    line: line!(),
+                }
+            }
+        })
+    });
+
+    let mut sites = RequireSites::default();
+    core::iter::once(&contract.constructor)
+        .chain(contract.alt_constructors.iter())
+        .chain(contract.functions.iter())
+        .for_each(|function: &Function| sites.visit_block(&function.body));
+    contract
+        .helpers
+        .iter()
+        .chain(
+            contract
+                .mixins
+                .iter()
+                .flat_map(|mixin| mixin.helpers.iter()),
+        )
+        .for_each(|helper| sites.visit_block(&helper.block));
+    let site_abis = sites.0.iter().map(|message| {
+        let span = message.span();
+        quote_spanned! { span =>
+            liquid_abi_gen::ErrorAbi {
+                code: liquid_lang::intrinsics::error_code(#message.as_bytes()),
+                name: String::new(),
+                message: String::from(#message),
+                file: String::from(file!()),
+                line: line!(),
+            }
+        }
+    });
+
+    quote! {
+        {
+            let mut error_abis = Vec::new();
+            #(error_abis.push(#variant_abis);)*
+            #(error_abis.push(#site_abis);)*
+            error_abis
+        }
+    }
+}
+
+/// Collects the literal messages of `require!` invocations. Invocations
+/// nested in other macros are invisible here, since the arguments of a macro
+/// are opaque tokens until it is expanded.
+#[derive(Default)]
+struct RequireSites(Vec<syn::LitStr>);
+
+impl<'ast> Visit<'ast> for RequireSites {
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let is_require = mac
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "require");
+        if is_require {
+            let args = mac.parse_body_with(
+                syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated,
+            );
+            if let Ok(args) = args {
+                if let Some(syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(message),
+                    ..
+                })) = args.iter().nth(1)
+                {
+                    self.0.push(message.clone());
+                }
+            }
+        }
+        visit::visit_macro(self, mac);
+    }
+}
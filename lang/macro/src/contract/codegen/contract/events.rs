@@ -10,12 +10,63 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{common::GenerateCode, contract::ir::Contract, utils as lang_utils};
+use crate::{
+    common::GenerateCode,
+    contract::{
+        codegen::utils as codegen_utils,
+        ir::{Contract, ItemEvent},
+    },
+    utils as lang_utils,
+};
 use derive_more::From;
-use proc_macro2::TokenStream as TokenStream2;
-use quote::{quote, quote_spanned};
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned};
 use syn::spanned::Spanned;
 
+/// Fields of these types are borrowed by the counterparts of events used by
+/// `emit_ref`, as `&str` and `&[u8]` respectively, while fields of other
+/// types are still owned.
+enum BorrowedField {
+    Str,
+    Bytes,
+}
+
+impl BorrowedField {
+    fn of(ty: &syn::Type) -> Option<Self> {
+        let path = match ty {
+            syn::Type::Path(ty_path) if ty_path.qself.is_none() => &ty_path.path,
+            _ => return None,
+        };
+        let segment = path.segments.last()?;
+        if !segment.arguments.is_empty() {
+            return None;
+        }
+        match segment.ident.to_string().as_str() {
+            "String" => Some(BorrowedField::Str),
+            "Bytes" | "bytes" => Some(BorrowedField::Bytes),
+            _ => None,
+        }
+    }
+
+    fn ty(&self) -> TokenStream2 {
+        match self {
+            BorrowedField::Str => quote! { &'a str },
+            BorrowedField::Bytes => quote! { &'a [u8] },
+        }
+    }
+}
+
+pub fn has_borrowed_fields(item_event: &ItemEvent) -> bool {
+    item_event
+        .fields
+        .iter()
+        .any(|field| BorrowedField::of(&field.ty).is_some())
+}
+
+fn ref_ident(item_event: &ItemEvent) -> Ident {
+    format_ident!("{}Ref", item_event.ident)
+}
+
 #[derive(From)]
 pub struct Events<'a> {
     contract: &'a Contract,
@@ -30,6 +81,12 @@ impl<'a> GenerateCode for Events<'a> {
         let event_enum = self.generate_event_enum();
         let topics_impls = self.generate_topics_impls();
         let emit_trait = self.generate_emit_trait();
+        let borrowed_events = self.generate_borrowed_events();
+        let borrowed_exports = if self.contract.events.iter().any(has_borrowed_fields) {
+            quote! { pub use __liquid_event::{EventRef, EmitRef}; }
+        } else {
+            quote! {}
+        };
 
         quote! {
             mod __liquid_event {
@@ -39,9 +96,11 @@ impl<'a> GenerateCode for Events<'a> {
                 #(#topics_impls)*
                 #event_enum
                 #emit_trait
+                #borrowed_events
             }
 
             pub use __liquid_event::{Event, Emit};
+            #borrowed_exports
         }
     }
 }
@@ -70,6 +129,206 @@ impl<'a> Events<'a> {
         }
     }
 
+    /// Generates `EventRef`, the counterpart of `Event` for events with
+    /// borrowed fields, and `EmitRef` to emit them.
+    fn generate_borrowed_events(&self) -> TokenStream2 {
+        let borrowed_events = self
+            .contract
+            .events
+            .iter()
+            .filter(|item_event| has_borrowed_fields(item_event))
+            .collect::<Vec<_>>();
+        if borrowed_events.is_empty() {
+            return quote! {};
+        }
+
+        let event_idents = borrowed_events
+            .iter()
+            .map(|item_event| &item_event.ident)
+            .collect::<Vec<_>>();
+        let ref_idents = borrowed_events
+            .iter()
+            .map(|item_event| ref_ident(item_event))
+            .collect::<Vec<_>>();
+        let impls = borrowed_events
+            .iter()
+            .map(|item_event| self.generate_borrowed_event_impls(item_event));
+
+        let encode = if cfg!(feature = "solidity-compatible") {
+            quote! {
+                impl<'a> liquid_abi_codec::Encode for EventRef<'a> {
+                    fn encode(&self) -> Vec<u8> {
+                        match self {
+                            #(
+                                EventRef::#event_idents(event) => event.encode(),
+                            )*
+                        }
+                    }
+                }
+            }
+        } else {
+            quote! {
+                impl<'a> scale::Encode for EventRef<'a> {
+                    fn encode(&self) -> Vec<u8> {
+                        match self {
+                            #(
+                                EventRef::#event_idents(event) => event.encode(),
+                            )*
+                        }
+                    }
+                }
+            }
+        };
+
+        quote! {
+            #(#impls)*
+
+            pub enum EventRef<'a> {
+                #(#event_idents(#ref_idents<'a>),)*
+            }
+
+            #(
+                impl<'a> From<#ref_idents<'a>> for EventRef<'a> {
+                    fn from(event: #ref_idents<'a>) -> Self {
+                        EventRef::#event_idents(event)
+                    }
+                }
+            )*
+
+            impl<'a> liquid_primitives::Topics for EventRef<'a> {
+                fn write_topics(
+                    &self,
+                    topics: &mut [liquid_primitives::types::Hash; liquid_primitives::MAX_TOPICS],
+                ) -> usize {
+                    match self {
+                        #(
+                            EventRef::#event_idents(event) => event.write_topics(topics),
+                        )*
+                    }
+                }
+            }
+
+            #encode
+
+            impl<'a> liquid_lang::BorrowedEvent for EventRef<'a> {
+                type Owned = Event;
+
+                fn into_owned_event(self) -> Event {
+                    match self {
+                        #(
+                            EventRef::#event_idents(event) => {
+                                Event::#event_idents(event.into_owned_event())
+                            }
+                        )*
+                    }
+                }
+            }
+
+            pub trait EmitRef {
+                fn emit_ref<'a, E>(self, event: E)
+                where
+                    E: Into<EventRef<'a>>;
+            }
+
+            impl EmitRef for liquid_lang::EnvAccess {
+                fn emit_ref<'a, E>(self, event: E)
+                where
+                    E: Into<EventRef<'a>>
+                {
+                    liquid_lang::env::emit_ref(event.into())
+                }
+            }
+        }
+    }
+
+    fn generate_borrowed_event_impls(&self, item_event: &ItemEvent) -> TokenStream2 {
+        let span = item_event.span;
+        let event_ident = &item_event.ident;
+        let ref_ident = ref_ident(item_event);
+        let event_fields = &item_event.fields;
+
+        // Topics and encodings of borrowed fields are the same as the ones of
+        // `String` and `Bytes`.
+        let calculate_topics = item_event.indexed_fields.iter().map(|index| {
+            let ident = &event_fields[*index].ident;
+            let ty = &event_fields[*index].ty;
+            match BorrowedField::of(ty) {
+                Some(BorrowedField::Str) => quote! {
+                    liquid_primitives::hash::hash(self.#ident.as_bytes()).into()
+                },
+                Some(BorrowedField::Bytes) => quote! {
+                    liquid_primitives::hash::hash(self.#ident).into()
+                },
+                None => quote_spanned! { ty.span() =>
+                    <#ty as liquid_lang::You_Should_Use_An_Valid_Event_Topic_Type>::topic(&self.#ident)
+                },
+            }
+        });
+
+        let owned_fields = event_fields.iter().map(|field| {
+            let ident = &field.ident;
+            let ty = &field.ty;
+            match BorrowedField::of(ty) {
+                Some(_) => quote! { #ident: <#ty>::from(self.#ident) },
+                None => quote! { #ident: self.#ident },
+            }
+        });
+
+        let mut impls = quote_spanned! { span =>
+            impl<'a> liquid_primitives::Topics for #ref_ident<'a> {
+                fn write_topics(
+                    &self,
+                    topics: &mut [liquid_primitives::types::Hash; liquid_primitives::MAX_TOPICS],
+                ) -> usize {
+                    let event_topics: &[liquid_primitives::types::Hash] =
+                        &[#event_ident::SIG_HASH.into(), #(#calculate_topics,)*];
+                    topics[..event_topics.len()].copy_from_slice(event_topics);
+                    event_topics.len()
+                }
+            }
+
+            impl<'a> liquid_lang::BorrowedEvent for #ref_ident<'a> {
+                type Owned = #event_ident;
+
+                fn into_owned_event(self) -> #event_ident {
+                    #event_ident {
+                        #(#owned_fields,)*
+                    }
+                }
+            }
+        };
+
+        if cfg!(feature = "solidity-compatible") {
+            let mediate_encode = item_event.unindexed_fields.iter().map(|index| {
+                let ident = &event_fields[*index].ident;
+                let ty = &event_fields[*index].ty;
+                match BorrowedField::of(ty) {
+                    Some(_) => quote! {
+                        liquid_abi_codec::MediateEncode::encode(self.#ident)
+                    },
+                    None => quote! {
+                        <#ty as liquid_abi_codec::MediateEncode>::encode(&self.#ident)
+                    },
+                }
+            });
+
+            impls.extend(quote_spanned! { span =>
+                impl<'a> liquid_abi_codec::Encode for #ref_ident<'a> {
+                    fn encode(&self) -> liquid_prelude::vec::Vec<u8> {
+                        #[allow(unused_mut)]
+                        let mut mediates = Vec::<liquid_abi_codec::Mediate>::new();
+                        #(
+                            mediates.push(#mediate_encode);
+                        )*
+                        liquid_abi_codec::encode_head_tail(&mediates).iter().flat_map(|word| word.to_vec()).collect()
+                    }
+                }
+            });
+        }
+
+        impls
+    }
+
     fn generate_event_enum(&self) -> TokenStream2 {
         let event_idents = self
             .contract
@@ -117,17 +376,48 @@ impl<'a> Events<'a> {
                 }
             )*
 
+            impl Event {
+                /// Recovers an event of this contract from topics and data of a
+                /// log, returns `None` if the log is not emitted as any of them.
+                pub fn try_from_log(
+                    topics: &[liquid_primitives::types::Hash],
+                    data: &[u8],
+                ) -> Option<Self> {
+                    #(
+                        if let Some(event) = #event_idents::try_from_log(topics, data) {
+                            return Some(Event::#event_idents(event));
+                        }
+                    )*
+                    None
+                }
+            }
+
             impl liquid_primitives::Topics for Event {
-                fn topics(&self) -> liquid_prelude::vec::Vec<liquid_primitives::types::Hash> {
+                fn write_topics(
+                    &self,
+                    topics: &mut [liquid_primitives::types::Hash; liquid_primitives::MAX_TOPICS],
+                ) -> usize {
                     match self {
                         #(
-                            Event::#event_idents(event) => event.topics(),
+                            Event::#event_idents(event) => event.write_topics(topics),
                         )*
                     }
                 }
             }
 
             #encode
+
+            #(
+                #[cfg(test)]
+                impl liquid_lang::env::test::EmittedEvent for #event_idents {
+                    fn from_payload(payload: &dyn core::any::Any) -> Option<&Self> {
+                        match payload.downcast_ref::<Event>() {
+                            Some(Event::#event_idents(event)) => Some(event),
+                            _ => payload.downcast_ref::<Self>(),
+                        }
+                    }
+                }
+            )*
         }
     }
 
@@ -193,10 +483,36 @@ impl<'a> Events<'a> {
                 }
             };
 
+            let decode = codegen_utils::generate_event_decode(
+                item_event,
+                cfg!(feature = "solidity-compatible"),
+            );
+
             let mut impls =  quote_spanned! { span =>
+                impl #event_ident {
+                    /// Hash of the signature of this event, which is the first topic of
+                    /// logs of this event.
+                    pub const SIG_HASH: [u8; 32] = #sig_hash;
+
+                    /// Recovers an event from topics and data of a log, returns `None`
+                    /// if the log is not emitted as this event.
+                    pub fn try_from_log(
+                        topics: &[liquid_primitives::types::Hash],
+                        data: &[u8],
+                    ) -> Option<Self> {
+                        #decode
+                    }
+                }
+
                 impl liquid_primitives::Topics for #event_ident {
-                    fn topics(&self) -> liquid_prelude::vec::Vec<liquid_primitives::types::Hash> {
-                        [#sig_hash.into(), #topic_hash].to_vec()
+                    fn write_topics(
+                        &self,
+                        topics: &mut [liquid_primitives::types::Hash; liquid_primitives::MAX_TOPICS],
+                    ) -> usize {
+                        let event_topics: &[liquid_primitives::types::Hash] =
+                            &[Self::SIG_HASH.into(), #topic_hash];
+                        topics[..event_topics.len()].copy_from_slice(event_topics);
+                        event_topics.len()
                     }
                 }
             };
@@ -264,13 +580,42 @@ impl<'a> EventStructs<'a> {
                     .retain(|attr| !lang_utils::is_liquid_attribute(attr));
             });
 
-            quote_spanned!(span =>
+            let mut structs = quote_spanned!(span =>
                 #(#attrs)*
-                #[cfg_attr(not(feature = "solidity-compatible"), derive(scale::Encode))]
+                #[cfg_attr(not(feature = "solidity-compatible"), derive(scale::Encode, scale::Decode))]
                 pub struct #ident {
                     #(#fields,)*
                 }
-            )
+            );
+
+            if has_borrowed_fields(item_event) {
+                let ref_ident = ref_ident(item_event);
+                let doc = format!(
+                    " Same as `{}`, but borrows its strings and bytes, so that it can \
+                     be emitted via `EmitRef::emit_ref` without cloning them.",
+                    ident
+                );
+                let ref_fields = fields.iter().map(|field| {
+                    let field_ident = &field.ident;
+                    let ty = match BorrowedField::of(&field.ty) {
+                        Some(borrowed) => borrowed.ty(),
+                        None => {
+                            let ty = &field.ty;
+                            quote! { #ty }
+                        }
+                    };
+                    quote! { pub #field_ident: #ty }
+                });
+                structs.extend(quote_spanned!(span =>
+                    #[doc = #doc]
+                    #[cfg_attr(not(feature = "solidity-compatible"), derive(scale::Encode))]
+                    pub struct #ref_ident<'a> {
+                        #(#ref_fields,)*
+                    }
+                ));
+            }
+
+            structs
         })
     }
 }
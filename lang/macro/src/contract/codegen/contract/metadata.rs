@@ -0,0 +1,52 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{common::GenerateCode, contract::ir::Contract};
+use derive_more::From;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+
+/// Should be kept in sync with `liquid_lang::metadata`.
+const SECTION_NAME: &str = "liquid_metadata";
+const SCHEMA_VERSION: u8 = 2;
+
+#[derive(From)]
+pub struct Metadata<'a> {
+    contract: &'a Contract,
+}
+
+impl<'a> GenerateCode for Metadata<'a> {
+    fn generate_code(&self) -> TokenStream2 {
+        let storage_ident = &self.contract.storage.ident;
+        let name = storage_ident.to_string();
+        // Same layout as the SCALE encoding of `liquid_lang::metadata::Metadata`.
+        let encoded = scale::Encode::encode(&(
+            SCHEMA_VERSION,
+            env!("CARGO_PKG_VERSION"),
+            name,
+            self.contract.source_hash,
+            &self.contract.meta_info.version,
+        ));
+        let len = encoded.len();
+
+        quote! {
+            #[doc(hidden)]
+            #[used]
+            #[cfg_attr(target_arch = "wasm32", link_section = #SECTION_NAME)]
+            pub static __LIQUID_METADATA: [u8; #len] = [#(#encoded),*];
+
+            impl liquid_lang::metadata::ContractMetadata for #storage_ident {
+                const ENCODED: &'static [u8] = &__LIQUID_METADATA;
+            }
+        }
+    }
+}
@@ -13,7 +13,9 @@
 mod abi_gen;
 mod assets;
 mod dispatch;
+mod errors;
 mod events;
+mod metadata;
 mod storage;
 mod testable;
 
@@ -21,7 +23,9 @@ use crate::{common::GenerateCode, contract::ir, utils};
 use abi_gen::AbiGen;
 use assets::Assets;
 use dispatch::Dispatch;
-use events::{EventStructs, Events};
+use errors::Errors;
+use events::{has_borrowed_fields, EventStructs, Events};
+use metadata::Metadata;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use storage::Storage;
@@ -43,10 +47,32 @@ impl GenerateCode for ir::Contract {
         //     }
         // );
         let event_struct = EventStructs::from(self).generate_code();
+        let errors = Errors::from(self).generate_code();
         let dispatch = Dispatch::from(self).generate_code();
         let testable = Testable::from(self).generate_code();
         let abi = AbiGen::from(self).generate_code();
+        let metadata = Metadata::from(self).generate_code();
         let rust_items = &self.rust_items;
+        // Events can be named `Event` as well, in which case the enum of all
+        // the events stays private.
+        let event_enum = if self.events.is_empty()
+            || self.events.iter().any(|event| event.ident == "Event")
+        {
+            quote! {}
+        } else {
+            quote! { pub use __liquid_private::Event; }
+        };
+        // Likewise for the counterparts of events borrowing their fields,
+        // whose names may be taken by the structs generated for such events.
+        let event_ref_enum = if !self.events.iter().any(has_borrowed_fields)
+            || self.events.iter().any(|event| {
+                ["Event", "EventRef", "Emit", "EmitRef"]
+                    .contains(&event.ident.to_string().as_str())
+            }) {
+            quote! {}
+        } else {
+            quote! { pub use __liquid_private::{EmitRef, EventRef}; }
+        };
 
         quote! {
             mod #ident {
@@ -82,7 +108,13 @@ impl GenerateCode for ir::Contract {
                 #[cfg(feature = "liquid-abi-gen")]
                 pub use __liquid_private::__LIQUID_ABI_GEN;
 
+                #metadata
+
                 #event_struct
+                #event_enum
+                #event_ref_enum
+
+                #errors
 
                 #(#rust_items)*
             }
@@ -12,13 +12,21 @@
 
 use crate::{
     common::GenerateCode,
-    contract::ir::{Contract, Function, FunctionKind},
+    contract::{
+        ir::{Contract, Function, FunctionKind},
+        READ_ONLY_VIOLATION,
+    },
     utils as lang_utils,
 };
 use derive_more::From;
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::{quote, quote_spanned};
-use syn::{punctuated::Punctuated, spanned::Spanned, Token};
+use syn::{
+    punctuated::Punctuated,
+    spanned::Spanned,
+    visit_mut::{self, VisitMut},
+    Token,
+};
 
 #[derive(From)]
 pub struct Storage<'a> {
@@ -31,6 +39,13 @@ impl<'a> GenerateCode for Storage<'a> {
         let storage_struct = self.generate_storage_struct();
         let function_impls = self.generate_functions();
         let constants = self.generate_constants();
+        // Makes the contract recompiled whenever the layout file or any of
+        // the included files changes.
+        let tracked_files = self
+            .contract
+            .storage_layout_file
+            .iter()
+            .chain(self.contract.included_files.iter());
 
         quote_spanned! { span =>
             mod __liquid_storage {
@@ -46,6 +61,8 @@ impl<'a> GenerateCode for Storage<'a> {
                 #function_impls
                 #constants
             };
+
+            #(const _: &[u8] = include_bytes!(#tracked_files);)*
         }
     }
 }
@@ -61,6 +78,9 @@ impl<'a> Storage<'a> {
             field.vis = syn::Visibility::Public(syn::VisPublic {
                 pub_token: Default::default(),
             });
+            field.attrs = lang_utils::filter_non_liquid_attributes(&field.attrs)
+                .cloned()
+                .collect();
 
             let ty = &field.ty;
             field.ty = syn::parse2::<syn::Type>(quote_spanned!( ty.span() => <#ty as liquid_lang::storage::You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage>::T)).unwrap();
@@ -85,7 +105,7 @@ impl<'a> Storage<'a> {
 
         let bind_stats = field_idents.iter().enumerate().map(|(i, ident)| {
             quote_spanned! { span =>
-                #ident: liquid_lang::storage::Bind::bind_with(Self::STORAGE_KEYS[#i].as_bytes()),
+                #ident: liquid_lang::storage::Bind::bind_with_name(Self::STORAGE_KEYS[#i].as_bytes(), Self::STORAGE_KEYS[#i]),
             }
         });
 
@@ -97,7 +117,9 @@ impl<'a> Storage<'a> {
 
             impl liquid_lang::storage::Flush for Storage {
                 fn flush(&mut self) {
-                    #(liquid_lang::storage::Flush::flush(&mut self.#field_idents);)*
+                    liquid_lang::env::batch_storage_writes(|| {
+                        #(liquid_lang::storage::Flush::flush(&mut self.#field_idents);)*
+                    })
                 }
             }
 
@@ -118,8 +140,7 @@ impl<'a> Storage<'a> {
         }
     }
 
-    fn generate_constructor(&self) -> TokenStream2 {
-        let constructor = &self.contract.constructor;
+    fn generate_constructor(&self, constructor: &Function) -> TokenStream2 {
         let span = constructor.span();
         let attrs = lang_utils::filter_non_liquid_attributes(constructor.attrs.iter());
         let ident = &constructor.sig.ident;
@@ -147,7 +168,11 @@ impl<'a> Storage<'a> {
         let ident = &sig.ident;
         let inputs = &sig.inputs;
         let output = &sig.output;
-        let body = &function.body;
+        let mut body = function.body.clone();
+        let immutable_fields = self.immutable_field_idents();
+        if !immutable_fields.is_empty() {
+            FreezeImmutables(&immutable_fields).visit_block_mut(&mut body);
+        }
         let stmts = &body.stmts;
         let is_mut = sig.is_mut();
 
@@ -161,9 +186,13 @@ impl<'a> Storage<'a> {
                 #[cfg(test)]
                 #(#attrs)*
                 #vis fn #ident(#inputs) #output {
-                    let result = (move || {
+                    let _call = liquid_lang::env::test::CallScope::enter();
+                    let result = (|| {
                         #(#stmts)*
                     })();
+                    // Write back like the dispatcher does, so that the
+                    // environment always holds the state of the contract.
+                    liquid_lang::storage::Flush::flush(self);
                     liquid_lang::storage::reset_mutable_call_flag();
                     result
                 }
@@ -172,14 +201,16 @@ impl<'a> Storage<'a> {
             quote_spanned! { span =>
                 #(#attrs)*
                 #vis fn #ident(#inputs) #output {
+                    #[cfg(test)]
+                    let _call = liquid_lang::env::test::CallScope::enter();
+                    // The flag may have been set already if this method is
+                    // called by a mutable one, which is allowed to write.
+                    let mutable_call_happened = liquid_lang::storage::has_mutable_call_happens();
                     let result = (move || {
                         #(#stmts)*
                     })();
-                    if liquid_lang::storage::has_mutable_call_happens() {
-                        liquid_lang::env::revert(&String::from(
-                            "attempt to call mutable external interfaces in an immutable \
-                             transaction, all writes will be discarded",
-                        ));
+                    if !mutable_call_happened && liquid_lang::storage::has_mutable_call_happens() {
+                        liquid_lang::env::revert(&String::from(#READ_ONLY_VIOLATION));
                     }
                     result
                 }
@@ -187,24 +218,66 @@ impl<'a> Storage<'a> {
         }
     }
 
+    fn immutable_field_idents(&self) -> Vec<Ident> {
+        let storage = &self.contract.storage;
+        storage
+            .immutable_fields
+            .iter()
+            .map(|i| storage.fields.named[*i].ident.clone().unwrap())
+            .collect()
+    }
+
     fn generate_functions(&self) -> TokenStream2 {
         let storage = &self.contract.storage;
         let span = storage.span();
-        let constructor = self.generate_constructor();
+        let constructors = core::iter::once(&self.contract.constructor)
+            .chain(self.contract.alt_constructors.iter())
+            .map(|constructor| self.generate_constructor(constructor));
         let functions = self
             .contract
             .functions
             .iter()
             .map(|func| self.generate_function(func));
+        let helpers = self
+            .contract
+            .helpers
+            .iter()
+            .map(|helper| self.generate_helper(helper));
+        let mixin_impls = self.contract.mixins.iter().map(|mixin| {
+            let mixin_path = mixin.mixin.as_ref().unwrap();
+            let constants = &mixin.constants;
+            let helpers = mixin
+                .helpers
+                .iter()
+                .map(|helper| self.generate_helper(helper));
+            quote! {
+                impl #mixin_path for Storage {
+                    #(#constants)*
+                    #(#helpers)*
+                }
+            }
+        });
 
         quote_spanned!(span =>
             impl Storage {
-                #constructor
+                #(#constructors)*
                 #(#functions)*
+                #(#helpers)*
             }
+
+            #(#mixin_impls)*
         )
     }
 
+    fn generate_helper(&self, helper: &syn::ImplItemMethod) -> TokenStream2 {
+        let mut helper = helper.clone();
+        let immutable_fields = self.immutable_field_idents();
+        if !immutable_fields.is_empty() {
+            FreezeImmutables(&immutable_fields).visit_block_mut(&mut helper.block);
+        }
+        quote! { #helper }
+    }
+
     fn generate_constants(&self) -> TokenStream2 {
         let constants = &self.contract.constants;
 
@@ -215,3 +288,30 @@ impl<'a> Storage<'a> {
         }
     }
 }
+
+/// Rewrites every `self.field` in a method other than the constructor, where
+/// `field` is marked with `#[liquid(immutable)]`, into a place behind a shared
+/// reference, so that the compiler rejects any attempt to modify it.
+struct FreezeImmutables<'a>(&'a [Ident]);
+
+impl<'a> VisitMut for FreezeImmutables<'a> {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        if let syn::Expr::Field(expr_field) = expr {
+            let is_self = matches!(
+                &*expr_field.base,
+                syn::Expr::Path(path) if path.path.is_ident("self")
+            );
+            if let syn::Member::Named(member) = &expr_field.member {
+                if is_self && self.0.contains(member) {
+                    let span = expr_field.span();
+                    *expr = syn::parse2(quote_spanned! { span =>
+                        (*liquid_lang::storage::read_only(&#expr_field))
+                    })
+                    .unwrap();
+                    return;
+                }
+            }
+        }
+        visit_mut::visit_expr_mut(self, expr);
+    }
+}
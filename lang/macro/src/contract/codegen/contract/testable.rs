@@ -12,7 +12,10 @@
 
 use crate::{
     common::GenerateCode,
-    contract::ir::{Contract, FnArg},
+    contract::{
+        codegen::utils,
+        ir::{Contract, FnArg, Function, FunctionKind},
+    },
     utils as lang_utils,
 };
 use derive_more::From;
@@ -27,7 +30,10 @@ pub struct Testable<'a> {
 impl<'a> GenerateCode for Testable<'a> {
     fn generate_code(&self) -> TokenStream2 {
         let testable_storage = self.generate_testable_storage();
-        let constructor = self.generate_constructor();
+        let constructors = core::iter::once(&self.contract.constructor)
+            .chain(self.contract.alt_constructors.iter())
+            .map(|constructor| self.generate_constructor(constructor));
+        let round_trips = self.generate_round_trips();
 
         quote! {
             #[cfg(test)]
@@ -37,8 +43,16 @@ impl<'a> GenerateCode for Testable<'a> {
                 #testable_storage
 
                 impl TestableStorage {
-                    #constructor
+                    #(#constructors)*
                 }
+
+                impl liquid_lang::env::test::Deployable for TestableStorage {
+                    fn dispatch() -> liquid_lang::DispatchResult {
+                        Storage::dispatch()
+                    }
+                }
+
+                #round_trips
             }
 
             #[cfg(test)]
@@ -83,8 +97,46 @@ impl<'a> Testable<'a> {
         }
     }
 
-    fn generate_constructor(&self) -> TokenStream2 {
-        let constructor = &self.contract.constructor;
+    /// Generates a test checking that the inputs and outputs of constructors
+    /// and external functions survive round trips through the codec.
+    fn generate_round_trips(&self) -> TokenStream2 {
+        let constructors = core::iter::once(&self.contract.constructor)
+            .chain(self.contract.alt_constructors.iter());
+        let external_fns = self
+            .contract
+            .functions
+            .iter()
+            .filter(|func| matches!(func.kind, FunctionKind::External(..)));
+
+        let mut checks = Vec::new();
+        for func in constructors.chain(external_fns) {
+            let sig = &func.sig;
+            let fn_name = sig.ident.to_string();
+            let input_tys = utils::generate_input_tys(sig);
+            if !input_tys.is_empty() {
+                let name = format!("the input of `{}`", fn_name);
+                checks.push(quote! {
+                    liquid_lang::fuzz::assert_round_trips::<(#(#input_tys,)*)>(#name);
+                });
+            }
+            if let syn::ReturnType::Type(_, ty) = &sig.output {
+                let name = format!("the output of `{}`", fn_name);
+                checks.push(quote! {
+                    liquid_lang::fuzz::assert_round_trips::<#ty>(#name);
+                });
+            }
+        }
+
+        quote! {
+            #[cfg(feature = "liquid-fuzz")]
+            #[test]
+            fn codec_round_trips() {
+                #(#checks)*
+            }
+        }
+    }
+
+    fn generate_constructor(&self, constructor: &Function) -> TokenStream2 {
         let attrs = &constructor.attrs;
         let sig = &constructor.sig;
         let ident = &sig.ident;
@@ -96,9 +148,11 @@ impl<'a> Testable<'a> {
 
         quote! {
             #(#attrs)*
-            pub fn #ident(#(#args)*) -> Self {
+            pub fn #ident(#(#args,)*) -> Self {
+                let _call = liquid_lang::env::test::CallScope::enter();
                 let mut contract = <Storage as liquid_lang::storage::New>::new();
-                contract.#ident(#(#arg_idents)*);
+                contract.#ident(#(#arg_idents,)*);
+                liquid_lang::storage::Flush::flush(&mut contract);
                 liquid_lang::storage::reset_mutable_call_flag();
                 Self {
                     contract
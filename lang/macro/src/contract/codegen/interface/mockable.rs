@@ -14,7 +14,7 @@ use crate::{
     common::GenerateCode,
     contract::{
         codegen::utils as codegen_utils,
-        ir::{FnArg, ForeignFn, Interface},
+        ir::{FnArg, ForeignFn, Interface, LangType},
     },
     utils as lang_utils,
 };
@@ -155,6 +155,8 @@ fn generate_mock_common(foreign_fn: &ForeignFn, suffix: usize) -> TokenStream2 {
         pub struct #expectation {
             matcher: #matcher,
             return_fn: #returner,
+            counter: liquid_lang::mock::CallCounter,
+            by_default: bool,
         }
 
         impl Default for #expectation {
@@ -162,6 +164,8 @@ fn generate_mock_common(foreign_fn: &ForeignFn, suffix: usize) -> TokenStream2 {
                 Self {
                     matcher: #matcher::Always,
                     return_fn: #returner::Default,
+                    counter: Default::default(),
+                    by_default: false,
                 }
             }
         }
@@ -202,6 +206,35 @@ fn generate_mock_common(foreign_fn: &ForeignFn, suffix: usize) -> TokenStream2 {
                 self
             }
 
+            pub fn times(&mut self, n: usize) -> &mut Self {
+                self.counter.set_times(liquid_lang::mock::Times::Exactly(n));
+                self
+            }
+
+            pub fn at_least(&mut self, n: usize) -> &mut Self {
+                self.counter.set_times(liquid_lang::mock::Times::AtLeast(n));
+                self
+            }
+
+            pub fn at_most(&mut self, n: usize) -> &mut Self {
+                self.counter.set_times(liquid_lang::mock::Times::AtMost(n));
+                self
+            }
+
+            pub fn never(&mut self) -> &mut Self {
+                self.times(0)
+            }
+
+            pub fn by_default(&mut self) -> &mut Self {
+                self.by_default = true;
+                self
+            }
+
+            pub fn in_sequence(&mut self, sequence: &liquid_lang::mock::Sequence) -> &mut Self {
+                self.counter.join_sequence(sequence);
+                self
+            }
+
             pub fn returns<T>(&mut self, return_value: T)
             where
                 T: Clone + Into<#output_ty> + 'static,
@@ -224,7 +257,109 @@ fn generate_mock_common(foreign_fn: &ForeignFn, suffix: usize) -> TokenStream2 {
     }
 }
 
-fn generate_trivial_fn(foreign_fn: &ForeignFn, interface_ident: &Ident) -> TokenStream2 {
+/// Generates the lookup of the expectation to answer a call of a mocked
+/// method, trying expectations marked by `by_default` only after the others.
+/// Calls matching no expectation go to the contract deployed at the address
+/// of the interface, if there is one, while optional methods return `None`
+/// if there isn't.
+fn generate_call_expectations(
+    foreign_fn: &ForeignFn,
+    expectations: &Ident,
+    interface_ident: &Ident,
+    mock_context_getter: &Ident,
+    address: TokenStream2,
+    is_sol: bool,
+) -> TokenStream2 {
+    let sig = &foreign_fn.sig;
+    let fn_ident = &sig.ident;
+    let inputs = &sig.inputs;
+    let input_idents = codegen_utils::generate_input_idents(inputs);
+    let ref_input_idents = input_idents.iter().map(|ident| quote! {&#ident});
+    let is_mut = sig.is_mut();
+    let optional = foreign_fn.optional;
+    let output_ty = match &sig.output {
+        syn::ReturnType::Default => quote! { () },
+        syn::ReturnType::Type(_, ty) => quote! { #ty },
+    };
+    let mock_name = format!("{}::{}", interface_ident, fn_ident);
+    let call_data = super::generate_call_data(foreign_fn, is_sol);
+
+    quote! {
+        #expectations.with(|expectations| {
+            let mut expectations = expectations.borrow_mut();
+            let mut exhausted = false;
+            for &by_default in [false, true].iter() {
+                for expectation in expectations
+                    .iter_mut()
+                    .filter(|expectation| expectation.by_default == by_default)
+                {
+                    if expectation.matches(#(#ref_input_idents,)*) {
+                        if !expectation.counter.is_available() {
+                            exhausted = true;
+                            continue;
+                        }
+                        expectation.counter.record(#mock_name);
+                        if #is_mut {
+                            liquid_lang::storage::mutable_call_happens();
+                        }
+                        return expectation.call(#(#input_idents,)*);
+                    }
+                }
+            }
+
+            if exhausted {
+                panic!("`{}` is called more times than expected", #mock_name);
+            }
+            // The callee may call this method again.
+            drop(expectations);
+            if liquid_lang::env::test::has_callee(#address) {
+                #call_data
+                let result = if #optional {
+                    liquid_lang::env::optional_call(|| {
+                        liquid_lang::env::call::<#output_ty>(#address, &encoded)
+                    })
+                    .ok()
+                } else {
+                    liquid_lang::env::call::<#output_ty>(#address, &encoded).ok()
+                };
+                if #is_mut {
+                    liquid_lang::storage::mutable_call_happens();
+                }
+                return result;
+            }
+            if #optional {
+                return None;
+            }
+            if liquid_lang::mock::is_lenient() {
+                if let Some(default_value) = DefaultReturner::<#output_ty>::return_default() {
+                    if #is_mut {
+                        liquid_lang::storage::mutable_call_happens();
+                    }
+                    return Some(default_value);
+                }
+            }
+            panic!(
+                "no matched expectation is found for `{}({})` in `{}`, set one up via \
+                 `{}::{}().expect()`, or use `liquid_lang::mock::lenient()` to \
+                 return default values for unexpected calls",
+                stringify!(#fn_ident),
+                stringify!(#inputs)
+                    .replace(" : ", ": ")
+                    .replace("& self", "&self")
+                    .replace("& mut", "&mut"),
+                stringify!(#interface_ident),
+                stringify!(#interface_ident),
+                stringify!(#mock_context_getter),
+            );
+        })
+    }
+}
+
+fn generate_trivial_fn(
+    foreign_fn: &ForeignFn,
+    interface_ident: &Ident,
+    is_sol: bool,
+) -> TokenStream2 {
     let attrs = lang_utils::filter_non_liquid_attributes(foreign_fn.attrs.iter());
     let sig = &foreign_fn.sig;
     let fn_ident = &sig.ident;
@@ -236,14 +371,20 @@ fn generate_trivial_fn(foreign_fn: &ForeignFn, interface_ident: &Ident) -> Token
     };
 
     let common = generate_mock_common(foreign_fn, 0);
+    let mock_name = format!("{}::{}", interface_ident, fn_ident);
+    let expectations = Ident::new("EXPECTATIONS", span);
+    let call_expectations = generate_call_expectations(
+        foreign_fn,
+        &expectations,
+        interface_ident,
+        &mock_context_getter,
+        quote! { &self.__liquid_address },
+        is_sol,
+    );
 
     let inputs = &sig.inputs;
-    let input_idents = codegen_utils::generate_input_idents(inputs);
     let no_self_inputs = inputs.iter().skip(1);
 
-    let ref_input_idents = input_idents.iter().map(|ident| quote! {&#ident});
-    let is_mut = sig.is_mut();
-
     let output = &sig.output;
     let output_ty = match output {
         syn::ReturnType::Default => {
@@ -275,9 +416,14 @@ fn generate_trivial_fn(foreign_fn: &ForeignFn, interface_ident: &Ident) -> Token
 
             impl Drop for Context {
                 fn drop(&mut self) {
-                    EXPECTATIONS.with(|expectations| {
-                        expectations.borrow_mut().clear();
+                    let expectations = EXPECTATIONS.with(|expectations| {
+                        core::mem::take(&mut *expectations.borrow_mut())
                     });
+                    if !std::thread::panicking() {
+                        for expectation in expectations.iter() {
+                            expectation.counter.verify(#mock_name);
+                        }
+                    }
                 }
             }
 
@@ -292,26 +438,8 @@ fn generate_trivial_fn(foreign_fn: &ForeignFn, interface_ident: &Ident) -> Token
                 #(#attrs)*
                 #[allow(non_snake_case)]
                 pub fn #fn_ident(&self, #(#no_self_inputs,)*) -> Option<#output_ty> {
-                    EXPECTATIONS.with(|expectations| {
-                        for expectation in expectations.borrow_mut().iter_mut() {
-                            if expectation.matches(#(#ref_input_idents,)*) {
-                                if #is_mut {
-                                    liquid_lang::storage::mutable_call_happens();
-                                }
-                                return expectation.call(#(#input_idents,)*);
-                            }
-                        }
-
-                        panic!(
-                            "no matched expectation is found for `{}({})` in `{}`",
-                            stringify!(#fn_ident),
-                            stringify!(#inputs)
-                                .replace(" : ", ": ")
-                                .replace("& self", "&self")
-                                .replace("& mut", "&mut"),
-                            stringify!(#interface_ident),
-                        );
-                    })
+                    liquid_lang::env::test::record_call();
+                    #call_expectations
                 }
             }
         };
@@ -322,6 +450,7 @@ fn generate_overriding_fn(
     fn_ident: &Ident,
     foreign_fns: &[ForeignFn],
     interface_ident: &Ident,
+    is_sol: bool,
 ) -> TokenStream2 {
     let mock_context_getter = match &foreign_fns[0].mock_context_getter {
         Some(getter) => getter.clone(),
@@ -331,13 +460,19 @@ fn generate_overriding_fn(
         ),
     };
 
+    let mock_name = format!("{}::{}", interface_ident, fn_ident);
     let all_expectations_drop = foreign_fns.iter().enumerate().map(|(i, foreign_fn)| {
         let expectations = Ident::new(&format!("EXPECTATIONS{}", i), foreign_fn.span);
 
         quote! {
-            #expectations.with(|expectations| {
-                expectations.borrow_mut().clear();
+            let expectations = #expectations.with(|expectations| {
+                core::mem::take(&mut *expectations.borrow_mut())
             });
+            if !std::thread::panicking() {
+                for expectation in expectations.iter() {
+                    expectation.counter.verify(#mock_name);
+                }
+            }
         }
     });
 
@@ -349,9 +484,6 @@ fn generate_overriding_fn(
         let input_tys = codegen_utils::generate_input_tys(&sig);
         let input_idents = codegen_utils::generate_input_idents(inputs);
 
-        let ref_input_idents = input_idents.iter().map(|ident| quote! {&#ident});
-        let is_mut = sig.is_mut();
-
         let output = &sig.output;
         let output_ty = match output {
             syn::ReturnType::Default => {
@@ -366,6 +498,15 @@ fn generate_overriding_fn(
         let call_expectation = Ident::new(&format!("call_expectation{}", i), span);
         let expectation = Ident::new(&format!("Expectation{}", i), span);
         let expectations = Ident::new(&format!("EXPECTATIONS{}", i), span);
+        let call_expectations =
+            generate_call_expectations(
+            foreign_fn,
+            &expectations,
+            interface_ident,
+            &mock_context_getter,
+            quote! { __liquid_address },
+            is_sol,
+        );
 
         quote! {
             #common
@@ -386,46 +527,31 @@ fn generate_overriding_fn(
             }
 
             impl #fn_ident {
-                fn #call_expectation((#(#input_idents,)*): (#(#input_tys,)*)) -> Option<#output_ty> {
-                    #expectations.with(|expectations| {
-                        for expectation in expectations.borrow_mut().iter_mut() {
-                            if expectation.matches(#(#ref_input_idents,)*) {
-                                if #is_mut {
-                                    liquid_lang::storage::mutable_call_happens();
-                                }
-                                return expectation.call(#(#input_idents,)*);
-                            }
-                        }
-
-                        panic!(
-                            "no matched expectation is found for `{}({})` in `{}`",
-                            stringify!(#fn_ident),
-                            stringify!(#inputs)
-                                .replace(" : ", ": ")
-                                .replace("& self", "&self")
-                                .replace("& mut", "&mut"),
-                            stringify!(#interface_ident)
-                        );
-                    })
+                fn #call_expectation(
+                    __liquid_address: &liquid_primitives::types::Address,
+                    (#(#input_idents,)*): (#(#input_tys,)*),
+                ) -> Option<#output_ty> {
+                    liquid_lang::env::test::record_call();
+                    #call_expectations
                 }
             }
 
             impl FnOnce<(#(#input_tys,)*)> for #fn_ident {
                 type Output = Option<#output_ty>;
                 extern "rust-call" fn call_once(self, args: (#(#input_tys,)*)) -> Self::Output {
-                    Self::#call_expectation(args)
+                    Self::#call_expectation(&self.__liquid_address, args)
                 }
             }
 
             impl FnMut<(#(#input_tys,)*)> for #fn_ident {
                 extern "rust-call" fn call_mut(&mut self, args: (#(#input_tys,)*)) -> Self::Output {
-                    Self::#call_expectation(args)
+                    Self::#call_expectation(&self.__liquid_address, args)
                 }
             }
 
             impl Fn<(#(#input_tys,)*)> for #fn_ident {
                 extern "rust-call" fn call(&self, args: (#(#input_tys,)*)) -> Self::Output {
-                    Self::#call_expectation(args)
+                    Self::#call_expectation(&self.__liquid_address, args)
                 }
             }
         }
@@ -434,7 +560,9 @@ fn generate_overriding_fn(
     quote! {
         #[allow(non_camel_case_types)]
         #[derive(Debug, Clone)]
-        pub struct #fn_ident;
+        pub struct #fn_ident {
+            __liquid_address: liquid_primitives::types::Address,
+        }
 
         const _: () = {
             #(#overriding_mocks)*
@@ -475,25 +603,28 @@ impl<'a> Mockable<'a> {
     fn generate_foreign_contract_mock(&self, interface_ident: &Ident) -> TokenStream2 {
         let interface = self.interface;
         let span = interface.span;
+        let is_sol = matches!(interface.lang_type, LangType::Solidity);
 
         let (trivial_mocks, overriding_fns): (Vec<_>, Vec<_>) =
             interface.foreign_fns.iter().partition_map(|(ident, fns)| {
                 if fns.len() == 1 {
                     let trivial_fn = fns.first().unwrap();
-                    Either::Left(generate_trivial_fn(trivial_fn, interface_ident))
+                    Either::Left(generate_trivial_fn(trivial_fn, interface_ident, is_sol))
                 } else {
                     Either::Right((
                         ident,
-                        generate_overriding_fn(ident, fns, interface_ident),
+                        generate_overriding_fn(ident, fns, interface_ident, is_sol),
                     ))
                 }
             });
         let (overriding_idents, overriding_mocks): (Vec<_>, Vec<_>) =
             overriding_fns.into_iter().unzip();
+        let address_impls = interface.generate_address_impls();
 
         quote_spanned! { span =>
             #[derive(Debug, Clone)]
             pub struct InterfaceImpl {
+                __liquid_address: liquid_primitives::types::Address,
                 #(
                     pub #overriding_idents: #overriding_idents,
                 )*
@@ -503,38 +634,40 @@ impl<'a> Mockable<'a> {
             pub struct Interface(InterfaceImpl);
 
             impl Interface {
-                pub fn at(_: liquid_primitives::types::Address) -> Self {
+                pub fn at(addr: liquid_primitives::types::Address) -> Self {
                     Self(InterfaceImpl {
+                        __liquid_address: addr,
                         #(
-                            #overriding_idents: #overriding_idents {},
+                            #overriding_idents: #overriding_idents {
+                                __liquid_address: addr,
+                            },
                         )*
                     })
                 }
-            }
 
-            impl From<liquid_primitives::types::Address> for Interface {
-                fn from(addr: liquid_primitives::types::Address) -> Interface {
-                    Self::at(addr)
+                pub fn at_cns(name: &str, version: &str) -> Self {
+                    Self::at(liquid_lang::env::cns_resolve(name, version))
                 }
-            }
 
-            impl scale::Decode for Interface {
-                fn decode<I: scale::Input>(value: &mut I) -> ::core::result::Result<Self, scale::Error> {
-                    let _ = <() as scale::Decode>::decode(value)?;
-                    Ok(Self(InterfaceImpl {
-                        #(
-                            #overriding_idents: #overriding_idents {},
-                        )*
-                    }))
+                /// Mocked calls consume no gas, so the limit is ignored.
+                pub fn with_gas(self, _gas: u64) -> Self {
+                    self
+                }
+
+                /// Mocked calls transfer nothing, so the value is ignored.
+                pub fn with_value(self, _value: u64) -> Self {
+                    self
                 }
             }
 
-            impl scale::Encode for Interface {
-                fn encode(&self) -> Vec<u8> {
-                    ().encode()
+            impl From<liquid_primitives::types::Address> for Interface {
+                fn from(addr: liquid_primitives::types::Address) -> Interface {
+                    Self::at(addr)
                 }
             }
 
+            #address_impls
+
             impl std::ops::Deref for Interface {
                 type Target = InterfaceImpl;
                 fn deref(&self) -> &Self::Target {
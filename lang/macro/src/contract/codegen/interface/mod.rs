@@ -16,7 +16,7 @@ use crate::{
     common::GenerateCode,
     contract::{
         codegen::utils as codegen_utils,
-        ir::{ForeignFn, Interface, LangType},
+        ir::{ForeignFn, Interface, ItemEvent, LangType},
     },
     utils as lang_utils,
 };
@@ -26,6 +26,7 @@ use itertools::Itertools;
 use mockable::Mockable;
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
 
 impl GenerateCode for Interface {
     fn generate_code(&self) -> TokenStream2 {
@@ -36,6 +37,8 @@ impl GenerateCode for Interface {
         let mockable = Mockable::from(self);
 
         let foreign_structs = self.generate_foreign_structs();
+        let foreign_events = self.generate_foreign_events();
+        let event_idents = self.foreign_events.iter().map(|event| &event.ident);
         let foreign_contract = self.generate_foreign_contract();
         let mockable_contract = mockable.generate_code();
         let cfg_checker = match self.lang_type {
@@ -68,6 +71,8 @@ impl GenerateCode for Interface {
 
                     #types
 
+                    #(#foreign_events)*
+
                     #[cfg(not(test))]
                     #[allow(dead_code)]
                     pub mod interface {
@@ -83,82 +88,170 @@ impl GenerateCode for Interface {
                 }
 
                 pub type #interface_ident = __liquid_private::interface::Interface;
+                pub use __liquid_private::{#(#event_idents),*};
             }
         }
     }
 }
 
+/// Returns the names of traits derived via `#[derive(...)]` among `attrs`.
+fn derived_traits(attrs: &[syn::Attribute]) -> Vec<Ident> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|nested| match nested {
+            syn::NestedMeta::Meta(meta) => {
+                meta.path().segments.last().map(|seg| seg.ident.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
 fn generate_selector_ident(fn_name: &Ident) -> Ident {
     let shouty_name = &fn_name.to_string().to_shouty_snake_case();
     Ident::new(&shouty_name, Span::call_site())
 }
 
-fn generate_trivial_fn(foreign_fn: &ForeignFn, is_sol: bool) -> TokenStream2 {
-    let attrs = lang_utils::filter_non_liquid_attributes(foreign_fn.attrs.iter());
+/// Generates the statements which encode the selector and the arguments of
+/// `foreign_fn` into `encoded`.
+fn generate_call_data(foreign_fn: &ForeignFn, is_sol: bool) -> TokenStream2 {
     let sig = &foreign_fn.sig;
     let span = foreign_fn.span;
     let fn_ident = &sig.ident;
 
-    let inputs = &sig.inputs;
     let input_tys = codegen_utils::generate_input_tys(&sig);
     let input_ty_checker = codegen_utils::generate_ty_checker(input_tys.as_slice());
-    let input_idents = codegen_utils::generate_input_idents(inputs);
-
-    let output = &sig.output;
-    let output_ty = match output {
-        syn::ReturnType::Default => {
-            quote! { () }
-        }
-        syn::ReturnType::Type(_, ty) => quote! {
-            <#ty as liquid_lang::You_Should_Use_An_Valid_Return_Type>::T
-        },
-    };
+    let input_idents = codegen_utils::generate_input_idents(&sig.inputs);
 
     let selector_ident = generate_selector_ident(fn_ident);
     let fn_name = fn_ident.to_string();
     let fn_name_bytes = fn_name.as_bytes();
     let fn_name_len = fn_name.len();
 
-    let inputs = inputs.iter().skip(1);
-    let is_mut = sig.is_mut();
-    let encode = if !is_sol {
+    let encode = if is_sol {
         quote! {
-            <Input as scale::Encode>::encode(&(#(#input_idents,)*))
+            <Input as liquid_abi_codec::Encode>::encode(&(#(#input_idents,)*))
         }
     } else {
         quote! {
-            <Input as liquid_abi_codec::Encode>::encode(&(#(#input_idents,)*))
+            <Input as scale::Encode>::encode(&(#(#input_idents,)*))
         }
     };
 
     quote_spanned! { span =>
-        #(#attrs)*
-        #[allow(non_snake_case)]
-        pub fn #fn_ident(&self, #(#inputs,)*) -> Option<#output_ty> {
-            #[allow(dead_code)]
-            type Input = #input_ty_checker;
+        #[allow(dead_code)]
+        type Input = #input_ty_checker;
 
-            #[allow(dead_code)]
-            const #selector_ident: liquid_primitives::Selector = {
-                const SIG_LEN: usize =
-                    liquid_ty_mapping::len::<Input>()
-                    + #fn_name_len
-                    + 2;
+        #[allow(dead_code)]
+        const #selector_ident: liquid_primitives::Selector = {
+            const SIG_LEN: usize =
+                liquid_ty_mapping::len::<Input>()
+                + #fn_name_len
+                + 2;
 
-                const SIG: [u8; SIG_LEN] =
-                    liquid_ty_mapping::composite::<Input, SIG_LEN>(&[#(#fn_name_bytes),*]);
+            const SIG: [u8; SIG_LEN] =
+                liquid_ty_mapping::composite::<Input, SIG_LEN>(&[#(#fn_name_bytes),*]);
 
-                let hash = liquid_primitives::hash::hash(&SIG);
-                [hash[0], hash[1], hash[2], hash[3]]
-            };
+            liquid_primitives::selector(&SIG)
+        };
 
-            let mut encoded = #selector_ident.to_vec();
-            encoded.extend(#encode);
+        let mut encoded = #selector_ident.to_vec();
+        encoded.extend(#encode);
+    }
+}
 
-            if #is_mut {
+fn generate_call(
+    foreign_fn: &ForeignFn,
+    output_ty: &TokenStream2,
+    address: TokenStream2,
+    call_params: TokenStream2,
+    delegate: bool,
+    is_sol: bool,
+) -> TokenStream2 {
+    if delegate {
+        let decode = if is_sol {
+            quote! { liquid_abi_codec::Decode }
+        } else {
+            quote! { scale::Decode }
+        };
+        quote! {
+            liquid_lang::env::delegate_call(#address, &encoded)
+                .ok()
+                .and_then(|data| <#output_ty as #decode>::decode(&mut data.as_slice()).ok())
+        }
+    } else if foreign_fn.cached {
+        // Calls made with a gas limit or a value are not memoized, as they
+        // may behave differently from the one in the cache.
+        quote! {
+            if #call_params.is_default() {
+                liquid_lang::env::cached_call::<#output_ty>(#address, &encoded).ok()
+            } else {
+                liquid_lang::env::call_with::<#output_ty>(#address, &encoded, #call_params).ok()
+            }
+        }
+    } else if foreign_fn.optional {
+        quote! {
+            liquid_lang::env::optional_call(|| {
+                liquid_lang::env::call_with::<#output_ty>(#address, &encoded, #call_params)
+            })
+            .ok()
+        }
+    } else {
+        quote! {
+            liquid_lang::env::call_with::<#output_ty>(#address, &encoded, #call_params).ok()
+        }
+    }
+}
+
+fn generate_trivial_fn(
+    foreign_fn: &ForeignFn,
+    delegate: bool,
+    is_sol: bool,
+) -> TokenStream2 {
+    let attrs = lang_utils::filter_non_liquid_attributes(foreign_fn.attrs.iter());
+    let sig = &foreign_fn.sig;
+    let span = foreign_fn.span;
+    let fn_ident = &sig.ident;
+
+    let output = &sig.output;
+    let output_ty = match output {
+        syn::ReturnType::Default => {
+            quote! { () }
+        }
+        syn::ReturnType::Type(_, ty) => quote! {
+            <#ty as liquid_lang::You_Should_Use_An_Valid_Return_Type>::T
+        },
+    };
+
+    let inputs = sig.inputs.iter().skip(1);
+    let is_mut = sig.is_mut();
+    let call_data = generate_call_data(foreign_fn, is_sol);
+    let call = generate_call(
+        foreign_fn,
+        &output_ty,
+        quote! { &self.__liquid_address },
+        quote! { self.__liquid_call_params },
+        delegate,
+        is_sol,
+    );
+
+    quote_spanned! { span =>
+        #(#attrs)*
+        #[allow(non_snake_case)]
+        pub fn #fn_ident(&self, #(#inputs,)*) -> Option<#output_ty> {
+            #call_data
+
+            if #is_mut || self.__liquid_call_params.value != 0 {
                 liquid_lang::storage::mutable_call_happens();
+                liquid_lang::env::clear_call_cache();
             }
-            liquid_lang::env::call::<#output_ty>(&self.__liquid_address, &encoded).ok()
+            #call
         }
     }
 }
@@ -166,6 +259,7 @@ fn generate_trivial_fn(foreign_fn: &ForeignFn, is_sol: bool) -> TokenStream2 {
 fn generate_overriding_fn(
     fn_ident: &Ident,
     foreign_fns: &[ForeignFn],
+    delegate: bool,
     is_sol: bool,
 ) -> TokenStream2 {
     let impls = foreign_fns.iter().enumerate().map(|(i, foreign_fn)| {
@@ -175,7 +269,6 @@ fn generate_overriding_fn(
 
         let inputs = &sig.inputs;
         let input_tys = codegen_utils::generate_input_tys(&sig);
-        let input_ty_checker = codegen_utils::generate_ty_checker(input_tys.as_slice());
         let input_idents = codegen_utils::generate_input_idents(inputs);
 
         let output = &sig.output;
@@ -192,51 +285,33 @@ fn generate_overriding_fn(
         let fn_name = format!("{}_{}", origin_fn_ident, i);
         let fn_ident = Ident::new(&fn_name, span);
 
-        let selector_ident = generate_selector_ident(&fn_ident);
-        let origin_fn_name = origin_fn_ident.to_string();
-        let origin_fn_name_bytes = origin_fn_name.as_bytes();
-        let origin_fn_name_len = origin_fn_name.len();
-
         let inputs = inputs.iter().skip(1);
         let is_mut = sig.is_mut();
-        let encode = if is_sol {
-            quote! {
-                <Input as scale::Encode>::encode(&(#(#input_idents,)*))
-            }
-        } else {
-            quote! {
-                <Input as liquid_abi_codec::Encode>::encode(&(#(#input_idents,)*))
-            }
-        };
+        let call_data = generate_call_data(foreign_fn, is_sol);
+        let call = generate_call(
+            foreign_fn,
+            &output_ty,
+            quote! { __liquid_address },
+            quote! { *__liquid_call_params },
+            delegate,
+            is_sol,
+        );
 
         quote_spanned! { span =>
             #[allow(non_snake_case)]
             #(#attrs)*
-            fn #fn_ident(__liquid_address: &liquid_primitives::types::Address, #(#inputs,)*) -> Option<#output_ty> {
-                #[allow(dead_code)]
-                type Input = #input_ty_checker;
-
-                #[allow(dead_code)]
-                const #selector_ident: liquid_primitives::Selector = {
-                    const SIG_LEN: usize =
-                        liquid_ty_mapping::len::<Input>()
-                        + #origin_fn_name_len
-                        + 2;
-
-                    const SIG: [u8; SIG_LEN] =
-                        liquid_ty_mapping::composite::<Input, SIG_LEN>(&[#(#origin_fn_name_bytes),*]);
-
-                    let hash = liquid_primitives::hash::hash(&SIG);
-                    [hash[0], hash[1], hash[2], hash[3]]
-                };
-
-                let mut encoded = #selector_ident.to_vec();
-                encoded.extend(#encode);
-
-                if #is_mut {
+            fn #fn_ident(
+                __liquid_address: &liquid_primitives::types::Address,
+                __liquid_call_params: &liquid_lang::env::CallParams,
+                #(#inputs,)*
+            ) -> Option<#output_ty> {
+                #call_data
+
+                if #is_mut || __liquid_call_params.value != 0 {
                     liquid_lang::storage::mutable_call_happens();
+                    liquid_lang::env::clear_call_cache();
                 }
-                liquid_lang::env::call::<#output_ty>(&__liquid_address, &encoded).ok()
+                #call
             }
 
             impl FnOnce<(#(#input_tys,)*)> for #origin_fn_ident {
@@ -245,6 +320,8 @@ fn generate_overriding_fn(
                 extern "rust-call" fn call_once(self, (#(#input_idents,)*): (#(#input_tys,)*)) -> Self::Output {
                     #fn_ident(unsafe {
                         &*self.__liquid_address
+                    }, unsafe {
+                        &*self.__liquid_call_params
                     }, #(#input_idents,)*)
                 }
             }
@@ -253,6 +330,8 @@ fn generate_overriding_fn(
                 extern "rust-call" fn call_mut(&mut self, (#(#input_idents,)*): (#(#input_tys,)*)) -> Self::Output {
                     #fn_ident(unsafe {
                         &*self.__liquid_address
+                    }, unsafe {
+                        &*self.__liquid_call_params
                     }, #(#input_idents,)*)
                 }
             }
@@ -261,6 +340,8 @@ fn generate_overriding_fn(
                 extern "rust-call" fn call(&self, (#(#input_idents,)*): (#(#input_tys,)*)) -> Self::Output {
                     #fn_ident(unsafe {
                         &*self.__liquid_address
+                    }, unsafe {
+                        &*self.__liquid_call_params
                     }, #(#input_idents,)*)
                 }
             }
@@ -271,11 +352,17 @@ fn generate_overriding_fn(
         #[allow(non_camel_case_types)]
         pub struct #fn_ident {
             __liquid_address: *const liquid_primitives::types::Address,
+            __liquid_call_params: *const liquid_lang::env::CallParams,
         }
 
         impl #fn_ident {
-            pub fn init(&mut self, addr: *const liquid_primitives::types::Address) {
+            pub fn init(
+                &mut self,
+                addr: *const liquid_primitives::types::Address,
+                call_params: *const liquid_lang::env::CallParams,
+            ) {
                 self.__liquid_address = addr;
+                self.__liquid_call_params = call_params;
             }
         }
 
@@ -283,6 +370,7 @@ fn generate_overriding_fn(
             fn default() -> Self {
                 Self {
                     __liquid_address: core::ptr::null(),
+                    __liquid_call_params: core::ptr::null(),
                 }
             }
         }
@@ -291,6 +379,114 @@ fn generate_overriding_fn(
     }
 }
 
+fn generate_foreign_event(foreign_event: &ItemEvent, is_sol: bool) -> TokenStream2 {
+    let span = foreign_event.span;
+    let attrs = lang_utils::filter_non_liquid_attributes(&foreign_event.attrs);
+    let ident = &foreign_event.ident;
+    let fields = &foreign_event.fields;
+    let event_name = ident.to_string();
+    let event_name_bytes = event_name.as_bytes();
+
+    let event_field_tys = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let ty = &field.ty;
+            if foreign_event.indexed_fields.contains(&i) {
+                quote_spanned! { ty.span() =>
+                    <#ty as liquid_lang::You_Should_Use_An_Valid_Event_Topic_Type>::T
+                }
+            } else {
+                quote_spanned! { ty.span() =>
+                    <#ty as liquid_lang::You_Should_Use_An_Valid_Event_Data_Type>::T
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    let sig_hash = if is_sol {
+        let event_name_len = event_name_bytes.len();
+        quote! {
+            {
+                const SIG_LEN: usize =
+                    liquid_ty_mapping::len::<(#(#event_field_tys,)*)>()
+                    + #event_name_len
+                    + 2;
+                const SIG: [u8; SIG_LEN] =
+                    liquid_ty_mapping::composite::<(#(#event_field_tys,)*), SIG_LEN>(&[#(#event_name_bytes),*]);
+                liquid_primitives::hash::hash(&SIG)
+            }
+        }
+    } else {
+        quote! {
+            liquid_primitives::hash::hash(&[#(#event_name_bytes),*])
+        }
+    };
+
+    let topics = foreign_event.indexed_fields.iter().map(|index| {
+        let ident = &fields[*index].ident;
+        let ty = &fields[*index].ty;
+        quote_spanned! { ty.span() =>
+            <#ty as liquid_lang::You_Should_Use_An_Valid_Event_Topic_Type>::topic(&self.#ident)
+        }
+    });
+
+    let mut pub_fields = fields.clone();
+    pub_fields.iter_mut().for_each(|field| {
+        field.vis = syn::Visibility::Public(syn::VisPublic {
+            pub_token: Default::default(),
+        });
+        field
+            .attrs
+            .retain(|attr| !lang_utils::is_liquid_attribute(attr));
+    });
+
+    let decode = codegen_utils::generate_event_decode(foreign_event, is_sol);
+
+    quote_spanned! { span =>
+        #(#attrs)*
+        pub struct #ident {
+            #(#pub_fields,)*
+        }
+
+        impl #ident {
+            /// Hash of the signature of this event, which is the first topic of
+            /// logs of this event.
+            pub const SIG_HASH: [u8; 32] = #sig_hash;
+
+            /// Recovers an event from topics and data of a log, returns `None`
+            /// if the log is not emitted as this event. When the log is encoded
+            /// by Solidity ABI, indexed fields of dynamic types can not be
+            /// recovered since only their hashes are kept in topics.
+            pub fn try_from_log(
+                topics: &[liquid_primitives::types::Hash],
+                data: &[u8],
+            ) -> Option<Self> {
+                #decode
+            }
+
+            /// Same as `try_from_log`.
+            pub fn decode(
+                topics: &[liquid_primitives::types::Hash],
+                data: &[u8],
+            ) -> Option<Self> {
+                Self::try_from_log(topics, data)
+            }
+        }
+
+        impl liquid_primitives::Topics for #ident {
+            fn write_topics(
+                &self,
+                topics: &mut [liquid_primitives::types::Hash; liquid_primitives::MAX_TOPICS],
+            ) -> usize {
+                let event_topics: &[liquid_primitives::types::Hash] =
+                    &[Self::SIG_HASH.into(), #(#topics,)*];
+                topics[..event_topics.len()].copy_from_slice(event_topics);
+                event_topics.len()
+            }
+        }
+    }
+}
+
 impl Interface {
     fn generate_foreign_structs(&self) -> impl Iterator<Item = TokenStream2> + '_ {
         self.foreign_structs.iter().map(|foreign_struct| {
@@ -307,9 +503,24 @@ impl Interface {
                 }
             });
 
+            // Mocks hand out clones of the values to return, and match
+            // arguments by predicates like `eq`, so these traits are derived
+            // unless they already are.
+            let derived = derived_traits(attrs);
+            let missing = ["Clone", "PartialEq"]
+                .iter()
+                .filter(|name| !derived.iter().any(|ident| ident == *name))
+                .map(|name| Ident::new(name, foreign_struct.span));
+            let debug = if derived.iter().any(|ident| ident == "Debug") {
+                quote! {}
+            } else {
+                quote! { #[cfg_attr(test, derive(Debug))] }
+            };
+
             quote_spanned! { foreign_struct.span =>
                 #(#attrs)*
-                #[derive(liquid_lang::InOut)]
+                #[derive(liquid_lang::InOut, #(#missing),*)]
+                #debug
                 pub struct #ident {
                     #(#fields)*
                 }
@@ -317,36 +528,67 @@ impl Interface {
         })
     }
 
+    fn generate_foreign_events(&self) -> impl Iterator<Item = TokenStream2> + '_ {
+        // Logs of a Solidity contract are always encoded by Solidity ABI, while
+        // logs of a Liquid contract are encoded in the same way as ours.
+        let is_sol = matches!(self.lang_type, LangType::Solidity)
+            || cfg!(feature = "solidity-compatible");
+        self.foreign_events
+            .iter()
+            .map(move |foreign_event| generate_foreign_event(foreign_event, is_sol))
+    }
+
     fn generate_foreign_contract(&self) -> TokenStream2 {
         let span = self.span;
         let is_sol = matches!(self.lang_type, LangType::Solidity);
+        let delegate = self.meta_info.delegate;
 
         let (trivial_fns, overriding_fns): (Vec<_>, Vec<_>) =
             self.foreign_fns.iter().partition_map(|(ident, fns)| {
                 if fns.len() == 1 {
                     let trivial_fn = fns.first().unwrap();
-                    Either::Left(generate_trivial_fn(trivial_fn, is_sol))
+                    Either::Left(generate_trivial_fn(trivial_fn, delegate, is_sol))
                 } else {
-                    Either::Right((ident, generate_overriding_fn(ident, fns, is_sol)))
+                    Either::Right((
+                        ident,
+                        generate_overriding_fn(ident, fns, delegate, is_sol),
+                    ))
                 }
             });
         let (overriding_idents, overriding_impls): (Vec<_>, Vec<_>) =
             overriding_fns.into_iter().unzip();
 
-        let type_notations = if cfg!(feature = "solidity-compatible") {
-            quote! {
-                impl liquid_lang::You_Should_Use_An_Valid_InOut_Type for Interface {}
-                impl liquid_lang::You_Should_Use_An_Valid_State_Type for Interface {}
-                impl liquid_lang::You_Should_Use_An_Valid_Element_Type for Interface {}
-            }
+        let call_params_setters = if delegate {
+            quote! {}
         } else {
             quote! {
-                impl liquid_lang::You_Should_Use_An_Valid_Field_Type for Interface {}
+                /// Limits the gas of the following calls via this interface
+                /// to `gas`, which is all the remaining gas by default.
+                pub fn with_gas(mut self, gas: u64) -> Self {
+                    self.call_params_mut().gas = Some(gas);
+                    self
+                }
+
+                /// Transfers `value` to the callee along with each of the
+                /// following calls via this interface, which makes the calls
+                /// mutable even if the methods called are not.
+                pub fn with_value(mut self, value: u64) -> Self {
+                    self.call_params_mut().value = value;
+                    self
+                }
+
+                fn call_params_mut(&mut self) -> &mut liquid_lang::env::CallParams {
+                    // Modifying the parameters in place doesn't move them, so
+                    // the pointers held by overloaded methods stay valid.
+                    unsafe { &mut self.0.as_mut().get_unchecked_mut().__liquid_call_params }
+                }
             }
         };
-        let mut impls = quote_spanned! { span =>
+        let address_impls = self.generate_address_impls();
+        let impls = quote_spanned! { span =>
             pub struct InterfaceImpl {
                 __liquid_address: liquid_primitives::types::Address,
+                __liquid_call_params: liquid_lang::env::CallParams,
                 __liquid_marker: core::marker::PhantomPinned,
                 #(
                     pub #overriding_idents: #overriding_idents,
@@ -359,6 +601,7 @@ impl Interface {
                 pub fn at(addr: liquid_primitives::types::Address) -> Self {
                     let iface = InterfaceImpl {
                         __liquid_address: addr,
+                        __liquid_call_params: Default::default(),
                         __liquid_marker: core::marker::PhantomPinned,
                         #(
                             #overriding_idents: Default::default(),
@@ -369,15 +612,25 @@ impl Interface {
                     let mut boxed = liquid_prelude::boxed::Box::pin(iface);
                     #[allow(unused_variables)]
                     let addr_ptr: *const liquid_primitives::types::Address = &boxed.as_ref().__liquid_address;
+                    #[allow(unused_variables)]
+                    let call_params_ptr: *const liquid_lang::env::CallParams = &boxed.as_ref().__liquid_call_params;
                     #[allow(unused_unsafe)]
                     unsafe {
                         #(
-                            boxed.as_mut().get_unchecked_mut().#overriding_idents.init(addr_ptr);
+                            boxed.as_mut().get_unchecked_mut().#overriding_idents.init(addr_ptr, call_params_ptr);
                         )*
                     }
 
                     Self(boxed)
                 }
+
+                #call_params_setters
+
+                /// Binds to the contract registered to CNS as `name` of
+                /// version `version`, which is resolved right away.
+                pub fn at_cns(name: &str, version: &str) -> Self {
+                    Self::at(liquid_lang::env::cns_resolve(name, version))
+                }
             }
 
             impl From<liquid_primitives::types::Address> for Interface {
@@ -386,6 +639,41 @@ impl Interface {
                 }
             }
 
+            #(#overriding_impls)*
+
+            #address_impls
+
+            impl InterfaceImpl {
+                #(#trivial_fns)*
+            }
+
+            impl core::ops::Deref for Interface {
+                type Target = InterfaceImpl;
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+        };
+
+        impls
+    }
+
+    /// Generates the implementations which let `Interface` be used just like
+    /// the address it is bound to, shared by the mocked one.
+    fn generate_address_impls(&self) -> TokenStream2 {
+        let span = self.span;
+        let type_notations = if cfg!(feature = "solidity-compatible") {
+            quote! {
+                impl liquid_lang::You_Should_Use_An_Valid_InOut_Type for Interface {}
+                impl liquid_lang::You_Should_Use_An_Valid_State_Type for Interface {}
+                impl liquid_lang::You_Should_Use_An_Valid_Element_Type for Interface {}
+            }
+        } else {
+            quote! {
+                impl liquid_lang::You_Should_Use_An_Valid_Field_Type for Interface {}
+            }
+        };
+        let mut impls = quote_spanned! { span =>
             impl scale::Decode for Interface {
                 fn decode<I: scale::Input>(value: &mut I) -> ::core::result::Result<Self, scale::Error> {
                     let addr = liquid_primitives::types::Address::decode(value)?;
@@ -399,8 +687,6 @@ impl Interface {
                 }
             }
 
-            #(#overriding_impls)*
-
             impl Into<liquid_primitives::types::Address> for Interface {
                 fn into(self) -> liquid_primitives::types::Address {
                     self.0.__liquid_address
@@ -416,17 +702,6 @@ impl Interface {
             impl liquid_lang::You_Should_Use_An_Valid_Event_Data_Type for Interface {}
             impl liquid_lang::You_Should_Use_An_Valid_Return_Type for Interface {}
             impl liquid_lang::You_Should_Use_An_Valid_Input_Type for Interface {}
-
-            impl InterfaceImpl {
-                #(#trivial_fns)*
-            }
-
-            impl core::ops::Deref for Interface {
-                type Target = InterfaceImpl;
-                fn deref(&self) -> &Self::Target {
-                    &self.0
-                }
-            }
         };
 
         if let LangType::Solidity = self.lang_type {
@@ -0,0 +1,192 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    common::GenerateCode,
+    contract::{
+        codegen::utils,
+        ir::{Mixin, Signature},
+        READ_ONLY_VIOLATION,
+    },
+};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+/// Appends two hidden provided methods to the mixin trait: one dispatches
+/// calls to the exported methods, the other generates their ABIs. As both of
+/// them are provided, every contract implementing the trait gets them for
+/// free, and its dispatcher falls back to the former for unknown selectors.
+/// The selectors of exported methods are kept in a hidden associated constant,
+/// against which contracts check their own selectors for collisions.
+impl GenerateCode for Mixin {
+    fn generate_code(&self) -> TokenStream2 {
+        let item_trait = &self.item_trait;
+        let attrs = &item_trait.attrs;
+        let vis = &item_trait.vis;
+        let trait_token = &item_trait.trait_token;
+        let ident = &item_trait.ident;
+        let colon_token = &item_trait.colon_token;
+        let supertraits = &item_trait.supertraits;
+        let items = &item_trait.items;
+
+        let selectors = self
+            .external_fns
+            .iter()
+            .map(|(sig, selector)| generate_selector(sig, *selector));
+        let fragments = self
+            .external_fns
+            .iter()
+            .enumerate()
+            .map(|(i, (sig, _))| generate_dispatch_fragment(ident, i, sig));
+        let fn_abis = self
+            .external_fns
+            .iter()
+            .map(|(sig, selector)| utils::generate_fn_abi(sig, *selector));
+
+        quote! {
+            #(#attrs)*
+            #vis #trait_token #ident #colon_token #supertraits {
+                #(#items)*
+
+                #[doc(hidden)]
+                const __LIQUID_SELECTORS: &'static [liquid_primitives::Selector] = &[
+                    #({ #selectors },)*
+                ];
+
+                #[doc(hidden)]
+                fn __liquid_dispatch(
+                    &mut self,
+                    selector: &liquid_primitives::Selector,
+                    data: &[u8],
+                ) -> Option<liquid_lang::DispatchResult>
+                where
+                    Self: Sized + liquid_lang::storage::Flush,
+                {
+                    #(#fragments)*
+                    None
+                }
+
+                #[cfg(feature = "liquid-abi-gen")]
+                #[doc(hidden)]
+                fn __liquid_external_fn_abis() -> Vec<liquid_abi_gen::ExternalFnAbi>
+                where
+                    Self: Sized,
+                {
+                    let mut external_fn_abis = Vec::new();
+                    #(external_fn_abis.push(#fn_abis);)*
+                    external_fn_abis
+                }
+            }
+        }
+    }
+}
+
+fn generate_selector(sig: &Signature, selector: Option<[u8; 4]>) -> TokenStream2 {
+    if let Some(selector) = selector {
+        return quote! { [#(#selector),*] };
+    }
+
+    let fn_name_str = sig.ident.to_string();
+    let fn_name_bytes = fn_name_str.as_bytes();
+    if cfg!(feature = "solidity-compatible") {
+        let fn_name_len = fn_name_str.len();
+        let input_tys = utils::generate_input_tys(sig);
+        let input_ty_checker = utils::generate_ty_checker(input_tys.as_slice());
+        quote! {
+            const SIG_LEN: usize =
+                liquid_ty_mapping::len::<#input_ty_checker>()
+                + #fn_name_len
+                + 2;
+            const SIG: [u8; SIG_LEN] = liquid_ty_mapping::composite::<(#(#input_tys,)*), SIG_LEN>(&[#(#fn_name_bytes),*]);
+            liquid_primitives::selector(&SIG)
+        }
+    } else {
+        quote! {
+            liquid_primitives::selector(&[#(#fn_name_bytes),*])
+        }
+    }
+}
+
+fn generate_dispatch_fragment(
+    mixin: &syn::Ident,
+    index: usize,
+    sig: &Signature,
+) -> TokenStream2 {
+    let fn_name = &sig.ident;
+    let input_tys = utils::generate_input_tys(sig);
+    let input_ty_checker = utils::generate_ty_checker(input_tys.as_slice());
+    let input_idents = utils::generate_input_idents(&sig.inputs);
+    let pat_idents = if input_idents.is_empty() {
+        quote! { _ }
+    } else {
+        quote! { (#(#input_idents,)*) }
+    };
+
+    let decode = if cfg!(feature = "solidity-compatible") {
+        quote! {
+            <(#(#input_tys,)*) as liquid_abi_codec::Decode>::decode(&mut &data[..])
+        }
+    } else {
+        quote! {
+            <(#(#input_tys,)*) as scale::Decode>::decode(&mut &data[..])
+        }
+    };
+
+    let call = quote! { self.#fn_name(#(#input_idents,)*) };
+    let (call, finish) = match &sig.output {
+        syn::ReturnType::Default => (quote! { #call; }, quote! {}),
+        syn::ReturnType::Type(_, ty) => (
+            quote! { let result = #call; },
+            quote_spanned! { ty.span() =>
+                let _ = core::marker::PhantomData::<
+                    <#ty as liquid_lang::You_Should_Use_An_Valid_Return_Type>::T,
+                >;
+                liquid_lang::env::finish(&result);
+            },
+        ),
+    };
+    let (check_before, check_after) = if sig.is_mut() {
+        (
+            quote! {},
+            quote! { liquid_lang::storage::Flush::flush(self); },
+        )
+    } else {
+        (
+            quote! {
+                let mutable_call_happened = liquid_lang::storage::has_mutable_call_happens();
+            },
+            quote! {
+                if !mutable_call_happened && liquid_lang::storage::has_mutable_call_happens() {
+                    liquid_lang::env::revert(&liquid_prelude::string::String::from(#READ_ONLY_VIOLATION));
+                }
+            },
+        )
+    };
+
+    quote! {
+        {
+            if *selector == <Self as #mixin>::__LIQUID_SELECTORS[#index] {
+                let _ = core::marker::PhantomData::<#input_ty_checker>;
+                let #pat_idents = match #decode {
+                    Ok(args) => args,
+                    Err(_) => return Some(Err(liquid_lang::DispatchError::InvalidParams)),
+                };
+                #check_before
+                #call
+                #check_after
+                #finish
+                return Some(Ok(()));
+            }
+        }
+    }
+}
@@ -12,4 +12,5 @@
 
 mod contract;
 mod interface;
+mod mixin;
 mod utils;
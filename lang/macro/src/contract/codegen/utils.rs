@@ -10,7 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::contract::ir::{FnArg, Signature};
+use crate::contract::ir::{FnArg, ItemEvent, Signature};
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, quote_spanned};
 use syn::{punctuated::Punctuated, spanned::Spanned, Token, Type};
@@ -37,6 +37,64 @@ pub fn generate_input_tys(sig: &Signature) -> Vec<&syn::Type> {
         .collect::<Vec<_>>()
 }
 
+pub fn generate_fn_inputs(sig: &Signature) -> impl Iterator<Item = TokenStream2> + '_ {
+    sig.inputs.iter().skip(1).map(|arg| match arg {
+        FnArg::Typed(ident_type) => {
+            let ident = &ident_type.ident.to_string();
+            let ty = &ident_type.ty;
+
+            quote! {
+                <#ty as liquid_abi_gen::traits::GenerateParamAbi>::generate_param_abi(#ident.to_owned())
+            }
+        }
+        _ => unreachable!(),
+    })
+}
+
+/// Generates the ABI of an external function, shared by contracts and mixins.
+pub fn generate_fn_abi(sig: &Signature, selector: Option<[u8; 4]>) -> TokenStream2 {
+    let ident = sig.ident.to_string();
+    let input_args = generate_fn_inputs(sig);
+    let output_args = match &sig.output {
+        syn::ReturnType::Default => quote! {},
+        syn::ReturnType::Type(_, ty) => {
+            quote! {
+                <#ty as liquid_abi_gen::traits::GenerateOutputs>::generate_outputs(&mut builder);
+            }
+        }
+    };
+
+    let constant = !sig.is_mut();
+    let build_args = if cfg!(feature = "solidity-compatible") {
+        let state_mutability = if constant { "view" } else { "nonpayable" };
+        quote! {
+            String::from(#ident), String::from(#state_mutability), #constant
+        }
+    } else {
+        quote! {
+            String::from(#ident), #constant
+        }
+    };
+
+    let selector = selector.map(|selector| {
+        let selector = format!(
+            "0x{:02x}{:02x}{:02x}{:02x}",
+            selector[0], selector[1], selector[2], selector[3]
+        );
+        quote! { builder.selector(String::from(#selector)); }
+    });
+
+    quote! {
+        {
+            let mut builder = liquid_abi_gen::ExternalFnAbi::new_builder(#build_args);
+            #(builder.input(#input_args);)*
+            #output_args
+            #selector
+            builder.done()
+        }
+    }
+}
+
 pub fn generate_input_idents(
     args: &Punctuated<FnArg, Token![,]>,
 ) -> Vec<&proc_macro2::Ident> {
@@ -48,3 +106,70 @@ pub fn generate_input_idents(
         })
         .collect::<Vec<_>>()
 }
+
+/// Generates the body of `try_from_log` of an event, which recovers the event
+/// from `topics` and `data` of a log, given the hash of its signature in
+/// `Self::SIG_HASH`.
+///
+/// When the log is encoded by Solidity ABI, indexed fields are decoded from
+/// their topics, which fails for dynamic types whose topics are hashes. The
+/// topics of the decoded event are compared with the given ones in the end to
+/// catch this.
+pub fn generate_event_decode(event: &ItemEvent, is_sol: bool) -> TokenStream2 {
+    let fields = &event.fields;
+    let field_idents = fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+    let field_tys = fields.iter().map(|field| &field.ty);
+    let topic_count = event.indexed_fields.len() + 1;
+
+    let decode = if is_sol {
+        let data_idents = event
+            .unindexed_fields
+            .iter()
+            .map(|index| &fields[*index].ident);
+        let data_tys = event
+            .unindexed_fields
+            .iter()
+            .map(|index| &fields[*index].ty);
+        let topic_decodes = event.indexed_fields.iter().enumerate().map(|(i, index)| {
+            let ident = &fields[*index].ident;
+            let ty = &fields[*index].ty;
+            let topic_index = i + 1;
+            quote! {
+                let #ident = <#ty as liquid_abi_codec::MediateDecode>::decode(
+                    &[*topics[#topic_index].as_bytes()],
+                    0,
+                )
+                .ok()?
+                .value;
+            }
+        });
+
+        quote! {
+            let (#(#data_idents,)*) =
+                <(#(#data_tys,)*) as liquid_abi_codec::Decode>::decode(&mut &data[..]).ok()?;
+            #(#topic_decodes)*
+        }
+    } else {
+        quote! {
+            let mut data = data;
+            #(
+                let #field_idents = <#field_tys as scale::Decode>::decode(&mut data).ok()?;
+            )*
+        }
+    };
+
+    quote! {
+        if topics.len() != #topic_count || *topics[0].as_bytes() != Self::SIG_HASH {
+            return None;
+        }
+
+        #decode
+        let event = Self {
+            #(#field_idents,)*
+        };
+        if liquid_primitives::Topics::topics(&event).as_slice() != topics {
+            return None;
+        }
+        Some(event)
+    }
+}
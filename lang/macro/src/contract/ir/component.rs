@@ -0,0 +1,176 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proc_macro2::{Group, Span, TokenStream as TokenStream2, TokenTree};
+use std::path::{Path, PathBuf};
+use syn::{spanned::Spanned, Result};
+
+/// Replaces every `include!("path/to/file.rs");` item in a contract module
+/// with the items defined in that file, so that a contract can be composed
+/// of components placed in separate files. Unlike the `include!` of std, the
+/// path is relative to the root of the crate, i.e., the directory containing
+/// `Cargo.toml`, since a procedural macro can't know which file it's invoked
+/// in. Included files may include other files as well.
+///
+/// Returns the expanded items and the paths of all included files.
+pub fn expand_includes(items: Vec<syn::Item>) -> Result<(Vec<syn::Item>, Vec<String>)> {
+    let mut expander = Expander::default();
+    let items = expander.expand(items)?;
+    Ok((items, expander.included_files))
+}
+
+#[derive(Default)]
+struct Expander {
+    /// Files being expanded, used to detect cyclic inclusions.
+    stack: Vec<PathBuf>,
+    included_files: Vec<String>,
+}
+
+impl Expander {
+    fn expand(&mut self, items: Vec<syn::Item>) -> Result<Vec<syn::Item>> {
+        let mut expanded = Vec::with_capacity(items.len());
+        for item in items {
+            match item {
+                syn::Item::Macro(item_macro)
+                    if item_macro.mac.path.is_ident("include") =>
+                {
+                    expanded.extend(self.include(&item_macro)?);
+                }
+                item => expanded.push(item),
+            }
+        }
+        Ok(expanded)
+    }
+
+    fn include(&mut self, item_macro: &syn::ItemMacro) -> Result<Vec<syn::Item>> {
+        let span = item_macro.span();
+        let file = item_macro.mac.parse_body::<syn::LitStr>()?.value();
+        let dir = match std::env::var("CARGO_MANIFEST_DIR") {
+            Ok(dir) => dir,
+            Err(_) => bail_span!(
+                span,
+                "`include!` in contract modules requires the environment variable \
+                 `CARGO_MANIFEST_DIR`, please build the contract via cargo"
+            ),
+        };
+        let path = Path::new(&dir).join(&file);
+        if self.stack.contains(&path) {
+            bail_span!(span, "`{}` includes itself", file)
+        }
+
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) => bail_span!(
+                span,
+                "failed to read `{}`: {}, note that paths in `include!` of contract \
+                 modules are relative to the directory containing `Cargo.toml`",
+                path.display(),
+                err
+            ),
+        };
+        let tokens = match content.parse::<TokenStream2>() {
+            Ok(tokens) => tokens,
+            Err(err) => bail_span!(span, "failed to parse `{}`: {}", file, err),
+        };
+        // Items in the file have no meaningful spans, so errors in them are
+        // reported at the `include!` which brings them in.
+        let file_items = syn::parse2::<syn::File>(respan(tokens, span))?.items;
+
+        self.included_files.push(path.to_string_lossy().into_owned());
+        self.stack.push(path);
+        let items = self.expand(file_items)?;
+        self.stack.pop();
+        Ok(items)
+    }
+}
+
+fn respan(tokens: TokenStream2, span: Span) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut respanned =
+                    Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                token = TokenTree::Group(respanned);
+            } else {
+                token.set_span(span);
+            }
+            token
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::ToTokens;
+    use syn::parse_quote;
+
+    /// Writes a file into a directory private to the test, and returns its
+    /// absolute path, which `include!` accepts as well.
+    fn write(test: &str, name: &str, content: &str) -> String {
+        let dir = std::env::temp_dir().join(format!("liquid-component-{}", test));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    fn idents(items: &[syn::Item]) -> Vec<String> {
+        items
+            .iter()
+            .map(|item| match item {
+                syn::Item::Struct(item_struct) => item_struct.ident.to_string(),
+                syn::Item::Fn(item_fn) => item_fn.sig.ident.to_string(),
+                item => item.to_token_stream().to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn expand_nested_includes() {
+        let inner = write("nested", "inner.rs", "fn inner() {}");
+        let outer = write(
+            "nested",
+            "outer.rs",
+            &format!("struct Outer {{}} include!({:?});", inner),
+        );
+        let items: Vec<syn::Item> = vec![
+            parse_quote!(struct First {}),
+            parse_quote!(include!(#outer);),
+            parse_quote!(fn last() {}),
+        ];
+
+        let (items, included_files) = expand_includes(items).unwrap();
+        assert_eq!(idents(&items), ["First", "Outer", "inner", "last"]);
+        assert_eq!(included_files, [outer, inner]);
+    }
+
+    #[test]
+    fn reject_cyclic_includes() {
+        let path = write("cyclic", "cyclic.rs", "");
+        std::fs::write(&path, format!("include!({:?});", path)).unwrap();
+        let items: Vec<syn::Item> = vec![parse_quote!(include!(#path);)];
+
+        let err = expand_includes(items).err().unwrap();
+        assert_eq!(err.to_string(), format!("`{}` includes itself", path));
+    }
+
+    #[test]
+    fn reject_missing_files() {
+        let items: Vec<syn::Item> =
+            vec![parse_quote!(include!("components/missing.rs");)];
+        let err = expand_includes(items).err().unwrap();
+        assert!(err.to_string().starts_with("failed to read"));
+    }
+}
@@ -0,0 +1,96 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doc comments exported into the ABI metadata.
+
+/// Joins the lines of doc comments among `attrs`, with the space following
+/// `///` removed.
+pub fn collect_docs<'a, I>(attrs: I) -> String
+where
+    I: IntoIterator<Item = &'a syn::Attribute>,
+{
+    let lines = attrs
+        .into_iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(doc),
+                ..
+            })) => Some(doc.value()),
+            _ => None,
+        })
+        .map(|line| match line.strip_prefix(' ') {
+            Some(line) => line.trim_end().to_owned(),
+            None => line.trim_end().to_owned(),
+        })
+        .collect::<Vec<_>>();
+    lines.join("\n").trim().to_owned()
+}
+
+/// Doc comments of the contract, a method or an event.
+#[derive(Default)]
+pub struct Docs {
+    /// The first paragraph, which describes the item for its users.
+    pub notice: String,
+    /// The remaining paragraphs, which are meant for developers.
+    pub details: String,
+}
+
+impl Docs {
+    pub fn from_attrs<'a, I>(attrs: I) -> Self
+    where
+        I: IntoIterator<Item = &'a syn::Attribute>,
+    {
+        let docs = collect_docs(attrs);
+        let (notice, details) = match docs.find("\n\n") {
+            Some(pos) => (&docs[..pos], docs[pos..].trim()),
+            None => (docs.as_str(), ""),
+        };
+
+        Self {
+            notice: notice.lines().collect::<Vec<_>>().join(" "),
+            details: details.to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_paragraphs() {
+        let item: syn::ItemFn = syn::parse_quote! {
+            /// Transfers tokens
+            /// to someone.
+            ///
+            /// Fails if the balance is insufficient.
+            ///
+            ///   Emits `Transfer`.
+            fn transfer() {}
+        };
+        let docs = Docs::from_attrs(&item.attrs);
+        assert_eq!(docs.notice, "Transfers tokens to someone.");
+        assert_eq!(
+            docs.details,
+            "Fails if the balance is insufficient.\n\n  Emits `Transfer`."
+        );
+
+        let item: syn::ItemFn = syn::parse_quote! {
+            #[inline]
+            fn noop() {}
+        };
+        let docs = Docs::from_attrs(&item.attrs);
+        assert!(docs.notice.is_empty());
+        assert!(docs.details.is_empty());
+    }
+}
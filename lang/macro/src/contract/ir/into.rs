@@ -13,7 +13,8 @@
 use crate::{
     contract::{
         ir::{self, utils as ir_utils},
-        SUPPORTS_ASSET_NAME, SUPPORTS_ASSET_SIGNATURE,
+        ASSET_RECEIVED_NAME, ASSET_RECEIVED_SIGNATURE, MIGRATE_NAME, SUPPORTS_ASSET_NAME,
+        SUPPORTS_ASSET_SIGNATURE, UPGRADE_TO_NAME, VERSION_NAME,
     },
     utils as lang_utils,
 };
@@ -78,7 +79,17 @@ impl TryFrom<&String> for ir::MetaVersion {
 
 impl Parse for ir::Marker {
     fn parse(input: ParseStream) -> Result<Self> {
-        const SINGLE_MARKER: [&str; 4] = ["indexed", "storage", "event", "methods"];
+        const SINGLE_MARKER: [&str; 9] = [
+            "indexed",
+            "storage",
+            "event",
+            "methods",
+            "upgradeable",
+            "asset_received",
+            "migration",
+            "constructor",
+            "internal",
+        ];
 
         let content;
         let paren_token = syn::parenthesized!(content in input);
@@ -150,6 +161,13 @@ impl TryFrom<(ir::ContractParams, syn::ItemMod)> for ir::Contract {
             ),
             Some((_, items)) => items.clone(),
         };
+        let (items, included_files) = ir::component::expand_includes(items)?;
+        let source_hash = if included_files.is_empty() {
+            liquid_primitives::hash::hash(quote!(#item_mod).to_string().as_bytes())
+        } else {
+            let source = quote!(#item_mod #(#items)*).to_string();
+            liquid_primitives::hash::hash(source.as_bytes())
+        };
 
         let (liquid_items, rust_items): (Vec<_>, Vec<_>) = items
             .into_iter()
@@ -162,8 +180,20 @@ impl TryFrom<(ir::ContractParams, syn::ItemMod)> for ir::Contract {
             });
 
         let span = item_mod.span();
-        let (storage, events, assets, mut functions, mut constants) =
-            ir_utils::split_items(liquid_items, span)?;
+        let (
+            storage,
+            mut events,
+            errors,
+            assets,
+            mut functions,
+            helpers,
+            mut constants,
+            mixins,
+        ) = ir_utils::split_items(liquid_items, span)?;
+
+        if !assets.is_empty() {
+            ir_utils::expand_asset_events(&mut events, span)?;
+        }
 
         storage.public_fields.iter().for_each(|index| {
             let field = &storage.fields.named[*index];
@@ -182,6 +212,8 @@ impl TryFrom<(ir::ContractParams, syn::ItemMod)> for ir::Contract {
                 kind: ir::FunctionKind::External(lang_utils::calculate_fn_id(ident), true),
                 sig: ir::Signature::try_from(&getter.sig).unwrap(),
                 body: *getter.block,
+                selector: None,
+                docs: ir::docs::Docs::from_attrs(&field.attrs),
                 span: field.span(),
             });
         });
@@ -211,13 +243,150 @@ impl TryFrom<(ir::ContractParams, syn::ItemMod)> for ir::Contract {
             ),
             sig: ir::Signature::try_from(&supports_asset_fn.sig).unwrap(),
             body: *supports_asset_fn.block,
+            selector: None,
+            docs: Default::default(),
+            span,
+        });
+
+        let asset_received_name = Ident::new(ASSET_RECEIVED_NAME, span);
+        let asset_received_body =
+            match ir_utils::take_asset_received_hook(&mut functions)? {
+                Some(hook) => quote! { self.#hook(asset_name, from, amount_or_id); },
+                None => quote! { let _ = (asset_name, from, amount_or_id); },
+            };
+        let asset_received_fn = syn::parse2::<syn::ItemFn>(quote! {
+            pub fn #asset_received_name(
+                &mut self,
+                asset_name: String,
+                from: address,
+                amount_or_id: u64,
+            ) {
+                #asset_received_body
+            }
+        })
+        .unwrap();
+        functions.push(ir::Function {
+            attrs: asset_received_fn.attrs,
+            kind: ir::FunctionKind::External(
+                lang_utils::calculate_fn_id(&ASSET_RECEIVED_SIGNATURE),
+                false,
+            ),
+            sig: ir::Signature::try_from(&asset_received_fn.sig).unwrap(),
+            body: *asset_received_fn.block,
+            selector: None,
+            docs: Default::default(),
             span,
         });
 
+        let migration = ir_utils::take_migration_hook(&mut functions)?;
+        if let Some(migration) = &migration {
+            if !storage.upgradeable {
+                bail!(
+                    migration,
+                    "`migration` hook can only be used in contracts whose storage is \
+                     marked with `#[liquid(upgradeable)]`"
+                )
+            }
+            if storage.storage_version == 0 {
+                bail!(
+                    migration,
+                    "`migration` hook requires the storage version to be specified via \
+                     `#[liquid(storage_version = N)]`, where `N` is greater than 0"
+                )
+            }
+        }
+
+        if storage.upgradeable {
+            let storage_version = storage.storage_version;
+            let migrate_name = Ident::new(MIGRATE_NAME, span);
+            let migrate_body = match migration {
+                Some(hook) => quote! {
+                    if let Some(from_version) = liquid_lang::upgrade::migrate(#storage_version) {
+                        self.#hook(from_version);
+                    }
+                },
+                None => quote! { liquid_lang::upgrade::migrate(#storage_version); },
+            };
+            let migrate_fn = syn::parse2::<syn::ItemFn>(quote! {
+                pub fn #migrate_name(&mut self) {
+                    #migrate_body
+                }
+            })
+            .unwrap();
+            functions.push(ir::Function {
+                attrs: migrate_fn.attrs,
+                kind: ir::FunctionKind::External(
+                    lang_utils::calculate_fn_id(&MIGRATE_NAME),
+                    false,
+                ),
+                sig: ir::Signature::try_from(&migrate_fn.sig).unwrap(),
+                body: *migrate_fn.block,
+                selector: None,
+                docs: Default::default(),
+                span,
+            });
+
+            let upgrade_to_name = Ident::new(UPGRADE_TO_NAME, span);
+            let upgrade_to_fn = syn::parse2::<syn::ItemFn>(quote! {
+                pub fn #upgrade_to_name(&mut self, new_impl: address) {
+                    liquid_lang::upgrade::upgrade_to(new_impl);
+                }
+            })
+            .unwrap();
+            functions.push(ir::Function {
+                attrs: upgrade_to_fn.attrs,
+                kind: ir::FunctionKind::External(
+                    lang_utils::calculate_fn_id(&UPGRADE_TO_NAME),
+                    false,
+                ),
+                sig: ir::Signature::try_from(&upgrade_to_fn.sig).unwrap(),
+                body: *upgrade_to_fn.block,
+                selector: None,
+                docs: Default::default(),
+                span,
+            });
+        }
+
+        let meta_info = ir::ContractMetaInfo::try_from(params)?;
+        if let Some(version) = &meta_info.version {
+            let version_name = Ident::new(VERSION_NAME, span);
+            if let Some(func) =
+                functions.iter().find(|func| func.sig.ident == version_name)
+            {
+                bail!(
+                    func.sig.ident,
+                    "`{}` is reserved for querying the version of the contract, which \
+                     is generated when `version` parameter is provided",
+                    VERSION_NAME
+                )
+            }
+
+            let version_fn = syn::parse2::<syn::ItemFn>(quote! {
+                pub fn #version_name(&self) -> String {
+                    String::from(#version)
+                }
+            })
+            .unwrap();
+            functions.push(ir::Function {
+                attrs: version_fn.attrs,
+                kind: ir::FunctionKind::External(
+                    lang_utils::calculate_fn_id(&VERSION_NAME),
+                    false,
+                ),
+                sig: ir::Signature::try_from(&version_fn.sig).unwrap(),
+                body: *version_fn.block,
+                selector: None,
+                docs: Default::default(),
+                span,
+            });
+        }
+
+        ir_utils::expand_access_control(&storage, &mut functions)?;
+
         let (mut constructor, mut external_func_count) = (None, 0);
         for (pos, func) in functions.iter().enumerate() {
             match func.kind {
-                ir::FunctionKind::Constructor => {
+                ir::FunctionKind::Constructor if func.sig.ident == "new" => {
                     if constructor.is_some() {
                         bail_span!(
                             func.span(),
@@ -236,26 +405,67 @@ impl TryFrom<(ir::ContractParams, syn::ItemMod)> for ir::Contract {
         }
 
         if constructor.is_none() {
+            if let Some(alt_constructor) = functions
+                .iter()
+                .find(|func| matches!(func.kind, ir::FunctionKind::Constructor))
+            {
+                bail!(
+                    alt_constructor.sig.ident,
+                    "`#[liquid(constructor)]` only declares alternative constructors, \
+                     the constructor `new` is still required"
+                )
+            }
             bail!(item_mod, "no constructor found for this contract")
         }
 
-        if external_func_count < 1 {
+        if external_func_count < 1 && mixins.is_empty() {
             bail!(item_mod, "contract needs at least one external function")
         }
 
-        let constructor = functions.remove(constructor.unwrap());
-        let meta_info = ir::ContractMetaInfo::try_from(params)?;
+        let mut constructor = functions.remove(constructor.unwrap());
+        let (mut alt_constructors, functions): (Vec<_>, Vec<_>) = functions
+            .into_iter()
+            .partition(|func| matches!(func.kind, ir::FunctionKind::Constructor));
+        for constructor in core::iter::once(&mut constructor).chain(&mut alt_constructors)
+        {
+            for asset in assets.iter().rev().filter(|asset| asset.auto_register) {
+                let ident = &asset.ident;
+                constructor
+                    .body
+                    .stmts
+                    .insert(0, syn::parse_quote! { #ident::ensure_registered(); });
+            }
+            if storage.upgradeable {
+                let storage_version = storage.storage_version;
+                constructor.body.stmts.insert(
+                    0,
+                    syn::parse_quote! { liquid_lang::upgrade::initialize(#storage_version); },
+                );
+            }
+        }
+        let storage_layout_file = match std::env::var("CARGO_MANIFEST_DIR") {
+            Ok(dir) => ir::layout::check_storage_layout(&storage, dir.as_ref())?,
+            Err(_) => None,
+        };
         Ok(Self {
             mod_token: item_mod.mod_token,
             ident: item_mod.ident,
+            docs: ir::docs::Docs::from_attrs(&item_mod.attrs),
             meta_info,
             storage,
             events,
+            errors,
             assets,
             constructor,
+            alt_constructors,
             functions,
+            helpers,
+            mixins,
             constants,
             rust_items,
+            source_hash,
+            storage_layout_file,
+            included_files,
         })
     }
 }
@@ -265,7 +475,7 @@ impl TryFrom<ir::ContractParams> for ir::ContractMetaInfo {
 
     fn try_from(params: ir::ContractParams) -> Result<Self> {
         let mut unique_param_names = HashSet::new();
-        let mut liquid_version = None;
+        let mut version = None;
         for param in params.params.iter() {
             let name = param.ident().to_string();
             if !unique_param_names.insert(name.clone()) {
@@ -274,17 +484,12 @@ impl TryFrom<ir::ContractParams> for ir::ContractMetaInfo {
 
             match param {
                 ir::ContractMetaParam::Version(param) => {
-                    liquid_version = Some(param.version.clone())
+                    version = Some(param.value.value())
                 }
             }
         }
 
-        let liquid_version = match liquid_version {
-            None => ir::MetaVersion::try_from(&"1.0.0-rc1".to_owned()).unwrap(),
-            Some(liquid_version) => liquid_version,
-        };
-
-        Ok(Self { liquid_version })
+        Ok(Self { version })
     }
 }
 
@@ -294,6 +499,8 @@ impl TryFrom<ir::InterfaceParams> for ir::InterfaceMetaInfo {
     fn try_from(params: ir::InterfaceParams) -> Result<Self> {
         let mut unique_param_names = HashSet::new();
         let mut interface_name = None;
+        let mut delegate = false;
+        let mut mock_context_getter = None;
         for param in params.params.iter() {
             let name = param.ident().to_string();
             if !unique_param_names.insert(name.clone()) {
@@ -312,6 +519,10 @@ impl TryFrom<ir::InterfaceParams> for ir::InterfaceMetaInfo {
                         interface_name = Some(String::new());
                     }
                 }
+                ir::InterfaceMetaParam::Delegate(_) => delegate = true,
+                ir::InterfaceMetaParam::MockContextGetter(param) => {
+                    mock_context_getter = Some(param.value.value())
+                }
             }
         }
 
@@ -323,7 +534,11 @@ impl TryFrom<ir::InterfaceParams> for ir::InterfaceMetaInfo {
             Some(interface_name) => interface_name,
         };
 
-        Ok(Self { interface_name })
+        Ok(Self {
+            interface_name,
+            delegate,
+            mock_context_getter,
+        })
     }
 }
 
@@ -349,6 +564,7 @@ impl TryFrom<syn::FnArg> for ir::FnArg {
                         ident: pat_ident.ident,
                         colon_token: pat_type.colon_token,
                         ty: *pat_type.ty,
+                        docs: ir::docs::collect_docs(&pat_type.attrs),
                         span,
                     })
                     .into())
@@ -500,11 +716,53 @@ impl TryFrom<syn::ImplItemMethod> for ir::Function {
             _ => (),
         }
 
+        // Private helpers never reach here, so the generics must be on a method
+        // exported to the dispatcher, which can't tell how to instantiate them.
+        let generics = &method.sig.generics;
+        if !generics.params.is_empty() {
+            bail!(
+                generics,
+                "generic is only supported for private helper methods in contract, \
+                 which are neither `pub` nor marked with liquid attributes",
+            )
+        }
+        if let Some(where_clause) = &generics.where_clause {
+            bail!(
+                where_clause,
+                "`where` clause is only supported for private helper methods in \
+                 contract, which are neither `pub` nor marked with liquid attributes",
+            )
+        }
+
         let span = method.span();
         let sig = ir::Signature::try_from(&method.sig)?;
         let ident = &sig.ident;
 
-        let kind = if ident == "new" {
+        let mut attrs = Vec::with_capacity(method.attrs.len());
+        let mut is_marked_constructor = false;
+        let mut selector = None;
+        for attr in method.attrs {
+            if lang_utils::is_liquid_attribute(&attr) {
+                let marker = ir::Marker::try_from(attr.clone())?;
+                if marker.ident == "constructor" {
+                    if is_marked_constructor {
+                        bail!(marker.ident, "duplicate `constructor` attribute")
+                    }
+                    is_marked_constructor = true;
+                    continue;
+                }
+                if marker.ident == "selector" {
+                    if selector.is_some() {
+                        bail!(marker.ident, "duplicate `selector` attribute")
+                    }
+                    selector = Some((ir_utils::parse_selector(&marker)?, marker.ident));
+                    continue;
+                }
+            }
+            attrs.push(attr);
+        }
+
+        let kind = if ident == "new" || is_marked_constructor {
             match method.vis {
                 syn::Visibility::Public(_) => {
                     // The process of parsing signature ensures that the first parameter must be a reference
@@ -539,11 +797,33 @@ impl TryFrom<syn::ImplItemMethod> for ir::Function {
             ir::FunctionKind::Normal
         };
 
+        let selector = match selector {
+            Some((_, marker)) if ident == "new" => bail!(
+                marker,
+                "the constructor `new` has no selector, as it's called without one"
+            ),
+            Some((_, marker)) if matches!(kind, ir::FunctionKind::Normal) => bail!(
+                marker,
+                "`selector` can only be used on external functions and alternative \
+                 constructors"
+            ),
+            Some((selector, _)) => Some(selector),
+            None => None,
+        };
+
+        if let ir::FunctionKind::External(..) = kind {
+            ir_utils::check_read_only(&sig, &method.block)?;
+        }
+
+        let docs = ir::docs::Docs::from_attrs(&attrs);
+
         Ok(Self {
-            attrs: method.attrs,
+            attrs,
             kind,
             sig,
             body: method.block,
+            selector,
+            docs,
             span,
         })
     }
@@ -576,12 +856,17 @@ impl TryFrom<syn::ItemImpl> for ir::ItemImpl {
             )
         }
 
-        if item_impl.trait_.is_some() {
-            bail!(
-                item_impl,
-                "trait implementations are not supported in liquid",
-            )
-        }
+        // Only traits marked with `#[liquid::mixin]` can be implemented,
+        // which is checked by the compiler as the dispatcher relies on the
+        // methods generated for them.
+        let mixin = match &item_impl.trait_ {
+            Some((Some(bang), ..)) => bail!(
+                bang,
+                "negative trait implementations are not supported in liquid",
+            ),
+            Some((None, path, _)) => Some(path.clone()),
+            None => None,
+        };
 
         let type_path = match &*item_impl.self_ty {
             syn::Type::Path(type_path) => type_path,
@@ -613,9 +898,15 @@ impl TryFrom<syn::ItemImpl> for ir::ItemImpl {
         };
 
         let mut functions = Vec::new();
+        let mut helpers = Vec::new();
         let mut constants = Vec::new();
         for item in item_impl.items.into_iter() {
             match item {
+                syn::ImplItem::Method(method)
+                    if mixin.is_some() || is_helper(&method) =>
+                {
+                    helpers.push(method);
+                }
                 syn::ImplItem::Method(method) => {
                     functions.push(ir::Function::try_from(method)?);
                 }
@@ -635,12 +926,103 @@ impl TryFrom<syn::ItemImpl> for ir::ItemImpl {
             impl_token: item_impl.impl_token,
             ty: ident,
             brace_token: item_impl.brace_token,
+            mixin,
             functions,
+            helpers,
             constants,
         })
     }
 }
 
+/// Methods which are neither `pub` nor marked by any liquid attribute are
+/// internal helpers. They are invisible to the dispatcher and the ABI, hence
+/// they are exempt from the restrictions on external methods, e.g., they may
+/// be generic or take no `self` at all.
+fn is_helper(method: &syn::ImplItemMethod) -> bool {
+    matches!(method.vis, syn::Visibility::Inherited)
+        && method.sig.ident != "new"
+        && !method.attrs.iter().any(lang_utils::is_liquid_attribute)
+}
+
+impl TryFrom<syn::ItemTrait> for ir::Mixin {
+    type Error = Error;
+
+    fn try_from(mut item_trait: syn::ItemTrait) -> Result<Self> {
+        if item_trait.unsafety.is_some() {
+            bail!(item_trait.unsafety, "mixin traits can not be unsafe")
+        }
+
+        if item_trait.auto_token.is_some() {
+            bail!(item_trait.auto_token, "mixin traits can not be auto traits")
+        }
+
+        if !(item_trait.generics.params.is_empty()
+            && item_trait.generics.where_clause.is_none())
+        {
+            bail!(
+                item_trait.generics,
+                "generic mixin traits are not supported"
+            )
+        }
+
+        let mut external_fns = Vec::new();
+        for item in item_trait.items.iter_mut() {
+            let method = match item {
+                syn::TraitItem::Method(method) => method,
+                _ => continue,
+            };
+
+            let markers = ir_utils::filter_map_liquid_attributes(&method.attrs)?;
+            let (mut is_internal, mut selector) = (false, None);
+            for marker in markers {
+                match marker.ident.to_string().as_str() {
+                    "internal" => is_internal = true,
+                    "selector" => selector = Some(ir_utils::parse_selector(&marker)?),
+                    _ => bail!(
+                        marker.ident,
+                        "unknown liquid attribute `{}` for methods in mixin traits",
+                        marker.ident
+                    ),
+                }
+                if method.default.is_none() {
+                    bail!(
+                        marker.ident,
+                        "only provided methods can be marked with `#[liquid({})]`",
+                        marker.ident
+                    )
+                }
+                if is_internal && selector.is_some() {
+                    bail!(
+                        marker.ident,
+                        "internal methods of mixin traits can not have selectors"
+                    )
+                }
+            }
+            method
+                .attrs
+                .retain(|attr| !lang_utils::is_liquid_attribute(attr));
+
+            if let (Some(block), false) = (&method.default, is_internal) {
+                let sig = ir::Signature::try_from(&method.sig)?;
+                ir_utils::check_read_only(&sig, block)?;
+                external_fns.push((sig, selector));
+            }
+        }
+
+        if external_fns.is_empty() {
+            bail!(
+                item_trait.ident,
+                "mixin traits should have at least one provided method to be exported"
+            )
+        }
+
+        Ok(Self {
+            item_trait,
+            external_fns,
+        })
+    }
+}
+
 impl TryFrom<syn::ItemStruct> for ir::ItemStorage {
     type Error = Error;
     fn try_from(item_struct: syn::ItemStruct) -> Result<Self> {
@@ -658,12 +1040,53 @@ impl TryFrom<syn::ItemStruct> for ir::ItemStorage {
             )
         }
 
+        let markers = ir_utils::filter_map_liquid_attributes(&item_struct.attrs)?;
+        let upgradeable = markers.iter().any(|marker| marker.ident == "upgradeable");
+        let (storage_version, storage_version_span) = match markers
+            .iter()
+            .find(|marker| marker.ident == "storage_version")
+        {
+            Some(marker) => match &marker.value {
+                ir::AttrValue::LitInt(version) => {
+                    (version.base10_parse::<u32>()?, version.span())
+                }
+                _ => bail_span!(
+                    marker.span(),
+                    "the attribute `storage_version` should be assigned with a literal \
+                     integer, e.g. `#[liquid(storage_version = 1)]`"
+                ),
+            },
+            None => (0, item_struct.ident.span()),
+        };
+
         let mut public_fields = Vec::new();
+        let mut immutable_fields = Vec::new();
         let span = item_struct.span();
         let fields = match item_struct.fields {
             syn::Fields::Named(named_fields) => {
                 let fields = &named_fields.named;
                 for (i, field) in fields.iter().enumerate() {
+                    for marker in ir_utils::filter_map_liquid_attributes(&field.attrs)? {
+                        if marker.ident != "immutable" {
+                            bail!(
+                                marker.ident,
+                                "unknown liquid attribute `{}` for storage fields",
+                                marker.ident
+                            )
+                        }
+                        if !matches!(marker.value, ir::AttrValue::None) {
+                            bail!(
+                                marker.ident,
+                                "the attribute `immutable` should not be assigned with \
+                                 a value"
+                            )
+                        }
+                        if immutable_fields.last() == Some(&i) {
+                            bail!(marker.ident, "duplicate `immutable` attribute")
+                        }
+                        immutable_fields.push(i);
+                    }
+
                     let visibility = &field.vis;
                     match visibility {
                         syn::Visibility::Public(_) => {
@@ -696,6 +1119,10 @@ impl TryFrom<syn::ItemStruct> for ir::ItemStorage {
             ident: item_struct.ident,
             fields,
             public_fields,
+            immutable_fields,
+            upgradeable,
+            storage_version,
+            storage_version_span,
             span,
         })
     }
@@ -745,7 +1172,19 @@ impl TryFrom<syn::ItemStruct> for ir::ItemAsset {
                         issuer_token: _,
                         eq_token: _,
                         value,
-                    } => asset_meta.issuer = value.value(),
+                    } => {
+                        let issuer = value.value();
+                        if issuer.parse::<liquid_primitives::types::Address>().is_err() {
+                            bail!(
+                                value,
+                                "`{}` is not a valid address, the issuer should be at \
+                                 most 40 hexadecimal digits with an optional `0x` \
+                                 prefix, and carry a valid checksum if mixed-case",
+                                issuer
+                            )
+                        }
+                        asset_meta.issuer = issuer;
+                    }
                     ir::AssetAttribute::TotalSupply {
                         total_token: _,
                         eq_token: _,
@@ -766,6 +1205,11 @@ impl TryFrom<syn::ItemStruct> for ir::ItemAsset {
                         eq_token: _,
                         value,
                     } => asset_meta.description = value.value(),
+                    ir::AssetAttribute::AutoRegister {
+                        auto_register_token: _,
+                        eq_token: _,
+                        value,
+                    } => asset_meta.auto_register = value.value,
                 }
             }
         } else {
@@ -784,6 +1228,7 @@ impl TryFrom<syn::ItemStruct> for ir::ItemAsset {
             // destroyable: asset_meta.destroyable,
             fungible: asset_meta.fungible,
             description: asset_meta.description,
+            auto_register: asset_meta.auto_register,
         })
     }
 }
@@ -870,6 +1315,73 @@ impl TryFrom<syn::ItemStruct> for ir::ItemEvent {
     }
 }
 
+impl TryFrom<syn::ItemEnum> for ir::ItemError {
+    type Error = Error;
+    fn try_from(item_enum: syn::ItemEnum) -> Result<Self> {
+        if !item_enum.generics.params.is_empty() {
+            bail!(
+                item_enum.generics,
+                "generics are not allowed for `#[liquid(error)]` enum"
+            )
+        }
+
+        if item_enum.variants.is_empty() {
+            bail!(
+                item_enum,
+                "`#[liquid(error)]` enum should have at least one variant"
+            )
+        }
+
+        let span = item_enum.span();
+        let variants = item_enum
+            .variants
+            .into_iter()
+            .map(|variant| {
+                if !matches!(variant.fields, syn::Fields::Unit) {
+                    bail!(
+                        variant.fields,
+                        "variants of `#[liquid(error)]` enum can not carry data"
+                    )
+                }
+                if let Some((_, discriminant)) = &variant.discriminant {
+                    bail!(
+                        discriminant,
+                        "variants of `#[liquid(error)]` enum can not have explicit \
+                         discriminants, their codes are derived from their names"
+                    )
+                }
+
+                let message = variant
+                    .attrs
+                    .iter()
+                    .filter(|attr| attr.path.is_ident("doc"))
+                    .find_map(|attr| match attr.parse_meta() {
+                        Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                            lit: syn::Lit::Str(doc),
+                            ..
+                        })) => Some(doc.value().trim().to_owned()),
+                        _ => None,
+                    })
+                    .unwrap_or_else(|| variant.ident.to_string());
+
+                Ok(ir::ErrorVariant {
+                    attrs: variant.attrs,
+                    ident: variant.ident,
+                    message,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(ir::ItemError {
+            attrs: item_enum.attrs,
+            enum_token: item_enum.enum_token,
+            ident: item_enum.ident,
+            variants,
+            span,
+        })
+    }
+}
+
 impl TryFrom<syn::Item> for ir::Item {
     type Error = Error;
 
@@ -880,6 +1392,40 @@ impl TryFrom<syn::Item> for ir::Item {
                 if markers.is_empty() {
                     return Ok(ir::Item::Rust(Box::new(item.into())));
                 }
+                const STORAGE_ONLY_MARKERS: [&str; 2] =
+                    ["upgradeable", "storage_version"];
+                if let Some(marker) = markers.iter().find(|marker| {
+                    STORAGE_ONLY_MARKERS
+                        .iter()
+                        .any(|&storage_only| marker.ident == storage_only)
+                }) {
+                    let is_storage_only = |marker: &ir::Marker| {
+                        marker.ident == "storage"
+                            || STORAGE_ONLY_MARKERS
+                                .iter()
+                                .any(|&storage_only| marker.ident == storage_only)
+                    };
+                    if !markers.iter().any(|marker| marker.ident == "storage")
+                        || !markers.iter().all(is_storage_only)
+                    {
+                        bail_span!(
+                            marker.span(),
+                            "`liquid({})` can only be used together with \
+                             `liquid(storage)`",
+                            marker.ident
+                        )
+                    }
+                    let mut unique_markers = HashSet::new();
+                    for marker in markers.iter() {
+                        if !unique_markers.insert(marker.ident.to_string()) {
+                            bail!(marker.ident, "duplicate `{}` attribute", marker.ident)
+                        }
+                    }
+                    return ir::ItemStorage::try_from(item_struct)
+                        .map(Into::into)
+                        .map(Box::new)
+                        .map(ir::Item::Liquid);
+                }
                 if markers.len() > 1 {
                     bail!(
                         item_struct,
@@ -906,6 +1452,21 @@ impl TryFrom<syn::Item> for ir::Item {
                     _ => Ok(ir::Item::Rust(Box::new(item.into()))),
                 }
             }
+            syn::Item::Enum(item_enum) => {
+                let markers = ir_utils::filter_map_liquid_attributes(&item_enum.attrs)?;
+                match markers.first() {
+                    None => Ok(ir::Item::Rust(Box::new(item.into()))),
+                    Some(marker) if markers.len() == 1 && marker.ident == "error" => {
+                        ir::ItemError::try_from(item_enum)
+                            .map(Into::into)
+                            .map(Box::new)
+                            .map(ir::Item::Liquid)
+                    }
+                    Some(_) => {
+                        bail!(item_enum, "an enum can only be marked by `liquid(error)`")
+                    }
+                }
+            }
             syn::Item::Impl(item_impl) => {
                 let is_contract_impl;
                 {
@@ -1031,11 +1592,54 @@ impl TryFrom<&syn::ForeignItem> for ir::ForeignFn {
                     None
                 };
 
+                let cached = if let Some(marker) =
+                    markers.iter().find(|marker| marker.ident == "cached")
+                {
+                    if !matches!(marker.value, ir::AttrValue::None) {
+                        bail_span!(
+                            marker.span(),
+                            "the attribute `cached` should not be assigned with a value"
+                        )
+                    }
+                    if sig.is_mut() {
+                        bail_span!(
+                            marker.span(),
+                            "only methods which don't mutate the state of the foreign \
+                             contract can be cached"
+                        )
+                    }
+                    true
+                } else {
+                    false
+                };
+
+                let optional = if let Some(marker) =
+                    markers.iter().find(|marker| marker.ident == "optional")
+                {
+                    if !matches!(marker.value, ir::AttrValue::None) {
+                        bail_span!(
+                            marker.span(),
+                            "the attribute `optional` should not be assigned with a value"
+                        )
+                    }
+                    if cached {
+                        bail_span!(
+                            marker.span(),
+                            "a method can not be both `cached` and `optional`"
+                        )
+                    }
+                    true
+                } else {
+                    false
+                };
+
                 Ok(Self {
                     attrs: foreign_fn.attrs.clone(),
                     sig,
                     semi_token: foreign_fn.semi_token,
                     mock_context_getter,
+                    cached,
+                    optional,
                     span,
                 })
             }
@@ -1067,6 +1671,7 @@ impl TryFrom<(ir::InterfaceParams, syn::ItemMod)> for ir::Interface {
         };
 
         let mut foreign_structs = Vec::new();
+        let mut foreign_events = Vec::new();
         let mut foreign_fns = BTreeMap::<_, Vec<ir::ForeignFn>>::new();
         let mut imports = Vec::new();
         let span = item_mod.span();
@@ -1083,7 +1688,13 @@ impl TryFrom<(ir::InterfaceParams, syn::ItemMod)> for ir::Interface {
         for item in items {
             match item {
                 syn::Item::Struct(item_struct) => {
-                    foreign_structs.push(ir::ForeignStruct::try_from(item_struct)?);
+                    let markers =
+                        ir_utils::filter_map_liquid_attributes(&item_struct.attrs)?;
+                    if markers.iter().any(|marker| marker.ident == "event") {
+                        foreign_events.push(ir::ItemEvent::try_from(item_struct)?);
+                    } else {
+                        foreign_structs.push(ir::ForeignStruct::try_from(item_struct)?);
+                    }
                 }
                 syn::Item::Use(item_use) => {
                     imports.push(item_use);
@@ -1169,6 +1780,44 @@ impl TryFrom<(ir::InterfaceParams, syn::ItemMod)> for ir::Interface {
             }
         }
 
+        if meta_info.delegate {
+            if let Some(cached_fn) = foreign_fns.values().flatten().find(|f| f.cached) {
+                bail_span!(
+                    cached_fn.span,
+                    "methods of a delegate interface run against the storage of the \
+                     caller, so they can not be cached"
+                )
+            }
+        }
+
+        // Methods without their own getter are named after the template given
+        // to the interface, or `{method}_context` by default.
+        let template = meta_info
+            .mock_context_getter
+            .as_deref()
+            .unwrap_or("{}_context");
+        let mut getters = HashSet::new();
+        for (ident, fns) in foreign_fns.iter_mut() {
+            let getter = match &fns[0].mock_context_getter {
+                Some(getter) => getter.clone(),
+                None => {
+                    Ident::new(&template.replace("{}", &ident.to_string()), fns[0].span)
+                }
+            };
+            if !getters.insert(getter.to_string()) {
+                bail_span!(
+                    fns[0].span,
+                    "the mock context getter `{}` of method `{}` conflicts with the one of \
+                     another method, please rename it via `mock_context_getter` attribute",
+                    getter,
+                    ident,
+                )
+            }
+            for foreign_fn in fns.iter_mut() {
+                foreign_fn.mock_context_getter = Some(getter.clone());
+            }
+        }
+
         if let ir::LangType::Liquid = lang_type {
             for value in foreign_fns.values() {
                 if value.len() > 1 {
@@ -1186,6 +1835,7 @@ impl TryFrom<(ir::InterfaceParams, syn::ItemMod)> for ir::Interface {
             ident: item_mod.ident,
             meta_info,
             foreign_structs,
+            foreign_events,
             foreign_fns,
             imports,
             interface_ident,
@@ -0,0 +1,268 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::contract::ir::ItemStorage;
+use proc_macro2::Span;
+use quote::ToTokens;
+use serde::Deserialize;
+use std::path::Path;
+use syn::{spanned::Spanned, Error, Result};
+
+/// Name of the file recording the storage layout of the last released
+/// version of a contract, which is looked up in the root of the crate.
+pub const STORAGE_LAYOUT_FILE: &str = "storage_layout.json";
+
+/// Same as `liquid_abi_gen::StorageLayout`, from which the layout file is
+/// generated.
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct StorageLayout {
+    pub version: u32,
+    pub fields: Vec<FieldLayout>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct FieldLayout {
+    pub name: String,
+    pub key: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// Renders a type in a canonical form, which only keeps whitespaces between
+/// two words and after commas, e.g., `storage::Mapping<address, u256>`.
+pub fn type_to_string(ty: &syn::Type) -> String {
+    let tokens = ty.to_token_stream().to_string();
+    let chars = tokens.chars().collect::<Vec<_>>();
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let mut rendered = String::with_capacity(tokens.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_whitespace() {
+            let prev = rendered.chars().last();
+            let next = chars.get(i + 1).copied();
+            match (prev, next) {
+                (Some(prev), Some(next)) if is_word(prev) && is_word(next) => {
+                    rendered.push(' ')
+                }
+                (Some(','), _) => rendered.push(' '),
+                _ => (),
+            }
+        } else {
+            rendered.push(c);
+        }
+    }
+    rendered
+}
+
+impl From<&ItemStorage> for StorageLayout {
+    fn from(storage: &ItemStorage) -> Self {
+        let fields = storage
+            .fields
+            .named
+            .iter()
+            .map(|field| {
+                let name = field.ident.as_ref().unwrap().to_string();
+                FieldLayout {
+                    key: name.clone(),
+                    name,
+                    ty: type_to_string(&field.ty),
+                }
+            })
+            .collect();
+        Self {
+            version: storage.storage_version,
+            fields,
+        }
+    }
+}
+
+/// Checks whether the layout of `storage` is compatible with the one recorded
+/// in `previous`.
+///
+/// Fields can always be appended, but removing, renaming or retyping a field
+/// orphans the data stored by the previous version, hence is only allowed
+/// after increasing the storage version.
+fn check_compatibility(storage: &ItemStorage, previous: &StorageLayout) -> Result<()> {
+    let current = StorageLayout::from(storage);
+    if current.version < previous.version {
+        return Err(Error::new(
+            storage.storage_version_span,
+            format!(
+                "the storage version can not be decreased, the version recorded in `{}` \
+                 is {}",
+                STORAGE_LAYOUT_FILE, previous.version
+            ),
+        ));
+    }
+    if current.version > previous.version {
+        return Ok(());
+    }
+
+    for field in previous.fields.iter() {
+        let index = current
+            .fields
+            .iter()
+            .position(|current_field| current_field.key == field.key);
+        match index {
+            None => {
+                return Err(Error::new(
+                    storage.storage_version_span,
+                    format!(
+                        "the storage field `{}` of storage version {} is missing, \
+                         please increase the storage version if the data stored in it \
+                         is to be abandoned",
+                        field.name, previous.version
+                    ),
+                ))
+            }
+            Some(index) if current.fields[index].ty != field.ty => {
+                let span = storage.fields.named[index].ty.span();
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "the type of storage field `{}` is `{}` in storage version {}, \
+                         please increase the storage version if the data stored in it \
+                         is to be abandoned",
+                        field.name, field.ty, previous.version
+                    ),
+                ));
+            }
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks the layout of `storage` against the layout file in `dir` if there
+/// is one, and returns the path of the file.
+pub fn check_storage_layout(storage: &ItemStorage, dir: &Path) -> Result<Option<String>> {
+    let path = dir.join(STORAGE_LAYOUT_FILE);
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let path = path.to_string_lossy().into_owned();
+    let content = std::fs::read_to_string(&path).map_err(|err| {
+        Error::new(
+            Span::call_site(),
+            format!("failed to read `{}`: {}", path, err),
+        )
+    })?;
+    let previous = serde_json::from_str::<StorageLayout>(&content).map_err(|err| {
+        Error::new(
+            Span::call_site(),
+            format!("failed to parse `{}`: {}", path, err),
+        )
+    })?;
+    check_compatibility(storage, &previous)?;
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::TryFrom;
+    use syn::parse_quote;
+
+    fn storage(item_struct: syn::ItemStruct) -> ItemStorage {
+        ItemStorage::try_from(item_struct).unwrap()
+    }
+
+    fn previous(version: u32, fields: &[(&str, &str)]) -> StorageLayout {
+        StorageLayout {
+            version,
+            fields: fields
+                .iter()
+                .map(|(name, ty)| FieldLayout {
+                    name: name.to_string(),
+                    key: name.to_string(),
+                    ty: ty.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn render_types() {
+        let ty: syn::Type = parse_quote!(storage::Mapping<address, Vec<(u8, String)>>);
+        assert_eq!(
+            type_to_string(&ty),
+            "storage::Mapping<address, Vec<(u8, String)>>"
+        );
+        let ty: syn::Type = parse_quote!(storage::Value<&'static str>);
+        assert_eq!(type_to_string(&ty), "storage::Value<&'static str>");
+    }
+
+    #[test]
+    fn generate_layout() {
+        let storage = storage(parse_quote! {
+            #[liquid(storage)]
+            #[liquid(storage_version = 2)]
+            struct Foo {
+                pub balances: storage::Mapping<address, u256>,
+                owner: storage::Value<address>,
+            }
+        });
+        assert_eq!(
+            StorageLayout::from(&storage),
+            previous(
+                2,
+                &[
+                    ("balances", "storage::Mapping<address, u256>"),
+                    ("owner", "storage::Value<address>"),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn check_layout_compatibility() {
+        let storage = storage(parse_quote! {
+            #[liquid(storage)]
+            #[liquid(storage_version = 1)]
+            struct Foo {
+                owner: storage::Value<address>,
+                total: storage::Value<u64>,
+            }
+        });
+
+        assert!(check_compatibility(
+            &storage,
+            &previous(1, &[("owner", "storage::Value<address>")])
+        )
+        .is_ok());
+        assert!(check_compatibility(
+            &storage,
+            &previous(0, &[("admin", "storage::Value<address>")])
+        )
+        .is_ok());
+
+        let err = check_compatibility(
+            &storage,
+            &previous(1, &[("admin", "storage::Value<address>")]),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`admin` of storage version 1 is missing"));
+        let err = check_compatibility(
+            &storage,
+            &previous(1, &[("total", "storage::Value<u32>")]),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("type of storage field `total` is `storage::Value<u32>`"));
+        let err = check_compatibility(&storage, &previous(2, &[])).unwrap_err();
+        assert!(err.to_string().contains("can not be decreased"));
+    }
+}
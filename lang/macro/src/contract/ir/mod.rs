@@ -10,7 +10,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod component;
+pub mod docs;
 mod into;
+pub mod layout;
 mod params;
 mod syn_def;
 pub mod utils;
@@ -21,9 +24,10 @@ pub use self::{
         NameValue, ParamName,
     },
     syn_def::{
-        AssetAttribute, AssetMetaInfo, AttrValue, Contract, ContractMetaInfo, FnArg,
-        ForeignFn, ForeignStruct, Function, FunctionKind, IdentType, Interface,
-        InterfaceMetaInfo, Item, ItemAsset, ItemEvent, ItemImpl, ItemStorage, LangType,
-        LiquidItem, Marker, MetaVersion, RustItem, Signature,
+        AssetAttribute, AssetMetaInfo, AttrValue, Contract, ContractMetaInfo,
+        ErrorVariant, FnArg, ForeignFn, ForeignStruct, Function, FunctionKind, IdentType,
+        Interface, InterfaceMetaInfo, Item, ItemAsset, ItemError, ItemEvent, ItemImpl,
+        ItemStorage, LangType, LiquidItem, Marker, MetaVersion, Mixin, RustItem,
+        Signature,
     },
 };
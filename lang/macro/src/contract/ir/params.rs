@@ -147,12 +147,16 @@ impl Spanned for ParamVersion {
 //
 // ```no_compile
 // #[liquid::interface(name = auto)]
+// #[liquid::interface(name = auto, delegate)]
+// #[liquid::interface(name = auto, mock_context_getter = "mock_{}")]
 // ```
 params!(Interface);
 
 #[derive(From)]
 pub enum InterfaceMetaParam {
     Name(ParamName),
+    Delegate(ParamDelegate),
+    MockContextGetter(ParamMockContextGetter),
 }
 
 impl Parse for InterfaceMetaParam {
@@ -160,6 +164,10 @@ impl Parse for InterfaceMetaParam {
         let ident = input.fork().parse::<Ident>()?;
         match ident.to_string().as_str() {
             "name" => input.parse::<ParamName>().map(Into::into),
+            "delegate" => input.parse::<ParamDelegate>().map(Into::into),
+            "mock_context_getter" => {
+                input.parse::<ParamMockContextGetter>().map(Into::into)
+            }
             unknown => Err(format_err_span!(
                 ident.span(),
                 "unknown parameter: `{}`",
@@ -173,6 +181,8 @@ impl Spanned for InterfaceMetaParam {
     fn span(&self) -> Span {
         match self {
             InterfaceMetaParam::Name(param) => param.span(),
+            InterfaceMetaParam::Delegate(param) => param.span(),
+            InterfaceMetaParam::MockContextGetter(param) => param.span(),
         }
     }
 }
@@ -181,6 +191,8 @@ impl InterfaceMetaParam {
     pub fn ident(&self) -> &Ident {
         match &self {
             InterfaceMetaParam::Name(param) => &param.ident,
+            InterfaceMetaParam::Delegate(param) => &param.ident,
+            InterfaceMetaParam::MockContextGetter(param) => &param.ident,
         }
     }
 }
@@ -261,3 +273,76 @@ impl Spanned for ParamName {
         self.span
     }
 }
+
+pub struct ParamDelegate {
+    /// The `delegate` identifier
+    pub ident: Ident,
+}
+
+impl Parse for ParamDelegate {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident = input.parse::<Ident>()?;
+        if ident != "delegate" {
+            bail!(ident, "invalid identifier for delegate flag");
+        }
+        if input.peek(Token![=]) {
+            bail_span!(input.span(), "`delegate` parameter doesn't need a value");
+        }
+        Ok(Self { ident })
+    }
+}
+
+impl Spanned for ParamDelegate {
+    fn span(&self) -> Span {
+        self.ident.span()
+    }
+}
+
+pub struct ParamMockContextGetter {
+    /// The `mock_context_getter` identifier
+    pub ident: Ident,
+    /// The `=` token
+    pub eq_token: Token![=],
+    /// The template of names of mock context getters, in which `{}` is
+    /// replaced with the name of the method.
+    pub value: LitStr,
+}
+
+impl Parse for ParamMockContextGetter {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident = input.parse::<Ident>()?;
+        if ident != "mock_context_getter" {
+            bail!(ident, "invalid identifier for mock context getter template");
+        }
+        let eq_token = input.parse::<Token![=]>()?;
+        let value = input.parse::<LitStr>()?;
+
+        let template = value.value();
+        if template.matches("{}").count() != 1
+            || syn::parse_str::<Ident>(&template.replace("{}", "method")).is_err()
+        {
+            bail!(
+                value,
+                "invalid template for mock context getters: `{}`, the template should \
+                 contain exactly one `{{}}` and form an identifier after `{{}}` is \
+                 replaced with the name of a method",
+                template,
+            );
+        }
+
+        Ok(Self {
+            ident,
+            eq_token,
+            value,
+        })
+    }
+}
+
+impl Spanned for ParamMockContextGetter {
+    fn span(&self) -> Span {
+        self.ident
+            .span()
+            .join(self.value.span())
+            .expect("both spans are in the same file AND we are using nightly Rust")
+    }
+}
@@ -10,6 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::docs::Docs;
 use derive_more::From;
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::ToTokens;
@@ -31,12 +32,19 @@ pub struct MetaVersion {
 
 /// The meta info for a contract.
 pub struct ContractMetaInfo {
-    pub liquid_version: MetaVersion,
+    /// The semantic version of the contract given via `version` parameter,
+    /// e.g. `"1.2.0"`.
+    pub version: Option<String>,
 }
 
 /// The meta info for an interface.
 pub struct InterfaceMetaInfo {
     pub interface_name: String,
+    /// Whether methods of the interface are invoked via `delegatecall`, i.e.
+    /// executed against the storage of the caller.
+    pub delegate: bool,
+    /// The template of names of mock context getters, e.g. `"mock_{}"`.
+    pub mock_context_getter: Option<String>,
 }
 
 /// Contract item.
@@ -51,6 +59,7 @@ pub enum LiquidItem {
     Storage(ItemStorage),
     Event(ItemEvent),
     Asset(ItemAsset),
+    Error(ItemError),
     Impl(ItemImpl),
 }
 
@@ -77,6 +86,17 @@ pub struct ItemStorage {
     pub fields: syn::FieldsNamed,
     /// Public fields that need to generate a corresponding getter.
     pub public_fields: Vec<usize>,
+    /// Fields marked with `#[liquid(immutable)]`, which can only be assigned
+    /// in the constructor.
+    pub immutable_fields: Vec<usize>,
+    /// Whether the contract can be upgraded via `upgrade_to`.
+    pub upgradeable: bool,
+    /// The version of the storage layout specified by
+    /// `#[liquid(storage_version = N)]`, 0 if absent.
+    pub storage_version: u32,
+    /// Span of the `storage_version` attribute, or of the storage struct if
+    /// the attribute is absent.
+    pub storage_version_span: Span,
     /// Span of the storage struct.
     pub span: Span,
 }
@@ -94,6 +114,7 @@ mod kw {
     // syn::custom_keyword!(destroyable);
     syn::custom_keyword!(fungible);
     syn::custom_keyword!(description);
+    syn::custom_keyword!(auto_register);
 }
 
 #[derive(Debug, Clone)]
@@ -123,6 +144,11 @@ pub enum AssetAttribute {
         eq_token: Token![=],
         value: syn::LitStr,
     },
+    AutoRegister {
+        auto_register_token: kw::auto_register,
+        eq_token: Token![=],
+        value: syn::LitBool,
+    },
 }
 
 impl Parse for AssetAttribute {
@@ -158,6 +184,12 @@ impl Parse for AssetAttribute {
                 eq_token: input.parse()?,
                 value: input.parse()?,
             })
+        } else if lookahead.peek(kw::auto_register) {
+            Ok(AssetAttribute::AutoRegister {
+                auto_register_token: input.parse::<kw::auto_register>()?,
+                eq_token: input.parse()?,
+                value: input.parse()?,
+            })
         } else {
             Err(lookahead.error())
         }
@@ -180,6 +212,8 @@ pub struct ItemAsset {
     // pub destroyable: bool,
     pub fungible: bool,
     pub description: String,
+    /// Whether the asset is registered in the constructor.
+    pub auto_register: bool,
 }
 
 impl Spanned for ItemAsset {
@@ -196,6 +230,7 @@ pub struct AssetMetaInfo {
     // pub destroyable: bool,
     pub fungible: bool,
     pub description: String,
+    pub auto_register: bool,
 }
 
 impl AssetMetaInfo {
@@ -206,6 +241,7 @@ impl AssetMetaInfo {
             // destroyable: true,
             fungible: true,
             description: String::new(),
+            auto_register: false,
         }
     }
 }
@@ -235,6 +271,35 @@ impl Spanned for ItemEvent {
     }
 }
 
+/// An enum marked with `#[liquid(error)]`.
+pub struct ItemError {
+    /// Outer attributes of the enum.
+    pub attrs: Vec<syn::Attribute>,
+    /// The `enum` token.
+    pub enum_token: Token![enum],
+    /// The name of the enum.
+    pub ident: Ident,
+    /// Variants of the enum, none of which carries data.
+    pub variants: Vec<ErrorVariant>,
+    /// Span of the enum.
+    pub span: Span,
+}
+
+impl Spanned for ItemError {
+    /// Returns the span of the original `enum` definition.
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+pub struct ErrorVariant {
+    pub attrs: Vec<syn::Attribute>,
+    pub ident: Ident,
+    /// The first line of doc comments of the variant, or the name of the
+    /// variant if it has no doc comments.
+    pub message: String,
+}
+
 /// The implementation of the storage struct.
 pub struct ItemImpl {
     /// Inner attributes.
@@ -245,8 +310,13 @@ pub struct ItemImpl {
     pub ty: Ident,
     /// The `{` and `}` tokens.
     pub brace_token: syn::token::Brace,
+    /// The trait implemented by this block, which should be marked with
+    /// `#[liquid::mixin]`. Methods in such blocks are all kept as helpers.
+    pub mixin: Option<syn::Path>,
     /// Constructor and external functions.
     pub functions: Vec<Function>,
+    /// Internal helper methods, kept as they are.
+    pub helpers: Vec<syn::ImplItemMethod>,
     /// Constants defined for the contract.
     pub constants: Vec<syn::ImplItemConst>,
 }
@@ -260,6 +330,10 @@ pub struct Function {
     pub sig: Signature,
     /// The body of the function.
     pub body: syn::Block,
+    /// The selector pinned via `#[liquid(selector = "...")]`.
+    pub selector: Option<[u8; 4]>,
+    /// Doc comments of the function.
+    pub docs: Docs,
     /// The span of the function.
     pub span: Span,
 }
@@ -348,6 +422,8 @@ pub struct IdentType {
     pub colon_token: Token![:],
     /// The type of the argument.
     pub ty: syn::Type,
+    /// Doc comments of the argument.
+    pub docs: String,
     /// The span of the argument
     pub span: Span,
 }
@@ -365,6 +441,7 @@ impl ToTokens for IdentType {
 #[derive(Debug)]
 pub enum AttrValue {
     LitStr(syn::LitStr),
+    LitInt(syn::LitInt),
     Ident(syn::Ident),
     Fields(Vec<AssetAttribute>),
     None,
@@ -382,9 +459,14 @@ impl Parse for AttrValue {
             return Ok(Self::LitStr(lit_str));
         }
 
+        if input.peek(syn::LitInt) {
+            let lit_int = input.parse::<syn::LitInt>()?;
+            return Ok(Self::LitInt(lit_int));
+        }
+
         Err(input.error(
-            "invalid value of an liquid attribute, identifier or a literal string \
-             required",
+            "invalid value of an liquid attribute, identifier, a literal string or a \
+             literal integer required",
         ))
     }
 }
@@ -425,22 +507,40 @@ pub struct Contract {
     pub mod_token: Token![mod],
     /// The modules snake case identifier.
     pub ident: Ident,
+    /// Doc comments of the contract module.
+    pub docs: Docs,
     /// Special contract meta attributes.
     pub meta_info: ContractMetaInfo,
     /// The contract storage.
     pub storage: ItemStorage,
     /// The contract events.
     pub events: Vec<ItemEvent>,
+    /// The error enums of the contract.
+    pub errors: Vec<ItemError>,
     /// The contract assets.
     pub assets: Vec<ItemAsset>,
     /// Constructor function.
     pub constructor: Function,
+    /// Constructors marked with `#[liquid(constructor)]`, one of which is
+    /// selected instead of `new` if the deployment data starts with its
+    /// selector.
+    pub alt_constructors: Vec<Function>,
     /// External and normal functions of the contract.
     pub functions: Vec<Function>,
+    /// Internal helper methods of the contract.
+    pub helpers: Vec<syn::ImplItemMethod>,
+    /// Implementations of mixin traits, whose provided methods are exported.
+    pub mixins: Vec<ItemImpl>,
     /// Constants defined for the contract.
     pub constants: Vec<syn::ImplItemConst>,
     /// The non-liquid items.
     pub rust_items: Vec<RustItem>,
+    /// Hash of the source of the contract module.
+    pub source_hash: [u8; 32],
+    /// Path of the storage layout file checked against, if any.
+    pub storage_layout_file: Option<String>,
+    /// Paths of the files brought in by `include!`.
+    pub included_files: Vec<String>,
 }
 
 /// The user-defined data structure declared in an interface.
@@ -470,8 +570,14 @@ pub struct ForeignFn {
     pub semi_token: Token![;],
     /// The span of the foreign method.
     pub span: Span,
-    /// The name of the mock context getter.
+    /// The name of the mock context getter, always present once the
+    /// interface has been parsed.
     pub mock_context_getter: Option<Ident>,
+    /// Whether results of this method are memoized within a transaction.
+    pub cached: bool,
+    /// Whether the callee may lack this method, in which case calling it
+    /// returns `None` rather than reverting.
+    pub optional: bool,
 }
 
 impl Spanned for ForeignFn {
@@ -485,6 +591,16 @@ pub enum LangType {
     Liquid,
 }
 
+/// A trait marked with `#[liquid::mixin]`, whose provided methods are
+/// exported by every contract implementing it.
+pub struct Mixin {
+    /// The trait with liquid attributes removed.
+    pub item_trait: syn::ItemTrait,
+    /// Signatures of the provided methods to be exported, along with their
+    /// pinned selectors.
+    pub external_fns: Vec<(Signature, Option<[u8; 4]>)>,
+}
+
 /// The interface with all required information.
 pub struct Interface {
     /// The `mod` token.
@@ -495,6 +611,8 @@ pub struct Interface {
     pub meta_info: InterfaceMetaInfo,
     /// The user-defined data structures.
     pub foreign_structs: Vec<ForeignStruct>,
+    /// The events emitted by the remote contract.
+    pub foreign_events: Vec<ItemEvent>,
     /// The declarations of methods.
     pub foreign_fns: BTreeMap<Ident, Vec<ForeignFn>>,
     /// The use declarations to import other symbols.
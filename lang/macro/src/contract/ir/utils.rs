@@ -10,10 +10,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{Function, ItemAsset, ItemEvent, ItemStorage, LiquidItem, Marker};
-use crate::utils as lang_utils;
-use proc_macro2::Span;
-use syn::{spanned::Spanned, Result};
+use super::{
+    AttrValue, Function, FunctionKind, ItemAsset, ItemError, ItemEvent, ItemImpl,
+    ItemStorage, LiquidItem, Marker, Signature,
+};
+use crate::{
+    contract::{ASSET_ISSUED, ASSET_TRANSFERRED},
+    utils as lang_utils,
+};
+use proc_macro2::{Ident, Span};
+use quote::{quote, quote_spanned};
+use syn::{
+    spanned::Spanned,
+    visit::{self, Visit},
+    Result,
+};
 
 pub fn filter_map_liquid_attributes<'a, I>(attrs: I) -> Result<Vec<Marker>>
 where
@@ -39,9 +50,12 @@ where
 pub type ContractItems = (
     ItemStorage,
     Vec<ItemEvent>,
+    Vec<ItemError>,
     Vec<ItemAsset>,
     Vec<Function>,
+    Vec<syn::ImplItemMethod>,
     Vec<syn::ImplItemConst>,
+    Vec<ItemImpl>,
 );
 
 pub fn split_items(items: Vec<LiquidItem>, span: Span) -> Result<ContractItems> {
@@ -53,27 +67,28 @@ pub fn split_items(items: Vec<LiquidItem>, span: Span) -> Result<ContractItems>
             LiquidItem::Storage(storage) => Either::Left(storage),
             other => Either::Right(other),
         });
-    let storage = match storages.len() {
-        0 => {
-            return Err(format_err_span!(
-                span,
-                "no `#[liquid(storage)]` struct found in this contract"
-            ))
-        }
-        1 => storages.pop().unwrap(),
-        _ => {
-            return Err(format_err_span!(
-                storages[1].span(),
-                "duplicate `#[liquid(storage)]` struct definition found here"
-            ))
-        }
-    };
+    if storages.is_empty() {
+        return Err(format_err_span!(
+            span,
+            "no `#[liquid(storage)]` struct found in this contract"
+        ));
+    }
+    let mut storage = storages.remove(0);
+    for segment in storages {
+        merge_storage_segment(&mut storage, segment)?;
+    }
     let (assets, others): (Vec<_>, Vec<_>) =
         others.into_iter().partition_map(|item| match item {
             LiquidItem::Asset(asset) => Either::Left(asset),
             other => Either::Right(other),
         });
 
+    let (errors, others): (Vec<_>, Vec<_>) =
+        others.into_iter().partition_map(|item| match item {
+            LiquidItem::Error(error) => Either::Left(error),
+            other => Either::Right(other),
+        });
+
     let (events, impl_blocks): (Vec<_>, Vec<_>) =
         others.into_iter().partition_map(|item| match item {
             LiquidItem::Event(event) => Either::Left(event),
@@ -91,12 +106,355 @@ pub fn split_items(items: Vec<LiquidItem>, span: Span) -> Result<ContractItems>
         }
     }
 
-    let (functions, constants): (Vec<_>, Vec<_>) = impl_blocks
+    let (mixins, impl_blocks): (Vec<_>, Vec<_>) = impl_blocks
         .into_iter()
-        .map(|block| (block.functions, block.constants))
-        .unzip();
+        .partition(|block| block.mixin.is_some());
+    let (mut functions, mut helpers, mut constants) =
+        (Vec::new(), Vec::new(), Vec::new());
+    for block in impl_blocks {
+        functions.extend(block.functions);
+        helpers.extend(block.helpers);
+        constants.extend(block.constants);
+    }
+    Ok((
+        storage, events, errors, assets, functions, helpers, constants, mixins,
+    ))
+}
+
+/// Appends the fields of another `#[liquid(storage)]` struct with the same
+/// name to the storage, which allows the storage to be declared in segments,
+/// e.g., one for each component of the contract. Fields are laid out in the
+/// order of the segments.
+fn merge_storage_segment(storage: &mut ItemStorage, segment: ItemStorage) -> Result<()> {
+    if segment.ident != storage.ident {
+        bail_span!(
+            segment.span(),
+            "duplicate `#[liquid(storage)]` struct definition found here"
+        )
+    }
+
+    if segment.storage_version != 0 {
+        if storage.storage_version != 0 {
+            bail_span!(
+                segment.storage_version_span,
+                "`storage_version` should be specified in only one segment of the \
+                 storage"
+            )
+        }
+        storage.storage_version = segment.storage_version;
+        storage.storage_version_span = segment.storage_version_span;
+    }
+    storage.upgradeable |= segment.upgradeable;
+
+    let offset = storage.fields.named.len();
+    for field in segment.fields.named {
+        let ident = field.ident.as_ref().unwrap();
+        if storage
+            .fields
+            .named
+            .iter()
+            .any(|existing| existing.ident.as_ref() == Some(ident))
+        {
+            bail!(ident, "duplicate storage field `{}` found here", ident)
+        }
+        storage.fields.named.push(field);
+    }
+    storage
+        .public_fields
+        .extend(segment.public_fields.into_iter().map(|i| i + offset));
+    storage
+        .immutable_fields
+        .extend(segment.immutable_fields.into_iter().map(|i| i + offset));
+    Ok(())
+}
+
+/// Finds the only storage field whose type is `liquid_lang::acl::<ty_name>`.
+fn find_acl_field<'a>(
+    storage: &'a ItemStorage,
+    ty_name: &str,
+    marker: &Marker,
+) -> Result<&'a Ident> {
+    let mut fields = storage.fields.named.iter().filter(|field| match &field.ty {
+        syn::Type::Path(ty_path) => ty_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == ty_name)
+            .unwrap_or(false),
+        _ => false,
+    });
+
+    match (fields.next(), fields.next()) {
+        (Some(field), None) => Ok(field.ident.as_ref().unwrap()),
+        (None, _) => bail!(
+            marker.ident,
+            "`{}` requires a storage field of type `liquid_lang::acl::{}`",
+            marker.ident,
+            ty_name,
+        ),
+        (Some(_), Some(another)) => bail!(
+            another,
+            "`{}` is ambiguous since there are multiple storage fields of type \
+             `liquid_lang::acl::{}`",
+            marker.ident,
+            ty_name,
+        ),
+    }
+}
+
+/// Declares the `AssetIssued` and `AssetTransferred` events, which are emitted
+/// by the operations on assets.
+pub fn expand_asset_events(events: &mut Vec<ItemEvent>, span: Span) -> Result<()> {
+    use core::convert::TryFrom;
+
+    for event in events.iter() {
+        let ident = event.ident.to_string();
+        if ident == ASSET_ISSUED || ident == ASSET_TRANSFERRED {
+            bail!(
+                event.ident,
+                "`{}` is reserved for the events of assets declared in this contract",
+                ident
+            )
+        }
+    }
+
+    let asset_issued = Ident::new(ASSET_ISSUED, span);
+    let asset_transferred = Ident::new(ASSET_TRANSFERRED, span);
+    let asset_events = [
+        syn::parse2::<syn::ItemStruct>(quote! {
+            #[liquid(event)]
+            struct #asset_issued {
+                #[liquid(indexed)]
+                asset: String,
+                #[liquid(indexed)]
+                to: address,
+                amount_or_id: u64,
+            }
+        })?,
+        syn::parse2::<syn::ItemStruct>(quote! {
+            #[liquid(event)]
+            struct #asset_transferred {
+                #[liquid(indexed)]
+                asset: String,
+                #[liquid(indexed)]
+                from: address,
+                #[liquid(indexed)]
+                to: address,
+                amount_or_id: u64,
+            }
+        })?,
+    ];
+    for asset_event in asset_events.iter() {
+        events.push(ItemEvent::try_from(asset_event.clone())?);
+    }
+
+    Ok(())
+}
+
+/// Takes the `#[liquid(asset_received)]` marker off the method it's attached
+/// to, and returns the name of that method if there is one.
+pub fn take_asset_received_hook(functions: &mut [Function]) -> Result<Option<Ident>> {
+    take_hook(
+        functions,
+        "asset_received",
+        "when assets are deposited into this contract",
+    )
+}
+
+/// Takes the `#[liquid(migration)]` marker off the method it's attached to,
+/// and returns the name of that method if there is one.
+pub fn take_migration_hook(functions: &mut [Function]) -> Result<Option<Ident>> {
+    take_hook(
+        functions,
+        "migration",
+        "when the contract is upgraded to a newer storage version",
+    )
+}
+
+/// Takes the marker `name` off the private `&mut self` method it's attached
+/// to. Such methods are hooks invoked by generated entry points only.
+fn take_hook(
+    functions: &mut [Function],
+    name: &str,
+    invoked_when: &str,
+) -> Result<Option<Ident>> {
+    use core::convert::TryFrom;
+
+    let mut hook: Option<Ident> = None;
+    for function in functions.iter_mut() {
+        let mut attrs = Vec::with_capacity(function.attrs.len());
+        for attr in function.attrs.drain(..) {
+            if !lang_utils::is_liquid_attribute(&attr) {
+                attrs.push(attr);
+                continue;
+            }
+            let marker = Marker::try_from(attr.clone())?;
+            if marker.ident != name {
+                attrs.push(attr);
+                continue;
+            }
+
+            let sig = &function.sig;
+            if hook.is_some() {
+                bail!(marker.ident, "duplicate `{}` hook found here", name)
+            }
+            match function.kind {
+                FunctionKind::Constructor => {
+                    bail!(marker.ident, "`{}` can not be used on constructor", name)
+                }
+                FunctionKind::External(..) => bail!(
+                    sig.ident,
+                    "`{}` hook should not be `pub`, it's only invoked {}",
+                    name,
+                    invoked_when
+                ),
+                FunctionKind::Normal => (),
+            }
+            if !sig.is_mut() {
+                bail_span!(
+                    sig.inputs[0].span(),
+                    "`&mut self` is mandatory first parameter for `{}` hook",
+                    name
+                )
+            }
+            if let syn::ReturnType::Type(_, ty) = &sig.output {
+                bail!(ty, "`{}` hook should not have return value", name)
+            }
+            hook = Some(sig.ident.clone());
+        }
+        function.attrs = attrs;
+    }
+
+    Ok(hook)
+}
+
+/// Parses `#[liquid(selector = "0x12345678")]`, which pins the selector of a
+/// method instead of deriving it from the signature.
+pub fn parse_selector(marker: &Marker) -> Result<[u8; 4]> {
+    let lit = match &marker.value {
+        AttrValue::LitStr(lit) => lit,
+        _ => bail!(
+            marker.ident,
+            "the selector should be a literal string, e.g. `#[liquid(selector = \
+             \"0x12345678\")]`"
+        ),
+    };
+    let value = lit.value();
+    let digits = value.strip_prefix("0x").unwrap_or(&value);
+    if digits.len() != 8 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!(
+            lit,
+            "`{}` is not a valid selector, which should be 8 hexadecimal digits with an \
+             optional `0x` prefix",
+            value
+        )
+    }
+
+    let mut selector = [0u8; 4];
+    for (i, byte) in selector.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16).unwrap();
+    }
+    Ok(selector)
+}
+
+/// Rejects events emitted via `self.env().emit(...)` in an external method
+/// taking `&self`. Such methods are called as queries, whose events would
+/// never be recorded on chain.
+pub fn check_read_only(sig: &Signature, block: &syn::Block) -> Result<()> {
+    if sig.is_mut() {
+        return Ok(());
+    }
+
+    let mut finder = EmitFinder(None);
+    finder.visit_block(block);
+    if let Some(span) = finder.0 {
+        bail_span!(
+            span,
+            "events can not be emitted in methods taking `&self`, consider \
+             taking `&mut self` instead"
+        )
+    }
+    Ok(())
+}
+
+struct EmitFinder(Option<Span>);
+
+impl<'ast> Visit<'ast> for EmitFinder {
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        let is_env = matches!(
+            &*call.receiver,
+            syn::Expr::MethodCall(receiver) if receiver.method == "env" && receiver.args.is_empty()
+        );
+        if self.0.is_none() && is_env && call.method == "emit" {
+            self.0 = Some(call.method.span());
+            return;
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+}
+
+/// Expands `#[liquid(only_owner)]` and `#[liquid(only_role = "...")]` on
+/// methods into permission checks inserted before the original body.
+pub fn expand_access_control(
+    storage: &ItemStorage,
+    functions: &mut [Function],
+) -> Result<()> {
+    for function in functions.iter_mut() {
+        let mut guards = Vec::new();
+        for marker in filter_map_liquid_attributes(&function.attrs)? {
+            let span = marker.span();
+            let ident_str = marker.ident.to_string();
+            match ident_str.as_str() {
+                "only_owner" => {
+                    if !matches!(marker.value, AttrValue::None) {
+                        bail!(
+                            marker.ident,
+                            "`only_owner` should be used without any parameters"
+                        )
+                    }
+                    let field = find_acl_field(storage, "Ownable", &marker)?;
+                    guards.push(quote_spanned! { span =>
+                        self.#field.ensure_owner();
+                    });
+                }
+                "only_role" => {
+                    let role = match &marker.value {
+                        AttrValue::LitStr(role) => role,
+                        _ => bail!(
+                            marker.ident,
+                            "the role should be a literal string, e.g. \
+                             `#[liquid(only_role = \"MINTER\")]`"
+                        ),
+                    };
+                    let field = find_acl_field(storage, "AccessControl", &marker)?;
+                    guards.push(quote_spanned! { span =>
+                        self.#field.ensure_role(#role);
+                    });
+                }
+                _ => bail!(
+                    marker.ident,
+                    "unknown liquid attribute `{}` for methods",
+                    ident_str
+                ),
+            }
+
+            if let FunctionKind::Constructor = function.kind {
+                bail!(
+                    marker.ident,
+                    "access control attributes can not be used on constructor"
+                )
+            }
+        }
+
+        if !guards.is_empty() {
+            let mut stmts = guards
+                .into_iter()
+                .map(syn::parse2::<syn::Stmt>)
+                .collect::<Result<Vec<_>>>()?;
+            stmts.append(&mut function.body.stmts);
+            function.body.stmts = stmts;
+        }
+    }
 
-    let functions = functions.into_iter().flatten().collect();
-    let constants = constants.into_iter().flatten().collect();
-    Ok((storage, events, assets, functions, constants))
+    Ok(())
 }
@@ -21,6 +21,7 @@ use syn::Result;
 pub enum GenerateMode {
     Contract,
     Interface,
+    Mixin,
 }
 
 pub fn generate(
@@ -54,8 +55,28 @@ fn generate_impl(
             let liquid_ir = ir::Interface::try_from((params, item_mod))?;
             Ok(liquid_ir.generate_code())
         }
+        GenerateMode::Mixin => {
+            if !attr.is_empty() {
+                bail!(attr, "`#[liquid::mixin]` does not accept any arguments");
+            }
+            let item_trait = syn::parse2::<syn::ItemTrait>(input)?;
+            let liquid_ir = ir::Mixin::try_from(item_trait)?;
+            Ok(liquid_ir.generate_code())
+        }
     }
 }
 
 pub const SUPPORTS_ASSET_NAME: &str = "__liquid_supports_asset";
 pub const SUPPORTS_ASSET_SIGNATURE: &str = "__liquid_supports_asset(string)";
+pub const ASSET_RECEIVED_NAME: &str = "__liquid_asset_received";
+pub const ASSET_RECEIVED_SIGNATURE: &str =
+    "__liquid_asset_received(string,address,uint64)";
+pub const ASSET_ISSUED: &str = "AssetIssued";
+pub const ASSET_TRANSFERRED: &str = "AssetTransferred";
+pub const UPGRADE_TO_NAME: &str = "upgrade_to";
+pub const VERSION_NAME: &str = "version";
+/// Should be kept in sync with `liquid_lang::upgrade::MIGRATE_SELECTOR`.
+pub const MIGRATE_NAME: &str = "__liquid_migrate";
+pub const READ_ONLY_VIOLATION: &str = "attempt to emit events or call mutable external \
+                                       interfaces in an immutable call, all writes will \
+                                       be discarded";
@@ -10,6 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::derive::utils;
 use liquid_prelude::vec::Vec;
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::{quote, quote_spanned};
@@ -83,6 +84,12 @@ fn generate_impl(input: TokenStream2) -> Result<TokenStream2> {
                 #encode_shadow_struct
                 #decode_shadow_struct
                 #abi_impls
+
+                impl liquid_lang::You_Should_Use_An_Valid_Event_Topic_Type for #ident {
+                    fn topic(&self) -> liquid_primitives::types::Hash {
+                        liquid_lang::composite_topic(self)
+                    }
+                }
             }
         }
         Data::Enum(ref enum_data) => {
@@ -176,6 +183,7 @@ fn generate_impl(input: TokenStream2) -> Result<TokenStream2> {
     };
 
     let abi_impls = quote! {};
+    let arbitrary = utils::generate_arbitrary(&ast);
 
     shadow.extend(quote! {
         impl liquid_lang::You_Should_Use_An_Valid_Field_Type for #ident {}
@@ -184,6 +192,7 @@ fn generate_impl(input: TokenStream2) -> Result<TokenStream2> {
         impl liquid_lang::You_Should_Use_An_Valid_Input_Type for #ident {}
 
         #abi_impls
+        #arbitrary
     });
     Ok(shadow)
 }
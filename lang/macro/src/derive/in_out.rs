@@ -11,10 +11,13 @@
 // limitations under the License.
 
 use crate::derive::utils;
-use liquid_prelude::{string::ToString, vec::Vec};
+use liquid_prelude::{
+    string::{String, ToString},
+    vec::Vec,
+};
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::{quote, quote_spanned};
-use syn::{self, parse::Result, spanned::Spanned, DeriveInput};
+use syn::{self, parse::Result, spanned::Spanned, Data, DataEnum, DeriveInput, Fields};
 
 pub fn generate(input: TokenStream2) -> TokenStream2 {
     match generate_impl(input) {
@@ -74,6 +77,10 @@ fn generate_abi_gen(
 
 fn generate_impl(input: TokenStream2) -> Result<TokenStream2> {
     let ast: DeriveInput = syn::parse2(input)?;
+    if let Data::Enum(ref enum_data) = ast.data {
+        return generate_enum(&ast, enum_data);
+    }
+
     let (field_names, field_tys, fields_span): (Vec<_>, Vec<_>, Span) =
         utils::struct_syntax_check(&ast)?;
     let ident = &ast.ident;
@@ -104,6 +111,7 @@ fn generate_impl(input: TokenStream2) -> Result<TokenStream2> {
     }
 
     let abi_gen_helper = generate_abi_gen(&field_names, &field_tys, &ident);
+    let arbitrary = utils::generate_arbitrary(&ast);
 
     Ok(quote_spanned! { fields_span =>
         #(#field_checkers)*
@@ -170,11 +178,474 @@ fn generate_impl(input: TokenStream2) -> Result<TokenStream2> {
         }
 
         #abi_gen_helper
+        #arbitrary
 
         impl liquid_lang::You_Should_Use_An_Valid_InOut_Type for #ident {}
         impl liquid_lang::You_Should_Use_An_Valid_Element_Type for #ident {}
         impl liquid_lang::You_Should_Use_An_Valid_Event_Data_Type for #ident {}
         impl liquid_lang::You_Should_Use_An_Valid_Return_Type for #ident {}
         impl liquid_lang::You_Should_Use_An_Valid_Input_Type for #ident {}
+        impl liquid_lang::You_Should_Use_An_Valid_Event_Topic_Type for #ident {
+            fn topic(&self) -> liquid_primitives::types::Hash {
+                liquid_lang::composite_topic(self)
+            }
+        }
     })
 }
+
+struct Variant<'a> {
+    ident: &'a Ident,
+    unnamed: bool,
+    field_names: Vec<&'a Ident>,
+    field_tys: Vec<&'a syn::Type>,
+}
+
+impl<'a> Variant<'a> {
+    fn is_unit(&self) -> bool {
+        self.field_tys.is_empty()
+    }
+
+    /// Identifiers used to bind the fields of this variant in generated code,
+    /// which never conflict with the names of local variables.
+    fn bindings(&self) -> Vec<Ident> {
+        (0..self.field_tys.len())
+            .map(|i| Ident::new(&format!("__liquid_field_{}", i), Span::call_site()))
+            .collect()
+    }
+
+    /// Generates a pattern or an expression of this variant, in which the
+    /// fields are bound to `bindings`.
+    fn destructure(&self, enum_ident: &Ident, bindings: &[Ident]) -> TokenStream2 {
+        let variant_ident = self.ident;
+        if self.is_unit() {
+            quote!(#enum_ident::#variant_ident)
+        } else if self.unnamed {
+            quote!(#enum_ident::#variant_ident(#(#bindings,)*))
+        } else {
+            let field_names = &self.field_names;
+            quote!(#enum_ident::#variant_ident { #(#field_names: #bindings,)* })
+        }
+    }
+
+    fn is_dynamic(&self) -> TokenStream2 {
+        let field_tys = &self.field_tys;
+        quote! {
+            (#(<#field_tys as liquid_abi_codec::TypeInfo>::is_dynamic() ||)* false)
+        }
+    }
+}
+
+/// Unit-only enums are encoded as `uint8`, while enums with data-carrying
+/// variants are encoded as a tagged tuple, in which a `uint8` tag is followed
+/// by the fields of every data-carrying variant in declaration order, and the
+/// fields of inactive variants are filled with their default values.
+fn generate_enum(ast: &DeriveInput, enum_data: &DataEnum) -> Result<TokenStream2> {
+    match &ast.vis {
+        syn::Visibility::Public(_) => (),
+        _ => bail!(ast, "the visibility of this type should be `pub`"),
+    }
+
+    if ast.generics.type_params().count() > 0 {
+        bail!(&ast.generics, "generic enums are not supported")
+    }
+
+    if enum_data.variants.is_empty() {
+        bail!(ast, "empty enums are not supported")
+    }
+
+    if enum_data.variants.len() > u8::MAX as usize + 1 {
+        bail!(ast, "an enum can have at most 256 variants")
+    }
+
+    let mut variants = Vec::new();
+    for variant in &enum_data.variants {
+        if let Some((_, discriminant)) = &variant.discriminant {
+            bail!(discriminant, "custom discriminants are not supported")
+        }
+
+        let (unnamed, field_names, field_tys) = match &variant.fields {
+            Fields::Named(fields_named) => {
+                let (field_names, field_tys) = fields_named
+                    .named
+                    .iter()
+                    .map(|field| (field.ident.as_ref().unwrap(), &field.ty))
+                    .unzip();
+                (false, field_names, field_tys)
+            }
+            Fields::Unnamed(fields_unnamed) => (
+                true,
+                Vec::new(),
+                fields_unnamed
+                    .unnamed
+                    .iter()
+                    .map(|field| &field.ty)
+                    .collect(),
+            ),
+            Fields::Unit => (false, Vec::new(), Vec::new()),
+        };
+
+        if field_tys.is_empty() && !matches!(variant.fields, Fields::Unit) {
+            bail!(
+                &variant.fields,
+                "variants without fields should be declared as unit variants"
+            )
+        }
+
+        variants.push(Variant {
+            ident: &variant.ident,
+            unnamed,
+            field_names,
+            field_tys,
+        });
+    }
+
+    let ident = &ast.ident;
+    let data_variants = variants
+        .iter()
+        .enumerate()
+        .filter(|(_, variant)| !variant.is_unit())
+        .map(|(i, variant)| (i as u8, variant))
+        .collect::<Vec<_>>();
+
+    if data_variants.len() > 15 {
+        bail!(ast, "an enum can have at most 15 variants carrying data")
+    }
+
+    let field_checkers = data_variants
+        .iter()
+        .flat_map(|(_, variant)| variant.field_tys.iter())
+        .enumerate()
+        .map(|(i, ty)| {
+            let field_checker = Ident::new(
+                &format!("__LIQUID_INOUT_FIELD_CHECKER_{}", i),
+                Span::call_site(),
+            );
+            quote_spanned! { ty.span() =>
+                #[allow(non_camel_case_types)]
+                struct #field_checker(<#ty as liquid_lang::You_Should_Use_An_Valid_InOut_Type>::T);
+            }
+        });
+
+    let invalid_tag = format!("Invalid variant index of enum `{}`", ident);
+    let codec_impls = if data_variants.is_empty() {
+        let (patterns, tags): (Vec<_>, Vec<_>) = variants
+            .iter()
+            .enumerate()
+            .map(|(i, variant)| (variant.destructure(ident, &[]), i as u8))
+            .unzip();
+
+        quote! {
+            impl liquid_abi_codec::TypeInfo for #ident {}
+
+            impl liquid_abi_codec::MediateEncode for #ident {
+                fn encode(&self) -> liquid_abi_codec::Mediate {
+                    let tag: u8 = match self {
+                        #(#patterns => #tags,)*
+                    };
+                    liquid_abi_codec::MediateEncode::encode(&tag)
+                }
+            }
+
+            impl liquid_abi_codec::MediateDecode for #ident {
+                fn decode(slices: &[liquid_abi_codec::Word], offset: usize) -> ::core::result::Result<liquid_abi_codec::DecodeResult<Self>, liquid_primitives::Error> {
+                    let tag = <u8 as liquid_abi_codec::MediateDecode>::decode(slices, offset)?;
+                    let value = match tag.value {
+                        #(#tags => #patterns,)*
+                        _ => return Err(#invalid_tag.into()),
+                    };
+
+                    Ok(liquid_abi_codec::DecodeResult {
+                        value,
+                        new_offset: tag.new_offset,
+                    })
+                }
+            }
+
+            impl liquid_ty_mapping::MappingToSolidityType for #ident {
+                const MAPPED_TYPE_NAME: [u8; liquid_ty_mapping::MAX_LENGTH_OF_MAPPED_TYPE_NAME] =
+                    <u8 as liquid_ty_mapping::MappingToSolidityType>::MAPPED_TYPE_NAME;
+            }
+        }
+    } else {
+        let all_field_tys = data_variants
+            .iter()
+            .flat_map(|(_, variant)| variant.field_tys.iter())
+            .collect::<Vec<_>>();
+        let slot_markers = (0..data_variants.len())
+            .map(|i| {
+                Ident::new(&format!("__LIQUID_INOUT_VARIANT_{}", i), Span::call_site())
+            })
+            .collect::<Vec<_>>();
+        let slot_bindings = (0..data_variants.len())
+            .map(|i| Ident::new(&format!("__liquid_variant_{}", i), Span::call_site()))
+            .collect::<Vec<_>>();
+
+        let encode_arms = variants.iter().enumerate().map(|(i, variant)| {
+            let tag = i as u8;
+            let bindings = variant.bindings();
+            let pattern = variant.destructure(ident, &bindings);
+            if variant.is_unit() {
+                quote!(#pattern => #tag,)
+            } else {
+                let is_dynamic = variant.is_dynamic();
+                quote! {
+                    #pattern => {
+                        let mut fields = __std::Vec::new();
+                        #(fields.push(liquid_abi_codec::MediateEncode::encode(#bindings));)*
+                        active = Some(if #is_dynamic {
+                            liquid_abi_codec::Mediate::PrefixedTuple(fields)
+                        } else {
+                            liquid_abi_codec::Mediate::RawTuple(fields)
+                        });
+                        #tag
+                    }
+                }
+            }
+        });
+        let encode_slots = data_variants.iter().map(|(tag, variant)| {
+            let defaults = variant.field_tys.iter().map(|ty| {
+                quote_spanned! { ty.span() =>
+                    <#ty as Default>::default()
+                }
+            });
+            let is_dynamic = variant.is_dynamic();
+            quote! {
+                mediates.push(if tag == #tag {
+                    active.take().unwrap()
+                } else {
+                    let mut fields = __std::Vec::new();
+                    #(fields.push(liquid_abi_codec::MediateEncode::encode(&#defaults));)*
+                    if #is_dynamic {
+                        liquid_abi_codec::Mediate::PrefixedTuple(fields)
+                    } else {
+                        liquid_abi_codec::Mediate::RawTuple(fields)
+                    }
+                });
+            }
+        });
+
+        let decode_slots = data_variants
+            .iter()
+            .zip(slot_bindings.iter())
+            .map(|((_, variant), slot_binding)| {
+                let bindings = variant.bindings();
+                let field_tys = &variant.field_tys;
+                let is_dynamic = variant.is_dynamic();
+                quote! {
+                    let #slot_binding = {
+                        let is_dynamic = #is_dynamic;
+                        let (slot, mut slot_offset) = liquid_abi_codec::tuple_tail(tail, new_offset, is_dynamic)?;
+                        #(
+                            let #bindings = {
+                                let decode_result = <#field_tys as liquid_abi_codec::MediateDecode>::decode(slot, slot_offset)?;
+                                slot_offset = decode_result.new_offset;
+                                decode_result.value
+                            };
+                        )*
+                        new_offset = if is_dynamic { new_offset + 1 } else { slot_offset };
+                        (#(#bindings,)*)
+                    };
+                }
+            });
+        let decode_arms = variants.iter().enumerate().map(|(i, variant)| {
+            let tag = i as u8;
+            let bindings = variant.bindings();
+            let value = variant.destructure(ident, &bindings);
+            if variant.is_unit() {
+                quote!(#tag => #value,)
+            } else {
+                let slot_binding = data_variants
+                    .iter()
+                    .zip(slot_bindings.iter())
+                    .find(|((data_tag, _), _)| *data_tag == tag)
+                    .map(|(_, slot_binding)| slot_binding)
+                    .unwrap();
+                quote! {
+                    #tag => {
+                        let (#(#bindings,)*) = #slot_binding;
+                        #value
+                    }
+                }
+            }
+        });
+
+        let slot_mappings = data_variants.iter().zip(slot_markers.iter()).map(
+            |((_, variant), slot_marker)| {
+                let field_tys = &variant.field_tys;
+                quote! {
+                    #[allow(non_camel_case_types)]
+                    struct #slot_marker;
+
+                    impl liquid_ty_mapping::MappingToSolidityType for #slot_marker {
+                        const MAPPED_TYPE_NAME: [u8; liquid_ty_mapping::MAX_LENGTH_OF_MAPPED_TYPE_NAME] = {
+                            const LEN: usize = liquid_ty_mapping::MAX_LENGTH_OF_MAPPED_TYPE_NAME;
+                            liquid_ty_mapping::composite::<(#(#field_tys,)*), LEN>(&[])
+                        };
+                    }
+                }
+            },
+        );
+
+        quote! {
+            impl liquid_abi_codec::TypeInfo for #ident {
+                #[inline(always)]
+                fn is_dynamic() -> bool {
+                    #(<#all_field_tys as liquid_abi_codec::TypeInfo>::is_dynamic() ||)* false
+                }
+
+                #[inline]
+                fn size_hint() -> u32 {
+                    if Self::is_dynamic() {
+                        unreachable!();
+                    } else {
+                        <u8 as liquid_abi_codec::TypeInfo>::size_hint() #(+ <#all_field_tys as liquid_abi_codec::TypeInfo>::size_hint())*
+                    }
+                }
+            }
+
+            impl liquid_abi_codec::MediateEncode for #ident {
+                fn encode(&self) -> liquid_abi_codec::Mediate {
+                    let mut active = None;
+                    let tag: u8 = match self {
+                        #(#encode_arms)*
+                    };
+
+                    let mut mediates = __std::Vec::new();
+                    mediates.push(liquid_abi_codec::MediateEncode::encode(&tag));
+                    #(#encode_slots)*
+                    if <Self as liquid_abi_codec::TypeInfo>::is_dynamic() {
+                        liquid_abi_codec::Mediate::PrefixedTuple(mediates)
+                    } else {
+                        liquid_abi_codec::Mediate::RawTuple(mediates)
+                    }
+                }
+            }
+
+            impl liquid_abi_codec::MediateDecode for #ident {
+                #[allow(unused_assignments)]
+                fn decode(slices: &[liquid_abi_codec::Word], offset: usize) -> ::core::result::Result<liquid_abi_codec::DecodeResult<Self>, liquid_primitives::Error> {
+                    let is_dynamic = <Self as liquid_abi_codec::TypeInfo>::is_dynamic();
+                    let (tail, mut new_offset) = liquid_abi_codec::tuple_tail(slices, offset, is_dynamic)?;
+                    let tag = <u8 as liquid_abi_codec::MediateDecode>::decode(tail, new_offset)?;
+                    new_offset = tag.new_offset;
+
+                    #(#decode_slots)*
+                    let value = match tag.value {
+                        #(#decode_arms)*
+                        _ => return Err(#invalid_tag.into()),
+                    };
+
+                    Ok(liquid_abi_codec::DecodeResult {
+                        value,
+                        new_offset: if is_dynamic { offset + 1 } else { new_offset },
+                    })
+                }
+            }
+
+            #(#slot_mappings)*
+
+            impl liquid_ty_mapping::MappingToSolidityType for #ident {
+                const MAPPED_TYPE_NAME: [u8; liquid_ty_mapping::MAX_LENGTH_OF_MAPPED_TYPE_NAME] = {
+                    const LEN: usize = liquid_ty_mapping::MAX_LENGTH_OF_MAPPED_TYPE_NAME;
+                    liquid_ty_mapping::composite::<(u8, #(#slot_markers,)*), LEN>(&[])
+                };
+            }
+        }
+    };
+
+    let abi_gen_helper = generate_enum_abi_gen(ident, &variants);
+    let arbitrary = utils::generate_arbitrary(ast);
+
+    Ok(quote! {
+        #(#field_checkers)*
+
+        #codec_impls
+
+        #abi_gen_helper
+        #arbitrary
+
+        impl liquid_lang::You_Should_Use_An_Valid_InOut_Type for #ident {}
+        impl liquid_lang::You_Should_Use_An_Valid_Element_Type for #ident {}
+        impl liquid_lang::You_Should_Use_An_Valid_Event_Data_Type for #ident {}
+        impl liquid_lang::You_Should_Use_An_Valid_Return_Type for #ident {}
+        impl liquid_lang::You_Should_Use_An_Valid_Input_Type for #ident {}
+    })
+}
+
+fn generate_enum_abi_gen(ident: &Ident, variants: &[Variant]) -> TokenStream2 {
+    let is_unit_only = variants.iter().all(Variant::is_unit);
+    let param_abi = if is_unit_only {
+        quote! {
+            liquid_abi_gen::ParamAbi::Trivial(
+                liquid_abi_gen::TrivialAbi::new(Self::generate_ty_name(), name)
+            )
+        }
+    } else {
+        let slot_abis = variants
+            .iter()
+            .filter(|variant| !variant.is_unit())
+            .map(|variant| {
+                let variant_name = variant.ident.to_string();
+                let field_abis = variant.field_tys.iter().enumerate().map(|(i, field_ty)| {
+                    let field_name = if variant.unnamed {
+                        String::new()
+                    } else {
+                        variant.field_names[i].to_string()
+                    };
+                    quote! {
+                        <#field_ty as liquid_abi_gen::traits::GenerateParamAbi>::generate_param_abi(#field_name.to_owned())
+                    }
+                });
+
+                quote! {
+                    liquid_abi_gen::ParamAbi::Composite(
+                        liquid_abi_gen::CompositeAbi {
+                            trivial: liquid_abi_gen::TrivialAbi::new(String::from("tuple"), String::from(#variant_name)),
+                            components: {
+                                let mut components = __std::Vec::new();
+                                #(components.push(#field_abis);)*
+                                components
+                            },
+                        }
+                    )
+                }
+            });
+
+        quote! {
+            let mut components = __std::Vec::new();
+            components.push(<u8 as liquid_abi_gen::traits::GenerateParamAbi>::generate_param_abi("tag".to_owned()));
+            #(components.push(#slot_abis);)*
+            liquid_abi_gen::ParamAbi::Composite(
+                liquid_abi_gen::CompositeAbi {
+                    trivial: liquid_abi_gen::TrivialAbi::new(Self::generate_ty_name(), name),
+                    components,
+                }
+            )
+        }
+    };
+    let ty_name = if is_unit_only { "uint8" } else { "tuple" };
+
+    quote! {
+        #[cfg(feature = "liquid-abi-gen")]
+        impl liquid_abi_gen::traits::GenerateParamAbi for #ident {
+            fn generate_ty_name() -> liquid_prelude::string::String {
+                String::from(#ty_name)
+            }
+
+            fn generate_param_abi(name: String) -> liquid_abi_gen::ParamAbi {
+                #param_abi
+            }
+        }
+
+        #[cfg(feature = "liquid-abi-gen")]
+        impl liquid_abi_gen::traits::GenerateOutputs for #ident {
+            fn generate_outputs<B>(builder: &mut B)
+            where
+                B: liquid_abi_gen::traits::FnOutputBuilder
+            {
+                let param_abi = <Self as liquid_abi_gen::traits::GenerateParamAbi>::generate_param_abi("".into());
+                builder.output(param_abi);
+            }
+        }
+    }
+}
@@ -11,7 +11,8 @@
 // limitations under the License.
 
 use liquid_prelude::vec::Vec;
-use proc_macro2::{Ident, Span};
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::quote;
 use syn::{self, parse::Result, Data, DeriveInput, Fields, Type};
 
 #[allow(dead_code)]
@@ -55,3 +56,66 @@ pub fn struct_syntax_check(ast: &DeriveInput) -> Result<(Vec<&Ident>, Vec<&Type>
     use syn::spanned::Spanned;
     Ok((field_names, field_tys, fields.span()))
 }
+
+/// Generates an expression building `path` out of arbitrary values of its
+/// fields, which are drawn from `u` in declaration order.
+fn generate_arbitrary_fields(path: TokenStream2, fields: &Fields) -> TokenStream2 {
+    let values = fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote! {
+            <#ty as liquid_lang::fuzz::Arbitrary>::arbitrary(u)?
+        }
+    });
+
+    match fields {
+        Fields::Named(fields_named) => {
+            let field_names = fields_named
+                .named
+                .iter()
+                .map(|field| field.ident.as_ref().unwrap());
+            quote!(#path { #(#field_names: #values,)* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#values,)*)),
+        Fields::Unit => path,
+    }
+}
+
+/// Generates the implementation of `Arbitrary` used by `liquid_lang::fuzz` to
+/// produce inputs of contract methods, which only exists when the crate of
+/// the contract enables its `liquid-fuzz` feature.
+pub fn generate_arbitrary(ast: &DeriveInput) -> TokenStream2 {
+    let ident = &ast.ident;
+    let body = match &ast.data {
+        Data::Struct(struct_data) => {
+            let value = generate_arbitrary_fields(quote!(Self), &struct_data.fields);
+            quote!(Ok(#value))
+        }
+        Data::Enum(enum_data) => {
+            let variants_count = enum_data.variants.len() as u32;
+            let tags = 0..variants_count;
+            let values = enum_data.variants.iter().map(|variant| {
+                let variant_ident = &variant.ident;
+                generate_arbitrary_fields(quote!(Self::#variant_ident), &variant.fields)
+            });
+            quote! {
+                let tag = <u32 as liquid_lang::fuzz::Arbitrary>::arbitrary(u)? % #variants_count;
+                Ok(match tag {
+                    #(#tags => #values,)*
+                    _ => unreachable!(),
+                })
+            }
+        }
+        Data::Union(_) => return quote!(),
+    };
+
+    quote! {
+        #[cfg(feature = "liquid-fuzz")]
+        impl<'__liquid_a> liquid_lang::fuzz::Arbitrary<'__liquid_a> for #ident {
+            fn arbitrary(
+                u: &mut liquid_lang::fuzz::Unstructured<'__liquid_a>,
+            ) -> liquid_lang::fuzz::arbitrary::Result<Self> {
+                #body
+            }
+        }
+    }
+}
@@ -19,60 +19,39 @@ extern crate proc_macro;
 mod error;
 mod common;
 mod derive;
+mod testing;
 mod utils;
 
 use cfg_if::cfg_if;
 use derive::wrapper;
 use proc_macro::TokenStream;
 
+mod collaboration;
+mod contract;
+
+use contract::GenerateMode;
+
 cfg_if! {
-    if #[cfg(all(not(feature = "contract"), not(feature = "collaboration")))] {
-        compile_error! {
-            "one of compilation feature `contract` and `collaboration` must \
-             be enabled"
-        }
-    } else if #[cfg(all(feature = "contract", feature = "collaboration"))] {
-        compile_error! {
-            "compilation feature `contract` and `collaboration` can not be \
-             enabled simultaneously"
-        }
-    } else if #[cfg(all(feature = "collaboration", feature = "solidity-compatible"))] {
-        compile_error! {
-            "compilation feature `collaboration` and `solidity-compatible` can not be \
-             enabled simultaneously"
-        }
-    } else if #[cfg(all(feature = "collaboration", feature = "solidity-interface"))] {
-        compile_error! {
-            "compilation feature `collaboration` and `solidity-interface` can not be \
-             enabled simultaneously"
-        }
-    } else if #[cfg(all(feature = "solidity-compatible", feature = "solidity-interface"))]{
+    if #[cfg(all(feature = "solidity-compatible", feature = "solidity-interface"))]{
         compile_error! {
             "it's unnecessary to enable `solidity-interface` feature when \
              `solidity-compatible` is enabled"
         }
-    } else if #[cfg(all(feature = "contract", not(feature = "solidity-compatible")))] {
+    } else if #[cfg(all(
+        feature = "contract",
+        not(feature = "collaboration"),
+        not(feature = "solidity-compatible")
+    ))] {
         compile_error! {
             "up till now, compilation feature `contract` and `solidity-compatible` must \
-             be enabled simultaneously"
-        }
-    } else if #[cfg(feature = "collaboration")] {
-        mod collaboration;
-        use derive::codec;
-
-        #[proc_macro_derive(InOut)]
-        pub fn inout_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-            wrapper::generate_wrapper(codec::generate(input.into())).into()
-        }
-
-        #[proc_macro_attribute]
-        pub fn collaboration(attr: TokenStream, item: TokenStream) -> TokenStream {
-            collaboration::generate(attr.into(), item.into()).into()
+             be enabled simultaneously, unless `collaboration` is enabled as well"
         }
-    } else if #[cfg(feature = "contract")] {
-        mod contract;
-        use contract::GenerateMode;
-
+    } else {
+        // The attribute being expanded rather than the compilation features
+        // decides the programming model, so that workspaces containing both
+        // kinds of crates still build after features get unified. Hybrid
+        // builds always use SCALE codec, hence `InOut` derives the same code
+        // for both of them.
         cfg_if! {
             if #[cfg(feature = "solidity-compatible")] {
                 use derive::{in_out, state};
@@ -95,15 +74,37 @@ cfg_if! {
                 }
             }
         }
+    }
+}
 
-        #[proc_macro_attribute]
-        pub fn interface(attr: TokenStream, item: TokenStream) -> TokenStream {
-            contract::generate(attr.into(), item.into(), GenerateMode::Interface).into()
-        }
+#[proc_macro_attribute]
+pub fn collaboration(attr: TokenStream, item: TokenStream) -> TokenStream {
+    collaboration::generate(attr.into(), item.into()).into()
+}
 
-        #[proc_macro_attribute]
-        pub fn contract(attr: TokenStream, item: TokenStream) -> TokenStream {
-            contract::generate(attr.into(), item.into(), GenerateMode::Contract).into()
-        }
-    }
+#[proc_macro_attribute]
+pub fn interface(attr: TokenStream, item: TokenStream) -> TokenStream {
+    contract::generate(attr.into(), item.into(), GenerateMode::Interface).into()
+}
+
+#[proc_macro_attribute]
+pub fn contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+    contract::generate(attr.into(), item.into(), GenerateMode::Contract).into()
+}
+
+/// Marks a trait as a bundle of methods reusable across contracts.
+/// Provided methods of the trait are exported by every contract
+/// implementing it via `#[liquid(methods)] impl Trait for Storage`,
+/// unless they are marked with `#[liquid(internal)]`, while required
+/// methods declare what the trait needs from the storage.
+#[proc_macro_attribute]
+pub fn mixin(attr: TokenStream, item: TokenStream) -> TokenStream {
+    contract::generate(attr.into(), item.into(), GenerateMode::Mixin).into()
+}
+
+/// Marks a function as a unit test which runs inside a fresh off-chain
+/// environment. See `liquid_lang::env::test::run_test` for details.
+#[proc_macro_attribute]
+pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    testing::generate(attr.into(), item.into()).into()
 }
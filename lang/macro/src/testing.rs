@@ -0,0 +1,54 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::Result;
+
+pub fn generate(attr: TokenStream2, input: TokenStream2) -> TokenStream2 {
+    match generate_impl(attr, input) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+fn generate_impl(attr: TokenStream2, input: TokenStream2) -> Result<TokenStream2> {
+    if !attr.is_empty() {
+        bail!(attr, "`#[liquid::test]` does not accept any arguments");
+    }
+
+    let item_fn = syn::parse2::<syn::ItemFn>(input)?;
+    let sig = &item_fn.sig;
+    if let Some(asyncness) = &sig.asyncness {
+        bail!(asyncness, "test functions can not be async");
+    }
+    if !sig.generics.params.is_empty() {
+        bail!(sig.generics, "test functions can not be generic");
+    }
+    if !sig.inputs.is_empty() {
+        bail!(sig.inputs, "test functions can not have parameters");
+    }
+
+    let attrs = &item_fn.attrs;
+    let vis = &item_fn.vis;
+    let ident = &sig.ident;
+    let output = &sig.output;
+    let block = &item_fn.block;
+
+    Ok(quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #ident() #output {
+            liquid_lang::env::test::run_test(move || #block)
+        }
+    })
+}
@@ -0,0 +1,276 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reusable access control containers for contract storage.
+//!
+//! Declare an [`Ownable`] or an [`AccessControl`] field in the storage struct,
+//! then guard methods with `#[liquid(only_owner)]` or
+//! `#[liquid(only_role = "MINTER")]`, the contract macro will insert the
+//! corresponding check at the beginning of the method.
+
+use crate::{
+    intrinsics::require,
+    lang_core::{
+        env,
+        storage::{
+            Bind, Flush, Getter, Mapping, Value,
+            You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+        },
+    },
+};
+use liquid_prelude::{string::String, vec::Vec};
+use liquid_primitives::{types::Address, __Liquid_Getter_Index_Placeholder};
+
+/// The role which administrates all roles without an explicit admin role.
+pub const DEFAULT_ADMIN_ROLE: &str = "DEFAULT_ADMIN";
+
+fn sub_key(key: &[u8], suffix: &[u8]) -> Vec<u8> {
+    let mut sub_key = key.to_vec();
+    sub_key.extend_from_slice(suffix);
+    sub_key
+}
+
+/// Single-owner access control.
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Ownable {
+    owner: Value<Address>,
+}
+
+impl Bind for Ownable {
+    fn bind_with(key: &[u8]) -> Self {
+        Self {
+            owner: Value::bind_with(&sub_key(key, b"$owner")),
+        }
+    }
+}
+
+impl Flush for Ownable {
+    fn flush(&mut self) {
+        self.owner.flush();
+    }
+}
+
+impl Ownable {
+    /// Sets the initial owner, usually called in the constructor.
+    pub fn initialize(&mut self, owner: Address) {
+        self.owner.initialize(owner);
+    }
+
+    pub fn owner(&self) -> Address {
+        *self.owner
+    }
+
+    pub fn is_owner(&self, account: &Address) -> bool {
+        self.owner() == *account
+    }
+
+    /// Reverts if the caller is not the owner.
+    pub fn ensure_owner(&self) {
+        require(
+            self.is_owner(&env::get_caller()),
+            "Ownable: caller is not the owner",
+        );
+    }
+
+    /// Transfers the ownership to `new_owner`, only the current owner is
+    /// permitted to do this.
+    pub fn transfer_ownership(&mut self, new_owner: Address) {
+        self.ensure_owner();
+        self.owner.set(new_owner);
+    }
+
+    /// Leaves the contract without owner, after that all methods guarded by
+    /// `#[liquid(only_owner)]` can not be called anymore.
+    pub fn renounce_ownership(&mut self) {
+        self.transfer_ownership(Address::empty());
+    }
+}
+
+/// Role-based access control.
+///
+/// Every role is identified by a string and is administrated by another
+/// role, which is [`DEFAULT_ADMIN_ROLE`] unless specified with
+/// [`set_role_admin`](AccessControl::set_role_admin). Only accounts having
+/// the admin role of a role can grant or revoke it.
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct AccessControl {
+    members: Mapping<(String, Address), bool>,
+    admin_roles: Mapping<String, String>,
+}
+
+impl Bind for AccessControl {
+    fn bind_with(key: &[u8]) -> Self {
+        Self {
+            members: Mapping::bind_with(&sub_key(key, b"$members")),
+            admin_roles: Mapping::bind_with(&sub_key(key, b"$admin_roles")),
+        }
+    }
+}
+
+impl Flush for AccessControl {
+    fn flush(&mut self) {
+        self.members.flush();
+        self.admin_roles.flush();
+    }
+}
+
+impl AccessControl {
+    /// Initializes the containers and grants [`DEFAULT_ADMIN_ROLE`] to
+    /// `admin`, usually called in the constructor.
+    pub fn initialize(&mut self, admin: Address) {
+        self.members.initialize();
+        self.admin_roles.initialize();
+        if !self.has_role(DEFAULT_ADMIN_ROLE, &admin) {
+            self.members
+                .insert(&(String::from(DEFAULT_ADMIN_ROLE), admin), true);
+        }
+    }
+
+    pub fn has_role(&self, role: &str, account: &Address) -> bool {
+        self.members.contains_key(&(String::from(role), *account))
+    }
+
+    /// Returns the role which administrates `role`.
+    pub fn role_admin(&self, role: &str) -> String {
+        self.admin_roles
+            .get(role)
+            .cloned()
+            .unwrap_or_else(|| String::from(DEFAULT_ADMIN_ROLE))
+    }
+
+    /// Reverts if the caller does not have `role`.
+    pub fn ensure_role(&self, role: &str) {
+        if !self.has_role(role, &env::get_caller()) {
+            let mut error_info = String::from("AccessControl: caller is missing role `");
+            error_info.push_str(role);
+            error_info.push('`');
+            env::revert(&error_info);
+        }
+    }
+
+    /// Grants `role` to `account`, the caller must have the admin role of
+    /// `role`.
+    pub fn grant_role(&mut self, role: &str, account: Address) {
+        self.ensure_role(&self.role_admin(role));
+        if !self.has_role(role, &account) {
+            self.members.insert(&(String::from(role), account), true);
+        }
+    }
+
+    /// Revokes `role` from `account`, the caller must have the admin role of
+    /// `role`.
+    pub fn revoke_role(&mut self, role: &str, account: &Address) {
+        self.ensure_role(&self.role_admin(role));
+        self.members.remove(&(String::from(role), *account));
+    }
+
+    /// Revokes `role` from the caller itself.
+    pub fn renounce_role(&mut self, role: &str) {
+        let caller = env::get_caller();
+        self.members.remove(&(String::from(role), caller));
+    }
+
+    /// Makes `admin_role` the admin role of `role`, the caller must have the
+    /// current admin role of `role`.
+    pub fn set_role_admin(&mut self, role: &str, admin_role: &str) {
+        self.ensure_role(&self.role_admin(role));
+        self.admin_roles.insert(role, String::from(admin_role));
+    }
+}
+
+impl You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for Ownable {}
+impl You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for AccessControl {}
+
+impl Getter for Ownable {
+    type Index = __Liquid_Getter_Index_Placeholder;
+    type Output = Address;
+
+    fn getter_impl(&self, _: Self::Index) -> Self::Output {
+        self.owner()
+    }
+}
+
+impl Getter for AccessControl {
+    type Index = (String, Address);
+    type Output = bool;
+
+    fn getter_impl(&self, (role, account): Self::Index) -> Self::Output {
+        self.has_role(&role, &account)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::test;
+
+    #[test]
+    fn ownable_works() {
+        test::run_test(|| {
+            let accounts = test::default_accounts();
+            let mut ownable = Ownable::bind_with(b"owner");
+            ownable.initialize(accounts.alice);
+            assert!(ownable.is_owner(&accounts.alice));
+            ownable.ensure_owner();
+
+            ownable.transfer_ownership(accounts.bob);
+            assert_eq!(ownable.owner(), accounts.bob);
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "Ownable: caller is not the owner")]
+    fn not_owner() {
+        test::run_test(|| {
+            let accounts = test::default_accounts();
+            let mut ownable = Ownable::bind_with(b"owner");
+            ownable.initialize(accounts.bob);
+            ownable.transfer_ownership(accounts.alice);
+        })
+    }
+
+    #[test]
+    fn roles_work() {
+        test::run_test(|| {
+            let accounts = test::default_accounts();
+            let mut acl = AccessControl::bind_with(b"acl");
+            acl.initialize(accounts.alice);
+            assert!(acl.has_role(DEFAULT_ADMIN_ROLE, &accounts.alice));
+
+            acl.grant_role("MINTER", accounts.bob);
+            assert!(acl.has_role("MINTER", &accounts.bob));
+            assert!(!acl.has_role("MINTER", &accounts.alice));
+
+            acl.set_role_admin("BURNER", "MINTER");
+            test::set_caller(accounts.bob);
+            acl.ensure_role("MINTER");
+            acl.grant_role("BURNER", accounts.charlie);
+            assert!(acl.has_role("BURNER", &accounts.charlie));
+            test::pop_execution_context();
+
+            acl.revoke_role("MINTER", &accounts.bob);
+            assert!(!acl.has_role("MINTER", &accounts.bob));
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "AccessControl: caller is missing role `DEFAULT_ADMIN`")]
+    fn grant_without_admin_role() {
+        test::run_test(|| {
+            let accounts = test::default_accounts();
+            let mut acl = AccessControl::bind_with(b"acl");
+            acl.initialize(accounts.alice);
+            test::set_caller(accounts.bob);
+            acl.grant_role("MINTER", accounts.bob);
+        })
+    }
+}
@@ -0,0 +1,136 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Guarantees about the codec which contracts use for their inputs and
+//! outputs, i.e., Solidity ABI when `solidity-compatible` is enabled and
+//! SCALE otherwise.
+
+use cfg_if::cfg_if;
+use liquid_prelude::vec::Vec;
+
+cfg_if! {
+    if #[cfg(feature = "solidity-compatible")] {
+        use liquid_abi_codec::{Decode, Encode};
+    } else {
+        use scale::{Decode, Encode};
+    }
+}
+
+/// Why a value does not survive [`round_trip`].
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum RoundTripError {
+    /// The encoding of the value can not be decoded.
+    Undecodable { encoded: Vec<u8> },
+    /// The decoded value is encoded differently from the original one.
+    Mismatch {
+        encoded: Vec<u8>,
+        reencoded: Vec<u8>,
+    },
+}
+
+/// Encodes `value`, decodes the encoding and returns the decoded value, as
+/// long as it is encoded exactly as `value` is.
+///
+/// Comparing the encodings rather than the values lets this work for types
+/// which are not `PartialEq`, and it rejects decoders which ignore trailing
+/// bytes or accept non-canonical encodings as well.
+///
+/// # Example
+///
+/// ```ignore
+/// let value = (u256::from(42u64), String::from("Alice"));
+/// assert_eq!(liquid_lang::codec::round_trip(&value), Ok(value));
+/// ```
+pub fn round_trip<T>(value: &T) -> Result<T, RoundTripError>
+where
+    T: Encode + Decode,
+{
+    let encoded = value.encode();
+    let decoded = match T::decode(&mut encoded.as_slice()) {
+        Ok(decoded) => decoded,
+        Err(_) => return Err(RoundTripError::Undecodable { encoded }),
+    };
+    let reencoded = decoded.encode();
+    if reencoded != encoded {
+        return Err(RoundTripError::Mismatch { encoded, reencoded });
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use liquid_prelude::string::String;
+    use liquid_primitives::types::*;
+
+    #[test]
+    fn primitives_round_trip() {
+        assert_eq!(round_trip(&true), Ok(true));
+        assert_eq!(round_trip(&42u8), Ok(42u8));
+        assert_eq!(round_trip(&-42i64), Ok(-42i64));
+        assert_eq!(round_trip(&u256::from(42u64)), Ok(u256::from(42u64)));
+        assert_eq!(round_trip(&i256::from(-42i64)), Ok(i256::from(-42i64)));
+        assert_eq!(
+            round_trip(&String::from("Alice")),
+            Ok(String::from("Alice"))
+        );
+        assert_eq!(
+            round_trip(&Address::from([0x42; 20])),
+            Ok(Address::from([0x42; 20]))
+        );
+        assert_eq!(round_trip(&Bytes4([1, 2, 3, 4])), Ok(Bytes4([1, 2, 3, 4])));
+    }
+
+    #[test]
+    fn composites_round_trip() {
+        let value = (vec![1u32, 2, 3], String::from("Bob"), [true, false]);
+        assert_eq!(round_trip(&value), Ok(value));
+
+        let value: Vec<String> = vec![];
+        assert_eq!(round_trip(&value), Ok(value));
+    }
+
+    #[test]
+    fn mismatched_encoding_fails() {
+        struct Lossy(u32);
+
+        impl Encode for Lossy {
+            fn encode(&self) -> Vec<u8> {
+                self.0.encode()
+            }
+        }
+
+        impl Decode for Lossy {
+            #[cfg(feature = "solidity-compatible")]
+            fn decode<I: liquid_abi_codec::Input>(
+                input: &mut I,
+            ) -> Result<Self, liquid_primitives::Error> {
+                u32::decode(input).map(|value| Self(value / 2))
+            }
+
+            #[cfg(not(feature = "solidity-compatible"))]
+            fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+                u32::decode(input).map(|value| Self(value / 2))
+            }
+        }
+
+        assert!(round_trip(&Lossy(2)).is_err());
+        match round_trip(&Lossy(3)) {
+            Err(RoundTripError::Mismatch { encoded, reencoded }) => {
+                assert_eq!(encoded, 3u32.encode());
+                assert_eq!(reencoded, 1u32.encode());
+            }
+            _ => panic!("expect a mismatch"),
+        }
+    }
+}
@@ -17,6 +17,7 @@ pub enum DispatchError {
     UnknownSelector,
     InvalidParams,
     CouldNotReadInput,
+    CouldNotDelegate,
 }
 
 pub struct DispatchRetInfo(bool, &'static str);
@@ -27,6 +28,9 @@ impl From<DispatchError> for DispatchRetInfo {
             DispatchError::UnknownSelector => Self(false, "unknown selector"),
             DispatchError::InvalidParams => Self(false, "invalid params"),
             DispatchError::CouldNotReadInput => Self(false, "could not read input"),
+            DispatchError::CouldNotDelegate => {
+                Self(false, "could not delegate call to implementation")
+            }
         }
     }
 }
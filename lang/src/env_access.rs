@@ -11,7 +11,7 @@
 // limitations under the License.
 
 use crate::lang_core::env::api;
-use liquid_primitives::types::{timestamp, Address};
+use liquid_primitives::types::{timestamp, Address, Hash};
 
 pub struct EnvAccess;
 
@@ -28,10 +28,39 @@ impl EnvAccess {
         api::now()
     }
 
+    pub fn get_block_number(self) -> u64 {
+        api::get_block_number()
+    }
+
     pub fn get_address(self) -> Address {
         api::get_address()
     }
 
+    pub fn gas_left(self) -> u64 {
+        api::gas_left()
+    }
+
+    pub fn gas_limit(self) -> u64 {
+        api::gas_limit()
+    }
+
+    pub fn get_chain_id(self) -> u64 {
+        api::get_chain_id()
+    }
+
+    pub fn get_tx_hash(self) -> Hash {
+        api::get_tx_hash()
+    }
+
+    pub fn get_block_hash(self, number: u64) -> Hash {
+        api::get_block_hash(number)
+    }
+
+    /// Predicts the address of the next contract deployed by this contract.
+    pub fn address_of_next_deploy(self) -> Address {
+        api::address_of_next_deploy()
+    }
+
     #[allow(clippy::wrong_self_convention)]
     pub fn is_contract(self, account: &Address) -> bool {
         matches!(api::get_external_code_size(account), 0)
@@ -0,0 +1,405 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Property-based testing of contracts in the off-chain environment.
+//!
+//! Crates of contracts enable it by adding a `liquid-fuzz` feature which
+//! enables the `fuzz` feature of `liquid_lang`, after which every
+//! `#[liquid::contract]` can be fuzzed in its unit tests:
+//!
+//! ```ignore
+//! #[liquid::test]
+//! fn fuzz_methods() {
+//!     liquid::fuzz::fuzz(|| HelloWorld::new(), 1000).assert_ok();
+//! }
+//! ```
+//!
+//! Each round calls an external method picked at random with arbitrary
+//! input through the dispatcher of the contract. Reverting is a valid
+//! outcome of a call, while any other panic is reported as a failure, as
+//! well as inputs and outputs which are not encoded the same after being
+//! decoded.
+
+use crate::{
+    codec,
+    env::test::{self, Deployable},
+    lang_core::{
+        env::{engine::off_chain, storage_batch, CallParams},
+        storage,
+    },
+    FnInput, FnOutput, FnSelector,
+};
+use cfg_if::cfg_if;
+use liquid_primitives::{
+    types::{address::ADDRESS_LENGTH, Address},
+    Selector,
+};
+use std::{
+    any::Any,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+};
+
+pub use arbitrary::{self, Arbitrary, Unstructured};
+
+cfg_if! {
+    if #[cfg(feature = "solidity-compatible")] {
+        use liquid_abi_codec::{Decode, Encode};
+    } else {
+        use scale::{Decode, Encode};
+    }
+}
+
+/// The address where contracts under fuzzing are deployed.
+pub const TARGET: [u8; ADDRESS_LENGTH] = [0xf0; ADDRESS_LENGTH];
+
+/// The number of random bytes arbitrary inputs are generated from.
+const INPUT_BYTES: usize = 1024;
+
+/// The number of arbitrary values checked by [`assert_round_trips`].
+const ROUND_TRIPS: usize = 64;
+
+/// The seed of the random bytes, which is fixed so that failures can be
+/// reproduced by running the test again.
+const SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+/// Implemented by `#[liquid::contract]` in test mode when the `liquid-fuzz`
+/// feature of the contract crate is enabled.
+pub trait Fuzzable {
+    /// External methods of the contract, excluding those exported by mixins.
+    fn methods() -> Vec<Method>;
+}
+
+/// An external method of a contract to be fuzzed.
+pub struct Method {
+    pub name: &'static str,
+    pub selector: Selector,
+    generate_input: fn(&mut Unstructured) -> arbitrary::Result<Vec<u8>>,
+    check_input: fn(&[u8]) -> bool,
+    check_output: fn(&[u8]) -> bool,
+}
+
+/// Returns whether `encoded` decodes to a value of `T` which is encoded as
+/// `encoded` again.
+fn is_symmetric<T>(encoded: &[u8]) -> bool
+where
+    T: Decode + Encode,
+{
+    match T::decode(&mut &encoded[..]) {
+        Ok(value) => value.encode() == encoded,
+        Err(_) => false,
+    }
+}
+
+fn generate_input<T>(u: &mut Unstructured) -> arbitrary::Result<Vec<u8>>
+where
+    T: for<'a> Arbitrary<'a> + Encode,
+{
+    Ok(T::arbitrary(u)?.encode())
+}
+
+impl Method {
+    pub fn new<M>(name: &'static str) -> Self
+    where
+        M: FnInput + FnOutput + FnSelector,
+        M::Input: for<'a> Arbitrary<'a> + Encode,
+        M::Output: Decode,
+    {
+        Self {
+            name,
+            selector: M::SELECTOR,
+            generate_input: generate_input::<M::Input>,
+            check_input: is_symmetric::<M::Input>,
+            check_output: is_symmetric::<M::Output>,
+        }
+    }
+}
+
+/// A problem found by [`fuzz`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The method panicked without reverting.
+    Panic {
+        method: &'static str,
+        call_data: Vec<u8>,
+        message: String,
+    },
+    /// The encoded input of the method decodes to a value encoded differently.
+    InputAsymmetry {
+        method: &'static str,
+        encoded: Vec<u8>,
+    },
+    /// The output of the method decodes to a value encoded differently.
+    OutputAsymmetry {
+        method: &'static str,
+        call_data: Vec<u8>,
+        encoded: Vec<u8>,
+    },
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Panic {
+                method,
+                call_data,
+                message,
+            } => write!(
+                f,
+                "`{}` panicked with `{}` when called with 0x{}",
+                method,
+                message,
+                to_hex(call_data)
+            ),
+            Failure::InputAsymmetry { method, encoded } => write!(
+                f,
+                "input 0x{} of `{}` is encoded differently after being decoded",
+                to_hex(encoded),
+                method
+            ),
+            Failure::OutputAsymmetry {
+                method,
+                call_data,
+                encoded,
+            } => write!(
+                f,
+                "output 0x{} of `{}` is encoded differently after being decoded, \
+                 which is returned when called with 0x{}",
+                to_hex(encoded),
+                method,
+                to_hex(call_data)
+            ),
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Outcomes of all the rounds of [`fuzz`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// The number of calls made.
+    pub calls: usize,
+    /// The number of calls which reverted.
+    pub reverts: usize,
+    pub failures: Vec<Failure>,
+}
+
+impl Report {
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Panics with all the failures if there is any.
+    pub fn assert_ok(&self) {
+        if !self.is_ok() {
+            let failures = self
+                .failures
+                .iter()
+                .map(|failure| format!("\n  {}", failure))
+                .collect::<String>();
+            panic!(
+                "fuzzing found {} failure(s) in {} call(s):{}",
+                self.failures.len(),
+                self.calls,
+                failures
+            );
+        }
+    }
+}
+
+/// A xorshift generator, which is good enough to feed `Unstructured`.
+struct Rng(u64);
+
+impl Rng {
+    fn fill(&mut self, buf: &mut [u8]) {
+        for chunk in buf.chunks_mut(8) {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            chunk.copy_from_slice(&self.0.to_le_bytes()[..chunk.len()]);
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("<unknown>")
+    }
+}
+
+/// Deploys the contract constructed by `constructor` at [`TARGET`], then
+/// calls its external methods with arbitrary inputs for `rounds` times.
+///
+/// Calls are made by the current contract of the off-chain environment, so
+/// this should run inside `#[liquid::test]`. Effects of calls which revert
+/// or panic are discarded, while those of successful calls are kept, so that
+/// later calls run against the state left by earlier ones.
+pub fn fuzz<C, F>(constructor: F, rounds: usize) -> Report
+where
+    C: Fuzzable + Deployable,
+    F: FnOnce() -> C,
+{
+    let addr = Address::from(TARGET);
+    test::deploy_at(addr, constructor);
+
+    let methods = C::methods();
+    let mut report = Report::default();
+    if methods.is_empty() {
+        return report;
+    }
+
+    let mut rng = Rng(SEED);
+    let mut bytes = vec![0u8; INPUT_BYTES];
+    for _ in 0..rounds {
+        rng.fill(&mut bytes);
+        let mut u = Unstructured::new(&bytes);
+        let method = match u.choose(&methods) {
+            Ok(method) => method,
+            Err(_) => continue,
+        };
+        let input = match (method.generate_input)(&mut u) {
+            Ok(input) => input,
+            Err(_) => continue,
+        };
+        if !(method.check_input)(&input) {
+            report.failures.push(Failure::InputAsymmetry {
+                method: method.name,
+                encoded: input,
+            });
+            continue;
+        }
+
+        let mut call_data = method.selector.to_vec();
+        call_data.extend(input);
+        let before = test::snapshot();
+        off_chain::take_reverted();
+        report.calls += 1;
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            off_chain::simulate_call(&addr, &call_data, CallParams::default())
+        }));
+        match result {
+            Ok(Ok(output)) => {
+                test::discard_snapshot(before);
+                if !(method.check_output)(&output) {
+                    report.failures.push(Failure::OutputAsymmetry {
+                        method: method.name,
+                        call_data,
+                        encoded: output,
+                    });
+                }
+            }
+            Ok(Err(_)) => unreachable!("the contract has been deployed"),
+            Err(payload) => {
+                storage::reset_mutable_call_flag();
+                storage_batch::clear_storage_batch();
+                test::revert_to(before);
+                test::discard_snapshot(before);
+                if off_chain::take_reverted() {
+                    report.reverts += 1;
+                } else {
+                    report.failures.push(Failure::Panic {
+                        method: method.name,
+                        call_data,
+                        message: panic_message(&*payload),
+                    });
+                }
+            }
+        }
+    }
+    report
+}
+
+/// Panics unless arbitrary values of `T` survive [`codec::round_trip`],
+/// with `name` describing `T` in the message.
+///
+/// `#[liquid::contract]` generates a test calling this for the inputs and
+/// outputs of every external method and constructor of the contract.
+pub fn assert_round_trips<T>(name: &str)
+where
+    T: for<'a> Arbitrary<'a> + Encode + Decode,
+{
+    let mut rng = Rng(SEED);
+    let mut bytes = vec![0u8; INPUT_BYTES];
+    for _ in 0..ROUND_TRIPS {
+        rng.fill(&mut bytes);
+        let value = match T::arbitrary(&mut Unstructured::new(&bytes)) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+        if let Err(err) = codec::round_trip(&value) {
+            panic!("{} does not survive a round trip: {:?}", name, err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rng_is_deterministic() {
+        let mut a = [0u8; 20];
+        let mut b = [0u8; 20];
+        Rng(SEED).fill(&mut a);
+        Rng(SEED).fill(&mut b);
+        assert_eq!(a, b);
+        assert_ne!(a, [0u8; 20]);
+    }
+
+    #[test]
+    fn check_symmetry() {
+        assert!(is_symmetric::<(u32, bool)>(&(42u32, true).encode()));
+        let mut encoded = 42u32.encode();
+        encoded.push(0);
+        assert!(!is_symmetric::<u32>(&encoded));
+    }
+
+    #[test]
+    fn primitives_round_trip() {
+        use liquid_primitives::types::*;
+
+        assert_round_trips::<(u256, i256, Address)>("integers and addresses");
+        assert_round_trips::<(String, Bytes, Bytes32)>("strings and bytes");
+        assert_round_trips::<Vec<u64>>("vectors");
+        // Tuples are not supported by the Solidity ABI codec.
+        #[cfg(not(feature = "solidity-compatible"))]
+        assert_round_trips::<Vec<(bool, u64)>>("vectors of tuples");
+    }
+
+    #[test]
+    fn report_failures() {
+        let mut report = Report::default();
+        assert!(report.is_ok());
+        report.assert_ok();
+
+        report.calls = 1;
+        report.failures.push(Failure::Panic {
+            method: "get",
+            call_data: vec![0x12, 0x34],
+            message: String::from("overflow"),
+        });
+        assert!(!report.is_ok());
+        assert_eq!(
+            report.failures[0].to_string(),
+            "`get` panicked with `overflow` when called with 0x1234"
+        );
+        let result = panic::catch_unwind(|| report.assert_ok());
+        assert!(result.is_err());
+    }
+}
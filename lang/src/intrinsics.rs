@@ -23,3 +23,256 @@ where
         env::revert(&err_info);
     }
 }
+
+/// Computes the stable error code of `key`, which is the message of a
+/// `require!` site or the `Enum::Variant` path of a `#[liquid(error)]`
+/// variant. The code only depends on `key`, so it survives reordering and
+/// recompilation of the contract.
+pub const fn error_code(key: &[u8]) -> u32 {
+    let hash = liquid_primitives::hash::hash(key);
+    u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+/// Panics with a message, which is only kept as its error code in on-chain
+/// builds with the `min-size` feature.
+#[cfg(not(all(feature = "min-size", not(feature = "std"))))]
+macro_rules! fail {
+    ($msg:literal $(,)?) => {
+        panic!($msg)
+    };
+}
+
+/// Reverts with the error code of the message, leaving the message itself out
+/// of the contract.
+#[cfg(all(feature = "min-size", not(feature = "std")))]
+macro_rules! fail {
+    ($msg:literal $(,)?) => {{
+        const CODE: u32 = $crate::intrinsics::error_code($msg.as_bytes());
+        $crate::intrinsics::fail_with_code(CODE)
+    }};
+}
+
+#[cfg(all(feature = "min-size", not(feature = "std")))]
+#[cold]
+pub(crate) fn fail_with_code(code: u32) -> ! {
+    use crate::lang_core::env::engine::on_chain::ext;
+
+    // Failures may happen while the environment is in use, so the host is
+    // asked to revert directly rather than via `env::revert`.
+    let err_info = with_code(code, "");
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "solidity-compatible")] {
+            let encoded = liquid_abi_codec::Encode::encode(&err_info);
+        } else {
+            let encoded = scale::Encode::encode(&err_info);
+        }
+    }
+    ext::revert(&encoded);
+    core::arch::wasm32::unreachable()
+}
+
+/// Implemented by enums marked with `#[liquid(error)]`.
+pub trait ErrorCode {
+    fn code(&self) -> u32;
+    fn message(&self) -> &'static str;
+}
+
+/// Prefixes `msg` with `code`, e.g. `[0x1a2b3c4d] out of stock`.
+fn with_code(code: u32, msg: &str) -> String {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut err_info = String::with_capacity(msg.len() + 13);
+    err_info.push_str("[0x");
+    for i in (0..8).rev() {
+        err_info.push(HEX_DIGITS[((code >> (i * 4)) & 0xf) as usize] as char);
+    }
+    err_info.push_str("] ");
+    err_info.push_str(msg);
+    err_info
+}
+
+/// Reverts with `msg` prefixed by `code`, e.g. `[0x1a2b3c4d] out of stock`.
+pub fn revert_with_code(code: u32, msg: &str) {
+    env::revert(&with_code(code, msg));
+}
+
+/// Reverts with a registered error code if `cond` is false.
+///
+/// The second argument is either a string literal or a value of an enum
+/// marked with `#[liquid(error)]`. The contract macro collects all
+/// `require!` sites with literal messages written directly in contract
+/// methods, together with all `#[liquid(error)]` variants, into the error
+/// registry emitted alongside the ABI.
+///
+/// # Example
+///
+/// ```ignore
+/// require!(balance >= value, "insufficient balance");
+/// require!(balance >= value, TokenError::InsufficientBalance);
+/// ```
+#[macro_export]
+macro_rules! require {
+    ($cond:expr, $msg:literal $(,)?) => {
+        if !$cond {
+            const CODE: u32 = $crate::intrinsics::error_code($msg.as_bytes());
+            $crate::intrinsics::revert_with_code(CODE, $msg);
+        }
+    };
+    ($cond:expr, $err:expr $(,)?) => {
+        if !$cond {
+            let err = $err;
+            $crate::intrinsics::revert_with_code(
+                $crate::intrinsics::ErrorCode::code(&err),
+                $crate::intrinsics::ErrorCode::message(&err),
+            );
+        }
+    };
+}
+
+pub use crate::require;
+
+/// Logs a message formatted like `format!` does, for debugging.
+///
+/// The message is printed by the node on-chain and to stdout in tests, where
+/// `test::debug_logs` returns it as well. The macro expands to nothing in
+/// release builds, so it costs no gas in contracts built for production. On
+/// chain, nothing is logged with the `min-size` feature either.
+///
+/// # Example
+///
+/// ```ignore
+/// debug!("balance of {:?}: {}", owner, balance);
+/// ```
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        {
+            #[cfg(debug_assertions)]
+            $crate::env::debug_log(::core::format_args!($($arg)+));
+        }
+    };
+}
+
+pub use crate::debug;
+
+/// Checks an internal invariant of the contract, panicking if it doesn't
+/// hold, e.g. in tests driving the contract off-chain.
+///
+/// Unlike `require!`, which guards against invalid inputs, invariants are
+/// only checked when `liquid_lang` is built with `std`. On-chain the check is
+/// compiled out, so it costs no gas, though `cond` and the message are still
+/// type-checked.
+///
+/// # Example
+///
+/// ```ignore
+/// invariant!(*self.total_supply == self.balances.values().sum::<u64>());
+/// invariant!(self.owners.len() <= 10, "too many owners: {}", self.owners.len());
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! invariant {
+    ($cond:expr $(,)?) => {
+        ::core::assert!(
+            $cond,
+            "invariant violated: {}",
+            ::core::stringify!($cond)
+        )
+    };
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond {
+            ::core::panic!(
+                "invariant violated: {}",
+                ::core::format_args!($($arg)+)
+            )
+        }
+    };
+}
+
+#[cfg(not(feature = "std"))]
+#[macro_export]
+macro_rules! invariant {
+    ($cond:expr $(,)?) => {
+        if false {
+            let _: bool = $cond;
+        }
+    };
+    ($cond:expr, $($arg:tt)+) => {
+        if false && !$cond {
+            ::core::panic!($($arg)+)
+        }
+    };
+}
+
+pub use crate::invariant;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang_core::env::test;
+
+    #[test]
+    fn error_code_is_stable() {
+        const CODE: u32 = error_code(b"insufficient balance");
+        assert_eq!(CODE, error_code(b"insufficient balance"));
+        assert_ne!(CODE, error_code(b"insufficient allowance"));
+    }
+
+    #[test]
+    #[should_panic(expected = "[0x0000abcd] out of stock")]
+    fn revert_message_carries_code() {
+        test::run_test(|| revert_with_code(0xabcd, "out of stock"))
+    }
+
+    #[test]
+    fn require_passes_when_condition_holds() {
+        test::run_test(|| require!(1 + 1 == 2, "math is broken"))
+    }
+
+    #[test]
+    #[should_panic(expected = "] math is broken")]
+    fn require_reverts_with_code() {
+        test::run_test(|| require!(1 + 1 == 3, "math is broken"))
+    }
+
+    #[test]
+    fn invariant_passes_when_it_holds() {
+        let owners = [1, 2, 3];
+        invariant!(owners.len() == 3);
+        invariant!(owners.len() <= 10, "too many owners: {}", owners.len());
+    }
+
+    #[test]
+    #[should_panic(expected = "invariant violated: 1 + 1 == 3")]
+    fn invariant_panics_with_condition() {
+        invariant!(1 + 1 == 3)
+    }
+
+    #[test]
+    #[should_panic(expected = "invariant violated: too many owners: 11")]
+    fn invariant_panics_with_message() {
+        let owners = 11;
+        invariant!(owners <= 10, "too many owners: {}", owners)
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn debug_logs_are_captured() {
+        test::run_test(|| {
+            let balance = 42;
+            debug!("balance: {}", balance);
+            debug!("done");
+            assert_eq!(test::debug_logs(), ["balance: 42", "done"]);
+        })
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn debug_logs_are_compiled_out_in_release_builds() {
+        test::run_test(|| {
+            debug!("balance: {}", 42);
+            debug!("done");
+            assert!(test::debug_logs().is_empty());
+        })
+    }
+}
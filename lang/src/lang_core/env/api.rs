@@ -12,15 +12,17 @@
 
 use crate::lang_core::env::{
     backend::Env,
+    call_cache,
     calldata::CallData,
     engine::{EnvInstance, OnInstance},
-    error::Result,
-    CallMode,
+    error::{EnvError, Result},
+    storage_batch, CallMode, CallParams,
 };
+use crate::BorrowedEvent;
 use cfg_if::cfg_if;
 use liquid_prelude::{string::String, vec::Vec};
 use liquid_primitives::{
-    types::{timestamp, Address},
+    types::{address::ADDRESS_LENGTH, timestamp, Address, Hash},
     Topics,
 };
 
@@ -28,6 +30,9 @@ pub fn set_storage<V>(key: &[u8], value: &V)
 where
     V: scale::Encode,
 {
+    if storage_batch::stage(key, Some(value.encode())) {
+        return;
+    }
     <EnvInstance as OnInstance>::on_instance(|instance| {
         Env::set_storage::<V>(instance, key, value);
     })
@@ -37,17 +42,89 @@ pub fn get_storage<R>(key: &[u8]) -> Result<R>
 where
     R: scale::Decode,
 {
-    <EnvInstance as OnInstance>::on_instance(|instance| {
-        Env::get_storage::<R>(instance, key)
-    })
+    match storage_batch::lookup(key) {
+        Some(Some(encoded)) => {
+            <R as scale::Decode>::decode(&mut encoded.as_slice()).map_err(Into::into)
+        }
+        Some(None) => Err(EnvError::UnableToReadFromStorage),
+        None => <EnvInstance as OnInstance>::on_instance(|instance| {
+            Env::get_storage::<R>(instance, key)
+        }),
+    }
 }
 
 pub fn remove_storage(key: &[u8]) {
+    if storage_batch::stage(key, None) {
+        return;
+    }
     <EnvInstance as OnInstance>::on_instance(|instance| {
         Env::remove_storage(instance, key);
     })
 }
 
+/// Runs `f` with the storage writes it makes held back, and commits them
+/// once `f` returns. Reads made by `f` see the held back writes.
+///
+/// The writes are committed one by one, or within a single host call if the
+/// `ext-storage-multi` feature is enabled and the chain provides
+/// `setStorageMulti`. Only the last write to a key is
+/// committed. Batches may be nested, in which case the writes are committed
+/// when the outermost one ends, and they are dropped if `f` panics.
+pub fn batch_storage_writes<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct AbortOnUnwind;
+
+    impl Drop for AbortOnUnwind {
+        fn drop(&mut self) {
+            storage_batch::abort();
+        }
+    }
+
+    storage_batch::open();
+    let guard = AbortOnUnwind;
+    let result = f();
+    core::mem::forget(guard);
+    if let Some(writes) = storage_batch::close() {
+        commit_storage_writes(&writes);
+    }
+    result
+}
+
+fn commit_storage_writes(writes: &storage_batch::Writes) {
+    /// A value which is already encoded.
+    struct Encoded<'a>(&'a [u8]);
+
+    impl<'a> scale::Encode for Encoded<'a> {
+        fn size_hint(&self) -> usize {
+            self.0.len()
+        }
+
+        fn encode_to<T: scale::Output>(&self, dest: &mut T) {
+            dest.write(self.0);
+        }
+    }
+
+    if cfg!(feature = "ext-storage-multi")
+        && writes.len() > 1
+        && has_ext("setStorageMulti")
+    {
+        return <EnvInstance as OnInstance>::on_instance(|instance| {
+            Env::set_storage_multi(instance, writes)
+        });
+    }
+
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        for (key, value) in writes {
+            match value {
+                Some(encoded) => Env::set_storage(instance, key, &Encoded(encoded)),
+                None => Env::remove_storage(instance, key),
+            }
+        }
+    })
+}
+
 pub fn get_call_data(mode: CallMode) -> Result<CallData> {
     <EnvInstance as OnInstance>::on_instance(|instance| {
         Env::get_call_data(instance, mode)
@@ -66,16 +143,185 @@ pub fn get_address() -> Address {
     <EnvInstance as OnInstance>::on_instance(|instance| Env::get_address(instance))
 }
 
+/// Returns the address of the next contract deployed by the current
+/// contract, which is derived from the address and the nonce of the current
+/// contract just like `CREATE` does. Reverts if the chain doesn't provide
+/// `getNonce`.
+pub fn address_of_next_deploy() -> Address {
+    require_ext("getNonce");
+    let nonce = <EnvInstance as OnInstance>::on_instance(Env::get_nonce);
+    Address::from_create(&get_address(), nonce)
+}
+
+/// Returns the gas remaining for the current transaction, which lets long
+/// loops stop before running out of gas. Reverts if the chain doesn't provide
+/// `getGasLeft`.
+pub fn gas_left() -> u64 {
+    require_ext("getGasLeft");
+    <EnvInstance as OnInstance>::on_instance(Env::gas_left)
+}
+
+/// Returns the gas limit of the current transaction. Reverts if the chain
+/// doesn't provide `getGasLimit`.
+pub fn gas_limit() -> u64 {
+    require_ext("getGasLimit");
+    <EnvInstance as OnInstance>::on_instance(Env::gas_limit)
+}
+
+/// Returns the id of the chain the contract runs on, e.g., to bind signatures
+/// to a domain. Reverts if the chain doesn't provide `getChainId`.
+pub fn get_chain_id() -> u64 {
+    require_ext("getChainId");
+    <EnvInstance as OnInstance>::on_instance(Env::get_chain_id)
+}
+
+/// Returns the hash of the current transaction. Reverts if the chain doesn't
+/// provide `getTxHash`.
+pub fn get_tx_hash() -> Hash {
+    require_ext("getTxHash");
+    <EnvInstance as OnInstance>::on_instance(Env::get_tx_hash)
+}
+
+/// Returns the hash of the block `number` like `BLOCKHASH` does. Reverts if
+/// `number` is not one of the 256 most recent blocks before the current one,
+/// or the chain doesn't provide `getBlockHash`.
+pub fn get_block_hash(number: u64) -> Hash {
+    require_ext("getBlockHash");
+    let current = get_block_number();
+    if number >= current || current - number > 256 {
+        revert(&String::from(
+            "only the hashes of the 256 most recent blocks are available",
+        ));
+    }
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::get_block_hash(instance, number)
+    })
+}
+
+/// Executes the code of `addr` within the storage context of the current
+/// contract, and returns the raw return data.
+///
+/// # Note
+///
+/// The callee writes the contract storage directly, while storage containers
+/// of the caller keep their own caches until they are flushed, so the caller
+/// should not touch the same state both before and after a delegate call in
+/// one transaction.
+#[cfg(not(feature = "std"))]
+pub fn delegate_call(addr: &Address, data: &[u8]) -> Result<Vec<u8>> {
+    require_ext("delegateCall");
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::delegate_call(instance, addr, data)
+    })
+}
+
+#[cfg(feature = "std")]
+pub fn delegate_call(addr: &Address, data: &[u8]) -> Result<Vec<u8>> {
+    super::engine::off_chain::simulate_delegate_call(addr, data)
+}
+
+/// Runs `call`, in which a call to another contract is made. Callees lacking
+/// the selector only make the call fail on-chain, which is what `call` returns
+/// in tests as well, rather than reverting the test.
+#[cfg(not(feature = "std"))]
+pub fn optional_call<R, F>(call: F) -> Result<R>
+where
+    F: FnOnce() -> Result<R>,
+{
+    call()
+}
+
+#[cfg(feature = "std")]
+pub fn optional_call<R, F>(call: F) -> Result<R>
+where
+    F: FnOnce() -> Result<R>,
+{
+    super::engine::off_chain::simulate_optional_call(call)
+}
+
+pub fn finish_raw(return_data: &[u8]) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::finish_raw(instance, return_data);
+    })
+}
+
 pub fn get_external_code_size(account: &Address) -> u32 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
         Env::get_external_code_size(instance, account)
     })
 }
 
+/// Logs a formatted message for debugging, which is printed by the node
+/// on-chain and to stdout in tests. Use `debug!` instead, which is compiled out
+/// in release builds.
+pub fn debug_log(args: core::fmt::Arguments) {
+    <EnvInstance as OnInstance>::on_instance(|instance| Env::debug_log(instance, args))
+}
+
+/// Returns whether the host interface of the chain provides the function
+/// named `name`, e.g. `registerAsset`.
+///
+/// Host functions added after the first release of the Wasm runtime are only
+/// available on-chain if the features of `liquid_lang` enabling them are, e.g.
+/// `ext-chain-info` for `getGasLeft`, otherwise contracts would fail to deploy
+/// on chains lacking them. With the `ext-probe` feature, the host is probed at
+/// the first use of every name as well and the result is reused for the rest
+/// of the execution, while without it enabled host functions are assumed to
+/// be provided. Every host function is available in tests unless disabled by
+/// `test::set_ext_available`.
+pub fn has_ext(name: &str) -> bool {
+    <EnvInstance as OnInstance>::on_instance(|instance| Env::has_ext(instance, name))
+}
+
+/// Reverts with a readable message if the host function `name` is not
+/// provided by the chain, instead of letting the execution trap.
+pub fn require_ext(name: &str) {
+    if !has_ext(name) {
+        let mut error_info = String::from("host function `");
+        error_info.push_str(name);
+        error_info.push_str("` unavailable on this chain version");
+        revert(&error_info);
+    }
+}
+
+/// Resolves the address of the contract registered to CNS (Contract Name
+/// Service) as `name` of version `version`, and reverts if there is none.
+pub fn cns_resolve(name: &str, version: &str) -> Address {
+    cfg_if! {
+        if #[cfg(feature = "std")] {
+            let resolved = <EnvInstance as OnInstance>::on_instance(|instance| {
+                instance.cns_resolve(name, version)
+            });
+        } else {
+            let resolved = crate::lang_core::precompiled::Cns::get_contract_address(
+                String::from(name),
+                String::from(version),
+            );
+        }
+    }
+
+    match resolved {
+        Some(addr) if !addr.is_zero() => addr,
+        _ => {
+            let mut error_info = String::from("contract `");
+            error_info.push_str(name);
+            error_info.push_str("` of version `");
+            error_info.push_str(version);
+            error_info.push_str("` is not registered to CNS");
+            revert(&error_info);
+            unreachable!()
+        }
+    }
+}
+
 pub fn now() -> timestamp {
     <EnvInstance as OnInstance>::on_instance(|instance| Env::now(instance))
 }
 
+pub fn get_block_number() -> u64 {
+    <EnvInstance as OnInstance>::on_instance(|instance| Env::get_block_number(instance))
+}
+
 pub fn register_asset(
     asset_name: &[u8],
     issuer: &Address,
@@ -83,18 +329,35 @@ pub fn register_asset(
     total: u64,
     description: &[u8],
 ) -> bool {
-    <EnvInstance as OnInstance>::on_instance(|instance| {
+    require_ext("registerAsset");
+    let registered = <EnvInstance as OnInstance>::on_instance(|instance| {
         Env::register_asset(instance, asset_name, issuer, fungible, total, description)
-    })
+    });
+    if registered {
+        set_storage(&asset_registered_key(asset_name), &true);
+    }
+    registered
+}
+
+fn asset_registered_key(asset_name: &[u8]) -> Vec<u8> {
+    [b"__liquid_asset_registered", asset_name].concat()
+}
+
+/// Returns whether the asset `asset_name` has been registered by the current
+/// contract.
+pub fn is_asset_registered(asset_name: &[u8]) -> bool {
+    get_storage(&asset_registered_key(asset_name)).unwrap_or(false)
 }
 
 pub fn issue_fungible_asset(to: &Address, asset_name: &[u8], amount: u64) -> bool {
+    require_ext("issueFungibleAsset");
     <EnvInstance as OnInstance>::on_instance(|instance| {
         Env::issue_fungible_asset(instance, to, asset_name, amount)
     })
 }
 
 pub fn issue_not_fungible_asset(to: &Address, asset_name: &[u8], uri: &[u8]) -> u64 {
+    require_ext("issueNotFungibleAsset");
     <EnvInstance as OnInstance>::on_instance(|instance| {
         Env::issue_not_fungible_asset(instance, to, asset_name, uri)
     })
@@ -106,18 +369,70 @@ pub fn transfer_asset(
     amount_or_id: u64,
     from_self: bool,
 ) -> bool {
+    require_ext("transferAsset");
     <EnvInstance as OnInstance>::on_instance(|instance| {
         Env::transfer_asset(instance, to, asset_name, amount_or_id, from_self)
     })
 }
 
+/// Issues a non-fungible token of `asset_name` to `to` for every URI in
+/// `uris`, and returns the IDs of the tokens issued before the first failure.
+///
+/// The tokens are issued within a single host call if the chain provides
+/// `issueNotFungibleAssets`, otherwise one by one.
+pub fn issue_not_fungible_assets(
+    to: &Address,
+    asset_name: &[u8],
+    uris: &[&[u8]],
+) -> Vec<u64> {
+    if has_ext("issueNotFungibleAssets") {
+        return <EnvInstance as OnInstance>::on_instance(|instance| {
+            Env::issue_not_fungible_assets(instance, to, asset_name, uris)
+        });
+    }
+
+    let mut ids = Vec::with_capacity(uris.len());
+    for uri in uris {
+        match issue_not_fungible_asset(to, asset_name, uri) {
+            0 => break,
+            id => ids.push(id),
+        }
+    }
+    ids
+}
+
+/// Transfers every amount or token ID in `amounts_or_ids` to `to`.
+///
+/// The transfers are made within a single host call if the chain provides
+/// `transferAssets`, in which case nothing is transferred on failure.
+/// Otherwise they are made one by one, and the transfers preceding a failure
+/// are only undone if the execution reverts.
+pub fn transfer_assets(
+    to: &Address,
+    asset_name: &[u8],
+    amounts_or_ids: &[u64],
+    from_self: bool,
+) -> bool {
+    if has_ext("transferAssets") {
+        return <EnvInstance as OnInstance>::on_instance(|instance| {
+            Env::transfer_assets(instance, to, asset_name, amounts_or_ids, from_self)
+        });
+    }
+
+    amounts_or_ids
+        .iter()
+        .all(|amount_or_id| transfer_asset(to, asset_name, *amount_or_id, from_self))
+}
+
 pub fn get_asset_balance(to: &Address, asset_name: &[u8]) -> u64 {
+    require_ext("getAssetBalance");
     <EnvInstance as OnInstance>::on_instance(|instance| {
         Env::get_asset_balance(instance, to, asset_name)
     })
 }
 
 pub fn get_not_fungible_asset_ids(account: &Address, asset_name: &[u8]) -> Vec<u64> {
+    require_ext("getNotFungibleAssetIDs");
     <EnvInstance as OnInstance>::on_instance(|instance| {
         Env::get_not_fungible_asset_ids(instance, account, asset_name)
     })
@@ -128,31 +443,166 @@ pub fn get_not_fungible_asset_info(
     asset_name: &[u8],
     asset_id: u64,
 ) -> String {
+    require_ext("getNotFungibleAssetInfo");
     <EnvInstance as OnInstance>::on_instance(|instance| {
         Env::get_not_fungible_asset_info(instance, account, asset_name, asset_id)
     })
 }
 
+/// Burns `amount_or_id` of the asset `asset_name` held by the caller, or by the
+/// current contract if `from_self` is set.
+///
+/// The host has no notion of destroying assets, so burnt assets are moved to
+/// the zero address, whose key is unknown to anyone.
+pub fn burn_asset(asset_name: &[u8], amount_or_id: u64, from_self: bool) -> bool {
+    transfer_asset(&Address::zero(), asset_name, amount_or_id, from_self)
+}
+
+/// Returns the amount of the fungible asset `asset_name` that has been burnt,
+/// or the number of burnt tokens of a non-fungible one.
+pub fn get_burnt_asset(asset_name: &[u8]) -> u64 {
+    get_asset_balance(&Address::zero(), asset_name)
+}
+
+fn asset_allowance_key(owner: &Address, spender: &Address, asset_name: &[u8]) -> Vec<u8> {
+    const PREFIX: &[u8] = b"__liquid_asset_allowance";
+    let mut key =
+        Vec::with_capacity(PREFIX.len() + asset_name.len() + 2 * ADDRESS_LENGTH);
+    key.extend_from_slice(PREFIX);
+    key.extend_from_slice(asset_name);
+    key.extend_from_slice(&owner.0);
+    key.extend_from_slice(&spender.0);
+    key
+}
+
+/// Returns the amount of the fungible asset `asset_name` that `owner` has
+/// approved `spender` to withdraw via the current contract.
+pub fn get_asset_allowance(owner: &Address, spender: &Address, asset_name: &[u8]) -> u64 {
+    get_storage(&asset_allowance_key(owner, spender, asset_name)).unwrap_or(0)
+}
+
+/// Sets the amount of the fungible asset `asset_name` that `spender` may
+/// withdraw from the caller.
+///
+/// Approved assets are kept in custody of the current contract until they
+/// are withdrawn by `spender`, hence the difference between the new and the
+/// current allowance is transferred from the caller to the current contract,
+/// or refunded to the caller if the allowance decreases. Returns `false`
+/// without changing the allowance if the transfer fails.
+pub fn approve_asset(spender: &Address, asset_name: &[u8], amount: u64) -> bool {
+    let owner = get_caller();
+    let key = asset_allowance_key(&owner, spender, asset_name);
+    let current = get_storage(&key).unwrap_or(0u64);
+    let transferred = if amount > current {
+        transfer_asset(&get_address(), asset_name, amount - current, false)
+    } else if amount < current {
+        transfer_asset(&owner, asset_name, current - amount, true)
+    } else {
+        true
+    };
+
+    if transferred {
+        set_storage(&key, &amount);
+    }
+    transferred
+}
+
+/// Deducts `amount` from the allowance of the caller over the fungible asset
+/// `asset_name` of `owner`. Returns `false` if the allowance is insufficient,
+/// otherwise the deducted amount is ready to be transferred from the current
+/// contract.
+pub fn spend_asset_allowance(owner: &Address, asset_name: &[u8], amount: u64) -> bool {
+    let key = asset_allowance_key(owner, &get_caller(), asset_name);
+    let current = get_storage(&key).unwrap_or(0u64);
+    if current < amount {
+        return false;
+    }
+
+    set_storage(&key, &(current - amount));
+    true
+}
+
 cfg_if! {
     if #[cfg(feature = "solidity-compatible")] {
         pub fn emit<Event>(event: Event)
         where
-            Event: Topics + liquid_abi_codec::Encode,
+            Event: Topics + liquid_abi_codec::Encode + 'static,
         {
+            // Events are writes as well, which immutable calls must not do.
+            crate::lang_core::storage::mutable_call_happens();
             <EnvInstance as OnInstance>::on_instance(|instance| {
                 Env::emit(instance, event)
             });
         }
 
+        /// Same as `emit`, but the event borrows its strings and bytes rather
+        /// than owning them, hence they are encoded without being cloned.
+        /// Off-chain, the event is recorded as the one owning its fields, so
+        /// that it can be inspected in tests as usual.
+        pub fn emit_ref<Event>(event: Event)
+        where
+            Event: Topics + liquid_abi_codec::Encode + BorrowedEvent,
+        {
+            crate::lang_core::storage::mutable_call_happens();
+            <EnvInstance as OnInstance>::on_instance(|instance| {
+                Env::emit_ref(instance, event)
+            });
+        }
+
         pub fn call<R>(addr: &Address, data: &[u8]) -> Result<R>
         where
             R: liquid_abi_codec::Decode + liquid_abi_codec::TypeInfo,
         {
+            call_with(addr, data, CallParams::default())
+        }
+
+        /// Same as `call`, but with the gas limit and the value in `params`.
+        /// Reverts if any of them is given while the chain doesn't provide
+        /// `callWithParams`.
+        #[cfg(not(feature = "std"))]
+        pub fn call_with<R>(addr: &Address, data: &[u8], params: CallParams) -> Result<R>
+        where
+            R: liquid_abi_codec::Decode + liquid_abi_codec::TypeInfo,
+        {
+            if !params.is_default() {
+                require_ext("callWithParams");
+            }
             <EnvInstance as OnInstance>::on_instance(|instance| {
-                Env::call(instance, addr, data)
+                Env::call(instance, addr, data, &params)
             })
         }
 
+        #[cfg(feature = "std")]
+        pub fn call_with<R>(addr: &Address, data: &[u8], params: CallParams) -> Result<R>
+        where
+            R: liquid_abi_codec::Decode + liquid_abi_codec::TypeInfo,
+        {
+            if !params.is_default() {
+                require_ext("callWithParams");
+            }
+            let encoded = super::engine::off_chain::simulate_call(addr, data, params)?;
+            <R as liquid_abi_codec::Decode>::decode(&mut encoded.as_slice())
+                .map_err(Into::into)
+        }
+
+        /// Same as `call`, but returns the memoized result if the same method
+        /// of `addr` has already been called with the same arguments in the
+        /// current transaction.
+        pub fn cached_call<R>(addr: &Address, data: &[u8]) -> Result<R>
+        where
+            R: liquid_abi_codec::Encode
+                + liquid_abi_codec::Decode
+                + liquid_abi_codec::TypeInfo,
+        {
+            if let Some(encoded) = call_cache::lookup(addr, data) {
+                return <R as liquid_abi_codec::Decode>::decode(&mut encoded.as_slice())
+                    .map_err(Into::into);
+            }
+            let result = call::<R>(addr, data)?;
+            call_cache::insert(addr, data, <R as liquid_abi_codec::Encode>::encode(&result));
+            Ok(result)
+        }
+
         pub fn finish<V>(return_value: &V)
         where
             V: liquid_abi_codec::Encode,
@@ -173,22 +623,80 @@ cfg_if! {
     } else {
         pub fn emit<Event>(event: Event)
         where
-            Event: Topics + scale::Encode,
+            Event: Topics + scale::Encode + 'static,
         {
+            // Events are writes as well, which immutable calls must not do.
+            crate::lang_core::storage::mutable_call_happens();
             <EnvInstance as OnInstance>::on_instance(|instance| {
                 Env::emit(instance, event)
             });
         }
 
+        /// Same as `emit`, but the event borrows its strings and bytes rather
+        /// than owning them, hence they are encoded without being cloned.
+        /// Off-chain, the event is recorded as the one owning its fields, so
+        /// that it can be inspected in tests as usual.
+        pub fn emit_ref<Event>(event: Event)
+        where
+            Event: Topics + scale::Encode + BorrowedEvent,
+        {
+            crate::lang_core::storage::mutable_call_happens();
+            <EnvInstance as OnInstance>::on_instance(|instance| {
+                Env::emit_ref(instance, event)
+            });
+        }
+
         pub fn call<R>(addr: &Address, data: &[u8]) -> Result<R>
         where
             R: scale::Decode,
         {
+            call_with(addr, data, CallParams::default())
+        }
+
+        /// Same as `call`, but with the gas limit and the value in `params`.
+        /// Reverts if any of them is given while the chain doesn't provide
+        /// `callWithParams`.
+        #[cfg(not(feature = "std"))]
+        pub fn call_with<R>(addr: &Address, data: &[u8], params: CallParams) -> Result<R>
+        where
+            R: scale::Decode,
+        {
+            if !params.is_default() {
+                require_ext("callWithParams");
+            }
             <EnvInstance as OnInstance>::on_instance(|instance| {
-                Env::call(instance, addr, data)
+                Env::call(instance, addr, data, &params)
             })
         }
 
+        #[cfg(feature = "std")]
+        pub fn call_with<R>(addr: &Address, data: &[u8], params: CallParams) -> Result<R>
+        where
+            R: scale::Decode,
+        {
+            if !params.is_default() {
+                require_ext("callWithParams");
+            }
+            let encoded = super::engine::off_chain::simulate_call(addr, data, params)?;
+            <R as scale::Decode>::decode(&mut encoded.as_slice()).map_err(Into::into)
+        }
+
+        /// Same as `call`, but returns the memoized result if the same method
+        /// of `addr` has already been called with the same arguments in the
+        /// current transaction.
+        pub fn cached_call<R>(addr: &Address, data: &[u8]) -> Result<R>
+        where
+            R: scale::Codec,
+        {
+            if let Some(encoded) = call_cache::lookup(addr, data) {
+                return <R as scale::Decode>::decode(&mut encoded.as_slice())
+                    .map_err(Into::into);
+            }
+            let result = call::<R>(addr, data)?;
+            call_cache::insert(addr, data, <R as scale::Encode>::encode(&result));
+            Ok(result)
+        }
+
         pub fn finish<V>(return_value: &V)
         where
             V: scale::Encode,
@@ -208,3 +716,505 @@ cfg_if! {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang_core::env::test;
+
+    #[test]
+    fn switch_callers() {
+        test::run_test(|| {
+            let accounts = test::default_accounts();
+            assert_eq!(get_caller(), accounts.alice);
+
+            test::push_caller(accounts.bob);
+            assert_eq!(get_caller(), accounts.bob);
+            test::push_caller(accounts.charlie);
+            assert_eq!(get_caller(), accounts.charlie);
+
+            assert_eq!(test::pop_caller(), Some(accounts.charlie));
+            assert_eq!(get_caller(), accounts.bob);
+            assert_eq!(test::pop_caller(), Some(accounts.bob));
+            assert_eq!(get_caller(), accounts.alice);
+        });
+    }
+
+    #[test]
+    fn report_gas() {
+        test::run_test(|| {
+            set_storage(b"key", &42u32);
+            set_storage(b"key", &43u32);
+            assert_eq!(get_storage::<u32>(b"key").ok(), Some(43));
+            remove_storage(b"key");
+
+            let report = test::gas_report();
+            assert_eq!(report.storage_writes, 2);
+            assert_eq!(report.storage_written_bytes, 8);
+            assert_eq!(report.storage_reads, 1);
+            assert_eq!(report.storage_read_bytes, 4);
+            assert_eq!(report.storage_removes, 1);
+            assert!(report.estimated_gas() > 0);
+
+            test::reset_gas_report();
+            assert_eq!(test::gas_report(), test::GasReport::default());
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "ext-storage-multi")]
+    fn batch_storage_writes_in_one_host_call() {
+        test::run_test(|| {
+            set_storage(b"removed", &0u8);
+            test::reset_gas_report();
+
+            let read = batch_storage_writes(|| {
+                set_storage(b"a", &1u32);
+                set_storage(b"a", &2u32);
+                set_storage(b"b", &3u32);
+                remove_storage(b"removed");
+                assert!(get_storage::<u8>(b"removed").is_err());
+                get_storage::<u32>(b"a").ok()
+            });
+            assert_eq!(read, Some(2));
+            assert_eq!(get_storage::<u32>(b"a").ok(), Some(2));
+            assert_eq!(get_storage::<u32>(b"b").ok(), Some(3));
+            assert!(get_storage::<u8>(b"removed").is_err());
+
+            let report = test::gas_report();
+            assert_eq!(report.storage_write_calls, 1);
+            assert_eq!(report.storage_writes, 2);
+            assert_eq!(report.storage_removes, 1);
+            // Only the reads made after the batch reach the host.
+            assert_eq!(report.storage_reads, 3);
+        });
+    }
+
+    #[test]
+    fn batch_storage_writes_on_old_chains() {
+        test::run_test(|| {
+            test::set_ext_available("setStorageMulti", false);
+            batch_storage_writes(|| {
+                set_storage(b"a", &1u32);
+                set_storage(b"b", &2u32);
+                batch_storage_writes(|| set_storage(b"c", &3u32));
+                assert_eq!(test::gas_report().storage_write_calls, 0);
+            });
+            assert_eq!(get_storage::<u32>(b"c").ok(), Some(3));
+            assert_eq!(test::gas_report().storage_write_calls, 3);
+        });
+    }
+
+    #[test]
+    #[cfg(not(feature = "ext-storage-multi"))]
+    fn batch_storage_writes_key_by_key_by_default() {
+        test::run_test(|| {
+            batch_storage_writes(|| {
+                set_storage(b"a", &1u32);
+                set_storage(b"a", &2u32);
+                set_storage(b"b", &3u32);
+                assert_eq!(test::gas_report().storage_write_calls, 0);
+            });
+            assert_eq!(get_storage::<u32>(b"a").ok(), Some(2));
+            assert_eq!(get_storage::<u32>(b"b").ok(), Some(3));
+            assert_eq!(test::gas_report().storage_write_calls, 2);
+        });
+    }
+
+    #[test]
+    fn drop_batched_storage_writes_on_panic() {
+        test::run_test(|| {
+            let result = std::panic::catch_unwind(|| {
+                batch_storage_writes(|| {
+                    set_storage(b"a", &1u32);
+                    panic!();
+                })
+            });
+            assert!(result.is_err());
+
+            set_storage(b"b", &2u32);
+            assert!(get_storage::<u32>(b"a").is_err());
+            assert_eq!(test::gas_report().storage_write_calls, 1);
+        });
+    }
+
+    #[test]
+    fn control_blocks() {
+        test::run_test(|| {
+            test::set_block_number(100);
+            test::set_block_timestamp(1_600_000_000);
+            assert_eq!(get_block_number(), 100);
+            assert_eq!(now(), 1_600_000_000);
+
+            test::advance_block();
+            assert_eq!(get_block_number(), 101);
+            assert_eq!(now(), 1_600_000_000);
+
+            test::set_block_timestamp(1_600_000_015);
+            assert_eq!(now(), 1_600_000_015);
+        });
+    }
+
+    #[test]
+    fn probe_host_functions() {
+        test::run_test(|| {
+            assert!(has_ext("registerAsset"));
+            test::set_ext_available("registerAsset", false);
+            assert!(!has_ext("registerAsset"));
+            assert!(has_ext("transferAsset"));
+            test::set_ext_available("registerAsset", true);
+            assert!(has_ext("registerAsset"));
+        });
+    }
+
+    #[test]
+    fn call_with_params() {
+        test::run_test(|| {
+            let callee = Address::new([0x42; 20]);
+            test::register_callee(callee, |_| Vec::new());
+            assert_eq!(test::last_call_params(), None);
+
+            assert!(call::<()>(&callee, b"").is_ok());
+            assert_eq!(test::last_call_params(), Some(CallParams::default()));
+
+            let params = CallParams {
+                gas: Some(100_000),
+                value: 1,
+            };
+            assert!(call_with::<()>(&callee, b"", params).is_ok());
+            assert_eq!(test::last_call_params(), Some(params));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "host function `callWithParams` unavailable")]
+    fn call_with_params_on_old_chains() {
+        test::run_test(|| {
+            let callee = Address::new([0x42; 20]);
+            test::register_callee(callee, |_| Vec::new());
+            test::set_ext_available("callWithParams", false);
+
+            assert!(call::<()>(&callee, b"").is_ok());
+            let params = CallParams {
+                gas: Some(100_000),
+                value: 0,
+            };
+            let _ = call_with::<()>(&callee, b"", params);
+        });
+    }
+
+    #[test]
+    fn predict_address_of_next_deploy() {
+        test::run_test(|| {
+            let contract = get_address();
+            assert_eq!(address_of_next_deploy(), Address::from_create(&contract, 1));
+
+            test::set_nonce(contract, 5);
+            assert_eq!(address_of_next_deploy(), Address::from_create(&contract, 5));
+            test::set_nonce(test::default_accounts().bob, 7);
+            assert_eq!(address_of_next_deploy(), Address::from_create(&contract, 5));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "host function `getNonce` unavailable")]
+    fn predict_address_on_old_chains() {
+        test::run_test(|| {
+            test::set_ext_available("getNonce", false);
+            address_of_next_deploy();
+        });
+    }
+
+    #[test]
+    fn consume_gas() {
+        test::run_test(|| {
+            assert_eq!(gas_limit(), 300_000_000);
+            assert_eq!(gas_left(), gas_limit());
+
+            test::set_gas_limit(30_000);
+            set_storage(b"key", &42u32);
+            assert_eq!(gas_limit(), 30_000);
+            assert_eq!(gas_left(), 30_000 - test::gas_report().estimated_gas());
+
+            set_storage(b"key", &43u32);
+            assert_eq!(gas_left(), 0);
+        });
+    }
+
+    #[test]
+    fn inspect_raw_storage() {
+        test::run_test(|| {
+            assert_eq!(test::storage_len(), 0);
+            assert_eq!(test::get_storage_raw(b"key"), None);
+
+            set_storage(b"key", &42u32);
+            set_storage(b"name", &String::from("Alice"));
+            assert_eq!(test::storage_len(), 2);
+            assert_eq!(
+                test::get_storage_raw(b"key"),
+                Some(scale::Encode::encode(&42u32))
+            );
+            assert_eq!(test::read_storage::<u32>(b"key"), Some(42));
+            assert_eq!(
+                test::read_storage::<String>(b"name").as_deref(),
+                Some("Alice")
+            );
+
+            remove_storage(b"key");
+            assert_eq!(test::storage_len(), 1);
+            assert_eq!(test::read_storage::<u32>(b"key"), None);
+        });
+    }
+
+    #[test]
+    fn chain_id_and_tx_hash() {
+        test::run_test(|| {
+            assert_eq!(get_chain_id(), 1);
+            assert_eq!(get_tx_hash(), Hash::default());
+
+            test::set_chain_id(20200);
+            test::set_tx_hash(Hash::from([0x11; 32]));
+            assert_eq!(get_chain_id(), 20200);
+            assert_eq!(get_tx_hash(), Hash::from([0x11; 32]));
+        });
+    }
+
+    #[test]
+    fn block_hashes() {
+        test::run_test(|| {
+            test::set_block_number(300);
+            assert_eq!(
+                get_block_hash(299),
+                liquid_primitives::hash::hash(&299u64.to_be_bytes()).into()
+            );
+            assert_ne!(get_block_hash(299), get_block_hash(44));
+
+            test::set_block_hash(44, Hash::from([0x44; 32]));
+            assert_eq!(get_block_hash(44), Hash::from([0x44; 32]));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "only the hashes of the 256 most recent blocks")]
+    fn block_hash_of_current_block() {
+        test::run_test(|| {
+            test::set_block_number(300);
+            get_block_hash(300);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "only the hashes of the 256 most recent blocks")]
+    fn block_hash_of_stale_block() {
+        test::run_test(|| {
+            test::set_block_number(300);
+            get_block_hash(43);
+        });
+    }
+
+    /// Counts the calls to it in its own storage, and returns the count.
+    struct Counter;
+
+    impl test::Deployable for Counter {
+        fn dispatch() -> crate::DispatchResult {
+            let call_data = get_call_data(CallMode::Call)
+                .map_err(|_| crate::DispatchError::CouldNotReadInput)?;
+            if call_data.selector != [0x01; 4] {
+                return Err(crate::DispatchError::UnknownSelector);
+            }
+            assert_eq!(get_caller(), Address::default());
+            assert_eq!(get_address(), Address::new([0x42; 20]));
+            let count = get_storage::<u32>(b"count").unwrap() + 1;
+            set_storage(b"count", &count);
+            finish(&count);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn call_deployed_contract() {
+        test::run_test(|| {
+            let callee = Address::new([0x42; 20]);
+            set_storage(b"count", &0u32);
+            test::deploy_at(callee, || {
+                assert_eq!(get_caller(), test::default_accounts().alice);
+                set_storage(b"count", &10u32);
+                Counter
+            });
+
+            assert_eq!(call::<u32>(&callee, &[0x01; 4]).ok(), Some(11));
+            assert_eq!(call::<u32>(&callee, &[0x01; 4]).ok(), Some(12));
+            assert_eq!(get_storage::<u32>(b"count").ok(), Some(0));
+            assert_eq!(get_caller(), test::default_accounts().alice);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown selector")]
+    fn call_deployed_contract_with_unknown_selector() {
+        test::run_test(|| {
+            let callee = Address::new([0x42; 20]);
+            test::deploy_at(callee, || Counter);
+            let _ = call::<u32>(&callee, &[0x02; 4]);
+        });
+    }
+
+    #[test]
+    fn optional_call_deployed_contract_with_unknown_selector() {
+        test::run_test(|| {
+            let callee = Address::new([0x42; 20]);
+            test::deploy_at(callee, || {
+                set_storage(b"count", &0u32);
+                Counter
+            });
+
+            assert!(optional_call(|| call::<u32>(&callee, &[0x02; 4])).is_err());
+            assert!(optional_call(|| call::<()>(&callee, &[0x02; 4])).is_err());
+            assert_eq!(
+                optional_call(|| call::<u32>(&callee, &[0x01; 4])).ok(),
+                Some(1)
+            );
+        });
+    }
+
+    #[test]
+    fn approve_and_spend_asset_allowance() {
+        test::run_test(|| {
+            let accounts = test::default_accounts();
+            let contract = get_address();
+            assert!(register_asset(b"Token", &accounts.alice, true, 100, b""));
+            assert!(issue_fungible_asset(&accounts.alice, b"Token", 100));
+
+            assert!(approve_asset(&accounts.bob, b"Token", 30));
+            assert_eq!(
+                get_asset_allowance(&accounts.alice, &accounts.bob, b"Token"),
+                30
+            );
+            assert_eq!(get_asset_balance(&accounts.alice, b"Token"), 70);
+            assert_eq!(get_asset_balance(&contract, b"Token"), 30);
+            assert!(!approve_asset(&accounts.charlie, b"Token", 71));
+            assert_eq!(
+                get_asset_allowance(&accounts.alice, &accounts.charlie, b"Token"),
+                0
+            );
+
+            test::push_caller(accounts.bob);
+            assert!(spend_asset_allowance(&accounts.alice, b"Token", 20));
+            assert!(transfer_asset(&accounts.bob, b"Token", 20, true));
+            assert!(!spend_asset_allowance(&accounts.alice, b"Token", 20));
+            test::pop_caller();
+            assert_eq!(
+                get_asset_allowance(&accounts.alice, &accounts.bob, b"Token"),
+                10
+            );
+            assert_eq!(get_asset_balance(&accounts.bob, b"Token"), 20);
+
+            assert!(approve_asset(&accounts.bob, b"Token", 5));
+            assert_eq!(
+                get_asset_allowance(&accounts.alice, &accounts.bob, b"Token"),
+                5
+            );
+            assert_eq!(get_asset_balance(&accounts.alice, b"Token"), 75);
+            assert_eq!(get_asset_balance(&contract, b"Token"), 5);
+        });
+    }
+
+    #[test]
+    fn issue_and_transfer_assets_in_batch() {
+        test::run_test(|| {
+            let accounts = test::default_accounts();
+            assert!(register_asset(b"Ticket", &accounts.alice, false, 5, b""));
+            let ids =
+                issue_not_fungible_assets(&accounts.alice, b"Ticket", &[b"a", b"b"]);
+            assert_eq!(ids, vec![1, 2]);
+            test::set_ext_available("issueNotFungibleAssets", false);
+            let ids = issue_not_fungible_assets(
+                &accounts.alice,
+                b"Ticket",
+                &[b"c", b"d", b"e", b"f"],
+            );
+            assert_eq!(ids, vec![3, 4, 5]);
+
+            assert!(!transfer_assets(
+                &accounts.bob,
+                b"Ticket",
+                &[1, 2, 6],
+                false
+            ));
+            assert_eq!(get_asset_balance(&accounts.alice, b"Ticket"), 5);
+            assert!(transfer_assets(&accounts.bob, b"Ticket", &[1, 2], false));
+            test::set_ext_available("transferAssets", false);
+            assert!(!transfer_assets(&accounts.bob, b"Ticket", &[3, 6], false));
+            assert_eq!(get_asset_balance(&accounts.bob, b"Ticket"), 3);
+            assert_eq!(
+                get_not_fungible_asset_info(&accounts.bob, b"Ticket", 3),
+                "c"
+            );
+        });
+    }
+
+    #[test]
+    fn resolve_cns_records() {
+        test::run_test(|| {
+            let accounts = test::default_accounts();
+            test::register_cns("Hello", "1.0", accounts.alice);
+            test::register_cns("Hello", "2.0", accounts.bob);
+            assert_eq!(cns_resolve("Hello", "1.0"), accounts.alice);
+            assert_eq!(cns_resolve("Hello", "2.0"), accounts.bob);
+        });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "contract `Hello` of version `1.0` is not registered to CNS"
+    )]
+    fn resolve_unregistered_cns_record() {
+        test::run_test(|| {
+            cns_resolve("Hello", "1.0");
+        });
+    }
+
+    #[test]
+    fn remember_registered_assets() {
+        test::run_test(|| {
+            let accounts = test::default_accounts();
+            assert!(!is_asset_registered(b"Token"));
+            assert!(register_asset(b"Token", &accounts.alice, true, 100, b""));
+            assert!(is_asset_registered(b"Token"));
+            assert!(!register_asset(b"Token", &accounts.alice, true, 100, b""));
+            assert!(is_asset_registered(b"Token"));
+        });
+    }
+
+    #[test]
+    fn burn_assets() {
+        test::run_test(|| {
+            let accounts = test::default_accounts();
+            assert!(register_asset(b"Token", &accounts.alice, true, 100, b""));
+            assert!(issue_fungible_asset(&accounts.alice, b"Token", 100));
+            assert!(burn_asset(b"Token", 40, false));
+            assert!(!burn_asset(b"Token", 61, false));
+            assert_eq!(get_asset_balance(&accounts.alice, b"Token"), 60);
+            assert_eq!(get_burnt_asset(b"Token"), 40);
+
+            assert!(register_asset(b"Ticket", &accounts.alice, false, 10, b""));
+            let id = issue_not_fungible_asset(&accounts.alice, b"Ticket", b"uri");
+            assert_eq!(get_burnt_asset(b"Ticket"), 0);
+            assert!(burn_asset(b"Ticket", id, false));
+            assert!(!burn_asset(b"Ticket", id, false));
+            assert_eq!(get_burnt_asset(b"Ticket"), 1);
+            assert!(get_not_fungible_asset_ids(&accounts.alice, b"Ticket").is_empty());
+        });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "host function `registerAsset` unavailable on this chain version"
+    )]
+    fn revert_on_unavailable_host_function() {
+        test::run_test(|| {
+            test::set_ext_available("registerAsset", false);
+            register_asset(b"Token", &Address::empty(), true, 100, b"");
+        });
+    }
+}
@@ -10,11 +10,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::lang_core::env::{calldata::CallData, error::Result};
+use crate::{
+    lang_core::env::{calldata::CallData, error::Result, storage_batch::Writes},
+    BorrowedEvent,
+};
 use cfg_if::cfg_if;
 use liquid_prelude::{string::String, vec::Vec};
 use liquid_primitives::{
-    types::{timestamp, Address},
+    types::{timestamp, Address, Hash},
     Topics,
 };
 
@@ -24,6 +27,25 @@ pub enum CallMode {
     Call,
 }
 
+/// Controls of a call to another contract.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct CallParams {
+    /// The most gas the callee may consume, or all the remaining gas if
+    /// `None`.
+    pub gas: Option<u64>,
+    /// The value transferred to the callee along with the call.
+    pub value: u64,
+}
+
+impl CallParams {
+    /// Returns whether the call is made as if no controls were given, i.e.,
+    /// with all the remaining gas and no value.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 pub trait Env {
     fn set_storage<V>(&mut self, key: &[u8], value: &V)
     where
@@ -35,6 +57,10 @@ pub trait Env {
 
     fn remove_storage(&mut self, key: &[u8]);
 
+    /// Writes or removes all the entries of `writes` within a single host
+    /// call.
+    fn set_storage_multi(&mut self, writes: &Writes);
+
     fn get_call_data(&mut self, mode: CallMode) -> Result<CallData>;
 
     fn get_caller(&mut self) -> Address;
@@ -46,7 +72,44 @@ pub trait Env {
     fn get_block_number(&mut self) -> u64;
 
     fn get_address(&mut self) -> Address;
+
+    /// Returns the nonce of the current contract, which determines the
+    /// address of the next contract deployed by it.
+    fn get_nonce(&mut self) -> u64;
+
+    /// Returns the gas remaining for the current transaction.
+    fn gas_left(&mut self) -> u64;
+
+    /// Returns the gas limit of the current transaction.
+    fn gas_limit(&mut self) -> u64;
+
+    /// Returns the id of the chain the contract runs on.
+    fn get_chain_id(&mut self) -> u64;
+
+    /// Returns the hash of the current transaction.
+    fn get_tx_hash(&mut self) -> Hash;
+
+    /// Returns the hash of the block `number`, which is assumed to be one of
+    /// the 256 most recent blocks.
+    fn get_block_hash(&mut self, number: u64) -> Hash;
+
+    /// Executes the code of `addr` within the storage context of the current
+    /// contract, and returns the raw return data of the callee.
+    fn delegate_call(&mut self, addr: &Address, data: &[u8]) -> Result<Vec<u8>>;
+
+    /// Finishes the execution with already encoded return data.
+    fn finish_raw(&mut self, return_data: &[u8]);
+
     fn get_external_code_size(&self, account: &Address) -> u32;
+
+    /// Logs a formatted message for debugging, which never affects the
+    /// execution.
+    fn debug_log(&mut self, args: core::fmt::Arguments);
+
+    /// Returns whether the host interface of the chain provides the function
+    /// named `name`.
+    fn has_ext(&mut self, name: &str) -> bool;
+
     fn register_asset(
         &mut self,
         asset_name: &[u8],
@@ -75,6 +138,23 @@ pub trait Env {
         from_self: bool,
     ) -> bool;
 
+    /// Issues a non-fungible token to `to` for every URI in `uris` at once,
+    /// and returns the IDs of the issued tokens.
+    fn issue_not_fungible_assets(
+        &mut self,
+        to: &Address,
+        asset_name: &[u8],
+        uris: &[&[u8]],
+    ) -> Vec<u64>;
+    /// Transfers all of `amounts_or_ids` to `to` at once, or none of them.
+    fn transfer_assets(
+        &mut self,
+        to: &Address,
+        asset_name: &[u8],
+        amounts_or_ids: &[u64],
+        from_self: bool,
+    ) -> bool;
+
     fn get_asset_balance(&self, to: &Address, asset_name: &[u8]) -> u64;
 
     fn get_not_fungible_asset_ids(
@@ -94,9 +174,19 @@ pub trait Env {
         if #[cfg(feature = "solidity-compatible")] {
             fn emit<Event>(&mut self, event: Event)
             where
-                Event: Topics + liquid_abi_codec::Encode;
+                Event: Topics + liquid_abi_codec::Encode + 'static;
 
-            fn call<R>(&mut self, addr: &Address, data: &[u8]) -> Result<R>
+            /// Same as `emit`, but accepts events borrowing their fields.
+            fn emit_ref<Event>(&mut self, event: Event)
+            where
+                Event: Topics + liquid_abi_codec::Encode + BorrowedEvent;
+
+            fn call<R>(
+                &mut self,
+                addr: &Address,
+                data: &[u8],
+                params: &CallParams,
+            ) -> Result<R>
             where
                 R: liquid_abi_codec::Decode + liquid_abi_codec::TypeInfo;
 
@@ -110,9 +200,19 @@ pub trait Env {
         } else {
             fn emit<Event>(&mut self, event: Event)
             where
-                Event: Topics + scale::Encode;
+                Event: Topics + scale::Encode + 'static;
 
-            fn call<R>(&mut self, addr: &Address, data: &[u8]) -> Result<R>
+            /// Same as `emit`, but accepts events borrowing their fields.
+            fn emit_ref<Event>(&mut self, event: Event)
+            where
+                Event: Topics + scale::Encode + BorrowedEvent;
+
+            fn call<R>(
+                &mut self,
+                addr: &Address,
+                data: &[u8],
+                params: &CallParams,
+            ) -> Result<R>
             where
                 R: scale::Decode;
 
@@ -0,0 +1,97 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memoized results of `#[liquid(cached)]` interface methods.
+//!
+//! Results are keyed by the callee address and the whole encoded call data
+//! (selector and arguments), and live until the end of the transaction or
+//! until a mutable cross-contract call happens, whichever comes first.
+
+use liquid_prelude::{collections::BTreeMap, vec::Vec};
+use liquid_primitives::types::Address;
+
+type Cache = BTreeMap<(Address, Vec<u8>), Vec<u8>>;
+
+#[cfg(feature = "std")]
+mod imp {
+    use super::Cache;
+    use core::cell::RefCell;
+
+    thread_local! {
+        static CALL_CACHE: RefCell<Cache> = RefCell::new(Cache::new());
+    }
+
+    pub fn with_cache<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut Cache) -> R,
+    {
+        CALL_CACHE.with(|cache| f(&mut cache.borrow_mut()))
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod imp {
+    use super::Cache;
+    use lazy_static::lazy_static;
+    use spin::Mutex;
+
+    lazy_static! {
+        static ref CALL_CACHE: Mutex<Cache> = Mutex::new(Cache::new());
+    }
+
+    pub fn with_cache<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut Cache) -> R,
+    {
+        f(&mut CALL_CACHE.lock())
+    }
+}
+
+pub fn lookup(addr: &Address, data: &[u8]) -> Option<Vec<u8>> {
+    imp::with_cache(|cache| cache.get(&(*addr, data.to_vec())).cloned())
+}
+
+pub fn insert(addr: &Address, data: &[u8], encoded_result: Vec<u8>) {
+    imp::with_cache(|cache| {
+        cache.insert((*addr, data.to_vec()), encoded_result);
+    })
+}
+
+/// Drops all memoized results. Generated code calls this before every
+/// mutable cross-contract call, because the callee may change the state the
+/// cached results were read from.
+pub fn clear_call_cache() {
+    imp::with_cache(|cache| cache.clear())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_after_insert() {
+        clear_call_cache();
+        let addr = "0x3e9afaa4a062a49d64b8ab057b3cb51892e17ecb"
+            .parse::<Address>()
+            .unwrap();
+        assert_eq!(lookup(&addr, &[1, 2, 3]), None);
+
+        insert(&addr, &[1, 2, 3], vec![42]);
+        assert_eq!(lookup(&addr, &[1, 2, 3]), Some(vec![42]));
+        // Different arguments or callee must not hit the cache.
+        assert_eq!(lookup(&addr, &[1, 2, 4]), None);
+        assert_eq!(lookup(&Address::empty(), &[1, 2, 3]), None);
+
+        clear_call_cache();
+        assert_eq!(lookup(&addr, &[1, 2, 3]), None);
+    }
+}
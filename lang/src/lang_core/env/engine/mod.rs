@@ -12,6 +12,8 @@
 
 use cfg_if::cfg_if;
 
+pub mod return_data;
+
 pub trait OnInstance {
     fn on_instance<F, R>(f: F) -> R
     where
@@ -10,6 +10,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[derive(Clone)]
 pub struct Block {
     /// The current block number.
     block_number: u64,
@@ -38,4 +39,23 @@ impl Block {
     pub fn timestamp(&self) -> u64 {
         self.timestamp
     }
+
+    /// Sets the block number.
+    pub fn set_block_number(&mut self, block_number: u64) {
+        self.block_number = block_number;
+    }
+
+    /// Sets the timestamp of the block.
+    pub fn set_timestamp(&mut self, timestamp: u64) {
+        self.timestamp = timestamp;
+    }
+
+    /// Returns the block following this one, which keeps the timestamp of
+    /// this block.
+    pub fn next(&self) -> Self {
+        Self {
+            block_number: self.block_number + 1,
+            timestamp: self.timestamp,
+        }
+    }
 }
@@ -14,6 +14,7 @@ use crate::lang_core::env::error::{EnvError, Result};
 use liquid_prelude::{collections::BTreeMap, vec::Vec};
 use scale::{Decode, Encode};
 
+#[derive(Clone)]
 pub struct ContractStorage {
     entries: BTreeMap<Vec<u8>, Vec<u8>>,
 }
@@ -35,11 +36,34 @@ impl ContractStorage {
         }
     }
 
-    pub fn set_storage<V>(&mut self, key: &[u8], value: &V)
+    /// Stores `value` under `key`, returning the length of encoded value.
+    pub fn set_storage<V>(&mut self, key: &[u8], value: &V) -> usize
     where
         V: Encode,
     {
-        self.entries.insert(key.to_vec(), value.encode());
+        self.set_encoded(key, value.encode())
+    }
+
+    /// Stores the already encoded value under `key`, returning its length.
+    pub fn set_encoded(&mut self, key: &[u8], encoded: Vec<u8>) -> usize {
+        let len = encoded.len();
+        self.entries.insert(key.to_vec(), encoded);
+        len
+    }
+
+    /// Returns the encoded value stored under `key`.
+    pub fn get_encoded(&self, key: &[u8]) -> Option<&[u8]> {
+        self.entries.get(key).map(Vec::as_slice)
+    }
+
+    /// Returns the number of keys with a value stored.
+    pub fn key_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the length of the encoded value stored under `key`.
+    pub fn encoded_len(&self, key: &[u8]) -> Option<usize> {
+        self.entries.get(key).map(Vec::len)
     }
 
     pub fn remove_storage(&mut self, key: &[u8]) {
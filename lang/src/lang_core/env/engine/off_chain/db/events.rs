@@ -10,8 +10,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::BorrowedEvent;
 use cfg_if::cfg_if;
 use liquid_primitives::{types::Hash, Topics};
+use std::{any::Any, rc::Rc};
 
 cfg_if! {
     if #[cfg(feature = "solidity-compatible")] {
@@ -21,23 +23,59 @@ cfg_if! {
     }
 }
 
+/// Implemented by event types that can be recovered from recorded events.
+///
+/// The contract macro implements this for every `#[liquid(event)]` struct,
+/// looking through the `Event` enum of the contract which is what actually
+/// gets emitted.
+pub trait EmittedEvent: Sized + 'static {
+    fn from_payload(payload: &dyn Any) -> Option<&Self> {
+        payload.downcast_ref::<Self>()
+    }
+}
+
 #[derive(Clone)]
 pub struct Event {
     pub data: Vec<u8>,
     pub topics: Vec<Hash>,
+    /// The emitted value itself, kept so that tests can inspect its fields
+    /// including the indexed ones, which are only hashes in `topics`.
+    payload: Rc<dyn Any>,
 }
 
 impl Event {
     pub fn new<E>(event: E) -> Self
     where
-        E: Topics + Encode,
+        E: Topics + Encode + 'static,
     {
         Self {
             data: event.encode(),
             topics: event.topics(),
+            payload: Rc::new(event),
         }
     }
 
+    /// Same as `new`, but for events borrowing their fields, which are kept
+    /// as the events owning them.
+    pub fn from_borrowed<E>(event: E) -> Self
+    where
+        E: Topics + Encode + BorrowedEvent,
+    {
+        Self {
+            data: event.encode(),
+            topics: event.topics(),
+            payload: Rc::new(event.into_owned_event()),
+        }
+    }
+
+    /// Returns the emitted event as `E`, or `None` if it is of another type.
+    pub fn downcast<E>(&self) -> Option<&E>
+    where
+        E: EmittedEvent,
+    {
+        E::from_payload(self.payload.as_ref())
+    }
+
     pub fn decode_data<R>(&self) -> R
     where
         R: Decode,
@@ -12,6 +12,7 @@
 
 use liquid_primitives::types::Address;
 
+#[derive(Clone)]
 pub struct ExecContext {
     /// The caller of the contract execution.
     ///
@@ -0,0 +1,87 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use liquid_primitives::types::Hash;
+
+/// Rough costs of host operations, modelled after the gas schedule of EVM.
+const STORAGE_READ_COST: u64 = 800;
+const STORAGE_WRITE_COST: u64 = 20_000;
+const STORAGE_REMOVE_COST: u64 = 5_000;
+const STORAGE_BYTE_COST: u64 = 68;
+const EVENT_COST: u64 = 375;
+const EVENT_TOPIC_COST: u64 = 375;
+const EVENT_BYTE_COST: u64 = 8;
+const CALL_COST: u64 = 700;
+
+/// Counts of the host operations performed in the off-chain environment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GasReport {
+    pub storage_reads: u64,
+    pub storage_read_bytes: u64,
+    pub storage_writes: u64,
+    pub storage_written_bytes: u64,
+    pub storage_removes: u64,
+    /// Host calls made to write or remove entries, where a batch of writes
+    /// counts as one.
+    pub storage_write_calls: u64,
+    pub events: u64,
+    pub event_topics: u64,
+    pub event_bytes: u64,
+    pub calls: u64,
+}
+
+impl GasReport {
+    pub fn record_storage_read(&mut self, len: usize) {
+        self.storage_reads += 1;
+        self.storage_read_bytes += len as u64;
+    }
+
+    pub fn record_storage_write(&mut self, len: usize) {
+        self.storage_writes += 1;
+        self.storage_written_bytes += len as u64;
+    }
+
+    pub fn record_storage_remove(&mut self) {
+        self.storage_removes += 1;
+    }
+
+    pub fn record_storage_write_call(&mut self) {
+        self.storage_write_calls += 1;
+    }
+
+    pub fn record_event(&mut self, topics: &[Hash], data: &[u8]) {
+        self.events += 1;
+        self.event_topics += topics.len() as u64;
+        self.event_bytes += data.len() as u64;
+    }
+
+    pub fn record_call(&mut self) {
+        self.calls += 1;
+    }
+
+    /// Returns a rough estimation of the gas consumed by the recorded
+    /// operations.
+    ///
+    /// The number only reflects host operations, the cost of executing the
+    /// contract code itself is not included, so it is meant to be compared
+    /// between runs rather than against the gas used on chain.
+    pub fn estimated_gas(&self) -> u64 {
+        self.storage_reads * STORAGE_READ_COST
+            + self.storage_writes * STORAGE_WRITE_COST
+            + self.storage_removes * STORAGE_REMOVE_COST
+            + self.storage_written_bytes * STORAGE_BYTE_COST
+            + self.events * EVENT_COST
+            + self.event_topics * EVENT_TOPIC_COST
+            + self.event_bytes * EVENT_BYTE_COST
+            + self.calls * CALL_COST
+    }
+}
@@ -14,8 +14,10 @@ mod block;
 mod contract_storage;
 mod events;
 mod exec_context;
+mod gas_report;
 
 pub use block::Block;
 pub use contract_storage::ContractStorage;
-pub use events::Event;
+pub use events::{EmittedEvent, Event};
 pub use exec_context::ExecContext;
+pub use gas_report::GasReport;
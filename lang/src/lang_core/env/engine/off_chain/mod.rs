@@ -13,15 +13,164 @@
 mod db;
 pub mod test_api;
 
-use self::db::{Block, ContractStorage, Event, ExecContext};
-use crate::lang_core::env::{
-    backend::Env, calldata::CallData, engine::OnInstance, error::Result, CallMode,
+use self::db::{Block, ContractStorage, Event, ExecContext, GasReport};
+use crate::{
+    lang_core::env::{
+        backend::Env,
+        calldata::CallData,
+        engine::OnInstance,
+        error::{EnvError, Result},
+        storage_batch::Writes,
+        CallMode, CallParams,
+    },
+    BorrowedEvent, DispatchError, DispatchResult, DispatchRetInfo,
 };
 use cfg_if::cfg_if;
-use core::cell::RefCell;
-use liquid_primitives::{types::address::Address, Topics};
-use std::{collections::HashMap, str};
+use core::cell::{Cell, RefCell};
+use liquid_primitives::{
+    types::{address::Address, Hash},
+    Topics,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+    str,
+};
+
+type Delegate = Rc<dyn Fn(&[u8]) -> Vec<u8>>;
+
+/// The gas limit of transactions by default, which is the default of FISCO
+/// BCOS as well.
+const DEFAULT_GAS_LIMIT: u64 = 300_000_000;
+
+thread_local! {
+    static DELEGATES: RefCell<HashMap<Address, Delegate>> = RefCell::new(HashMap::new());
+    static CALLEES: RefCell<HashMap<Address, Delegate>> = RefCell::new(HashMap::new());
+    static LAST_CALL_PARAMS: RefCell<Option<CallParams>> = RefCell::new(None);
+    /// Whether the callee of the next call may lack the selector.
+    static OPTIONAL_CALL: Cell<bool> = Cell::new(false);
+    /// Whether the callee of the last optional call lacks the selector.
+    static SELECTOR_MISSING: Cell<bool> = Cell::new(false);
+    /// Whether `Env::revert` has been called since the flag was taken.
+    static REVERTED: Cell<bool> = Cell::new(false);
+}
+
+/// Returns whether a contract has reverted since the last call of this
+/// function, which tells reverts from other panics.
+#[cfg(feature = "fuzz")]
+pub(crate) fn take_reverted() -> bool {
+    REVERTED.with(|reverted| reverted.replace(false))
+}
+
+/// Simulates a call to `addr` by invoking the callee registered for it.
+///
+/// Like delegates, callees are invoked without holding the environment
+/// instance, so that they are free to use the environment themselves.
+pub fn simulate_call(addr: &Address, data: &[u8], params: CallParams) -> Result<Vec<u8>> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.gas_report.record_call();
+    });
+    LAST_CALL_PARAMS.with(|last| *last.borrow_mut() = Some(params));
+    let callee = CALLEES.with(|callees| callees.borrow().get(addr).cloned());
+    match callee {
+        Some(callee) => Ok(callee(data)),
+        None => Err(EnvError::FailToCallForeignContract),
+    }
+}
+
+/// Runs `call`, in which a call to another contract is made, treating the lack
+/// of the selector in a deployed callee as a failure of the call rather than
+/// a revert of the whole test.
+pub fn simulate_optional_call<R, F>(call: F) -> Result<R>
+where
+    F: FnOnce() -> Result<R>,
+{
+    OPTIONAL_CALL.with(|optional| optional.set(true));
+    SELECTOR_MISSING.with(|missing| missing.set(false));
+    let result = call();
+    // Callees other than deployed contracts never consume the flag.
+    OPTIONAL_CALL.with(|optional| optional.set(false));
+    if SELECTOR_MISSING.with(|missing| missing.replace(false)) {
+        return Err(EnvError::FailToCallForeignContract);
+    }
+    result
+}
+
+/// Simulates a `delegatecall` by invoking the delegate registered for `addr`.
+///
+/// Contract storage is shared by the whole off-chain environment, so the
+/// delegate naturally runs within the storage context of the caller. The
+/// delegate is invoked without holding the environment instance, which is
+/// why this can not be done in `Env::delegate_call`.
+pub fn simulate_delegate_call(addr: &Address, data: &[u8]) -> Result<Vec<u8>> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.gas_report.record_call();
+    });
+    let delegate = DELEGATES.with(|delegates| delegates.borrow().get(addr).cloned());
+    match delegate {
+        Some(delegate) => Ok(delegate(data)),
+        None => Err(EnvError::FailToCallForeignContract),
+    }
+}
+
+/// Runs `dispatch` as the code of the contract deployed at `addr`, with the
+/// current contract as the caller, and returns the data it finishes with.
+fn call_deployed(
+    addr: Address,
+    data: &[u8],
+    dispatch: fn() -> DispatchResult,
+) -> Vec<u8> {
+    // Calls made by the callee itself are not optional.
+    let optional = OPTIONAL_CALL.with(|optional| optional.replace(false));
+    let caller = <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .exec_contexts
+            .last()
+            .map(ExecContext::self_address)
+            .unwrap_or_default()
+    });
+    let _scope = DeployedScope::enter(caller, addr);
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.call_data = Some(data.to_vec());
+        instance.return_data = None;
+    });
+    let result = dispatch();
+    if optional && matches!(result, Err(DispatchError::UnknownSelector)) {
+        SELECTOR_MISSING.with(|missing| missing.set(true));
+        return Vec::new();
+    }
+    let ret_info = DispatchRetInfo::from(result);
+    if !ret_info.is_success() {
+        panic!("{}", ret_info.get_info_string());
+    }
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.return_data.take())
+        .unwrap_or_default()
+}
+
+/// Executes code of the contract deployed at an address until dropped, during
+/// which the storage of that contract is accessed instead.
+struct DeployedScope(());
+
+impl DeployedScope {
+    fn enter(caller: Address, addr: Address) -> Self {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            instance.exec_contexts.push(ExecContext::new(caller, addr));
+            instance.storage_owners.push(addr);
+        });
+        Self(())
+    }
+}
 
+impl Drop for DeployedScope {
+    fn drop(&mut self) {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            instance.storage_owners.pop();
+            instance.exec_contexts.pop();
+        });
+    }
+}
+
+#[derive(Clone)]
 struct AssetInfo {
     issuer: Address,
     fungible: bool,
@@ -30,14 +179,59 @@ struct AssetInfo {
     supplied: u64,
 }
 
+/// The state of the simulated chain captured by `test::snapshot`.
+#[derive(Clone)]
+struct Snapshot {
+    contract_storage: ContractStorage,
+    deployed_storages: HashMap<Address, ContractStorage>,
+    blocks: Vec<Block>,
+    exec_contexts: Vec<ExecContext>,
+    events: Vec<Event>,
+    assets_info: HashMap<String, AssetInfo>,
+    fungible_asset: HashMap<String, HashMap<Address, u64>>,
+    not_fungible_asset: HashMap<String, HashMap<Address, HashMap<u64, String>>>,
+    nonces: HashMap<Address, u64>,
+    block_hashes: HashMap<u64, Hash>,
+}
+
 pub struct EnvInstance {
     contract_storage: ContractStorage,
+    /// Storage of the contracts deployed via `test::deploy_at`.
+    deployed_storages: HashMap<Address, ContractStorage>,
+    /// Addresses of the deployed contracts being executed, the innermost
+    /// one owns the storage being accessed.
+    storage_owners: Vec<Address>,
+    /// Call data to be read by the dispatcher of a deployed contract.
+    call_data: Option<Vec<u8>>,
+    /// Data the last dispatched call finished with.
+    return_data: Option<Vec<u8>>,
     blocks: Vec<Block>,
     exec_contexts: Vec<ExecContext>,
     events: Vec<Event>,
+    /// How deep the contract calls made by the test are nested.
+    call_depth: usize,
+    /// Index of the first event emitted by the last top-level call.
+    last_call_events: usize,
     assets_info: HashMap<String, AssetInfo>,
     fungible_asset: HashMap<String, HashMap<Address, u64>>,
     not_fungible_asset: HashMap<String, HashMap<Address, HashMap<u64, String>>>,
+    /// Host operations performed since the last reset of the report.
+    gas_report: GasReport,
+    /// Host functions which are treated as absent from the chain.
+    unavailable_exts: HashSet<String>,
+    /// Contract addresses registered to CNS by name and version.
+    cns_records: HashMap<(String, String), Address>,
+    /// Nonces of accounts set via `test::set_nonce`.
+    nonces: HashMap<Address, u64>,
+    /// Block hashes set via `test::set_block_hash`.
+    block_hashes: HashMap<u64, Hash>,
+    gas_limit: u64,
+    chain_id: u64,
+    tx_hash: Hash,
+    /// Messages logged via `debug!`, which are kept even if the state is
+    /// reverted.
+    debug_logs: Vec<String>,
+    snapshots: Vec<Snapshot>,
 }
 
 impl Default for EnvInstance {
@@ -45,17 +239,56 @@ impl Default for EnvInstance {
         let blocks = vec![Block::new(0)];
         Self {
             contract_storage: ContractStorage::new(),
+            deployed_storages: HashMap::new(),
+            storage_owners: Vec::new(),
+            call_data: None,
+            return_data: None,
             blocks,
             exec_contexts: Vec::new(),
             events: Vec::new(),
+            call_depth: 0,
+            last_call_events: 0,
             assets_info: HashMap::new(),
             fungible_asset: HashMap::new(),
             not_fungible_asset: HashMap::new(),
+            gas_report: GasReport::default(),
+            unavailable_exts: HashSet::new(),
+            cns_records: HashMap::new(),
+            nonces: HashMap::new(),
+            block_hashes: HashMap::new(),
+            gas_limit: DEFAULT_GAS_LIMIT,
+            chain_id: 1,
+            tx_hash: Hash::default(),
+            debug_logs: Vec::new(),
+            snapshots: Vec::new(),
         }
     }
 }
 
 impl EnvInstance {
+    pub fn cns_resolve(&self, name: &str, version: &str) -> Option<Address> {
+        self.cns_records
+            .get(&(name.to_owned(), version.to_owned()))
+            .copied()
+    }
+
+    fn storage(&self) -> &ContractStorage {
+        match self.storage_owners.last() {
+            Some(owner) => &self.deployed_storages[owner],
+            None => &self.contract_storage,
+        }
+    }
+
+    fn storage_mut(&mut self) -> &mut ContractStorage {
+        match self.storage_owners.last() {
+            Some(owner) => self
+                .deployed_storages
+                .get_mut(owner)
+                .expect("the contract being executed must have been deployed"),
+            None => &mut self.contract_storage,
+        }
+    }
+
     pub fn current_exec_context(&self) -> &ExecContext {
         self.exec_contexts
             .last()
@@ -74,9 +307,48 @@ impl EnvInstance {
             .expect("there must be at least one block in test environment")
     }
 
+    pub fn current_block_mut(&mut self) -> &mut Block {
+        self.blocks
+            .last_mut()
+            .expect("there must be at least one block in test environment")
+    }
+
+    fn take_snapshot(&self) -> Snapshot {
+        Snapshot {
+            contract_storage: self.contract_storage.clone(),
+            deployed_storages: self.deployed_storages.clone(),
+            blocks: self.blocks.clone(),
+            exec_contexts: self.exec_contexts.clone(),
+            events: self.events.clone(),
+            assets_info: self.assets_info.clone(),
+            fungible_asset: self.fungible_asset.clone(),
+            not_fungible_asset: self.not_fungible_asset.clone(),
+            nonces: self.nonces.clone(),
+            block_hashes: self.block_hashes.clone(),
+        }
+    }
+
+    fn restore_snapshot(&mut self, snapshot: Snapshot) {
+        self.contract_storage = snapshot.contract_storage;
+        self.deployed_storages = snapshot.deployed_storages;
+        self.blocks = snapshot.blocks;
+        self.exec_contexts = snapshot.exec_contexts;
+        self.events = snapshot.events;
+        self.last_call_events = self.last_call_events.min(self.events.len());
+        self.assets_info = snapshot.assets_info;
+        self.fungible_asset = snapshot.fungible_asset;
+        self.not_fungible_asset = snapshot.not_fungible_asset;
+        self.nonces = snapshot.nonces;
+        self.block_hashes = snapshot.block_hashes;
+    }
+
     pub fn get_events(&self) -> std::slice::Iter<Event> {
         self.events.iter()
     }
+
+    pub fn get_last_call_events(&self) -> std::slice::Iter<Event> {
+        self.events[self.last_call_events..].iter()
+    }
 }
 
 impl Env for EnvInstance {
@@ -84,22 +356,60 @@ impl Env for EnvInstance {
     where
         V: scale::Encode,
     {
-        self.contract_storage.set_storage(key, value);
+        let len = self.storage_mut().set_storage(key, value);
+        self.gas_report.record_storage_write(len);
+        self.gas_report.record_storage_write_call();
     }
 
     fn get_storage<R>(&mut self, key: &[u8]) -> Result<R>
     where
         R: scale::Decode,
     {
-        self.contract_storage.get_storage::<R>(key)
+        let len = self.storage().encoded_len(key).unwrap_or_default();
+        self.gas_report.record_storage_read(len);
+        self.storage().get_storage::<R>(key)
     }
 
     fn remove_storage(&mut self, key: &[u8]) {
-        self.contract_storage.remove_storage(key);
+        self.gas_report.record_storage_remove();
+        self.gas_report.record_storage_write_call();
+        self.storage_mut().remove_storage(key);
     }
 
-    fn get_call_data(&mut self, _: CallMode) -> Result<CallData> {
-        unimplemented!();
+    fn set_storage_multi(&mut self, writes: &Writes) {
+        for (key, value) in writes {
+            match value {
+                Some(encoded) => {
+                    let len = self.storage_mut().set_encoded(key, encoded.clone());
+                    self.gas_report.record_storage_write(len);
+                }
+                None => {
+                    self.gas_report.record_storage_remove();
+                    self.storage_mut().remove_storage(key);
+                }
+            }
+        }
+        self.gas_report.record_storage_write_call();
+    }
+
+    fn get_call_data(&mut self, mode: CallMode) -> Result<CallData> {
+        let call_data = self
+            .call_data
+            .take()
+            .ok_or(EnvError::UnableToReadCallData)?;
+        if mode == CallMode::Call {
+            #[cfg(feature = "solidity-compatible")]
+            use liquid_abi_codec::Decode;
+            #[cfg(not(feature = "solidity-compatible"))]
+            use scale::Decode;
+
+            CallData::decode(&mut call_data.as_slice()).map_err(Into::into)
+        } else {
+            Ok(CallData {
+                selector: [0x00; 4],
+                data: call_data,
+            })
+        }
     }
 
     fn get_caller(&mut self) -> Address {
@@ -114,10 +424,58 @@ impl Env for EnvInstance {
         self.current_exec_context().self_address()
     }
 
+    fn delegate_call(&mut self, _addr: &Address, _data: &[u8]) -> Result<Vec<u8>> {
+        unreachable!("delegate calls are simulated via `simulate_delegate_call`");
+    }
+
+    fn get_nonce(&mut self) -> u64 {
+        // Contracts start with nonce 1 since EIP-161.
+        let address = self.get_address();
+        self.nonces.get(&address).copied().unwrap_or(1)
+    }
+
+    fn gas_left(&mut self) -> u64 {
+        self.gas_limit
+            .saturating_sub(self.gas_report.estimated_gas())
+    }
+
+    fn gas_limit(&mut self) -> u64 {
+        self.gas_limit
+    }
+
+    fn get_chain_id(&mut self) -> u64 {
+        self.chain_id
+    }
+
+    fn get_tx_hash(&mut self) -> Hash {
+        self.tx_hash
+    }
+
+    fn get_block_hash(&mut self, number: u64) -> Hash {
+        match self.block_hashes.get(&number) {
+            Some(hash) => *hash,
+            None => liquid_primitives::hash::hash(&number.to_be_bytes()).into(),
+        }
+    }
+
+    fn finish_raw(&mut self, return_data: &[u8]) {
+        self.return_data = Some(return_data.to_vec());
+    }
+
     fn get_external_code_size(&self, _account: &Address) -> u32 {
         unimplemented!();
     }
 
+    fn debug_log(&mut self, args: core::fmt::Arguments) {
+        let message = args.to_string();
+        println!("{}", message);
+        self.debug_logs.push(message);
+    }
+
+    fn has_ext(&mut self, name: &str) -> bool {
+        !self.unavailable_exts.contains(name)
+    }
+
     fn now(&mut self) -> u64 {
         self.current_block().timestamp()
     }
@@ -130,29 +488,46 @@ impl Env for EnvInstance {
         if #[cfg(feature = "solidity-compatible")] {
             fn emit<E>(&mut self, event: E)
             where
-                E: Topics + liquid_abi_codec::Encode,
+                E: Topics + liquid_abi_codec::Encode + 'static,
             {
-                self.events.push(Event::new(event));
+                let event = Event::new(event);
+                self.gas_report.record_event(&event.topics, &event.data);
+                self.events.push(event);
             }
 
-            fn call<R>(&mut self, _addr: &Address, _data: &[u8]) -> Result<R>
+            fn emit_ref<E>(&mut self, event: E)
+            where
+                E: Topics + liquid_abi_codec::Encode + BorrowedEvent,
+            {
+                let event = Event::from_borrowed(event);
+                self.gas_report.record_event(&event.topics, &event.data);
+                self.events.push(event);
+            }
+
+            fn call<R>(
+                &mut self,
+                _addr: &Address,
+                _data: &[u8],
+                _params: &CallParams,
+            ) -> Result<R>
             where
                 R: liquid_abi_codec::Decode + liquid_abi_codec::TypeInfo,
             {
                 unimplemented!();
             }
 
-            fn finish<V>(&mut self, _: &V)
+            fn finish<V>(&mut self, return_value: &V)
             where
                 V: liquid_abi_codec::Encode,
             {
-                unimplemented!();
+                self.return_data = Some(return_value.encode());
             }
 
             fn revert<V>(&mut self, msg: &V)
             where
                 V: liquid_abi_codec::Encode,
             {
+                REVERTED.with(|reverted| reverted.set(true));
                 // Ensure that the type of `V` can only be String.
                 panic!("{}", <String as liquid_abi_codec::Decode>::decode(
                     &mut msg.encode().as_slice()
@@ -162,29 +537,46 @@ impl Env for EnvInstance {
         } else {
             fn emit<E>(&mut self, event: E)
             where
-                E: Topics + scale::Encode,
+                E: Topics + scale::Encode + 'static,
+            {
+                let event = Event::new(event);
+                self.gas_report.record_event(&event.topics, &event.data);
+                self.events.push(event);
+            }
+
+            fn emit_ref<E>(&mut self, event: E)
+            where
+                E: Topics + scale::Encode + BorrowedEvent,
             {
-                self.events.push(Event::new(event));
+                let event = Event::from_borrowed(event);
+                self.gas_report.record_event(&event.topics, &event.data);
+                self.events.push(event);
             }
 
-            fn call<R>(&mut self, _addr: &Address, _data: &[u8]) -> Result<R>
+            fn call<R>(
+                &mut self,
+                _addr: &Address,
+                _data: &[u8],
+                _params: &CallParams,
+            ) -> Result<R>
             where
                 R: scale::Decode,
             {
                 unimplemented!();
             }
 
-            fn finish<V>(&mut self, _: &V)
+            fn finish<V>(&mut self, return_value: &V)
             where
                 V: scale::Encode,
             {
-                unimplemented!();
+                self.return_data = Some(return_value.encode());
             }
 
             fn revert<V>(&mut self, msg: &V)
             where
                 V: scale::Encode,
             {
+                REVERTED.with(|reverted| reverted.set(true));
                 // Ensure that the type of `V` can only be String.
                 panic!("{}", <String as scale::Decode>::decode(
                     &mut msg.encode().as_slice()
@@ -355,6 +747,35 @@ impl Env for EnvInstance {
         }
     }
 
+    fn issue_not_fungible_assets(
+        &mut self,
+        to: &Address,
+        asset_name: &[u8],
+        uris: &[&[u8]],
+    ) -> Vec<u64> {
+        uris.iter()
+            .map(|uri| self.issue_not_fungible_asset(to, asset_name, uri))
+            .take_while(|id| *id != 0)
+            .collect()
+    }
+
+    fn transfer_assets(
+        &mut self,
+        to: &Address,
+        asset_name: &[u8],
+        amounts_or_ids: &[u64],
+        from_self: bool,
+    ) -> bool {
+        let snapshot = self.take_snapshot();
+        for amount_or_id in amounts_or_ids {
+            if !self.transfer_asset(to, asset_name, *amount_or_id, from_self) {
+                self.restore_snapshot(snapshot);
+                return false;
+            }
+        }
+        true
+    }
+
     fn get_asset_balance(&self, to: &Address, asset_name: &[u8]) -> u64 {
         let asset_name = str::from_utf8(asset_name).unwrap();
         if !self.assets_info.contains_key(asset_name) {
@@ -10,9 +10,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{EnvInstance, Event, ExecContext};
-use crate::lang_core::env::engine::OnInstance;
-use liquid_primitives::types::address::*;
+pub use super::db::{EmittedEvent, GasReport};
+
+use super::{
+    db::ContractStorage, DeployedScope, EnvInstance, Event, ExecContext, CALLEES,
+    DELEGATES, LAST_CALL_PARAMS, REVERTED,
+};
+use crate::{
+    lang_core::env::{engine::OnInstance, CallParams},
+    DispatchResult,
+};
+use liquid_primitives::types::{address::*, Hash};
 
 /// Pushes a contract execution context.
 ///
@@ -58,6 +66,70 @@ pub fn pop_execution_context() {
     })
 }
 
+/// Makes `caller` the sender of subsequent calls, until the matching
+/// [`pop_caller`].
+///
+/// Unlike [`set_caller`], the address of the contract being called is kept
+/// from the current execution context, so only the sender changes.
+pub fn push_caller(caller: Address) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let callee = instance
+            .exec_contexts
+            .last()
+            .map(ExecContext::self_address)
+            .unwrap_or_default();
+        instance
+            .exec_contexts
+            .push(ExecContext::new(caller, callee))
+    });
+}
+
+/// Restores the sender which was active before the last [`push_caller`],
+/// returning the sender being popped.
+pub fn pop_caller() -> Option<Address> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .exec_contexts
+            .pop()
+            .map(|exec_context| exec_context.caller())
+    })
+}
+
+/// Sets the number of the current block.
+pub fn set_block_number(block_number: u64) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.current_block_mut().set_block_number(block_number);
+    })
+}
+
+/// Sets the timestamp of the current block, which is what `now()` returns.
+pub fn set_block_timestamp(timestamp: u64) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.current_block_mut().set_timestamp(timestamp);
+    })
+}
+
+/// Moves to the next block.
+///
+/// The block number is increased by one while the timestamp stays unchanged,
+/// use [`set_block_timestamp`] to let time pass.
+pub fn advance_block() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let next = instance.current_block().next();
+        instance.blocks.push(next);
+    })
+}
+
+/// Sets the hash of the block `number`.
+///
+/// Blocks whose hashes are not set have the hash of their big-endian block
+/// number as their hash, so that different blocks have different hashes.
+pub fn set_block_hash(number: u64, hash: Hash) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.block_hashes.insert(number, hash);
+    })
+}
+
 /// The default accounts.
 pub struct DefaultAccounts {
     pub alice: Address,
@@ -88,3 +160,373 @@ pub fn get_events() -> Vec<Event> {
         instance.get_events().cloned().collect::<Vec<_>>()
     })
 }
+
+/// Returns the events emitted by the last contract call made by the test,
+/// including the ones emitted by nested calls.
+pub fn last_events() -> Vec<Event> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.get_last_call_events().cloned().collect::<Vec<_>>()
+    })
+}
+
+/// Asserts that the last contract call made by the test emitted an event of
+/// type `E` for which `predicate` returns `true`.
+///
+/// # Example
+///
+/// ```ignore
+/// test::assert_emitted::<SetResult>(|e| e.count == 0.into());
+/// ```
+pub fn assert_emitted<E>(predicate: impl Fn(&E) -> bool)
+where
+    E: EmittedEvent,
+{
+    let events = last_events();
+    let candidates = events
+        .iter()
+        .filter_map(|event| event.downcast::<E>())
+        .collect::<Vec<_>>();
+    if !candidates.iter().any(|event| predicate(event)) {
+        panic!(
+            "no `{}` event matching the predicate was emitted by the last call, {} \
+             event(s) of this type emitted",
+            std::any::type_name::<E>(),
+            candidates.len()
+        );
+    }
+}
+
+/// Marks the scope of a contract call, used to tell which events are emitted
+/// by the last call. Generated code enters a scope for every call of contract
+/// methods in test mode.
+#[doc(hidden)]
+pub struct CallScope(());
+
+impl CallScope {
+    pub fn enter() -> Self {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            if instance.call_depth == 0 {
+                instance.last_call_events = instance.events.len();
+            }
+            instance.call_depth += 1;
+        });
+        Self(())
+    }
+}
+
+impl Drop for CallScope {
+    fn drop(&mut self) {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            instance.call_depth = instance.call_depth.saturating_sub(1);
+        });
+    }
+}
+
+/// Registers `delegate` as the code of `addr` for delegate calls.
+///
+/// `delegate` receives the call data and returns the raw return data, it may
+/// access the contract storage as if it was the calling contract. Panics in
+/// `delegate` behave like a revert of the callee.
+pub fn register_delegate<F>(addr: Address, delegate: F)
+where
+    F: Fn(&[u8]) -> Vec<u8> + 'static,
+{
+    DELEGATES.with(|delegates| {
+        delegates
+            .borrow_mut()
+            .insert(addr, std::rc::Rc::new(delegate));
+    });
+}
+
+/// Registers `callee` as the code of `addr` for calls, which is how a test
+/// fakes a precompiled contract or any other contract without an interface.
+///
+/// `callee` receives the call data and returns the encoded return data.
+/// Panics in `callee` behave like a revert of the callee.
+pub fn register_callee<F>(addr: Address, callee: F)
+where
+    F: Fn(&[u8]) -> Vec<u8> + 'static,
+{
+    CALLEES.with(|callees| {
+        callees.borrow_mut().insert(addr, std::rc::Rc::new(callee));
+    });
+}
+
+/// Returns whether calls to `addr` are answered by a registered callee or a
+/// deployed contract, which mocked interfaces fall back to when no
+/// expectation matches.
+#[doc(hidden)]
+pub fn has_callee(addr: &Address) -> bool {
+    CALLEES.with(|callees| callees.borrow().contains_key(addr))
+}
+
+/// Contracts which can be deployed via [`deploy_at`], implemented by
+/// `#[liquid::contract]` in test mode.
+#[doc(hidden)]
+pub trait Deployable {
+    /// Dispatches the call data of the current call to the contract.
+    fn dispatch() -> DispatchResult;
+}
+
+/// Deploys the contract constructed by `constructor` at `addr`, so that calls
+/// to `addr` via interfaces execute the real methods of the contract instead
+/// of being mocked, unless an expectation of the mock matches the call.
+///
+/// The deployed contract has a storage of its own, the constructor runs with
+/// `addr` as the address of the contract, and each call is dispatched with
+/// the calling contract as the caller. Deploying another contract at `addr`
+/// discards the storage of the previous one.
+///
+/// # Example
+///
+/// ```ignore
+/// test::deploy_at(addr, || callee::Callee::new(42.into()));
+/// let callee = Callee::at(addr);
+/// assert_eq!(callee.get(), Some(42.into()));
+/// ```
+pub fn deploy_at<C, F>(addr: Address, constructor: F)
+where
+    C: Deployable,
+    F: FnOnce() -> C,
+{
+    let caller = <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .deployed_storages
+            .insert(addr, ContractStorage::new());
+        instance
+            .exec_contexts
+            .last()
+            .map(ExecContext::caller)
+            .unwrap_or_default()
+    });
+    {
+        let _scope = DeployedScope::enter(caller, addr);
+        drop(constructor());
+    }
+    register_deployed(addr, C::dispatch);
+}
+
+fn register_deployed(addr: Address, dispatch: fn() -> DispatchResult) {
+    register_callee(addr, move |data| super::call_deployed(addr, data, dispatch));
+}
+
+/// Returns the gas limit and the value of the latest call to another
+/// contract, or `None` if no call has been made since the last [`reset`].
+pub fn last_call_params() -> Option<CallParams> {
+    LAST_CALL_PARAMS.with(|last| *last.borrow())
+}
+
+/// Makes the host function `name` available or unavailable, which is how a
+/// test simulates a chain of an older version. All host functions are
+/// available by default.
+pub fn set_ext_available(name: &str, available: bool) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        if available {
+            instance.unavailable_exts.remove(name);
+        } else {
+            instance.unavailable_exts.insert(name.to_owned());
+        }
+    })
+}
+
+/// Sets the gas limit of transactions, which is 300,000,000 by default.
+///
+/// The gas left is the limit minus the gas estimated by [`gas_report`], so
+/// [`reset_gas_report`] makes all the gas available again.
+pub fn set_gas_limit(limit: u64) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.gas_limit = limit;
+    })
+}
+
+/// Sets the chain id, which is 1 by default.
+pub fn set_chain_id(chain_id: u64) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.chain_id = chain_id;
+    })
+}
+
+/// Sets the hash of the current transaction, which is all zeros by default.
+pub fn set_tx_hash(tx_hash: Hash) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.tx_hash = tx_hash;
+    })
+}
+
+/// Sets the nonce of `addr`, which is 1 for any account by default, to
+/// predict what `env::address_of_next_deploy` returns in the contract at
+/// `addr`.
+pub fn set_nonce(addr: Address, nonce: u64) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.nonces.insert(addr, nonce);
+    })
+}
+
+/// Registers `addr` to CNS as the contract named `name` of version `version`,
+/// replacing any previous registration.
+pub fn register_cns(name: &str, version: &str, addr: Address) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .cns_records
+            .insert((name.to_owned(), version.to_owned()), addr);
+    })
+}
+
+/// Returns the host operations performed since the environment was reset or
+/// [`reset_gas_report`] was called.
+///
+/// Comparing the report, or [`GasReport::estimated_gas`], against a fixed
+/// bound in tests catches regressions in storage access patterns.
+pub fn gas_report() -> GasReport {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.gas_report)
+}
+
+/// Clears the counters of [`gas_report`], e.g. after the setup of a test.
+pub fn reset_gas_report() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.gas_report = GasReport::default();
+    })
+}
+
+/// Returns the SCALE encoded value stored under `key` in the storage of the
+/// contract being executed, or `None` if nothing is stored there.
+///
+/// Only values which have been written to the storage are visible, e.g. a
+/// `storage::Mapping` writes its entries when it's flushed at the end of a
+/// call. Writes staged in an open storage batch are not visible either.
+pub fn get_storage_raw(key: &[u8]) -> Option<Vec<u8>> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.storage().get_encoded(key).map(<[u8]>::to_vec)
+    })
+}
+
+/// Same as [`get_storage_raw`], but decodes the value as `T`.
+///
+/// # Panics
+///
+/// If the value stored under `key` can not be decoded as `T`.
+pub fn read_storage<T>(key: &[u8]) -> Option<T>
+where
+    T: scale::Decode,
+{
+    get_storage_raw(key).map(|encoded| {
+        <T as scale::Decode>::decode(&mut encoded.as_slice())
+            .expect("the stored value can not be decoded as the given type")
+    })
+}
+
+/// Returns the number of keys with a value stored in the storage of the
+/// contract being executed.
+pub fn storage_len() -> usize {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.storage().key_count())
+}
+
+/// Returns the messages logged via `debug!` since the environment was reset,
+/// in the order they were logged. The messages are printed to stdout as well.
+pub fn debug_logs() -> Vec<String> {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.debug_logs.clone())
+}
+
+#[doc(hidden)]
+pub fn record_call() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.gas_report.record_call();
+    })
+}
+
+/// Returns the sender of the current execution context, or `None` when the
+/// test is not inside any call.
+#[doc(hidden)]
+pub fn current_caller() -> Option<Address> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.exec_contexts.last().map(ExecContext::caller)
+    })
+}
+
+/// Identifies a state of the off-chain environment captured by [`snapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotId(usize);
+
+/// Captures the contract storage, the recorded events, the blocks, the
+/// execution contexts, the assets and the nonces of the off-chain environment,
+/// so that they can be restored by [`revert_to`] later.
+pub fn snapshot() -> SnapshotId {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let snapshot = instance.take_snapshot();
+        instance.snapshots.push(snapshot);
+        SnapshotId(instance.snapshots.len() - 1)
+    })
+}
+
+/// Restores the state captured by `id`.
+///
+/// Snapshots taken after `id` are discarded while `id` itself stays valid,
+/// so the same state can be restored multiple times. Storage fields of
+/// contracts reload their values on next access, but references obtained
+/// from them before reverting must not be used afterwards.
+///
+/// # Panics
+///
+/// If `id` has been discarded by reverting to an earlier snapshot.
+pub fn revert_to(id: SnapshotId) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let snapshot = instance
+            .snapshots
+            .get(id.0)
+            .cloned()
+            .expect("the snapshot has been discarded");
+        instance.snapshots.truncate(id.0 + 1);
+        instance.restore_snapshot(snapshot);
+    });
+    crate::lang_core::storage::invalidate_caches();
+    crate::lang_core::env::clear_call_cache();
+}
+
+/// Discards `id` together with the snapshots taken after it, without
+/// restoring the state captured by it.
+#[cfg(feature = "fuzz")]
+pub(crate) fn discard_snapshot(id: SnapshotId) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.snapshots.truncate(id.0);
+    });
+}
+
+/// Discards all the state of the off-chain environment, including the contract
+/// storage, the execution contexts, the recorded events, the assets, the
+/// deployed contracts and the registered delegates and callees.
+pub fn reset() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        *instance = EnvInstance::default();
+    });
+    crate::lang_core::storage::reset_mutable_call_flag();
+    crate::lang_core::env::clear_call_cache();
+    crate::lang_core::env::storage_batch::clear_storage_batch();
+    DELEGATES.with(|delegates| delegates.borrow_mut().clear());
+    CALLEES.with(|callees| callees.borrow_mut().clear());
+    LAST_CALL_PARAMS.with(|last| *last.borrow_mut() = None);
+    REVERTED.with(|reverted| reverted.set(false));
+}
+
+/// Runs `f` inside a freshly initialized off-chain environment, with Alice as
+/// the caller.
+///
+/// The environment is reset again when `f` returns or panics, so that state
+/// can not leak into the next test executed on the same thread. This is what
+/// `#[liquid::test]` expands to.
+pub fn run_test<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct ResetOnDrop;
+
+    impl Drop for ResetOnDrop {
+        fn drop(&mut self) {
+            reset();
+        }
+    }
+
+    reset();
+    let _guard = ResetOnDrop;
+    set_caller(default_accounts().alice);
+    f()
+}
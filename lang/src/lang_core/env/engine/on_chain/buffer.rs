@@ -38,7 +38,7 @@ impl StaticBuffer {
 
     pub fn write_bytes(&mut self, bytes: &[u8]) {
         if self.len + bytes.len() > Self::CAPACITY {
-            panic!("static buffer overflowed");
+            fail!("static buffer overflowed");
         }
 
         let start = self.len;
@@ -47,9 +47,16 @@ impl StaticBuffer {
         self.len += bytes_len;
     }
 
+    /// Returns the whole space of the buffer regardless of its length, e.g.,
+    /// for the host to write into.
+    #[inline(always)]
+    pub fn space_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer[..]
+    }
+
     pub fn resize(&mut self, new_len: usize) {
         if new_len > Self::CAPACITY {
-            panic!("static buffer overflowed");
+            fail!("static buffer overflowed");
         }
         self.len = new_len;
     }
@@ -72,6 +79,18 @@ impl liquid_abi_codec::Output for StaticBuffer {
     }
 }
 
+/// Formatting stops with an error instead of panicking once the buffer is
+/// full, so that the formatted text is truncated.
+impl core::fmt::Write for StaticBuffer {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        if self.len + s.len() > Self::CAPACITY {
+            return Err(core::fmt::Error);
+        }
+        self.write_bytes(s.as_bytes());
+        Ok(())
+    }
+}
+
 impl<I: core::slice::SliceIndex<[u8]>> core::ops::Index<I> for StaticBuffer {
     type Output = I::Output;
 
@@ -25,6 +25,13 @@ mod sys {
             value_length: u32,
         );
 
+        #[cfg(feature = "ext-storage-multi")]
+        pub fn setStorageMulti(
+            entries_offset: u32,
+            entries_length: u32,
+            number_of_entries: u32,
+        );
+
         pub fn getStorage(key_offset: u32, key_length: u32, result_offset: u32) -> u32;
 
         pub fn getCallDataSize() -> u32;
@@ -55,11 +62,44 @@ mod sys {
 
         pub fn call(address_offset: u32, data_offset: u32, data_length: u32) -> u32;
 
+        #[cfg(feature = "ext-call-params")]
+        pub fn callWithParams(
+            address_offset: u32,
+            data_offset: u32,
+            data_length: u32,
+            gas: u64,
+            value: u64,
+        ) -> u32;
+
+        #[cfg(feature = "ext-delegate-call")]
+        pub fn delegateCall(
+            address_offset: u32,
+            data_offset: u32,
+            data_length: u32,
+        ) -> u32;
+
         pub fn getReturnDataSize() -> u32;
 
         pub fn getReturnData(result_offset: u32);
+
+        #[cfg(feature = "ext-return-data-within")]
+        pub fn getReturnDataWithin(result_offset: u32, capacity: u32) -> u32;
         pub fn getAddress(result_offset: u32);
+        #[cfg(feature = "ext-chain-info")]
+        pub fn getNonce() -> u64;
+        #[cfg(feature = "ext-chain-info")]
+        pub fn getGasLeft() -> u64;
+        #[cfg(feature = "ext-chain-info")]
+        pub fn getGasLimit() -> u64;
+        #[cfg(feature = "ext-chain-info")]
+        pub fn getChainId() -> u64;
+        #[cfg(feature = "ext-chain-info")]
+        pub fn getTxHash(result_offset: u32);
+        #[cfg(feature = "ext-chain-info")]
+        pub fn getBlockHash(number: u64, result_offset: u32);
         pub fn getExternalCodeSize(address_offset: u32) -> u32;
+        #[cfg(feature = "ext-probe")]
+        pub fn hasExt(name_offset: u32, name_length: u32) -> u32;
         pub fn registerAsset(
             asset_name_offset: u32,
             asset_name_length: u32,
@@ -89,6 +129,25 @@ mod sys {
             amount_or_id: u64,
             from_self: u32,
         ) -> u32;
+        #[cfg(feature = "ext-asset-batch")]
+        pub fn issueNotFungibleAssets(
+            address_offset: u32,
+            asset_name_offset: u32,
+            asset_name_length: u32,
+            uris_offset: u32,
+            uri_lengths_offset: u32,
+            count: u32,
+            result_offset: u32,
+        ) -> u32;
+        #[cfg(feature = "ext-asset-batch")]
+        pub fn transferAssets(
+            address_offset: u32,
+            asset_name_offset: u32,
+            asset_name_length: u32,
+            ids_offset: u32,
+            count: u32,
+            from_self: u32,
+        ) -> u32;
         pub fn getAssetBalance(
             address_offset: u32,
             asset_name_offset: u32,
@@ -121,6 +180,46 @@ mod sys {
     }
 }
 
+/// Returns whether the host function `name` may be imported by this build.
+///
+/// Host functions added after the first release of FISCO BCOS Wasm runtime
+/// are only imported if the features of `liquid_lang` enabling them are, as
+/// Wasm links all the imports when instantiating a contract, and an unknown
+/// one makes the deployment fail on chains of older versions.
+pub fn is_enabled(name: &str) -> bool {
+    match name {
+        "setStorageMulti" => cfg!(feature = "ext-storage-multi"),
+        "getReturnDataWithin" => cfg!(feature = "ext-return-data-within"),
+        "callWithParams" => cfg!(feature = "ext-call-params"),
+        "delegateCall" => cfg!(feature = "ext-delegate-call"),
+        "getNonce" | "getGasLeft" | "getGasLimit" | "getChainId" | "getTxHash"
+        | "getBlockHash" => cfg!(feature = "ext-chain-info"),
+        "issueNotFungibleAssets" | "transferAssets" => cfg!(feature = "ext-asset-batch"),
+        _ => true,
+    }
+}
+
+/// Defines the wrapper of a host function which chains of older versions lack,
+/// so that it is only imported if `feature` is enabled. Otherwise the wrapper
+/// fails, which never happens as `require_ext` reverts before it is called.
+macro_rules! optional_ext {
+    (
+        #[cfg(feature = $feature:literal)]
+        $(#[$attr:meta])*
+        pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)? $body:block
+    ) => {
+        #[cfg(feature = $feature)]
+        $(#[$attr])*
+        pub fn $name($($arg: $ty),*) $(-> $ret)? $body
+
+        #[cfg(not(feature = $feature))]
+        $(#[$attr])*
+        pub fn $name($(_: $ty),*) $(-> $ret)? {
+            fail!("the host function is disabled by features of liquid_lang")
+        }
+    };
+}
+
 pub fn set_storage(key: &[u8], encoded_value: &[u8]) {
     unsafe {
         sys::setStorage(
@@ -132,6 +231,23 @@ pub fn set_storage(key: &[u8], encoded_value: &[u8]) {
     }
 }
 
+optional_ext! {
+    #[cfg(feature = "ext-storage-multi")]
+    /// Writes `number_of_entries` entries packed in `entries`, each of which is
+    /// laid out as the length of the key, the key, the length of the value and
+    /// the value, with lengths being little-endian `u32`s. An empty value
+    /// removes the entry.
+    pub fn set_storage_multi(entries: &[u8], number_of_entries: u32) {
+        unsafe {
+            sys::setStorageMulti(
+                entries.as_ptr() as u32,
+                entries.len() as u32,
+                number_of_entries,
+            )
+        }
+    }
+}
+
 pub fn get_storage(key: &[u8], result: &mut [u8]) -> Result<u32> {
     let size = unsafe {
         sys::getStorage(
@@ -217,7 +333,7 @@ pub fn log(data: &[u8], topics: &[Hash]) {
         0 => unsafe {
             sys::log(data.as_ptr() as u32, data.len() as u32, 0, 0, 0, 0, 0);
         },
-        _ => unreachable!(),
+        _ => fail!("a log carries at most 4 topics"),
     }
 }
 
@@ -239,10 +355,63 @@ pub fn get_address(result_offset: &mut [u8]) {
     }
 }
 
+optional_ext! {
+    #[cfg(feature = "ext-chain-info")]
+    pub fn get_nonce() -> u64 {
+        unsafe { sys::getNonce() }
+    }
+}
+
+optional_ext! {
+    #[cfg(feature = "ext-chain-info")]
+    pub fn get_gas_left() -> u64 {
+        unsafe { sys::getGasLeft() }
+    }
+}
+
+optional_ext! {
+    #[cfg(feature = "ext-chain-info")]
+    pub fn get_gas_limit() -> u64 {
+        unsafe { sys::getGasLimit() }
+    }
+}
+
+optional_ext! {
+    #[cfg(feature = "ext-chain-info")]
+    pub fn get_chain_id() -> u64 {
+        unsafe { sys::getChainId() }
+    }
+}
+
+optional_ext! {
+    #[cfg(feature = "ext-chain-info")]
+    pub fn get_tx_hash(result_offset: &mut [u8]) {
+        unsafe {
+            sys::getTxHash(result_offset.as_mut_ptr() as u32);
+        }
+    }
+}
+
+optional_ext! {
+    #[cfg(feature = "ext-chain-info")]
+    pub fn get_block_hash(number: u64, result_offset: &mut [u8]) {
+        unsafe {
+            sys::getBlockHash(number, result_offset.as_mut_ptr() as u32);
+        }
+    }
+}
+
 pub fn get_external_code_size(account: &[u8]) -> u32 {
     unsafe { sys::getExternalCodeSize(account.as_ptr() as u32) }
 }
 
+optional_ext! {
+    #[cfg(feature = "ext-probe")]
+    pub fn has_ext(name: &[u8]) -> bool {
+        unsafe { !matches!(sys::hasExt(name.as_ptr() as u32, name.len() as u32), 0) }
+    }
+}
+
 pub fn get_block_timestamp() -> u64 {
     unsafe { sys::getBlockTimestamp() }
 }
@@ -261,6 +430,34 @@ pub fn call(address: &[u8], data: &[u8]) -> u32 {
     }
 }
 
+optional_ext! {
+    #[cfg(feature = "ext-call-params")]
+    pub fn call_with_params(address: &[u8], data: &[u8], gas: u64, value: u64) -> u32 {
+        unsafe {
+            sys::callWithParams(
+                address.as_ptr() as u32,
+                data.as_ptr() as u32,
+                data.len() as u32,
+                gas,
+                value,
+            )
+        }
+    }
+}
+
+optional_ext! {
+    #[cfg(feature = "ext-delegate-call")]
+    pub fn delegate_call(address: &[u8], data: &[u8]) -> u32 {
+        unsafe {
+            sys::delegateCall(
+                address.as_ptr() as u32,
+                data.as_ptr() as u32,
+                data.len() as u32,
+            )
+        }
+    }
+}
+
 pub fn get_return_data_size() -> u32 {
     unsafe { sys::getReturnDataSize() }
 }
@@ -271,6 +468,16 @@ pub fn get_return_data(result: &mut [u8]) {
     }
 }
 
+optional_ext! {
+    #[cfg(feature = "ext-return-data-within")]
+    /// Copies the return data into `result` if it fits, and returns its size.
+    pub fn get_return_data_within(result: &mut [u8]) -> u32 {
+        unsafe {
+            sys::getReturnDataWithin(result.as_mut_ptr() as u32, result.len() as u32)
+        }
+    }
+}
+
 pub fn register_asset(
     asset_name: &[u8],
     issuer: &[u8],
@@ -340,6 +547,56 @@ pub fn transfer_asset(
     }
 }
 
+optional_ext! {
+    #[cfg(feature = "ext-asset-batch")]
+    /// Issues a token for each URI, where `uris` holds all the URIs back to back
+    /// and `uri_lengths` the length of each of them. The IDs of the issued tokens
+    /// are written to `ids`, and their count is returned.
+    pub fn issue_not_fungible_assets(
+        to: &[u8],
+        asset_name: &[u8],
+        uris: &[u8],
+        uri_lengths: &[u32],
+        ids: &mut [u64],
+    ) -> u32 {
+        unsafe {
+            sys::issueNotFungibleAssets(
+                to.as_ptr() as u32,
+                asset_name.as_ptr() as u32,
+                asset_name.len() as u32,
+                uris.as_ptr() as u32,
+                uri_lengths.as_ptr() as u32,
+                uri_lengths.len() as u32,
+                ids.as_mut_ptr() as u32,
+            )
+        }
+    }
+}
+
+optional_ext! {
+    #[cfg(feature = "ext-asset-batch")]
+    pub fn transfer_assets(
+        to: &[u8],
+        asset_name: &[u8],
+        amounts_or_ids: &[u64],
+        from_self: bool,
+    ) -> bool {
+        unsafe {
+            !matches!(
+                sys::transferAssets(
+                    to.as_ptr() as u32,
+                    asset_name.as_ptr() as u32,
+                    asset_name.len() as u32,
+                    amounts_or_ids.as_ptr() as u32,
+                    amounts_or_ids.len() as u32,
+                    from_self as u32,
+                ),
+                0
+            )
+        }
+    }
+}
+
 pub fn get_asset_balance(account: &[u8], asset_name: &[u8]) -> u64 {
     unsafe {
         sys::getAssetBalance(
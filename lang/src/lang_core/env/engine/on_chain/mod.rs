@@ -14,21 +14,35 @@ mod buffer;
 pub mod ext;
 
 use self::buffer::StaticBuffer;
-use super::OnInstance;
-use crate::env::{
-    backend::Env,
-    calldata::CallData,
-    error::{EnvError, Result},
-    CallMode,
+use super::{
+    return_data::{self, ReturnData, ReturnDataExt},
+    OnInstance,
+};
+use crate::{
+    env::{
+        backend::Env,
+        calldata::CallData,
+        error::{EnvError, Result},
+        storage_batch::Writes,
+        CallMode, CallParams,
+    },
+    BorrowedEvent,
 };
 use cfg_if::cfg_if;
 use core::convert::TryInto;
 use liquid_prelude::{string::String, vec::Vec};
-use liquid_primitives::{types::address::*, Topics};
+use liquid_primitives::{
+    types::{address::*, hash::HASH_LENGTH, Hash},
+    Topics, MAX_TOPICS,
+};
 
 /// The on-chain environment
 pub struct EnvInstance {
     buffer: StaticBuffer,
+    /// Results of probing the host interface, so that every host function
+    /// is only probed once during an execution.
+    #[cfg(feature = "ext-probe")]
+    probed_exts: Vec<(String, bool)>,
 }
 
 impl OnInstance for EnvInstance {
@@ -38,13 +52,73 @@ impl OnInstance for EnvInstance {
     {
         static mut INSTANCE: EnvInstance = EnvInstance {
             buffer: StaticBuffer::new(),
+            #[cfg(feature = "ext-probe")]
+            probed_exts: Vec::new(),
         };
 
         f(unsafe { &mut INSTANCE })
     }
 }
 
+/// Calls `addr` via `callWithParams` if any control is given, whose
+/// availability is checked by the caller, otherwise via `call`.
+fn call_foreign_contract(addr: &Address, data: &[u8], params: &CallParams) -> u32 {
+    if params.is_default() {
+        ext::call(&addr.0, data)
+    } else {
+        ext::call_with_params(&addr.0, data, params.gas.unwrap_or(u64::MAX), params.value)
+    }
+}
+
+/// Reads the return data via the host interface, in one host call if the
+/// `ext-return-data-within` feature is enabled and the chain provides
+/// `getReturnDataWithin`.
+struct HostReturnData {
+    within_available: bool,
+}
+
+impl ReturnDataExt for HostReturnData {
+    fn get_return_data_size(&mut self) -> u32 {
+        ext::get_return_data_size()
+    }
+
+    fn get_return_data(&mut self, dest: &mut [u8]) {
+        ext::get_return_data(dest);
+    }
+
+    fn get_return_data_within(&mut self, dest: &mut [u8]) -> Option<u32> {
+        if self.within_available {
+            Some(ext::get_return_data_within(dest))
+        } else {
+            None
+        }
+    }
+}
+
 impl EnvInstance {
+    /// Reads the return data of the latest call, which is left in the buffer
+    /// if it fits.
+    fn read_return_data(&mut self) -> ReturnData {
+        let mut host = HostReturnData {
+            within_available: self.has_ext("getReturnDataWithin"),
+        };
+        let read = return_data::read_return_data(&mut host, self.buffer.space_mut());
+        if let ReturnData::Buffered(size) = read {
+            self.buffer.resize(size);
+        }
+        read
+    }
+
+    /// Logs the encoding of `event` left in the buffer.
+    fn log_buffer<E>(&self, event: &E)
+    where
+        E: Topics,
+    {
+        let mut topics = [Hash::default(); MAX_TOPICS];
+        let len = event.write_topics(&mut topics);
+        ext::log(&self.buffer[..self.buffer.len()], &topics[..len]);
+    }
+
     fn reset_buffer(&mut self) {
         self.buffer.clear();
     }
@@ -106,6 +180,22 @@ impl Env for EnvInstance {
         ext::set_storage(key, &[]);
     }
 
+    fn set_storage_multi(&mut self, writes: &Writes) {
+        let packed_len = writes
+            .iter()
+            .map(|(key, value)| 8 + key.len() + value.as_ref().map_or(0, Vec::len))
+            .sum();
+        let mut entries = Vec::with_capacity(packed_len);
+        for (key, value) in writes {
+            let value = value.as_deref().unwrap_or_default();
+            entries.extend_from_slice(&(key.len() as u32).to_le_bytes());
+            entries.extend_from_slice(key);
+            entries.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            entries.extend_from_slice(value);
+        }
+        ext::set_storage_multi(&entries, writes.len() as u32);
+    }
+
     fn get_call_data(&mut self, mode: CallMode) -> Result<CallData> {
         let call_data_size = ext::get_call_data_size();
         if mode == CallMode::Call {
@@ -138,18 +228,30 @@ impl Env for EnvInstance {
         if #[cfg(feature = "solidity-compatible")] {
             fn emit<Event>(&mut self, event: Event)
             where
-                Event: Topics + liquid_abi_codec::Encode,
+                Event: Topics + liquid_abi_codec::Encode + 'static,
+            {
+                self.encode_into_buffer_abi(&event);
+                self.log_buffer(&event);
+            }
+
+            fn emit_ref<Event>(&mut self, event: Event)
+            where
+                Event: Topics + liquid_abi_codec::Encode + BorrowedEvent,
             {
                 self.encode_into_buffer_abi(&event);
-                let topics = event.topics();
-                ext::log(&self.buffer[..self.buffer.len()], &topics);
+                self.log_buffer(&event);
             }
 
-            fn call<R>(&mut self, addr: &Address, data: &[u8]) -> Result<R>
+            fn call<R>(
+                &mut self,
+                addr: &Address,
+                data: &[u8],
+                params: &CallParams,
+            ) -> Result<R>
             where
                 R: liquid_abi_codec::Decode + liquid_abi_codec::TypeInfo,
             {
-                let status = ext::call(&addr.0, data);
+                let status = call_foreign_contract(addr, data, params);
                 if status != 0 {
                     return Err(EnvError::FailToCallForeignContract);
                 }
@@ -157,12 +259,18 @@ impl Env for EnvInstance {
                     // The `R` is unit type.
                     self.buffer.clear();
                     self.decode_from_buffer_abi()
+                } else if <R as liquid_abi_codec::TypeInfo>::is_dynamic() {
+                    match self.read_return_data() {
+                        ReturnData::Buffered(_) => self.decode_from_buffer_abi(),
+                        ReturnData::Allocated(data) => {
+                            liquid_abi_codec::Decode::decode(&mut data.as_slice())
+                                .map_err(Into::into)
+                        }
+                    }
                 } else {
-                    let return_data_size = if <R as liquid_abi_codec::TypeInfo>::is_dynamic() {
-                        ext::get_return_data_size()
-                    } else {
-                        <R as liquid_abi_codec::TypeInfo>::size_hint()
-                    };
+                    // The size of static types is known, so there is no need
+                    // to ask the host for it.
+                    let return_data_size = <R as liquid_abi_codec::TypeInfo>::size_hint();
                     if return_data_size <= StaticBuffer::CAPACITY as u32 {
                         if return_data_size != 0 {
                             ext::get_return_data(&mut self.buffer[..]);
@@ -197,18 +305,30 @@ impl Env for EnvInstance {
         } else {
             fn emit<Event>(&mut self, event: Event)
             where
-                Event: Topics + scale::Encode,
+                Event: Topics + scale::Encode + 'static,
+            {
+                self.encode_into_buffer_scale(&event);
+                self.log_buffer(&event);
+            }
+
+            fn emit_ref<Event>(&mut self, event: Event)
+            where
+                Event: Topics + scale::Encode + BorrowedEvent,
             {
                 self.encode_into_buffer_scale(&event);
-                let topics = event.topics();
-                ext::log(&self.buffer[..self.buffer.len()], &topics);
+                self.log_buffer(&event);
             }
 
-            fn call<R>(&mut self, addr: &Address, data: &[u8]) -> Result<R>
+            fn call<R>(
+                &mut self,
+                addr: &Address,
+                data: &[u8],
+                params: &CallParams,
+            ) -> Result<R>
             where
                 R: scale::Decode,
             {
-                let status = ext::call(&addr.0, data);
+                let status = call_foreign_contract(addr, data, params);
                 if status != 0 {
                     return Err(EnvError::FailToCallForeignContract);
                 }
@@ -217,20 +337,11 @@ impl Env for EnvInstance {
                     self.buffer.clear();
                     self.decode_from_buffer_scale()
                 } else {
-                    // TODO(#1): Optimize the performance of getting return data size
-                    let return_data_size = ext::get_return_data_size();
-                    if return_data_size <= StaticBuffer::CAPACITY as u32 {
-                        if return_data_size != 0 {
-                            ext::get_return_data(&mut self.buffer[..]);
+                    match self.read_return_data() {
+                        ReturnData::Buffered(_) => self.decode_from_buffer_scale(),
+                        ReturnData::Allocated(data) => {
+                            scale::Decode::decode(&mut data.as_slice()).map_err(Into::into)
                         }
-                        self.buffer.resize(return_data_size as usize);
-                        self.decode_from_buffer_scale()
-                    } else {
-                        let mut return_data_buffer =
-                            liquid_prelude::vec::from_elem(0u8, return_data_size as usize);
-                        ext::get_return_data(&mut return_data_buffer);
-                        scale::Decode::decode(&mut return_data_buffer.as_slice())
-                            .map_err(Into::into)
                     }
                 }
             }
@@ -277,6 +388,38 @@ impl Env for EnvInstance {
         Address::new(addr)
     }
 
+    fn get_nonce(&mut self) -> u64 {
+        ext::get_nonce()
+    }
+
+    fn gas_left(&mut self) -> u64 {
+        ext::get_gas_left()
+    }
+
+    fn gas_limit(&mut self) -> u64 {
+        ext::get_gas_limit()
+    }
+
+    fn get_chain_id(&mut self) -> u64 {
+        ext::get_chain_id()
+    }
+
+    fn get_tx_hash(&mut self) -> Hash {
+        self.buffer.resize(HASH_LENGTH);
+        ext::get_tx_hash(&mut self.buffer[..HASH_LENGTH]);
+        let mut hash = [0u8; HASH_LENGTH];
+        hash.copy_from_slice(&self.buffer[..HASH_LENGTH]);
+        Hash::from(hash)
+    }
+
+    fn get_block_hash(&mut self, number: u64) -> Hash {
+        self.buffer.resize(HASH_LENGTH);
+        ext::get_block_hash(number, &mut self.buffer[..HASH_LENGTH]);
+        let mut hash = [0u8; HASH_LENGTH];
+        hash.copy_from_slice(&self.buffer[..HASH_LENGTH]);
+        Hash::from(hash)
+    }
+
     fn now(&mut self) -> u64 {
         ext::get_block_timestamp() as u64
     }
@@ -284,9 +427,60 @@ impl Env for EnvInstance {
     fn get_block_number(&mut self) -> u64 {
         ext::get_block_number() as u64
     }
+    fn delegate_call(&mut self, addr: &Address, data: &[u8]) -> Result<Vec<u8>> {
+        let status = ext::delegate_call(&addr.0, data);
+        if status != 0 {
+            return Err(EnvError::FailToCallForeignContract);
+        }
+        match self.read_return_data() {
+            ReturnData::Buffered(_) => Ok(self.buffer[..].to_vec()),
+            ReturnData::Allocated(data) => Ok(data),
+        }
+    }
+
+    fn finish_raw(&mut self, return_data: &[u8]) {
+        ext::finish(return_data);
+    }
+
     fn get_external_code_size(&self, account: &Address) -> u32 {
         ext::get_external_code_size(&account.0)
     }
+
+    #[cfg(feature = "min-size")]
+    fn debug_log(&mut self, _args: core::fmt::Arguments) {}
+
+    #[cfg(not(feature = "min-size"))]
+    fn debug_log(&mut self, args: core::fmt::Arguments) {
+        self.buffer.clear();
+        // A message too long for the buffer is logged truncated.
+        let _ = core::fmt::write(&mut self.buffer, args);
+        let message = &self.buffer[..];
+        ext::print_mem(message.as_ptr() as u32, message.len() as u32);
+    }
+
+    #[cfg(feature = "ext-probe")]
+    fn has_ext(&mut self, name: &str) -> bool {
+        if !ext::is_enabled(name) {
+            return false;
+        }
+
+        if let Some((_, available)) =
+            self.probed_exts.iter().find(|(probed, _)| probed == name)
+        {
+            return *available;
+        }
+
+        let available = ext::has_ext(name.as_bytes());
+        self.probed_exts.push((String::from(name), available));
+        available
+    }
+
+    /// Without probing, host functions enabled by features are assumed to be
+    /// provided by the chain.
+    #[cfg(not(feature = "ext-probe"))]
+    fn has_ext(&mut self, name: &str) -> bool {
+        ext::is_enabled(name)
+    }
     fn register_asset(
         &mut self,
         asset_name: &[u8],
@@ -326,6 +520,37 @@ impl Env for EnvInstance {
         ext::transfer_asset(&to.0, asset_name, amount_or_id, from_self)
     }
 
+    fn issue_not_fungible_assets(
+        &mut self,
+        to: &Address,
+        asset_name: &[u8],
+        uris: &[&[u8]],
+    ) -> Vec<u64> {
+        let uri_lengths = uris.iter().map(|uri| uri.len() as u32).collect::<Vec<_>>();
+        let uris = uris.concat();
+        let mut ids = Vec::new();
+        ids.resize(uri_lengths.len(), 0);
+        let issued = ext::issue_not_fungible_assets(
+            &to.0,
+            asset_name,
+            &uris,
+            &uri_lengths,
+            &mut ids,
+        );
+        ids.truncate(issued as usize);
+        ids
+    }
+
+    fn transfer_assets(
+        &mut self,
+        to: &Address,
+        asset_name: &[u8],
+        amounts_or_ids: &[u64],
+        from_self: bool,
+    ) -> bool {
+        ext::transfer_assets(&to.0, asset_name, amounts_or_ids, from_self)
+    }
+
     fn get_asset_balance(&self, to: &Address, asset_name: &[u8]) -> u64 {
         ext::get_asset_balance(&to.0, asset_name)
     }
@@ -346,7 +571,8 @@ impl Env for EnvInstance {
             return String::new();
         }
         self.buffer.resize(size as usize);
-        String::from_utf8(self.buffer[..].to_vec()).unwrap()
+        String::from_utf8(self.buffer[..].to_vec())
+            .unwrap_or_else(|_| fail!("the asset info should be a valid UTF-8 string"))
     }
 
     fn get_not_fungible_asset_ids(
@@ -364,7 +590,7 @@ impl Env for EnvInstance {
                 ret.push(u64::from_le_bytes(
                     self.buffer[start..start + core::mem::size_of::<u64>()]
                         .try_into()
-                        .unwrap(),
+                        .unwrap_or_else(|_| fail!("asset ids should be 8 bytes each")),
                 ));
                 // ext::print64(*ret.last().unwrap());
                 start += core::mem::size_of::<u64>();
@@ -0,0 +1,210 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use liquid_prelude::vec::{self, Vec};
+
+/// Host functions reading the return data of the latest call to another
+/// contract. Abstracted from the chain so that the number of host calls made
+/// to read the return data can be checked off-chain.
+pub trait ReturnDataExt {
+    fn get_return_data_size(&mut self) -> u32;
+
+    /// Copies the whole return data into `dest`, which must be large enough.
+    fn get_return_data(&mut self, dest: &mut [u8]);
+
+    /// Copies the return data into `dest` if it fits, and returns the size of
+    /// the return data either way, both within a single host call. Returns
+    /// `None` if the chain doesn't provide such a host function.
+    fn get_return_data_within(&mut self, dest: &mut [u8]) -> Option<u32>;
+}
+
+/// Where the return data ends up after being read.
+#[cfg_attr(feature = "std", derive(Debug, PartialEq))]
+pub enum ReturnData {
+    /// The return data occupies the first `usize` bytes of the buffer given.
+    Buffered(usize),
+    /// The return data is too large for the buffer given.
+    Allocated(Vec<u8>),
+}
+
+/// Reads the return data into `buffer` whenever it fits.
+///
+/// Most return data fits, in which case only one host call is made if the
+/// chain can copy the return data and report its size at once. Otherwise the
+/// size has to be queried before copying.
+pub fn read_return_data<E>(ext: &mut E, buffer: &mut [u8]) -> ReturnData
+where
+    E: ReturnDataExt,
+{
+    let size = match ext.get_return_data_within(buffer) {
+        Some(size) if size as usize <= buffer.len() => {
+            return ReturnData::Buffered(size as usize)
+        }
+        Some(size) => size,
+        None => {
+            let size = ext.get_return_data_size();
+            if size as usize <= buffer.len() {
+                if size != 0 {
+                    ext.get_return_data(&mut buffer[..size as usize]);
+                }
+                return ReturnData::Buffered(size as usize);
+            }
+            size
+        }
+    };
+
+    let mut data = vec::from_elem(0u8, size as usize);
+    ext.get_return_data(&mut data);
+    ReturnData::Allocated(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts the host calls made and the bytes copied to read `data`.
+    struct CountingExt {
+        data: Vec<u8>,
+        within_available: bool,
+        host_calls: usize,
+        copied_bytes: usize,
+    }
+
+    impl CountingExt {
+        fn new(len: usize, within_available: bool) -> Self {
+            Self {
+                data: (0..len).map(|i| i as u8).collect(),
+                within_available,
+                host_calls: 0,
+                copied_bytes: 0,
+            }
+        }
+    }
+
+    impl ReturnDataExt for CountingExt {
+        fn get_return_data_size(&mut self) -> u32 {
+            self.host_calls += 1;
+            self.data.len() as u32
+        }
+
+        fn get_return_data(&mut self, dest: &mut [u8]) {
+            self.host_calls += 1;
+            self.copied_bytes += self.data.len();
+            dest[..self.data.len()].copy_from_slice(&self.data);
+        }
+
+        fn get_return_data_within(&mut self, dest: &mut [u8]) -> Option<u32> {
+            if !self.within_available {
+                return None;
+            }
+            self.host_calls += 1;
+            if self.data.len() <= dest.len() {
+                self.copied_bytes += self.data.len();
+                dest[..self.data.len()].copy_from_slice(&self.data);
+            }
+            Some(self.data.len() as u32)
+        }
+    }
+
+    fn read_with(len: usize, within_available: bool) -> (ReturnData, Vec<u8>, usize) {
+        let mut ext = CountingExt::new(len, within_available);
+        let mut buffer = [0u8; 64];
+        let read = read_return_data(&mut ext, &mut buffer);
+        let data = match &read {
+            ReturnData::Buffered(size) => buffer[..*size].to_vec(),
+            ReturnData::Allocated(data) => data.clone(),
+        };
+        assert_eq!(data, ext.data);
+        (read, data, ext.host_calls)
+    }
+
+    #[test]
+    fn read_small_return_data_in_one_host_call() {
+        let (read, _, host_calls) = read_with(32, true);
+        assert_eq!(read, ReturnData::Buffered(32));
+        assert_eq!(host_calls, 1);
+
+        let (read, _, host_calls) = read_with(32, false);
+        assert_eq!(read, ReturnData::Buffered(32));
+        assert_eq!(host_calls, 2);
+    }
+
+    #[test]
+    fn read_empty_return_data() {
+        assert_eq!(read_with(0, true).2, 1);
+        assert_eq!(read_with(0, false).2, 1);
+    }
+
+    #[test]
+    fn read_large_return_data() {
+        for within_available in [true, false].iter() {
+            let (read, data, host_calls) = read_with(100, *within_available);
+            assert_eq!(read, ReturnData::Allocated(data));
+            assert_eq!(host_calls, 2);
+        }
+    }
+
+    #[test]
+    fn count_host_calls_of_typical_returns() {
+        // The sizes of typical ABI-encoded return values: a `bool`, a `u256`,
+        // a pair of `address`es and a `u64`.
+        let sizes = [32, 32, 64, 32];
+        let count = |within_available| {
+            sizes
+                .iter()
+                .map(|size| read_with(*size, within_available).2)
+                .sum::<usize>()
+        };
+        assert_eq!(count(false), 8);
+        assert_eq!(count(true), 4);
+    }
+
+    /// Rough costs of a host call and of copying a byte across the host
+    /// interface.
+    const HOST_CALL_COST: usize = 40;
+    const COPY_BYTE_COST: usize = 3;
+
+    /// Returns the cost of reading return data of `len` bytes into a buffer of
+    /// 64 bytes, in which the return data is read `READS` times.
+    fn cost_of_reads(len: usize, within_available: bool) -> usize {
+        const READS: usize = 1000;
+
+        let mut ext = CountingExt::new(len, within_available);
+        let mut buffer = [0u8; 64];
+        for _ in 0..READS {
+            read_return_data(&mut ext, &mut buffer);
+        }
+        ext.host_calls * HOST_CALL_COST + ext.copied_bytes * COPY_BYTE_COST
+    }
+
+    #[test]
+    fn bench_buffer_copy_against_within_read() {
+        for len in [0, 1, 32, 64, 65, 256, 4096].iter() {
+            let buffer_copy = cost_of_reads(*len, false);
+            let within_read = cost_of_reads(*len, true);
+            println!(
+                "return data of {} bytes: {} by copying to the buffer, {} by reading \
+                 within the buffer",
+                len, buffer_copy, within_read
+            );
+            if *len == 0 || *len > 64 {
+                // Neither way copies anything into the buffer, or the return
+                // data has to be queried and copied separately either way.
+                assert_eq!(within_read, buffer_copy);
+            } else {
+                // The query of the size is saved, while the same bytes are
+                // copied.
+                assert_eq!(buffer_copy - within_read, 1000 * HOST_CALL_COST);
+            }
+        }
+    }
+}
@@ -14,18 +14,26 @@
 
 pub(crate) mod api;
 pub(crate) mod backend;
+pub(crate) mod call_cache;
 pub(crate) mod calldata;
 pub mod engine;
 pub mod error;
+pub(crate) mod storage_batch;
 
 pub use self::{
     api::{
-        call, emit, finish, get_address, get_asset_balance, get_call_data, get_caller,
-        get_external_code_size, get_not_fungible_asset_ids, get_not_fungible_asset_info,
-        issue_fungible_asset, issue_not_fungible_asset, now, register_asset, revert,
-        transfer_asset,
+        address_of_next_deploy, approve_asset, batch_storage_writes, burn_asset,
+        cached_call, call, call_with, cns_resolve, debug_log, delegate_call, emit,
+        emit_ref, finish, finish_raw, gas_left, gas_limit, get_address,
+        get_asset_allowance, get_asset_balance, get_block_hash, get_block_number,
+        get_burnt_asset, get_call_data, get_caller, get_chain_id, get_external_code_size,
+        get_not_fungible_asset_ids, get_not_fungible_asset_info, get_tx_hash, has_ext,
+        is_asset_registered, issue_fungible_asset, issue_not_fungible_asset,
+        issue_not_fungible_assets, now, optional_call, register_asset, require_ext,
+        revert, spend_asset_allowance, transfer_asset, transfer_assets,
     },
-    backend::CallMode,
+    backend::{CallMode, CallParams},
+    call_cache::clear_call_cache,
 };
 
 #[cfg(any(feature = "std", test))]
@@ -0,0 +1,134 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage writes staged while a batch is open.
+//!
+//! Writes are keyed by the storage key, so that only the last write to a key
+//! reaches the chain, and are committed together when the outermost batch is
+//! closed. `None` stands for the removal of the entry.
+
+use liquid_prelude::{collections::BTreeMap, vec::Vec};
+
+pub type Writes = BTreeMap<Vec<u8>, Option<Vec<u8>>>;
+
+#[derive(Default)]
+struct Batch {
+    /// How many batches are currently open.
+    depth: usize,
+    writes: Writes,
+}
+
+#[cfg(feature = "std")]
+mod imp {
+    use super::Batch;
+    use core::cell::RefCell;
+
+    thread_local! {
+        static STORAGE_BATCH: RefCell<Batch> = RefCell::new(Batch::default());
+    }
+
+    pub fn with_batch<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut Batch) -> R,
+    {
+        STORAGE_BATCH.with(|batch| f(&mut batch.borrow_mut()))
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod imp {
+    use super::Batch;
+    use lazy_static::lazy_static;
+    use spin::Mutex;
+
+    lazy_static! {
+        static ref STORAGE_BATCH: Mutex<Batch> = Mutex::new(Batch::default());
+    }
+
+    pub fn with_batch<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut Batch) -> R,
+    {
+        f(&mut STORAGE_BATCH.lock())
+    }
+}
+
+pub fn open() {
+    imp::with_batch(|batch| batch.depth += 1)
+}
+
+/// Closes the innermost batch, and returns the staged writes if it was the
+/// outermost one.
+pub fn close() -> Option<Writes> {
+    imp::with_batch(|batch| {
+        batch.depth -= 1;
+        if batch.depth == 0 {
+            Some(core::mem::take(&mut batch.writes))
+        } else {
+            None
+        }
+    })
+}
+
+/// Closes the innermost batch and drops the staged writes if it was the
+/// outermost one.
+pub fn abort() {
+    close();
+}
+
+/// Stages a write if a batch is open, and returns whether it was staged.
+pub fn stage(key: &[u8], encoded_value: Option<Vec<u8>>) -> bool {
+    imp::with_batch(|batch| {
+        if batch.depth == 0 {
+            return false;
+        }
+        batch.writes.insert(key.to_vec(), encoded_value);
+        true
+    })
+}
+
+/// Returns the write staged for `key`, if any.
+pub fn lookup(key: &[u8]) -> Option<Option<Vec<u8>>> {
+    imp::with_batch(|batch| batch.writes.get(key).cloned())
+}
+
+/// Drops all the staged writes and closes every open batch.
+pub fn clear_storage_batch() {
+    imp::with_batch(|batch| *batch = Batch::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stage_writes_in_nested_batches() {
+        clear_storage_batch();
+        assert!(!stage(b"a", Some(vec![1])));
+
+        open();
+        assert!(stage(b"a", Some(vec![1])));
+        open();
+        assert!(stage(b"a", Some(vec![2])));
+        assert!(stage(b"b", None));
+        assert_eq!(close(), None);
+        assert_eq!(lookup(b"a"), Some(Some(vec![2])));
+        assert_eq!(lookup(b"b"), Some(None));
+        assert_eq!(lookup(b"c"), None);
+
+        let writes = close().unwrap();
+        assert_eq!(writes.len(), 2);
+        assert_eq!(writes.get(&b"a".to_vec()), Some(&Some(vec![2])));
+        assert_eq!(lookup(b"a"), None);
+        assert!(!stage(b"a", Some(vec![1])));
+    }
+}
@@ -0,0 +1,86 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::precompiled::{call_precompiled, selector, CRUD_ADDRESS};
+use liquid_prelude::string::String;
+use liquid_primitives::{types::i256, Selector};
+
+const INSERT: Selector = selector("insert(string,string,string,string)");
+const UPDATE: Selector = selector("update(string,string,string,string,string)");
+const REMOVE: Selector = selector("remove(string,string,string,string)");
+const SELECT: Selector = selector("select(string,string,string,string)");
+const DESC: Selector = selector("desc(string)");
+
+/// The `CRUD` precompiled contract, which accesses tables with entries and
+/// conditions encoded as JSON strings.
+pub struct Crud;
+
+impl Crud {
+    pub fn insert(table: &str, key: &str, entry: &str) -> Option<i256> {
+        call_precompiled(
+            &CRUD_ADDRESS,
+            INSERT,
+            (
+                String::from(table),
+                String::from(key),
+                String::from(entry),
+                String::new(),
+            ),
+        )
+    }
+
+    pub fn update(table: &str, key: &str, entry: &str, condition: &str) -> Option<i256> {
+        call_precompiled(
+            &CRUD_ADDRESS,
+            UPDATE,
+            (
+                String::from(table),
+                String::from(key),
+                String::from(entry),
+                String::from(condition),
+                String::new(),
+            ),
+        )
+    }
+
+    pub fn remove(table: &str, key: &str, condition: &str) -> Option<i256> {
+        call_precompiled(
+            &CRUD_ADDRESS,
+            REMOVE,
+            (
+                String::from(table),
+                String::from(key),
+                String::from(condition),
+                String::new(),
+            ),
+        )
+    }
+
+    /// Returns the selected rows as a JSON array.
+    pub fn select(table: &str, key: &str, condition: &str) -> Option<String> {
+        call_precompiled(
+            &CRUD_ADDRESS,
+            SELECT,
+            (
+                String::from(table),
+                String::from(key),
+                String::from(condition),
+                String::new(),
+            ),
+        )
+    }
+
+    /// Returns the primary key and the comma-separated value fields of `table`.
+    pub fn desc(table: &str) -> Option<(String, String)> {
+        call_precompiled(&CRUD_ADDRESS, DESC, String::from(table))
+    }
+}
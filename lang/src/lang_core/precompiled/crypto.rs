@@ -0,0 +1,177 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Wrappers for the cryptography precompiled contracts of FISCO BCOS.
+
+use crate::lang_core::precompiled::{call_precompiled, selector};
+use liquid_prelude::string::String;
+use liquid_primitives::{
+    types::{u256, Address},
+    Selector,
+};
+
+pub const PAILLIER_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x50, 0x03,
+]);
+
+pub const GROUP_SIG_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x50, 0x04,
+]);
+
+pub const RING_SIG_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x50, 0x05,
+]);
+
+pub const CRYPTO_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x50, 0x06,
+]);
+
+const PAILLIER_ADD: Selector = selector("paillierAdd(string,string)");
+const GROUP_SIG_VERIFY: Selector =
+    selector("groupSigVerify(string,string,string,string)");
+const RING_SIG_VERIFY: Selector = selector("ringSigVerify(string,string,string)");
+const CURVE25519_VRF_VERIFY: Selector =
+    selector("curve25519VRFVerify(string,string,string)");
+
+/// Adds two paillier ciphertexts encrypted with the same public key, and
+/// returns the ciphertext of the sum.
+pub fn paillier_add(cipher1: &str, cipher2: &str) -> Option<String> {
+    call_precompiled(
+        &PAILLIER_ADDRESS,
+        PAILLIER_ADD,
+        (String::from(cipher1), String::from(cipher2)),
+    )
+}
+
+/// Verifies a group signature of `message` against the group public key
+/// `gpk_info` and the group parameters `param_info`.
+pub fn group_sig_verify(
+    signature: &str,
+    message: &str,
+    gpk_info: &str,
+    param_info: &str,
+) -> Option<bool> {
+    call_precompiled(
+        &GROUP_SIG_ADDRESS,
+        GROUP_SIG_VERIFY,
+        (
+            String::from(signature),
+            String::from(message),
+            String::from(gpk_info),
+            String::from(param_info),
+        ),
+    )
+}
+
+/// Verifies a ring signature of `message` against the ring parameters
+/// `param_info`.
+pub fn ring_sig_verify(signature: &str, message: &str, param_info: &str) -> Option<bool> {
+    call_precompiled(
+        &RING_SIG_ADDRESS,
+        RING_SIG_VERIFY,
+        (
+            String::from(signature),
+            String::from(message),
+            String::from(param_info),
+        ),
+    )
+}
+
+/// Verifies the curve25519 VRF proof `vrf_proof` of `input`, and returns the
+/// random number derived from the proof if it is valid.
+pub fn curve25519_vrf_verify(
+    input: &str,
+    vrf_public_key: &str,
+    vrf_proof: &str,
+) -> Option<u256> {
+    let (valid, random) = call_precompiled::<_, (bool, u256)>(
+        &CRYPTO_ADDRESS,
+        CURVE25519_VRF_VERIFY,
+        (
+            String::from(input),
+            String::from(vrf_public_key),
+            String::from(vrf_proof),
+        ),
+    )?;
+    if valid {
+        Some(random)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang_core::env::test;
+    use liquid_abi_codec::{Decode, Encode};
+    use std::string::ToString;
+
+    #[test]
+    fn add_paillier_ciphertexts() {
+        test::run_test(|| {
+            assert_eq!(paillier_add("a", "b"), None);
+            test::register_callee(PAILLIER_ADDRESS, |data| {
+                assert_eq!(&data[..4], &PAILLIER_ADD);
+                let (cipher1, cipher2) =
+                    <(String, String) as Decode>::decode(&mut &data[4..]).unwrap();
+                (cipher1 + &cipher2).encode()
+            });
+            assert_eq!(paillier_add("a", "b"), Some("ab".to_string()));
+        });
+    }
+
+    #[test]
+    fn verify_signatures() {
+        test::run_test(|| {
+            test::register_callee(GROUP_SIG_ADDRESS, |data| {
+                assert_eq!(&data[..4], &GROUP_SIG_VERIFY);
+                let (signature, ..) =
+                    <(String, String, String, String) as Decode>::decode(&mut &data[4..])
+                        .unwrap();
+                (signature == "valid").encode()
+            });
+            test::register_callee(RING_SIG_ADDRESS, |data| {
+                assert_eq!(&data[..4], &RING_SIG_VERIFY);
+                false.encode()
+            });
+            assert_eq!(group_sig_verify("valid", "msg", "gpk", "param"), Some(true));
+            assert_eq!(
+                group_sig_verify("invalid", "msg", "gpk", "param"),
+                Some(false)
+            );
+            assert_eq!(ring_sig_verify("valid", "msg", "param"), Some(false));
+        });
+    }
+
+    #[test]
+    fn verify_vrf_proofs() {
+        test::run_test(|| {
+            test::register_callee(CRYPTO_ADDRESS, |data| {
+                assert_eq!(&data[..4], &CURVE25519_VRF_VERIFY);
+                let (_, _, proof) =
+                    <(String, String, String) as Decode>::decode(&mut &data[4..])
+                        .unwrap();
+                (proof == "valid", u256::from(42)).encode()
+            });
+            assert_eq!(
+                curve25519_vrf_verify("input", "pk", "valid"),
+                Some(u256::from(42))
+            );
+            assert_eq!(curve25519_vrf_verify("input", "pk", "invalid"), None);
+        });
+    }
+}
@@ -0,0 +1,72 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::precompiled::{
+    call_precompiled, selector, Entry, KV_TABLE_FACTORY_ADDRESS,
+};
+use liquid_prelude::string::String;
+use liquid_primitives::{
+    types::{i256, Address},
+    Selector,
+};
+
+const OPEN_TABLE: Selector = selector("openTable(string)");
+const CREATE_TABLE: Selector = selector("createTable(string,string,string)");
+
+const GET: Selector = selector("get(string)");
+const SET: Selector = selector("set(string,address)");
+const NEW_ENTRY: Selector = selector("newEntry()");
+
+/// The `KVTableFactory` precompiled contract, which creates and opens tables
+/// whose rows are accessed by primary key.
+pub struct KvTableFactory;
+
+impl KvTableFactory {
+    pub fn open_table(name: &str) -> Option<KvTable> {
+        call_precompiled(&KV_TABLE_FACTORY_ADDRESS, OPEN_TABLE, String::from(name))
+            .map(KvTable)
+    }
+
+    pub fn create_table(name: &str, key: &str, fields: &str) -> Option<i256> {
+        call_precompiled(
+            &KV_TABLE_FACTORY_ADDRESS,
+            CREATE_TABLE,
+            (String::from(name), String::from(key), String::from(fields)),
+        )
+    }
+}
+
+/// A table opened by `KvTableFactory`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct KvTable(pub Address);
+
+impl KvTable {
+    /// Returns the row of `key`, or `None` if there is no such row.
+    pub fn get(&self, key: &str) -> Option<Entry> {
+        let (found, entry) =
+            call_precompiled::<_, (bool, Address)>(&self.0, GET, String::from(key))?;
+        if found {
+            Some(Entry(entry))
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&self, key: &str, entry: &Entry) -> Option<i256> {
+        call_precompiled(&self.0, SET, (String::from(key), entry.0))
+    }
+
+    pub fn new_entry(&self) -> Option<Entry> {
+        call_precompiled(&self.0, NEW_ENTRY, ()).map(Entry)
+    }
+}
@@ -11,17 +11,61 @@
 // limitations under the License.
 
 mod cns;
+mod crud;
+pub mod crypto;
+mod kv_table;
+mod table;
 
+use crate::lang_core::env::call;
 use cfg_if::cfg_if;
 pub use cns::Cns;
+pub use crud::Crud;
+pub use kv_table::{KvTable, KvTableFactory};
 use liquid_prelude::vec::{self, Vec};
-use liquid_primitives::types::Address;
+use liquid_primitives::{types::Address, Selector};
+pub use table::{
+    Condition, ConditionValue, Entries, Entry, EntryValue, Table, TableFactory,
+};
+
+pub const TABLE_FACTORY_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x10, 0x01,
+]);
+
+pub const CRUD_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x10, 0x02,
+]);
 
 pub const CNS_ADDRESS: Address = Address::new([
     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
     0x00, 0x00, 0x00, 0x00, 0x10, 0x04,
 ]);
 
+pub const KV_TABLE_FACTORY_ADDRESS: Address = Address::new([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x10, 0x10,
+]);
+
+/// Computes the selector of a precompiled method from its solidity signature,
+/// with the hash algorithm of the chain.
+const fn selector(signature: &str) -> Selector {
+    liquid_primitives::selector(signature.as_bytes())
+}
+
+/// Calls a method of the precompiled contract at `addr`. Precompiled contracts
+/// always speak solidity ABI, whichever codec the contract itself uses.
+fn call_precompiled<A, R>(addr: &Address, selector: Selector, args: A) -> Option<R>
+where
+    A: liquid_abi_codec::Encode,
+    R: liquid_abi_codec::Decode,
+{
+    let mut input_data = selector.to_vec();
+    input_data.extend(&args.encode());
+    let ret = call::<ReturnDataWrapper>(addr, &input_data).ok()?;
+    <R as liquid_abi_codec::Decode>::decode(&mut ret.data.as_slice()).ok()
+}
+
 struct ReturnDataWrapper {
     pub data: Vec<u8>,
 }
@@ -61,3 +105,153 @@ cfg_if! {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang_core::env::test;
+    use liquid_abi_codec::{Decode, Encode};
+    use liquid_primitives::types::i256;
+    use std::{
+        cell::RefCell,
+        collections::HashMap,
+        rc::Rc,
+        string::{String, ToString},
+    };
+
+    #[test]
+    fn compute_selectors() {
+        if cfg!(feature = "gm") {
+            assert_eq!(
+                selector("insert(string,string,string,string)"),
+                [0xb8, 0xea, 0xa0, 0x8d]
+            );
+            assert_eq!(
+                selector("getContractAddress(string,string)"),
+                [0xf1, 0xa3, 0x1b, 0xfa]
+            );
+        } else {
+            assert_eq!(
+                selector("insert(string,string,string,string)"),
+                [0xa2, 0x16, 0x46, 0x4b]
+            );
+            assert_eq!(
+                selector("getContractAddress(string,string)"),
+                [0xf8, 0x5f, 0x81, 0x26]
+            );
+        }
+    }
+
+    fn decode<T: Decode>(data: &[u8]) -> T {
+        <T as Decode>::decode(&mut &data[4..]).unwrap()
+    }
+
+    fn register_entry(addr: Address) {
+        let fields = Rc::new(RefCell::new(HashMap::<String, String>::new()));
+        test::register_callee(addr, move |data| {
+            let sel = [data[0], data[1], data[2], data[3]];
+            if sel == selector("getString(string)") {
+                let field = decode::<String>(data);
+                fields
+                    .borrow()
+                    .get(&field)
+                    .cloned()
+                    .unwrap_or_default()
+                    .encode()
+            } else if sel == selector("set(string,string)") {
+                let (field, value) = decode::<(String, String)>(data);
+                fields.borrow_mut().insert(field, value);
+                Vec::new()
+            } else {
+                panic!("unexpected call to entry")
+            }
+        });
+    }
+
+    fn register_kv_table(addr: Address) {
+        let rows = Rc::new(RefCell::new(HashMap::<String, Address>::new()));
+        let next_entry = RefCell::new(0x20u8);
+        test::register_callee(addr, move |data| {
+            let sel = [data[0], data[1], data[2], data[3]];
+            if sel == selector("get(string)") {
+                let key = decode::<String>(data);
+                match rows.borrow().get(&key) {
+                    Some(entry) => (true, *entry).encode(),
+                    None => (false, Address::zero()).encode(),
+                }
+            } else if sel == selector("set(string,address)") {
+                let (key, entry) = decode::<(String, Address)>(data);
+                rows.borrow_mut().insert(key, entry);
+                i256::from(1).encode()
+            } else if sel == selector("newEntry()") {
+                let mut next_entry = next_entry.borrow_mut();
+                let entry = Address::new([*next_entry; 20]);
+                *next_entry += 1;
+                register_entry(entry);
+                entry.encode()
+            } else {
+                panic!("unexpected call to kv table")
+            }
+        });
+    }
+
+    #[test]
+    fn access_kv_table() {
+        test::run_test(|| {
+            let table_addr = Address::new([0x10; 20]);
+            test::register_callee(KV_TABLE_FACTORY_ADDRESS, move |data| {
+                let sel = [data[0], data[1], data[2], data[3]];
+                if sel == selector("openTable(string)") {
+                    assert_eq!(decode::<String>(data), "t_test");
+                    table_addr.encode()
+                } else {
+                    let (name, key, fields) = decode::<(String, String, String)>(data);
+                    assert_eq!(
+                        (name.as_str(), key.as_str(), fields.as_str()),
+                        ("t_test", "id", "name,price")
+                    );
+                    i256::from(0).encode()
+                }
+            });
+            register_kv_table(table_addr);
+
+            assert_eq!(
+                KvTableFactory::create_table("t_test", "id", "name,price"),
+                Some(i256::from(0))
+            );
+            let table = KvTableFactory::open_table("t_test").unwrap();
+            assert_eq!(table, KvTable(table_addr));
+            assert_eq!(table.get("apple"), None);
+
+            let entry = table.new_entry().unwrap();
+            assert_eq!(entry.set("name", "apple".to_string()), Some(()));
+            assert_eq!(table.set("apple", &entry), Some(i256::from(1)));
+            let entry = table.get("apple").unwrap();
+            assert_eq!(entry.get_string("name"), Some("apple".to_string()));
+            assert_eq!(entry.get_string("price"), Some(String::new()));
+        });
+    }
+
+    #[test]
+    fn fail_to_call_missing_precompiled() {
+        test::run_test(|| {
+            assert_eq!(TableFactory::open_table("t_test"), None);
+            assert_eq!(Crud::desc("t_test"), None);
+        });
+    }
+
+    #[test]
+    fn call_crud() {
+        test::run_test(|| {
+            test::register_callee(CRUD_ADDRESS, |data| {
+                assert_eq!(&data[..4], &selector("desc(string)"));
+                assert_eq!(decode::<String>(data), "t_test");
+                ("id".to_string(), "name,price".to_string()).encode()
+            });
+            assert_eq!(
+                Crud::desc("t_test"),
+                Some(("id".to_string(), "name,price".to_string()))
+            );
+        });
+    }
+}
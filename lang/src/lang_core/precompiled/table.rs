@@ -0,0 +1,221 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::precompiled::{call_precompiled, selector, TABLE_FACTORY_ADDRESS};
+use liquid_abi_codec::MediateEncode;
+use liquid_prelude::string::String;
+use liquid_primitives::{
+    types::{i256, u256, Address},
+    Selector,
+};
+
+const OPEN_TABLE: Selector = selector("openTable(string)");
+const CREATE_TABLE: Selector = selector("createTable(string,string,string)");
+
+const SELECT: Selector = selector("select(string,address)");
+const INSERT: Selector = selector("insert(string,address)");
+const UPDATE: Selector = selector("update(string,address,address)");
+const REMOVE: Selector = selector("remove(string,address)");
+const NEW_ENTRY: Selector = selector("newEntry()");
+const NEW_CONDITION: Selector = selector("newCondition()");
+
+const ENTRIES_GET: Selector = selector("get(int256)");
+const ENTRIES_SIZE: Selector = selector("size()");
+
+const GET_INT: Selector = selector("getInt(string)");
+const GET_UINT: Selector = selector("getUInt(string)");
+const GET_ADDRESS: Selector = selector("getAddress(string)");
+const GET_STRING: Selector = selector("getString(string)");
+
+const GT: Selector = selector("GT(string,int256)");
+const GE: Selector = selector("GE(string,int256)");
+const LT: Selector = selector("LT(string,int256)");
+const LE: Selector = selector("LE(string,int256)");
+const LIMIT: Selector = selector("limit(int256)");
+const LIMIT_RANGE: Selector = selector("limit(int256,int256)");
+
+/// The `TableFactory` precompiled contract, which creates and opens tables
+/// whose rows are selected by conditions.
+pub struct TableFactory;
+
+impl TableFactory {
+    pub fn open_table(name: &str) -> Option<Table> {
+        call_precompiled(&TABLE_FACTORY_ADDRESS, OPEN_TABLE, String::from(name))
+            .map(Table)
+    }
+
+    pub fn create_table(name: &str, key: &str, fields: &str) -> Option<i256> {
+        call_precompiled(
+            &TABLE_FACTORY_ADDRESS,
+            CREATE_TABLE,
+            (String::from(name), String::from(key), String::from(fields)),
+        )
+    }
+}
+
+/// A table opened by `TableFactory`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Table(pub Address);
+
+impl Table {
+    pub fn select(&self, key: &str, condition: &Condition) -> Option<Entries> {
+        call_precompiled(&self.0, SELECT, (String::from(key), condition.0)).map(Entries)
+    }
+
+    pub fn insert(&self, key: &str, entry: &Entry) -> Option<i256> {
+        call_precompiled(&self.0, INSERT, (String::from(key), entry.0))
+    }
+
+    pub fn update(
+        &self,
+        key: &str,
+        entry: &Entry,
+        condition: &Condition,
+    ) -> Option<i256> {
+        call_precompiled(&self.0, UPDATE, (String::from(key), entry.0, condition.0))
+    }
+
+    pub fn remove(&self, key: &str, condition: &Condition) -> Option<i256> {
+        call_precompiled(&self.0, REMOVE, (String::from(key), condition.0))
+    }
+
+    pub fn new_entry(&self) -> Option<Entry> {
+        call_precompiled(&self.0, NEW_ENTRY, ()).map(Entry)
+    }
+
+    pub fn new_condition(&self) -> Option<Condition> {
+        call_precompiled(&self.0, NEW_CONDITION, ()).map(Condition)
+    }
+}
+
+/// The rows returned by `Table::select`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Entries(pub Address);
+
+impl Entries {
+    pub fn get(&self, index: i256) -> Option<Entry> {
+        call_precompiled(&self.0, ENTRIES_GET, index).map(Entry)
+    }
+
+    pub fn size(&self) -> Option<i256> {
+        call_precompiled(&self.0, ENTRIES_SIZE, ())
+    }
+}
+
+/// Values that can be stored in the fields of an `Entry`.
+pub trait EntryValue: MediateEncode {
+    #[doc(hidden)]
+    const SET: Selector;
+}
+
+impl EntryValue for i256 {
+    const SET: Selector = selector("set(string,int256)");
+}
+
+impl EntryValue for u256 {
+    const SET: Selector = selector("set(string,uint256)");
+}
+
+impl EntryValue for Address {
+    const SET: Selector = selector("set(string,address)");
+}
+
+impl EntryValue for String {
+    const SET: Selector = selector("set(string,string)");
+}
+
+/// A row of a table, shared by `Table` and `KvTable`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Entry(pub Address);
+
+impl Entry {
+    pub fn get_int(&self, field: &str) -> Option<i256> {
+        call_precompiled(&self.0, GET_INT, String::from(field))
+    }
+
+    pub fn get_uint(&self, field: &str) -> Option<u256> {
+        call_precompiled(&self.0, GET_UINT, String::from(field))
+    }
+
+    pub fn get_address(&self, field: &str) -> Option<Address> {
+        call_precompiled(&self.0, GET_ADDRESS, String::from(field))
+    }
+
+    pub fn get_string(&self, field: &str) -> Option<String> {
+        call_precompiled(&self.0, GET_STRING, String::from(field))
+    }
+
+    pub fn set<V: EntryValue>(&self, field: &str, value: V) -> Option<()> {
+        call_precompiled(&self.0, V::SET, (String::from(field), value))
+    }
+}
+
+/// Values that a field can be compared with for equality in a `Condition`.
+pub trait ConditionValue: MediateEncode {
+    #[doc(hidden)]
+    const EQ: Selector;
+    #[doc(hidden)]
+    const NE: Selector;
+}
+
+impl ConditionValue for i256 {
+    const EQ: Selector = selector("EQ(string,int256)");
+    const NE: Selector = selector("NE(string,int256)");
+}
+
+impl ConditionValue for String {
+    const EQ: Selector = selector("EQ(string,string)");
+    const NE: Selector = selector("NE(string,string)");
+}
+
+/// The condition used to select, update or remove rows of a `Table`. Every
+/// method narrows the condition further.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Condition(pub Address);
+
+impl Condition {
+    pub fn eq<V: ConditionValue>(&self, field: &str, value: V) -> Option<()> {
+        call_precompiled(&self.0, V::EQ, (String::from(field), value))
+    }
+
+    pub fn ne<V: ConditionValue>(&self, field: &str, value: V) -> Option<()> {
+        call_precompiled(&self.0, V::NE, (String::from(field), value))
+    }
+
+    pub fn gt(&self, field: &str, value: i256) -> Option<()> {
+        call_precompiled(&self.0, GT, (String::from(field), value))
+    }
+
+    pub fn ge(&self, field: &str, value: i256) -> Option<()> {
+        call_precompiled(&self.0, GE, (String::from(field), value))
+    }
+
+    pub fn lt(&self, field: &str, value: i256) -> Option<()> {
+        call_precompiled(&self.0, LT, (String::from(field), value))
+    }
+
+    pub fn le(&self, field: &str, value: i256) -> Option<()> {
+        call_precompiled(&self.0, LE, (String::from(field), value))
+    }
+
+    pub fn limit(&self, count: i256) -> Option<()> {
+        call_precompiled(&self.0, LIMIT, count)
+    }
+
+    pub fn limit_range(&self, offset: i256, count: i256) -> Option<()> {
+        call_precompiled(&self.0, LIMIT_RANGE, (offset, count))
+    }
+}
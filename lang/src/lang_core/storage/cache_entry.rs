@@ -48,9 +48,12 @@ impl<T> CacheEntry<T> {
     /// Returns a mutable reference to the synchronized cached value.
     ///
     /// This also marks the cache entry as being dirty since
-    /// the callee could potentially mutate the value.
+    /// the callee could potentially mutate the value. An empty entry
+    /// stays clean because there is nothing to mutate.
     pub fn get_mut(&mut self) -> Option<&mut T> {
-        self.mark_dirty();
+        if self.cell_val.is_some() {
+            self.mark_dirty();
+        }
         (&mut *self.cell_val).as_mut()
     }
 
@@ -71,7 +74,15 @@ impl<T> CacheEntry<T> {
     }
 
     /// Takes the value in the cache.
+    ///
+    /// # Note
+    ///
+    /// Marks the cache value as dirty only if there was a value to take.
     pub fn take(&mut self) -> Option<T> {
-        self.put(None)
+        let old_value = self.cell_val.take();
+        if old_value.is_some() {
+            self.mark_dirty();
+        }
+        old_value
     }
 }
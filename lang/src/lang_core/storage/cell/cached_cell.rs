@@ -62,14 +62,14 @@ impl<T> Cache<T> {
 
     pub fn get(&self) -> Option<&T> {
         match self {
-            Cache::Desync => panic!("Error: tried to get the value from a desync cache"),
+            Cache::Desync => fail!("Error: tried to get the value from a desync cache"),
             Cache::Sync(entry) => entry.get(),
         }
     }
 
     pub fn get_mut(&mut self) -> Option<&mut T> {
         match self {
-            Cache::Desync => panic!("Error: tried to get the value from a desync cache"),
+            Cache::Desync => fail!("Error: tried to get the value from a desync cache"),
             Cache::Sync(sync_entry) => sync_entry.get_mut(),
         }
     }
@@ -79,6 +79,8 @@ impl<T> Cache<T> {
 pub struct CachedCell<T> {
     cell: TypedCell<T>,
     cache: RefCell<Cache<T>>,
+    #[cfg(feature = "std")]
+    epoch: core::cell::Cell<u64>,
 }
 
 impl<T> CachedCell<T> {
@@ -86,6 +88,21 @@ impl<T> CachedCell<T> {
         Self {
             cell: TypedCell::new(key),
             cache: Default::default(),
+            #[cfg(feature = "std")]
+            epoch: core::cell::Cell::new(crate::lang_core::storage::cache_epoch()),
+        }
+    }
+
+    /// Drops the cache if the contract storage has been rolled back since
+    /// the cache was filled.
+    fn check_epoch(&self) {
+        #[cfg(feature = "std")]
+        {
+            let current = crate::lang_core::storage::cache_epoch();
+            if self.epoch.get() != current {
+                *self.cache.borrow_mut() = Cache::Desync;
+                self.epoch.set(current);
+            }
         }
     }
 }
@@ -95,6 +112,7 @@ where
     T: scale::Encode,
 {
     fn flush(&mut self) {
+        self.check_epoch();
         if self.cache.borrow().is_dirty() {
             if let Some(val) = self.cache.borrow_mut().get() {
                 self.cell.store(val);
@@ -114,6 +132,7 @@ where
     }
 
     fn load(&self) {
+        self.check_epoch();
         if !self.cache.borrow().is_synced() {
             let loaded = self.cell.load();
             self.cache.borrow_mut().update(loaded);
@@ -126,6 +145,7 @@ where
     T: scale::Encode,
 {
     pub fn set(&mut self, new_val: T) {
+        self.check_epoch();
         self.cache.borrow_mut().update(Some(new_val));
         self.cache.borrow_mut().mark_dirty();
     }
@@ -184,4 +204,21 @@ mod tests {
         // Cache has been flushed before
         assert_eq!(dummy_cell::<i32>().get(), Some(&5));
     }
+
+    #[test]
+    fn flush_only_when_dirty() {
+        let mut cell_1 = dummy_cell::<i32>();
+        cell_1.set(5);
+        cell_1.flush();
+
+        let mut cell_2 = dummy_cell::<i32>();
+        cell_2.set(10);
+        cell_2.flush();
+
+        // `cell_1` is clean after the first flush, reading it must not
+        // write the stale cached value back.
+        assert_eq!(cell_1.get(), Some(&5));
+        cell_1.flush();
+        assert_eq!(dummy_cell::<i32>().get(), Some(&10));
+    }
 }
@@ -19,10 +19,26 @@ use scale::{Codec, Decode, Encode};
 pub struct CachedChunk<T> {
     chunk: TypedChunk<T>,
     cache: RefCell<BTreeMap<Vec<u8>, CacheEntry<T>>>,
+    #[cfg(feature = "std")]
+    epoch: core::cell::Cell<u64>,
 }
 
 impl<T> CachedChunk<T> {
+    /// Drops the cache if the contract storage has been rolled back since
+    /// the cache was filled.
+    fn check_epoch(&self) {
+        #[cfg(feature = "std")]
+        {
+            let current = crate::lang_core::storage::cache_epoch();
+            if self.epoch.get() != current {
+                self.cache.borrow_mut().clear();
+                self.epoch.set(current);
+            }
+        }
+    }
+
     pub fn insert_cache(&self, key: &[u8], val: Option<T>, is_dirty: bool) {
+        self.check_epoch();
         let mut entry = CacheEntry::<T>::new(val);
         if is_dirty {
             entry.mark_dirty();
@@ -36,11 +52,17 @@ impl<T> CachedChunk<T> {
         Self {
             chunk: TypedChunk::<T>::new(key),
             cache: Default::default(),
+            #[cfg(feature = "std")]
+            epoch: core::cell::Cell::new(crate::lang_core::storage::cache_epoch()),
         }
     }
+
+    /// Removes the value at `index`.
+    ///
+    /// The removal is only recorded in the cache and is written back to the
+    /// contract storage upon the next flush.
     pub fn remove(&mut self, index: &[u8]) {
-        self.chunk.remove(index);
-        self.cache.borrow_mut().remove(index.borrow());
+        self.insert_cache(index, None, true);
     }
 }
 
@@ -49,10 +71,12 @@ where
     T: Decode,
 {
     fn get_cache_entry(&self, index: &[u8]) -> Option<&CacheEntry<T>> {
+        self.check_epoch();
         unsafe { (*self.cache.as_ptr()).get(index.borrow()) }
     }
 
     fn get_cache_entry_mut(&self, index: &[u8]) -> Option<&mut CacheEntry<T>> {
+        self.check_epoch();
         unsafe { (*self.cache.as_ptr()).get_mut(index.borrow()) }
     }
 
@@ -136,11 +160,15 @@ where
     T: Encode,
 {
     fn flush(&mut self) {
-        for (index, entry) in &*self.cache.borrow() {
+        self.check_epoch();
+        let chunk = &mut self.chunk;
+        for (index, entry) in self.cache.get_mut().iter_mut() {
             if entry.is_dirty() {
-                if let Some(new_val) = entry.get() {
-                    self.chunk.store(index, new_val);
+                match entry.get() {
+                    Some(new_val) => chunk.store(index, new_val),
+                    None => chunk.remove(index),
                 }
+                entry.mark_clean();
             }
         }
     }
@@ -178,4 +206,36 @@ mod tests {
         // Cache has been flushed before
         assert_eq!(dummy_chunk().get(b"Alice"), Some(&5));
     }
+
+    #[test]
+    fn deferred_remove() {
+        let mut chunk_1 = dummy_chunk();
+        chunk_1.set(b"Alice", 5);
+        chunk_1.flush();
+
+        chunk_1.remove(b"Alice");
+        assert_eq!(chunk_1.get(b"Alice"), None);
+        // Removal has not been written back yet.
+        assert_eq!(dummy_chunk().get(b"Alice"), Some(&5));
+        chunk_1.flush();
+        assert_eq!(dummy_chunk().get(b"Alice"), None);
+    }
+
+    #[test]
+    fn flush_only_dirty_entries() {
+        let mut chunk_1 = dummy_chunk();
+        chunk_1.set(b"Alice", 5);
+        chunk_1.flush();
+
+        let mut chunk_2 = dummy_chunk();
+        chunk_2.set(b"Alice", 10);
+        chunk_2.flush();
+
+        // Reading or touching an absent entry must not make `chunk_1` write
+        // its stale value back.
+        assert_eq!(chunk_1.get(b"Alice"), Some(&5));
+        assert_eq!(chunk_1.get_mut(b"Bob"), None);
+        chunk_1.flush();
+        assert_eq!(dummy_chunk().get(b"Alice"), Some(&10));
+    }
 }
@@ -49,6 +49,7 @@ impl<T> TypedChunk<T> {
     pub fn remove<Q: AsRef<[u8]>>(&mut self, index: Q) {
         self.prepare_inner_key(index);
         env::api::remove_storage(self.key_buf.borrow().as_slice());
+        self.key_buf.borrow_mut().truncate(self.prefix_len);
     }
 }
 
@@ -0,0 +1,162 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::storage::{
+    collections::vec::{Iter, Vec},
+    Bind, Flush, You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+};
+use cfg_if::cfg_if;
+use scale::{Codec, Encode};
+
+/// A priority queue living in contract storage, implemented as a max-heap
+/// over a [`Vec`], so that the greatest element is always at index 0.
+///
+/// Pushing and popping only touch the `O(log n)` elements on the path
+/// between the root and a leaf.
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct BinaryHeap<T> {
+    elems: Vec<T>,
+}
+
+impl<T> Bind for BinaryHeap<T> {
+    fn bind_with(key: &[u8]) -> Self {
+        Self {
+            elems: Vec::<T>::bind_with(key),
+        }
+    }
+}
+
+impl<T> Flush for BinaryHeap<T>
+where
+    T: Encode,
+{
+    fn flush(&mut self) {
+        self.elems.flush();
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "contract")] {
+        use crate::lang_core::storage::Getter;
+        use liquid_primitives::__Liquid_Getter_Index_Placeholder;
+
+        impl<T> Getter for BinaryHeap<T>
+        where
+            T: Codec + Ord + Clone,
+        {
+            type Index = __Liquid_Getter_Index_Placeholder;
+            type Output = T;
+
+            fn getter_impl(&self, _: Self::Index) -> Self::Output {
+                self.peek()
+                    .unwrap_or_else(|| {
+                        fail!(
+                            "[liquid_lang::BinaryHeap::getter] Error: expected the heap to \
+                             be non-empty"
+                        )
+                    })
+                    .clone()
+            }
+        }
+    }
+}
+
+impl<T> BinaryHeap<T> {
+    pub fn initialize(&mut self) {
+        self.elems.initialize();
+    }
+
+    pub fn len(&self) -> u32 {
+        self.elems.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elems.is_empty()
+    }
+
+    /// Returns an iterator visiting all the elements in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.elems.iter()
+    }
+}
+
+impl<T> BinaryHeap<T>
+where
+    T: Codec + Ord,
+{
+    /// Returns a reference to the greatest element.
+    pub fn peek(&self) -> Option<&T> {
+        self.elems.get(0)
+    }
+
+    /// Pushes an element onto the heap.
+    pub fn push(&mut self, val: T) {
+        self.elems.push(val);
+        self.sift_up(self.len() - 1);
+    }
+
+    /// Removes the greatest element and returns it.
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.len().checked_sub(1)?;
+        self.elems.swap(0, last);
+        let ret = self.elems.pop();
+        if !self.is_empty() {
+            self.sift_down(0);
+        }
+        ret
+    }
+
+    fn sift_up(&mut self, mut n: u32) {
+        while n > 0 {
+            let parent = (n - 1) / 2;
+            if self.elems[n] <= self.elems[parent] {
+                break;
+            }
+            self.elems.swap(n, parent);
+            n = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut n: u32) {
+        let len = self.len();
+        loop {
+            let mut child = match n.checked_mul(2).and_then(|i| i.checked_add(1)) {
+                Some(child) if child < len => child,
+                _ => break,
+            };
+            if child + 1 < len && self.elems[child + 1] > self.elems[child] {
+                child += 1;
+            }
+            if self.elems[n] >= self.elems[child] {
+                break;
+            }
+            self.elems.swap(n, child);
+            n = child;
+        }
+    }
+}
+
+impl<T> Extend<T> for BinaryHeap<T>
+where
+    T: Codec + Ord,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for i in iter {
+            self.push(i);
+        }
+    }
+}
+
+impl<T> You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for BinaryHeap<T> {}
@@ -0,0 +1,78 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::BinaryHeap;
+use crate::lang_core::storage::traits::{Bind, Flush};
+
+fn new_empty_heap() -> BinaryHeap<u32> {
+    let mut heap = BinaryHeap::<u32>::bind_with(b"heap");
+    heap.initialize();
+    heap
+}
+
+#[test]
+fn empty() {
+    let mut heap = new_empty_heap();
+    assert_eq!(heap.len(), 0);
+    assert!(heap.is_empty());
+    assert_eq!(heap.peek(), None);
+    assert_eq!(heap.pop(), None);
+}
+
+#[test]
+fn pop_in_descending_order() {
+    let mut heap = new_empty_heap();
+    heap.extend(vec![5, 1, 8, 3, 9, 2, 8, 0]);
+    assert_eq!(heap.len(), 8);
+    assert_eq!(heap.peek(), Some(&9));
+
+    let mut popped = Vec::new();
+    while let Some(val) = heap.pop() {
+        popped.push(val);
+    }
+    assert_eq!(popped, vec![9, 8, 8, 5, 3, 2, 1, 0]);
+}
+
+#[test]
+fn behave_like_std_heap() {
+    let mut heap = new_empty_heap();
+    let mut expected = std::collections::BinaryHeap::new();
+    // A simple LCG, so that the sequence of operations is reproducible.
+    let mut seed = 42u32;
+    for _ in 0..200 {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        if seed % 3 == 0 {
+            assert_eq!(heap.pop(), expected.pop());
+        } else {
+            heap.push(seed % 100);
+            expected.push(seed % 100);
+        }
+        assert_eq!(heap.peek(), expected.peek());
+        assert_eq!(heap.len() as usize, expected.len());
+    }
+    let mut sorted = heap.iter().copied().collect::<Vec<_>>();
+    sorted.sort_unstable();
+    assert_eq!(sorted, expected.into_sorted_vec());
+}
+
+#[test]
+fn persist() {
+    let mut heap = new_empty_heap();
+    heap.extend(vec![3, 7, 5]);
+    heap.flush();
+
+    let mut reloaded = BinaryHeap::<u32>::bind_with(b"heap");
+    assert_eq!(reloaded.pop(), Some(7));
+    assert_eq!(reloaded.pop(), Some(5));
+    assert_eq!(reloaded.pop(), Some(3));
+    assert_eq!(reloaded.pop(), None);
+}
@@ -0,0 +1,204 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::storage::{
+    Bind, CachedCell, CachedChunk, Flush,
+    You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+};
+use cfg_if::cfg_if;
+use core::{
+    convert::TryFrom,
+    ops::{Bound, RangeBounds},
+};
+use liquid_prelude::vec::Vec;
+
+/// Number of bytes held by every chunk but the last one.
+const CHUNK_SIZE: u32 = 128;
+
+/// A byte string stored in fixed-size chunks, so that appending to it or
+/// reading a part of it only touches the chunks concerned instead of the
+/// whole string.
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Bytes {
+    len: CachedCell<u32>,
+    chunks: CachedChunk<Vec<u8>>,
+}
+
+impl Bind for Bytes {
+    fn bind_with(key: &[u8]) -> Self {
+        Self {
+            len: CachedCell::<u32>::new(key),
+            chunks: CachedChunk::<Vec<u8>>::new(key),
+        }
+    }
+}
+
+impl Flush for Bytes {
+    fn flush(&mut self) {
+        self.len.flush();
+        self.chunks.flush();
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "contract")] {
+        use crate::lang_core::storage::Getter;
+        use liquid_primitives::__Liquid_Getter_Index_Placeholder;
+
+        impl Getter for Bytes {
+            type Index = __Liquid_Getter_Index_Placeholder;
+            type Output = liquid_primitives::types::Bytes;
+
+            fn getter_impl(&self, _: Self::Index) -> Self::Output {
+                self.read_all().into()
+            }
+        }
+    }
+}
+
+impl Bytes {
+    pub fn initialize(&mut self) {
+        if self.len.get().is_none() {
+            self.len.set(0);
+        }
+    }
+
+    /// Returns the number of bytes stored.
+    pub fn len(&self) -> u32 {
+        *self.len.get().unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Bytes::len] Error: expected `len` field to be existed in \
+                 storage"
+            )
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends `data` to the end, only rewriting the last chunk if it is not
+    /// full yet.
+    ///
+    /// # Panics
+    ///
+    /// If the length would exceed `u32::MAX`.
+    pub fn append(&mut self, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+
+        let len = self.len();
+        let new_len = u32::try_from(data.len())
+            .ok()
+            .and_then(|appended| len.checked_add(appended))
+            .unwrap_or_else(|| {
+                fail!(
+                    "[liquid_lang::Bytes::append] Error: cannot store more bytes than \
+                     `u32::MAX`"
+                )
+            });
+
+        let mut rest = data;
+        let mut index = len / CHUNK_SIZE;
+        let filled = (len % CHUNK_SIZE) as usize;
+        if filled != 0 {
+            let taken = rest.len().min(CHUNK_SIZE as usize - filled);
+            let last = self
+                .chunks
+                .get_mut(&index.to_le_bytes())
+                .unwrap_or_else(|| {
+                    fail!(
+                        "[liquid_lang::Bytes::append] Error: expected the last chunk to \
+                         be existed in storage"
+                    )
+                });
+            last.extend_from_slice(&rest[..taken]);
+            rest = &rest[taken..];
+            index += 1;
+        }
+
+        for chunk in rest.chunks(CHUNK_SIZE as usize) {
+            self.chunks.set(&index.to_le_bytes(), chunk.to_vec());
+            index += 1;
+        }
+        self.len.set(new_len);
+    }
+
+    /// Returns the bytes within `range`, only loading the chunks overlapping
+    /// it.
+    ///
+    /// Returns `None` if `range` is out of bounds.
+    pub fn read<R>(&self, range: R) -> Option<Vec<u8>>
+    where
+        R: RangeBounds<u32>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => end.checked_add(1)?,
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => self.len(),
+        };
+        if start > end || end > self.len() {
+            return None;
+        }
+
+        let mut data = Vec::with_capacity((end - start) as usize);
+        let mut pos = start;
+        while pos < end {
+            let index = pos / CHUNK_SIZE;
+            let chunk = self.chunks.get(&index.to_le_bytes()).unwrap_or_else(|| {
+                fail!(
+                    "[liquid_lang::Bytes::read] Error: expected chunks within bounds to \
+                     be existed in storage"
+                )
+            });
+            let from = (pos % CHUNK_SIZE) as usize;
+            let to = chunk.len().min(from + (end - pos) as usize);
+            data.extend_from_slice(&chunk[from..to]);
+            pos += (to - from) as u32;
+        }
+        Some(data)
+    }
+
+    /// Returns all the bytes stored.
+    pub fn read_all(&self) -> Vec<u8> {
+        self.read(..).unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Bytes::read_all] Error: expected the full range to be \
+                 within bounds"
+            )
+        })
+    }
+
+    /// Replaces the bytes stored with `data`.
+    pub fn set(&mut self, data: &[u8]) {
+        self.clear();
+        self.append(data);
+    }
+
+    /// Removes all the bytes stored.
+    pub fn clear(&mut self) {
+        if let Some(last) = self.len().checked_sub(1) {
+            for index in 0..=last / CHUNK_SIZE {
+                self.chunks.remove(&index.to_le_bytes());
+            }
+        }
+        self.len.set(0);
+    }
+}
+
+impl You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for Bytes {}
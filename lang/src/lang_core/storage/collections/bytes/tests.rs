@@ -0,0 +1,98 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Bytes;
+use crate::lang_core::{
+    env::test,
+    storage::traits::{Bind, Flush},
+};
+
+fn new_empty_bytes() -> Bytes {
+    let mut bytes = Bytes::bind_with(b"bytes");
+    bytes.initialize();
+    bytes
+}
+
+fn sample(len: usize) -> Vec<u8> {
+    (0..len).map(|i| i as u8).collect()
+}
+
+#[test]
+fn empty() {
+    let bytes = new_empty_bytes();
+    assert_eq!(bytes.len(), 0);
+    assert!(bytes.is_empty());
+    assert_eq!(bytes.read_all(), Vec::<u8>::new());
+    assert_eq!(bytes.read(0..1), None);
+}
+
+#[test]
+fn append_across_chunks() {
+    let mut bytes = new_empty_bytes();
+    let data = sample(300);
+    bytes.append(&data[..100]);
+    bytes.append(&data[100..]);
+    bytes.append(&[]);
+    assert_eq!(bytes.len(), 300);
+    assert_eq!(bytes.read_all(), data);
+    bytes.flush();
+
+    let reloaded = Bytes::bind_with(b"bytes");
+    assert_eq!(reloaded.read_all(), data);
+}
+
+#[test]
+fn read_ranges() {
+    let mut bytes = new_empty_bytes();
+    let data = sample(300);
+    bytes.append(&data);
+
+    assert_eq!(bytes.read(120..140), Some(data[120..140].to_vec()));
+    assert_eq!(bytes.read(..=128), Some(data[..=128].to_vec()));
+    assert_eq!(bytes.read(256..), Some(data[256..].to_vec()));
+    assert_eq!(bytes.read(300..), Some(Vec::new()));
+    assert_eq!(bytes.read(290..301), None);
+    assert_eq!(bytes.read(20..10), None);
+}
+
+#[test]
+fn set_and_clear() {
+    let mut bytes = new_empty_bytes();
+    bytes.append(&sample(300));
+    bytes.set(b"liquid");
+    assert_eq!(bytes.read_all(), b"liquid".to_vec());
+    bytes.flush();
+
+    let mut reloaded = Bytes::bind_with(b"bytes");
+    assert_eq!(reloaded.read_all(), b"liquid".to_vec());
+    reloaded.clear();
+    assert!(reloaded.is_empty());
+    reloaded.flush();
+    assert!(Bytes::bind_with(b"bytes").is_empty());
+}
+
+#[test]
+fn append_only_rewrites_last_chunk() {
+    test::run_test(|| {
+        let mut bytes = new_empty_bytes();
+        bytes.append(&sample(1000));
+        bytes.flush();
+        test::reset_gas_report();
+
+        let mut bytes = Bytes::bind_with(b"bytes");
+        bytes.append(b"tail");
+        bytes.flush();
+        // The length and the last chunk.
+        assert_eq!(test::gas_report().storage_writes, 2);
+        assert_eq!(bytes.read(1000..).unwrap(), b"tail".to_vec());
+    });
+}
@@ -0,0 +1,307 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::storage::{
+    Bind, CachedCell, CachedChunk, Flush,
+    You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+};
+use cfg_if::cfg_if;
+use scale::{Codec, Encode};
+
+/// A double-ended queue living in contract storage.
+///
+/// Elements are stored at consecutive slots between two cursors which wrap
+/// around `u32::MAX`, so pushing and popping at either end never moves the
+/// other elements.
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Deque<T> {
+    /// The slot of the front element and the slot following the back one.
+    cursors: CachedCell<(u32, u32)>,
+    chunk: CachedChunk<T>,
+}
+
+pub struct Iter<'a, T> {
+    deque: &'a Deque<T>,
+    begin: u32,
+    end: u32,
+}
+
+impl<'a, T> Iter<'a, T> {
+    pub(crate) fn new(deque: &'a Deque<T>) -> Self {
+        Self {
+            deque,
+            begin: 0,
+            end: deque.len(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: Codec,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        debug_assert!(self.begin <= self.end);
+
+        if self.begin == self.end {
+            return None;
+        }
+
+        let ret = self.deque.get(self.begin);
+        self.begin += 1;
+        ret
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end - self.begin) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> where T: Codec {}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T>
+where
+    T: Codec,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        debug_assert!(self.begin <= self.end);
+
+        if self.begin == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        self.deque.get(self.end)
+    }
+}
+
+impl<T> Bind for Deque<T> {
+    fn bind_with(key: &[u8]) -> Self {
+        Self {
+            cursors: CachedCell::<(u32, u32)>::new(key),
+            chunk: CachedChunk::<T>::new(key),
+        }
+    }
+}
+
+impl<T> Flush for Deque<T>
+where
+    T: Encode,
+{
+    fn flush(&mut self) {
+        self.cursors.flush();
+        self.chunk.flush();
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "contract")] {
+        use crate::lang_core::storage::Getter;
+
+        impl<T> Getter for Deque<T>
+        where
+            T: Codec + Clone,
+        {
+            type Index = u32;
+            type Output = T;
+
+            fn getter_impl(&self, index: Self::Index) -> Self::Output {
+                self.get(index)
+                    .unwrap_or_else(|| {
+                        fail!(
+                            "[liquid_lang::Deque::getter] Error: expected `index` to be within \
+                             bounds"
+                        )
+                    })
+                    .clone()
+            }
+        }
+    }
+}
+
+impl<T> Deque<T> {
+    pub fn initialize(&mut self) {
+        if self.cursors.get().is_none() {
+            self.cursors.set((0, 0));
+        }
+    }
+
+    fn cursors(&self) -> (u32, u32) {
+        *self.cursors.get().unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Deque::cursors] Error: expected `cursors` field to be \
+                 existed in storage"
+            )
+        })
+    }
+
+    fn cursors_mut(&mut self) -> &mut (u32, u32) {
+        self.cursors.get_mut().unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Deque::cursors_mut] Error: expected `cursors` field to \
+                 be existed in storage"
+            )
+        })
+    }
+
+    pub fn len(&self) -> u32 {
+        let (head, tail) = self.cursors();
+        tail.wrapping_sub(head)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::<T>::new(self)
+    }
+
+    fn ensure_capacity(&self) {
+        if self.len() == u32::MAX {
+            fail!(
+                "[liquid_lang::Deque::push] Error: cannot push more elements than \
+                 `u32::MAX`"
+            );
+        }
+    }
+
+    /// Returns the slot of the `n`-th element, counting from the front.
+    fn slot(&self, n: u32) -> Option<[u8; 4]> {
+        if n >= self.len() {
+            return None;
+        }
+        Some(self.cursors().0.wrapping_add(n).to_le_bytes())
+    }
+}
+
+impl<T> Deque<T>
+where
+    T: Codec,
+{
+    /// Returns a reference to the `n`-th element, counting from the front.
+    ///
+    /// Returns `None` if `n` is out of bounds.
+    pub fn get(&self, n: u32) -> Option<&T> {
+        self.slot(n).and_then(|slot| self.chunk.get(&slot))
+    }
+
+    /// Returns a mutable reference to the `n`-th element, counting from the
+    /// front.
+    ///
+    /// Returns `None` if `n` is out of bounds.
+    pub fn get_mut(&mut self, n: u32) -> Option<&mut T> {
+        self.slot(n).and_then(move |slot| self.chunk.get_mut(&slot))
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|n| self.get(n))
+    }
+
+    /// Prepends an element to the front of the deque.
+    pub fn push_front(&mut self, val: T) {
+        self.ensure_capacity();
+        let cursors = self.cursors_mut();
+        cursors.0 = cursors.0.wrapping_sub(1);
+        let slot = cursors.0.to_le_bytes();
+        self.chunk.set(&slot, val);
+    }
+
+    /// Appends an element to the back of the deque.
+    pub fn push_back(&mut self, val: T) {
+        self.ensure_capacity();
+        let cursors = self.cursors_mut();
+        let slot = cursors.1.to_le_bytes();
+        cursors.1 = cursors.1.wrapping_add(1);
+        self.chunk.set(&slot, val);
+    }
+
+    /// Removes the front element and returns it.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let cursors = self.cursors_mut();
+        let slot = cursors.0.to_le_bytes();
+        cursors.0 = cursors.0.wrapping_add(1);
+        let ret = self.chunk.take(&slot);
+        self.chunk.remove(&slot);
+        ret
+    }
+
+    /// Removes the back element and returns it.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let cursors = self.cursors_mut();
+        cursors.1 = cursors.1.wrapping_sub(1);
+        let slot = cursors.1.to_le_bytes();
+        let ret = self.chunk.take(&slot);
+        self.chunk.remove(&slot);
+        ret
+    }
+}
+
+impl<T> Extend<T> for Deque<T>
+where
+    T: Codec,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for i in iter {
+            self.push_back(i);
+        }
+    }
+}
+
+impl<T> core::ops::Index<u32> for Deque<T>
+where
+    T: Codec,
+{
+    type Output = T;
+
+    fn index(&self, index: u32) -> &Self::Output {
+        self.get(index).unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Deque::index] Error: expected `index` to be within bounds"
+            )
+        })
+    }
+}
+
+impl<T> core::ops::IndexMut<u32> for Deque<T>
+where
+    T: Codec,
+{
+    fn index_mut(&mut self, index: u32) -> &mut Self::Output {
+        self.get_mut(index).unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Deque::index_mut] Error: expected `index` to be within \
+                 bounds"
+            )
+        })
+    }
+}
+
+impl<T> You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for Deque<T> {}
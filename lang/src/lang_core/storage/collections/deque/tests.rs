@@ -0,0 +1,93 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Deque;
+use crate::lang_core::storage::traits::{Bind, Flush};
+
+fn new_empty_deque() -> Deque<u32> {
+    let mut deque = Deque::<u32>::bind_with(b"deque");
+    deque.initialize();
+    deque
+}
+
+#[test]
+fn empty() {
+    let mut deque = new_empty_deque();
+    assert_eq!(deque.len(), 0);
+    assert!(deque.is_empty());
+    assert_eq!(deque.front(), None);
+    assert_eq!(deque.back(), None);
+    assert_eq!(deque.pop_front(), None);
+    assert_eq!(deque.pop_back(), None);
+    assert_eq!(deque.iter().next(), None);
+}
+
+#[test]
+fn push_and_pop_both_ends() {
+    let mut deque = new_empty_deque();
+    deque.push_back(2);
+    deque.push_front(1);
+    deque.push_back(3);
+    deque.push_front(0);
+    assert_eq!(deque.len(), 4);
+    assert_eq!(deque.front(), Some(&0));
+    assert_eq!(deque.back(), Some(&3));
+    assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+
+    assert_eq!(deque.pop_front(), Some(0));
+    assert_eq!(deque.pop_back(), Some(3));
+    assert_eq!(deque.get(0), Some(&1));
+    assert_eq!(deque.get(1), Some(&2));
+    assert_eq!(deque.get(2), None);
+
+    assert_eq!(deque.pop_back(), Some(2));
+    assert_eq!(deque.pop_back(), Some(1));
+    assert_eq!(deque.pop_back(), None);
+    assert!(deque.is_empty());
+}
+
+#[test]
+fn iter_back() {
+    let mut deque = new_empty_deque();
+    deque.extend(0..4);
+    deque.push_front(42);
+    let mut iter = deque.iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next_back(), Some(&3));
+    assert_eq!(iter.next(), Some(&42));
+    assert_eq!(iter.rev().copied().collect::<Vec<_>>(), vec![2, 1, 0]);
+}
+
+#[test]
+fn index_mut() {
+    let mut deque = new_empty_deque();
+    deque.push_front(1);
+    deque[0] += 10;
+    assert_eq!(deque[0], 11);
+}
+
+#[test]
+fn persist_after_wrapping_around() {
+    let mut deque = new_empty_deque();
+    // The front cursor wraps around to `u32::MAX` here.
+    deque.push_front(1);
+    deque.push_front(0);
+    deque.push_back(2);
+    deque.flush();
+
+    let mut reloaded = Deque::<u32>::bind_with(b"deque");
+    assert_eq!(reloaded.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(reloaded.pop_front(), Some(0));
+    assert_eq!(reloaded.pop_front(), Some(1));
+    assert_eq!(reloaded.pop_front(), Some(2));
+    assert!(reloaded.is_empty());
+}
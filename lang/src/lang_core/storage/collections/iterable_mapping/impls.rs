@@ -128,10 +128,12 @@ cfg_if! {
                 type Output = V;
                 fn getter_impl(&self, index: Self::Index) -> Self::Output {
                     self.get(&index)
-                        .expect(
-                            "liquid_lang::IterableMapping::getter] Error: expected `index` to be \
-                             existed",
-                        )
+                        .unwrap_or_else(|| {
+                            fail!(
+                                "liquid_lang::IterableMapping::getter] Error: expected `index` to be \
+                                 existed"
+                            )
+                        })
                         .clone()
                 }
             };
@@ -273,9 +275,12 @@ where
     type Output = V;
 
     fn index(&self, index: &'a Q) -> &Self::Output {
-        self.get(index).expect(
-            "[liquid_lang::IterableMapping::index] Error: expected `index` to be existed",
-        )
+        self.get(index).unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::IterableMapping::index] Error: expected `index` to be \
+                 existed"
+            )
+        })
     }
 }
 
@@ -286,10 +291,12 @@ where
     Q: Encode,
 {
     fn index_mut(&mut self, index: &'a Q) -> &mut Self::Output {
-        self.get_mut(index).expect(
-            "[liquid_lang::IterableMapping::index_mut] Error: expected `index` to be \
-             existed",
-        )
+        self.get_mut(index).unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::IterableMapping::index_mut] Error: expected `index` to \
+                 be existed"
+            )
+        })
     }
 }
 
@@ -57,9 +57,11 @@ cfg_if! {
 
                 fn getter_impl(&self, index: Self::Index) -> Self::Output {
                     self.get(&index)
-                        .expect(
-                            "[liquid_lang::Mapping::getter] Error: expected `index` to be existed",
-                        )
+                        .unwrap_or_else(|| {
+                            fail!(
+                                "[liquid_lang::Mapping::getter] Error: expected `index` to be existed"
+                            )
+                        })
                         .clone()
                 }
             };
@@ -85,6 +87,18 @@ cfg_if! {
     }
 }
 
+/// Derives the index of `key` in the underlying chunk.
+///
+/// The SCALE encoding of the key is hashed, so every entry occupies a slot
+/// of fixed length right after the field prefix, no matter how long the key
+/// is, e.g. for `String` or tuple keys.
+fn storage_index<Q>(key: &Q) -> [u8; 32]
+where
+    Q: Encode + ?Sized,
+{
+    key.using_encoded(liquid_primitives::hash::hash)
+}
+
 impl<K, V> Mapping<K, V> {
     pub fn initialize(&mut self) {
         if self.len.get().is_none() {
@@ -93,10 +107,12 @@ impl<K, V> Mapping<K, V> {
     }
 
     pub fn len(&self) -> u32 {
-        *self.len.get().expect(
-            "[liquid_lang::Mapping::len] Error: expected `len` field to be existed in \
-             storage",
-        )
+        *self.len.get().unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Mapping::len] Error: expected `len` field to be existed \
+                 in storage"
+            )
+        })
     }
 
     pub fn is_empty(&self) -> bool {
@@ -106,7 +122,7 @@ impl<K, V> Mapping<K, V> {
 
 impl<K, V> Mapping<K, V>
 where
-    K: Codec,
+    K: Encode,
     V: Codec,
 {
     /// Inserts a key-value pair into the map.
@@ -118,24 +134,26 @@ where
     pub fn insert<Q>(&mut self, key: &Q, val: V) -> Option<V>
     where
         K: Borrow<Q>,
-        Q: Encode,
+        Q: Encode + ?Sized,
     {
         if self.len() == u32::MAX {
-            panic!(
+            fail!(
                 "[liquid_lang::Mapping::insert] Error: cannot insert more elements than \
                  `u32::MAX`"
             );
         }
 
-        let encoded_key = key.encode();
+        let encoded_key = storage_index(key);
         let ret = self.chunk.take(&encoded_key);
         self.chunk.set(&encoded_key, val);
 
         if ret.is_none() {
-            let len = self.len.get_mut().expect(
-                "[liquid_lang::Mapping::insert] Error: expected `len` field to be \
-                 existed in storage",
-            );
+            let len = self.len.get_mut().unwrap_or_else(|| {
+                fail!(
+                    "[liquid_lang::Mapping::insert] Error: expected `len` field to be \
+                     existed in storage"
+                )
+            });
             *len += 1;
         }
         ret
@@ -150,9 +168,9 @@ where
     where
         K: Borrow<Q>,
         F: FnOnce(&mut V),
-        Q: Encode,
+        Q: Encode + ?Sized,
     {
-        let encoded_key = key.encode();
+        let encoded_key = storage_index(key);
         self.chunk.mutate_with(&encoded_key, f)
     }
 
@@ -160,17 +178,19 @@ where
     pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
     where
         K: Borrow<Q>,
-        Q: Encode,
+        Q: Encode + ?Sized,
     {
-        let encoded_key = key.encode();
+        let encoded_key = storage_index(key);
         let ret = self.chunk.take(&encoded_key);
         self.chunk.remove(&encoded_key);
 
         if ret.is_some() {
-            let len = self.len.get_mut().expect(
-                "[liquid_lang::Mapping::remove] Error: expected `len` field to be \
-                 existed in storage",
-            );
+            let len = self.len.get_mut().unwrap_or_else(|| {
+                fail!(
+                    "[liquid_lang::Mapping::remove] Error: expected `len` field to be \
+                     existed in storage"
+                )
+            });
             *len -= 1;
         }
         ret
@@ -179,61 +199,64 @@ where
     pub fn get<Q>(&self, key: &Q) -> Option<&V>
     where
         K: Borrow<Q>,
-        Q: Encode,
+        Q: Encode + ?Sized,
     {
-        let encoded_key = key.encode();
+        let encoded_key = storage_index(key);
         self.chunk.get(&encoded_key)
     }
 
     pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
     where
         K: Borrow<Q>,
-        Q: Encode,
+        Q: Encode + ?Sized,
     {
-        let encoded_key = key.encode();
+        let encoded_key = storage_index(key);
         self.chunk.get_mut(&encoded_key)
     }
 
     pub fn contains_key<Q>(&self, key: &Q) -> bool
     where
         K: Borrow<Q>,
-        Q: Encode,
+        Q: Encode + ?Sized,
     {
-        let encoded_key = key.encode();
+        let encoded_key = storage_index(key);
         matches!(self.chunk.get(&encoded_key), Some(_))
     }
 }
 
 impl<'a, K, Q, V> core::ops::Index<&'a Q> for Mapping<K, V>
 where
-    K: Codec + Borrow<Q>,
+    K: Encode + Borrow<Q>,
     V: Codec,
-    Q: Encode,
+    Q: Encode + ?Sized,
 {
     type Output = V;
 
     fn index(&self, index: &'a Q) -> &Self::Output {
-        self.get(index)
-            .expect("[liquid_lang::Mapping::index] Error: expected `index` to be existed")
+        self.get(index).unwrap_or_else(|| {
+            fail!("[liquid_lang::Mapping::index] Error: expected `index` to be existed")
+        })
     }
 }
 
 impl<'a, K, Q, V> core::ops::IndexMut<&'a Q> for Mapping<K, V>
 where
-    K: Codec + Borrow<Q>,
+    K: Encode + Borrow<Q>,
     V: Codec,
-    Q: Encode,
+    Q: Encode + ?Sized,
 {
     fn index_mut(&mut self, index: &'a Q) -> &mut Self::Output {
-        self.get_mut(index).expect(
-            "[liquid_lang::Mapping::index_mut] Error: expected `index` to be existed",
-        )
+        self.get_mut(index).unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Mapping::index_mut] Error: expected `index` to be existed"
+            )
+        })
     }
 }
 
 impl<K, V> Extend<(K, V)> for Mapping<K, V>
 where
-    K: Codec,
+    K: Encode,
     V: Codec,
 {
     fn extend<I>(&mut self, iter: I)
@@ -248,7 +271,7 @@ where
 
 impl<'a, K, V> Extend<(&'a K, &'a V)> for Mapping<K, V>
 where
-    K: Codec + Copy,
+    K: Encode + Copy,
     V: Codec + Copy,
 {
     fn extend<I>(&mut self, iter: I)
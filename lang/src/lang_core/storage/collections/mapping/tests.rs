@@ -142,3 +142,32 @@ fn extend_works() {
         assert_eq!(map[&keys[i]], vals[i]);
     }
 }
+
+#[test]
+fn str_lookup_works() {
+    let mut map = new_empty::<String, u8>();
+    assert_eq!(map.insert("Alice", 0), None);
+    assert_eq!(map.get("Alice"), Some(&0));
+    assert_eq!(map["Alice"], 0);
+    assert!(map.contains_key(&"Alice".to_string()));
+    assert_eq!(map.remove("Alice"), Some(0));
+    assert_eq!(map.len(), 0);
+}
+
+#[test]
+fn tuple_key_works() {
+    use crate::lang_core::storage::traits::Flush;
+
+    let mut map = new_empty::<(u64, String), u32>();
+    assert_eq!(map.insert(&(1, "buy".to_string()), 10), None);
+    assert_eq!(map.insert(&(1, "sell".to_string()), 20), None);
+    assert_eq!(map.insert(&(2, "buy".to_string()), 30), None);
+    assert_eq!(map.len(), 3);
+    map.flush();
+
+    let reloaded = Mapping::<(u64, String), u32>::bind_with(b"var");
+    assert_eq!(reloaded[&(1, "buy".to_string())], 10);
+    assert_eq!(reloaded[&(1, "sell".to_string())], 20);
+    assert_eq!(reloaded[&(2, "buy".to_string())], 30);
+    assert_eq!(reloaded.get(&(2, "sell".to_string())), None);
+}
@@ -10,10 +10,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod binary_heap;
+mod bytes;
+mod deque;
 mod iterable_mapping;
 mod mapping;
+mod set;
+mod string;
 mod vec;
+mod work_queue;
 
+pub use binary_heap::BinaryHeap;
+pub use bytes::Bytes;
+pub use deque::Deque;
 pub use iterable_mapping::IterableMapping;
 pub use mapping::Mapping;
+pub use set::Set;
+pub use string::String;
 pub use vec::Vec;
+pub use work_queue::WorkQueue;
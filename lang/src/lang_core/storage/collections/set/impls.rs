@@ -0,0 +1,171 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::storage::{
+    collections::vec::Iter, Bind, Flush, Mapping, Vec,
+    You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+};
+use cfg_if::cfg_if;
+use core::borrow::Borrow;
+use scale::{Codec, Encode};
+
+/// A set of distinct elements living in contract storage.
+///
+/// The elements are kept densely in a [`Vec`], and a [`Mapping`] from every
+/// element to its position in the vector makes membership checks take a
+/// single storage read. A removed element is replaced by the last one, so
+/// that no space is wasted and iteration never skips holes.
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Set<T> {
+    elems: Vec<T>,
+    positions: Mapping<T, u32>,
+}
+
+impl<T> Bind for Set<T> {
+    fn bind_with(key: &[u8]) -> Self {
+        let mut elems_bind_key = key.to_vec();
+        elems_bind_key.extend_from_slice(b"$elems");
+        let mut positions_bind_key = key.to_vec();
+        positions_bind_key.extend_from_slice(b"$positions");
+
+        Self {
+            elems: Vec::<T>::bind_with(&elems_bind_key),
+            positions: Mapping::<T, u32>::bind_with(&positions_bind_key),
+        }
+    }
+}
+
+impl<T> Flush for Set<T>
+where
+    T: Encode,
+{
+    fn flush(&mut self) {
+        self.elems.flush();
+        self.positions.flush();
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "contract")] {
+        use crate::lang_core::storage::Getter;
+
+        macro_rules! getter_impl {
+            () => {
+                type Index = T;
+                type Output = bool;
+
+                fn getter_impl(&self, index: Self::Index) -> Self::Output {
+                    self.contains(&index)
+                }
+            };
+        }
+
+        #[cfg(feature = "solidity-compatible")]
+        impl<T> Getter for Set<T>
+        where
+            T: Codec + liquid_abi_codec::Decode,
+        {
+            getter_impl!();
+        }
+
+        #[cfg(not(feature = "solidity-compatible"))]
+        impl<T> Getter for Set<T>
+        where
+            T: Codec,
+        {
+            getter_impl!();
+        }
+    }
+}
+
+impl<T> Set<T> {
+    pub fn initialize(&mut self) {
+        self.elems.initialize();
+        self.positions.initialize();
+    }
+
+    pub fn len(&self) -> u32 {
+        self.elems.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elems.is_empty()
+    }
+
+    /// Returns an iterator visiting all the elements in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.elems.iter()
+    }
+}
+
+impl<T> Set<T>
+where
+    T: Codec,
+{
+    /// Adds an element to the set.
+    ///
+    /// Returns whether the element was newly inserted.
+    pub fn insert(&mut self, val: T) -> bool {
+        if self.contains(&val) {
+            return false;
+        }
+
+        self.positions.insert(&val, self.elems.len());
+        self.elems.push(val);
+        true
+    }
+
+    pub fn contains<Q>(&self, val: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Encode + ?Sized,
+    {
+        self.positions.contains_key(val)
+    }
+
+    /// Removes an element from the set.
+    ///
+    /// Returns whether the element was present in the set.
+    pub fn remove<Q>(&mut self, val: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Encode + ?Sized,
+    {
+        let pos = match self.positions.remove(val) {
+            Some(pos) => pos,
+            None => return false,
+        };
+
+        self.elems.swap_remove(pos);
+        if let Some(moved) = self.elems.get(pos) {
+            self.positions
+                .mutate_with::<T, _>(moved, |moved_pos| *moved_pos = pos);
+        }
+        true
+    }
+}
+
+impl<T> Extend<T> for Set<T>
+where
+    T: Codec,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for i in iter {
+            self.insert(i);
+        }
+    }
+}
+
+impl<T> You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for Set<T> {}
@@ -0,0 +1,92 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::Set;
+use crate::lang_core::storage::traits::{Bind, Flush};
+
+fn new_empty_set() -> Set<String> {
+    let mut set = Set::<String>::bind_with(b"set");
+    set.initialize();
+    set
+}
+
+fn sorted(set: &Set<String>) -> Vec<&str> {
+    let mut elems = set.iter().map(String::as_str).collect::<Vec<_>>();
+    elems.sort_unstable();
+    elems
+}
+
+#[test]
+fn empty() {
+    let mut set = new_empty_set();
+    assert_eq!(set.len(), 0);
+    assert!(set.is_empty());
+    assert!(!set.contains("Alice"));
+    assert!(!set.remove("Alice"));
+    assert_eq!(set.iter().next(), None);
+}
+
+#[test]
+fn insert_and_contains() {
+    let mut set = new_empty_set();
+    assert!(set.insert("Alice".to_owned()));
+    assert!(set.insert("Bob".to_owned()));
+    assert!(!set.insert("Alice".to_owned()));
+    assert_eq!(set.len(), 2);
+    assert!(set.contains("Alice"));
+    assert!(set.contains("Bob"));
+    assert!(!set.contains("Charlie"));
+    assert_eq!(sorted(&set), vec!["Alice", "Bob"]);
+}
+
+#[test]
+fn remove_keeps_elements_dense() {
+    let mut set = new_empty_set();
+    set.extend(
+        vec!["Alice", "Bob", "Charlie", "Dave"]
+            .into_iter()
+            .map(String::from),
+    );
+
+    assert!(set.remove("Alice"));
+    assert!(!set.remove("Alice"));
+    assert_eq!(set.len(), 3);
+    assert_eq!(sorted(&set), vec!["Bob", "Charlie", "Dave"]);
+
+    // The element moved into the hole must still be removable.
+    assert!(set.remove("Dave"));
+    assert!(set.remove("Bob"));
+    assert_eq!(sorted(&set), vec!["Charlie"]);
+    assert!(set.remove("Charlie"));
+    assert!(set.is_empty());
+
+    assert!(set.insert("Alice".to_owned()));
+    assert_eq!(sorted(&set), vec!["Alice"]);
+}
+
+#[test]
+fn persist() {
+    let mut set = new_empty_set();
+    set.extend(
+        vec!["Alice", "Bob", "Charlie"]
+            .into_iter()
+            .map(String::from),
+    );
+    set.remove("Alice");
+    set.flush();
+
+    let mut reloaded = Set::<String>::bind_with(b"set");
+    assert_eq!(sorted(&reloaded), vec!["Bob", "Charlie"]);
+    assert!(!reloaded.contains("Alice"));
+    assert!(reloaded.remove("Charlie"));
+    assert!(reloaded.contains("Bob"));
+}
@@ -0,0 +1,110 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::storage::{
+    Bind, Bytes, Flush, You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+};
+use cfg_if::cfg_if;
+use core::ops::RangeBounds;
+use liquid_prelude::string;
+
+/// A UTF-8 string stored in chunks like [`Bytes`],
+/// with ranges being counted in bytes.
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct String {
+    bytes: Bytes,
+}
+
+impl Bind for String {
+    fn bind_with(key: &[u8]) -> Self {
+        Self {
+            bytes: Bytes::bind_with(key),
+        }
+    }
+}
+
+impl Flush for String {
+    fn flush(&mut self) {
+        self.bytes.flush();
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "contract")] {
+        use crate::lang_core::storage::Getter;
+        use liquid_primitives::__Liquid_Getter_Index_Placeholder;
+
+        impl Getter for String {
+            type Index = __Liquid_Getter_Index_Placeholder;
+            type Output = string::String;
+
+            fn getter_impl(&self, _: Self::Index) -> Self::Output {
+                self.read_all()
+            }
+        }
+    }
+}
+
+impl String {
+    pub fn initialize(&mut self) {
+        self.bytes.initialize();
+    }
+
+    /// Returns the length of the string in bytes.
+    pub fn len(&self) -> u32 {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Appends `s` to the end, only rewriting the last chunk if it is not
+    /// full yet.
+    pub fn append(&mut self, s: &str) {
+        self.bytes.append(s.as_bytes());
+    }
+
+    /// Returns the part of the string within the byte range `range`.
+    ///
+    /// Returns `None` if `range` is out of bounds or does not lie on `char`
+    /// boundaries.
+    pub fn read<R>(&self, range: R) -> Option<string::String>
+    where
+        R: RangeBounds<u32>,
+    {
+        self.bytes
+            .read(range)
+            .and_then(|bytes| string::String::from_utf8(bytes).ok())
+    }
+
+    /// Returns the whole string.
+    pub fn read_all(&self) -> string::String {
+        self.read(..).unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::String::read_all] Error: expected the stored bytes to be \
+                 valid UTF-8"
+            )
+        })
+    }
+
+    /// Replaces the string with `s`.
+    pub fn set(&mut self, s: &str) {
+        self.bytes.set(s.as_bytes());
+    }
+
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+}
+
+impl You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for String {}
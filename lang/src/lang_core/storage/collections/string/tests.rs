@@ -0,0 +1,51 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::String;
+use crate::lang_core::storage::traits::{Bind, Flush};
+
+fn new_empty_string() -> String {
+    let mut s = String::bind_with(b"string");
+    s.initialize();
+    s
+}
+
+#[test]
+fn append_and_read() {
+    let mut s = new_empty_string();
+    assert!(s.is_empty());
+    s.append("hello, ");
+    s.append(&"liquid".repeat(50));
+    assert_eq!(s.len(), 307);
+    assert_eq!(s.read(0..5).as_deref(), Some("hello"));
+    assert_eq!(s.read(7..13).as_deref(), Some("liquid"));
+    s.flush();
+
+    let reloaded = String::bind_with(b"string");
+    assert_eq!(
+        reloaded.read_all(),
+        format!("hello, {}", "liquid".repeat(50))
+    );
+}
+
+#[test]
+fn read_within_char_boundaries() {
+    let mut s = new_empty_string();
+    s.set("流动性");
+    assert_eq!(s.len(), 9);
+    assert_eq!(s.read(3..6).as_deref(), Some("动"));
+    assert_eq!(s.read(1..6), None);
+    assert_eq!(s.read(0..10), None);
+
+    s.clear();
+    assert_eq!(s.read_all(), "");
+}
@@ -114,9 +114,11 @@ cfg_if! {
 
             fn getter_impl(&self, index: Self::Index) -> Self::Output {
                 self.get(index)
-                    .expect(
-                        "[liquid_lang::Vec::getter] Error: expected `index` to be within bounds",
-                    )
+                    .unwrap_or_else(|| {
+                        fail!(
+                            "[liquid_lang::Vec::getter] Error: expected `index` to be within bounds"
+                        )
+                    })
                     .clone()
             }
         }
@@ -131,10 +133,12 @@ impl<T> Vec<T> {
     }
 
     pub fn len(&self) -> u32 {
-        *self.len.get().expect(
-            "[liquid_lang::Vec::len] Error: expected `len` field to be existed in \
-             storage",
-        )
+        *self.len.get().unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Vec::len] Error: expected `len` field to be existed in \
+                 storage"
+            )
+        })
     }
 
     pub fn is_empty(&self) -> bool {
@@ -188,16 +192,18 @@ where
     /// Appends an element to the back of the vector.
     pub fn push(&mut self, val: T) {
         if self.len() == u32::MAX {
-            panic!(
+            fail!(
                 "[liquid_lang::Vec::push] Error: cannot push more elements than \
                  `u32::MAX`"
             );
         }
 
-        let len = self.len.get_mut().expect(
-            "[liquid_lang::Vec::push] Error: expected `len` field to be existed in \
-             storage",
-        );
+        let len = self.len.get_mut().unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Vec::push] Error: expected `len` field to be existed in \
+                 storage"
+            )
+        });
         self.chunk.set(&len.to_le_bytes(), val);
         *len += 1;
     }
@@ -208,10 +214,12 @@ where
             return None;
         }
 
-        let len = self.len.get_mut().expect(
-            "[liquid_lang::Vec::push] Error: expected `len` field to be existed in \
-             storage",
-        );
+        let len = self.len.get_mut().unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Vec::push] Error: expected `len` field to be existed in \
+                 storage"
+            )
+        });
         *len -= 1;
         let ret = self.chunk.take(&len.to_le_bytes());
         self.chunk.remove(&len.to_le_bytes());
@@ -229,18 +237,24 @@ where
         }
 
         let a_index = a.to_le_bytes();
-        self.within_bounds(a)
-            .expect("[liquid_lang::Vec::swap] Error: expected `a` to be within bounds");
-        self.within_bounds(b)
-            .expect("[liquid_lang::Vec::swap] Error: expected `b` to be within bounds");
-        let item_a = self.chunk.take(&a_index).expect(
-            "[liquid_lang::Vec::swap] Error: expected `Some` value since vector is not \
-             empty",
-        );
-        let item_b = self.chunk.put(&b.to_le_bytes(), item_a).expect(
-            "[liquid_lang::Vec::swap] Error: expected `Some` value since vector is not \
-             empty",
-        );
+        self.within_bounds(a).unwrap_or_else(|| {
+            fail!("[liquid_lang::Vec::swap] Error: expected `a` to be within bounds")
+        });
+        self.within_bounds(b).unwrap_or_else(|| {
+            fail!("[liquid_lang::Vec::swap] Error: expected `b` to be within bounds")
+        });
+        let item_a = self.chunk.take(&a_index).unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Vec::swap] Error: expected `Some` value since vector is \
+                 not empty"
+            )
+        });
+        let item_b = self.chunk.put(&b.to_le_bytes(), item_a).unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Vec::swap] Error: expected `Some` value since vector is \
+                 not empty"
+            )
+        });
         self.chunk.set(&a_index, item_b);
     }
 
@@ -261,10 +275,12 @@ where
         if n == self.len() - 1 {
             self.pop()
         } else {
-            let last_elem = self.pop().expect(
-                "[liquid_lang::Vec::swap_remove] Error: expected `Some` value since \
-                 vector is not empty",
-            );
+            let last_elem = self.pop().unwrap_or_else(|| {
+                fail!(
+                    "[liquid_lang::Vec::swap_remove] Error: expected `Some` value since \
+                     vector is not empty"
+                )
+            });
 
             self.chunk.put(&n.to_le_bytes(), last_elem)
         }
@@ -304,9 +320,9 @@ where
     type Output = T;
 
     fn index(&self, index: u32) -> &Self::Output {
-        self.get(index).expect(
-            "[liquid_lang::Vec::index] Error: expected `index` to be within bounds",
-        )
+        self.get(index).unwrap_or_else(|| {
+            fail!("[liquid_lang::Vec::index] Error: expected `index` to be within bounds")
+        })
     }
 }
 
@@ -315,9 +331,12 @@ where
     T: Codec,
 {
     fn index_mut(&mut self, index: u32) -> &mut Self::Output {
-        self.get_mut(index).expect(
-            "[liquid_lang::Vec::index_mut] Error: expected `index` to be within bounds",
-        )
+        self.get_mut(index).unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::Vec::index_mut] Error: expected `index` to be within \
+                 bounds"
+            )
+        })
     }
 }
 
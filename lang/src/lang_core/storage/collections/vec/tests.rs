@@ -211,3 +211,22 @@ fn extend() {
         assert_eq!(vec[i], i as u8);
     }
 }
+
+#[test]
+fn pop_then_reload() {
+    use crate::lang_core::storage::traits::Flush;
+
+    let mut vec = new_filled_vec();
+    vec.flush();
+    assert_eq!(vec.pop(), Some(0x41));
+    assert_eq!(vec.swap_remove(0), Some(0x56));
+    vec.push(0x42);
+    vec.flush();
+
+    let reloaded = Vec::<u8>::bind_with(b"vec");
+    assert_eq!(reloaded.len(), 3);
+    assert_eq!(reloaded[0], 0x54);
+    assert_eq!(reloaded[1], 0x49);
+    assert_eq!(reloaded[2], 0x42);
+    assert_eq!(reloaded.get(3), None);
+}
@@ -0,0 +1,203 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::lang_core::storage::{
+    Bind, CachedCell, CachedChunk, Flush,
+    You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+};
+use cfg_if::cfg_if;
+use liquid_prelude::vec::Vec;
+use scale::{Codec, Encode};
+
+/// A FIFO queue of jobs living in contract storage.
+///
+/// Jobs are appended with [`enqueue`](WorkQueue::enqueue) and consumed in
+/// batches with [`dequeue_batch`](WorkQueue::dequeue_batch), so that a
+/// workload which is too heavy for a single transaction can be processed
+/// by several calls, e.g. triggered by a keeper bot. The consumer cursor is
+/// persisted along with the queue, hence every call resumes from where the
+/// previous one stopped.
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct WorkQueue<T> {
+    /// The cursor of the next job to be dequeued and the index of the next
+    /// slot to be enqueued.
+    cursors: CachedCell<(u32, u32)>,
+    chunk: CachedChunk<T>,
+}
+
+impl<T> Bind for WorkQueue<T> {
+    fn bind_with(key: &[u8]) -> Self {
+        Self {
+            cursors: CachedCell::<(u32, u32)>::new(key),
+            chunk: CachedChunk::<T>::new(key),
+        }
+    }
+}
+
+impl<T> Flush for WorkQueue<T>
+where
+    T: Encode,
+{
+    fn flush(&mut self) {
+        self.cursors.flush();
+        self.chunk.flush();
+    }
+}
+
+cfg_if! {
+    if #[cfg(feature = "contract")] {
+        use crate::lang_core::storage::Getter;
+
+        impl<T> Getter for WorkQueue<T>
+        where
+            T: Codec + Clone,
+        {
+            type Index = u32;
+            type Output = T;
+
+            fn getter_impl(&self, index: Self::Index) -> Self::Output {
+                self.get(index)
+                    .unwrap_or_else(|| {
+                        fail!(
+                            "[liquid_lang::WorkQueue::getter] Error: expected `index` to be \
+                             within bounds"
+                        )
+                    })
+                    .clone()
+            }
+        }
+    }
+}
+
+impl<T> WorkQueue<T> {
+    pub fn initialize(&mut self) {
+        if self.cursors.get().is_none() {
+            self.cursors.set((0, 0));
+        }
+    }
+
+    fn cursors(&self) -> (u32, u32) {
+        *self.cursors.get().unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::WorkQueue::cursors] Error: expected `cursors` field to \
+                 be existed in storage"
+            )
+        })
+    }
+
+    /// Returns the number of pending jobs.
+    pub fn len(&self) -> u32 {
+        let (head, tail) = self.cursors();
+        tail - head
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of jobs which have been dequeued so far.
+    pub fn cursor(&self) -> u32 {
+        self.cursors().0
+    }
+}
+
+impl<T> WorkQueue<T>
+where
+    T: Codec,
+{
+    /// Returns a reference to the `n`-th pending job, counting from the
+    /// front of the queue.
+    ///
+    /// Returns `None` if `n` is out of bounds.
+    pub fn get(&self, n: u32) -> Option<&T> {
+        let (head, tail) = self.cursors();
+        if n >= tail - head {
+            return None;
+        }
+        self.chunk.get(&(head + n).to_le_bytes())
+    }
+
+    /// Returns a reference to the job at the front of the queue.
+    pub fn peek(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Appends a job to the back of the queue.
+    pub fn enqueue(&mut self, job: T) {
+        let cursors = self.cursors.get_mut().unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::WorkQueue::enqueue] Error: expected `cursors` field to \
+                 be existed in storage"
+            )
+        });
+        if cursors.1 == u32::MAX {
+            fail!(
+                "[liquid_lang::WorkQueue::enqueue] Error: cannot enqueue more than \
+                 `u32::MAX` jobs"
+            );
+        }
+
+        self.chunk.set(&cursors.1.to_le_bytes(), job);
+        cursors.1 += 1;
+    }
+
+    /// Removes the job at the front of the queue and returns it.
+    pub fn dequeue(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let cursors = self.cursors.get_mut().unwrap_or_else(|| {
+            fail!(
+                "[liquid_lang::WorkQueue::dequeue] Error: expected `cursors` field to \
+                 be existed in storage"
+            )
+        });
+        let index = cursors.0.to_le_bytes();
+        cursors.0 += 1;
+        let ret = self.chunk.take(&index);
+        self.chunk.remove(&index);
+        ret
+    }
+
+    /// Removes at most `n` jobs from the front of the queue and returns them
+    /// in the order they were enqueued.
+    pub fn dequeue_batch(&mut self, n: u32) -> Vec<T> {
+        let count = core::cmp::min(n, self.len());
+        let mut jobs = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            jobs.push(self.dequeue().unwrap_or_else(|| {
+                fail!(
+                    "[liquid_lang::WorkQueue::dequeue_batch] Error: expected `Some` \
+                     value since queue is not empty"
+                )
+            }));
+        }
+        jobs
+    }
+}
+
+impl<T> Extend<T> for WorkQueue<T>
+where
+    T: Codec,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for job in iter {
+            self.enqueue(job);
+        }
+    }
+}
+
+impl<T> You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for WorkQueue<T> {}
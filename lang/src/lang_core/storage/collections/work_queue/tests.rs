@@ -0,0 +1,80 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::WorkQueue;
+use crate::lang_core::storage::traits::{Bind, Flush};
+
+fn new_empty_queue() -> WorkQueue<u32> {
+    let mut queue = WorkQueue::<u32>::bind_with(b"queue");
+    queue.initialize();
+    queue
+}
+
+#[test]
+fn empty() {
+    let mut queue = new_empty_queue();
+    assert_eq!(queue.len(), 0);
+    assert!(queue.is_empty());
+    assert_eq!(queue.peek(), None);
+    assert_eq!(queue.dequeue(), None);
+    assert!(queue.dequeue_batch(10).is_empty());
+    assert_eq!(queue.cursor(), 0);
+}
+
+#[test]
+fn fifo_order() {
+    let mut queue = new_empty_queue();
+    queue.enqueue(1);
+    queue.enqueue(2);
+    queue.enqueue(3);
+    assert_eq!(queue.len(), 3);
+    assert_eq!(queue.peek(), Some(&1));
+    assert_eq!(queue.get(2), Some(&3));
+    assert_eq!(queue.get(3), None);
+
+    assert_eq!(queue.dequeue(), Some(1));
+    assert_eq!(queue.peek(), Some(&2));
+    assert_eq!(queue.get(0), Some(&2));
+    assert_eq!(queue.cursor(), 1);
+    assert_eq!(queue.len(), 2);
+}
+
+#[test]
+fn dequeue_batch() {
+    let mut queue = new_empty_queue();
+    queue.extend(0..10);
+    assert_eq!(queue.dequeue_batch(4), vec![0, 1, 2, 3]);
+    assert_eq!(queue.dequeue_batch(4), vec![4, 5, 6, 7]);
+    assert_eq!(queue.dequeue_batch(4), vec![8, 9]);
+    assert!(queue.is_empty());
+    assert_eq!(queue.cursor(), 10);
+}
+
+#[test]
+fn cursor_persists_across_calls() {
+    let mut queue = new_empty_queue();
+    queue.extend(0..5);
+    queue.flush();
+
+    let mut consumer = WorkQueue::<u32>::bind_with(b"queue");
+    assert_eq!(consumer.dequeue_batch(2), vec![0, 1]);
+    consumer.flush();
+
+    let mut producer = WorkQueue::<u32>::bind_with(b"queue");
+    producer.enqueue(5);
+    producer.flush();
+
+    let mut consumer = WorkQueue::<u32>::bind_with(b"queue");
+    assert_eq!(consumer.cursor(), 2);
+    assert_eq!(consumer.len(), 4);
+    assert_eq!(consumer.dequeue_batch(10), vec![2, 3, 4, 5]);
+}
@@ -18,7 +18,9 @@ mod traits;
 mod value;
 
 pub use self::{
-    collections::{IterableMapping, Mapping, Vec},
+    collections::{
+        BinaryHeap, Bytes, Deque, IterableMapping, Mapping, Set, String, Vec, WorkQueue,
+    },
     traits::*,
     value::Value,
 };
@@ -73,3 +75,37 @@ mod mutable_call_flag {
 }
 
 pub use mutable_call_flag::*;
+
+/// Storage caches remember the epoch in which they were filled, and drop
+/// their content once the epoch moves on. The off-chain environment moves
+/// to a new epoch whenever its contract storage is rolled back, which is
+/// the only way for the storage to change behind the caches.
+#[cfg(feature = "std")]
+mod cache_epoch {
+    use core::cell::Cell;
+
+    thread_local! {
+        static CACHE_EPOCH: Cell<u64> = Cell::new(0);
+    }
+
+    pub fn cache_epoch() -> u64 {
+        CACHE_EPOCH.with(|epoch| epoch.get())
+    }
+
+    #[doc(hidden)]
+    pub fn invalidate_caches() {
+        CACHE_EPOCH.with(|epoch| epoch.set(epoch.get() + 1));
+    }
+}
+
+#[cfg(feature = "std")]
+pub use cache_epoch::*;
+
+/// Reborrows a storage field as shared. The contract macro routes every
+/// access to a `#[liquid(immutable)]` field outside of the constructor
+/// through this function, so modifying such a field fails to compile.
+#[doc(hidden)]
+#[inline(always)]
+pub fn read_only<T>(field: &T) -> &T {
+    field
+}
@@ -22,6 +22,16 @@ pub trait New {
 
 pub trait Bind {
     fn bind_with(key: &[u8]) -> Self;
+
+    /// Binds the container to `key` and tells it which storage field it
+    /// backs, so that diagnostics can name the field. Containers that have
+    /// nothing to report just ignore the name.
+    fn bind_with_name(key: &[u8], _name: &'static str) -> Self
+    where
+        Self: Sized,
+    {
+        Self::bind_with(key)
+    }
 }
 
 cfg_if! {
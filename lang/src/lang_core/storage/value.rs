@@ -10,22 +10,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::lang_core::storage::{
-    Bind, CachedCell, Flush,
-    You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+use crate::lang_core::{
+    env,
+    storage::{
+        Bind, CachedCell, Flush,
+        You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage,
+    },
 };
 use cfg_if::cfg_if;
+use liquid_prelude::string::String;
 use scale::Encode;
 
 #[cfg_attr(feature = "std", derive(Debug))]
 pub struct Value<T> {
     cell: CachedCell<T>,
+    /// Name of the storage field backed by this value, used to report
+    /// accesses before initialization.
+    name: &'static str,
 }
 
 impl<T> Bind for Value<T> {
     fn bind_with(key: &[u8]) -> Self {
+        Self::bind_with_name(key, "")
+    }
+
+    fn bind_with_name(key: &[u8], name: &'static str) -> Self {
         Self {
             cell: CachedCell::new(key),
+            name,
         }
     }
 }
@@ -75,18 +87,40 @@ where
     where
         F: FnOnce(&mut T),
     {
-        self.cell.mutate_with(f).unwrap()
+        let name = self.name;
+        match self.cell.mutate_with(f) {
+            Some(val) => val,
+            None => uninitialized(name),
+        }
     }
 
     pub fn get(&self) -> &T {
-        self.cell.get().unwrap()
+        match self.cell.get() {
+            Some(val) => val,
+            None => uninitialized(self.name),
+        }
     }
 
     pub fn get_mut(&mut self) -> &mut T {
-        self.cell.get_mut().unwrap()
+        let name = self.name;
+        match self.cell.get_mut() {
+            Some(val) => val,
+            None => uninitialized(name),
+        }
     }
 }
 
+/// Reverts the execution because the storage field `name` is read before
+/// being initialized.
+#[cold]
+fn uninitialized(name: &str) -> ! {
+    let mut err_info = String::from("storage field `");
+    err_info.push_str(if name.is_empty() { "<unnamed>" } else { name });
+    err_info.push_str("` is accessed before being initialized");
+    env::revert(&err_info);
+    unreachable!();
+}
+
 impl<T, R> AsRef<R> for Value<T>
 where
     T: AsRef<R> + scale::Codec,
@@ -399,6 +433,38 @@ mod tests {
         let v2 = Value::<i32>::bind_with(b"v");
         assert_eq!(*v2, 3);
     }
+
+    #[test]
+    fn reload_after_revert() {
+        use crate::lang_core::env::test;
+
+        test::run_test(|| {
+            let mut v = Value::<i32>::bind_with(b"v");
+            v.set(1);
+            v.flush();
+            let id = test::snapshot();
+
+            v.set(2);
+            v.flush();
+            assert_eq!(*v, 2);
+
+            test::revert_to(id);
+            assert_eq!(*v, 1);
+            v.set(3);
+            v.flush();
+            test::revert_to(id);
+            assert_eq!(*v, 1);
+        });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "storage field `total_supply` is accessed before being initialized"
+    )]
+    fn uninitialized_access_names_field() {
+        let v = Value::<u128>::bind_with_name(b"total_supply", "total_supply");
+        let _ = *v + 1;
+    }
 }
 
 impl<T> You_Should_Use_A_Container_To_Wrap_Your_State_Field_In_Storage for Value<T> {}
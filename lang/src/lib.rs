@@ -17,13 +17,25 @@
 #![feature(const_panic)]
 #![feature(specialization)]
 
+#[cfg(feature = "contract")]
+pub mod acl;
+pub mod codec;
 mod dispatch_error;
 mod env_access;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+#[macro_use]
 pub mod intrinsics;
 mod lang_core;
+#[cfg(feature = "collaboration")]
+pub mod lifecycle;
+#[cfg(feature = "contract")]
+pub mod metadata;
 #[cfg(feature = "std")]
 pub mod mock;
 mod traits;
+#[cfg(feature = "contract")]
+pub mod upgrade;
 
 pub use dispatch_error::{DispatchError, DispatchResult, DispatchRetInfo};
 pub use env_access::EnvAccess;
@@ -50,10 +62,16 @@ cfg_if! {
             fn fetch(&self) -> Self::Target;
         }
 
-        pub use liquid_lang_macro::{collaboration, InOut};
-    } else if #[cfg(all(feature = "contract", feature = "solidity-compatible"))] {
-        pub use liquid_lang_macro::{contract, interface, InOut, State};
-    } else if #[cfg(all(feature = "contract", not(feature = "solidity-compatible")))] {
-        pub use liquid_lang_macro::{contract, interface, InOut};
+        pub use liquid_lang_macro::collaboration;
+    }
+}
+
+cfg_if! {
+    if #[cfg(all(feature = "contract", feature = "solidity-compatible"))] {
+        pub use liquid_lang_macro::{contract, interface, mixin, test, InOut, State};
+    } else if #[cfg(feature = "contract")] {
+        pub use liquid_lang_macro::{contract, interface, mixin, test, InOut};
+    } else if #[cfg(feature = "collaboration")] {
+        pub use liquid_lang_macro::{test, InOut};
     }
 }
@@ -0,0 +1,110 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Events emitted by the generated code of collaborations whenever a contract
+//! is signed, has one of its rights exercised or is archived, so that
+//! off-chain systems can follow the ledger without knowing the templates.
+//!
+//! Every log carries 3 topics:
+//! 1. the hash of the name of the operation, i.e., `ContractCreated`,
+//!    `ContractExercised` or `ContractArchived`;
+//! 2. the hash of the name of the contract template;
+//! 3. the id of the contract, padded with leading zeros.
+//!
+//! The data of the log is the SCALE encoding of the whole `LifecycleEvent`.
+
+use crate::lang_core::env;
+use liquid_prelude::{string::String, vec::Vec};
+use liquid_primitives::{
+    hash::hash,
+    types::{Address, Hash},
+    Topics, MAX_TOPICS,
+};
+use scale::{Decode, Encode};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum Operation {
+    Created,
+    Exercised,
+    Archived,
+}
+
+impl Operation {
+    fn name(self) -> &'static str {
+        match self {
+            Operation::Created => "ContractCreated",
+            Operation::Exercised => "ContractExercised",
+            Operation::Archived => "ContractArchived",
+        }
+    }
+
+    /// Hash of the name of this operation, which is the first topic of logs
+    /// of this operation.
+    pub fn sig_hash(self) -> Hash {
+        hash(self.name().as_bytes()).into()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct LifecycleEvent {
+    pub operation: Operation,
+    pub template: String,
+    pub contract_id: u32,
+    /// Name of the exercised right, empty for other operations.
+    pub right: String,
+    /// Parties on whose authority the operation is performed.
+    pub parties: Vec<Address>,
+}
+
+impl LifecycleEvent {
+    /// Recovers an event from topics and data of a log, returns `None` if the
+    /// log is not emitted as a lifecycle event.
+    pub fn try_from_log(topics: &[Hash], data: &[u8]) -> Option<Self> {
+        let event = <Self as Decode>::decode(&mut &data[..]).ok()?;
+        if topics.first() != Some(&event.operation.sig_hash()) {
+            return None;
+        }
+        Some(event)
+    }
+}
+
+impl Topics for LifecycleEvent {
+    fn write_topics(&self, topics: &mut [Hash; MAX_TOPICS]) -> usize {
+        topics[0] = self.operation.sig_hash();
+        topics[1] = hash(self.template.as_bytes()).into();
+        let mut id = [0x00; 32];
+        id[28..].copy_from_slice(&self.contract_id.to_be_bytes());
+        topics[2] = id.into();
+        3
+    }
+}
+
+#[cfg(feature = "std")]
+impl env::test::EmittedEvent for LifecycleEvent {}
+
+/// Emits a lifecycle event, which is used by the generated code only.
+#[doc(hidden)]
+pub fn emit(
+    operation: Operation,
+    template: &str,
+    contract_id: u32,
+    right: &str,
+    parties: Vec<Address>,
+) {
+    env::emit(LifecycleEvent {
+        operation,
+        template: template.into(),
+        contract_id,
+        right: right.into(),
+        parties,
+    });
+}
@@ -0,0 +1,55 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metadata embedded into contracts for on-chain source verification.
+//!
+//! Every contract carries a SCALE encoded [`Metadata`] blob. When compiled to
+//! Wasm, the blob is placed in a custom section named [`SECTION_NAME`], which
+//! can be extracted from the deployed code without executing it and then be
+//! decoded via `<Metadata as scale::Decode>::decode`.
+
+use liquid_prelude::string::String;
+use scale::{Decode, Encode};
+
+/// Name of the Wasm custom section holding the metadata.
+pub const SECTION_NAME: &str = "liquid_metadata";
+
+/// Version of the layout of [`Metadata`], increased whenever a field is
+/// added, removed or changed.
+pub const SCHEMA_VERSION: u8 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Metadata {
+    /// Version of the layout, see [`SCHEMA_VERSION`].
+    pub schema_version: u8,
+    /// Version of liquid by which the contract is compiled.
+    pub compiler_version: String,
+    /// Name of the contract, i.e., the name of its storage struct.
+    pub name: String,
+    /// Hash of the source of the contract module, computed by the same hash
+    /// algorithm as the one used for selectors.
+    pub source_hash: [u8; 32],
+    /// Semantic version of the contract given via
+    /// `#[liquid::contract(version = "...")]`, if any.
+    pub version: Option<String>,
+}
+
+/// Implemented by the storage struct of every contract.
+pub trait ContractMetadata {
+    /// The SCALE encoded metadata, as is embedded in the custom section.
+    const ENCODED: &'static [u8];
+
+    fn metadata() -> Metadata {
+        <Metadata as Decode>::decode(&mut &Self::ENCODED[..])
+            .expect("the embedded metadata should always be well-formed")
+    }
+}
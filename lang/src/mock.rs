@@ -10,7 +10,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::marker::PhantomData;
+use std::{
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    rc::Rc,
+};
 
 pub trait ReturnDefault<T> {
     fn return_default() -> Option<T>;
@@ -32,3 +36,232 @@ where
         Some(T::default())
     }
 }
+
+/// How many times an expectation is allowed to be matched.
+#[derive(Debug, Clone, Copy)]
+pub enum Times {
+    Any,
+    Exactly(usize),
+    AtLeast(usize),
+    AtMost(usize),
+}
+
+impl Times {
+    fn allows(self, calls: usize) -> bool {
+        match self {
+            Times::Any | Times::AtLeast(_) => true,
+            Times::Exactly(n) | Times::AtMost(n) => calls <= n,
+        }
+    }
+
+    fn is_satisfied_by(self, calls: usize) -> bool {
+        match self {
+            Times::Any => true,
+            Times::Exactly(n) => calls == n,
+            Times::AtLeast(n) => calls >= n,
+            Times::AtMost(n) => calls <= n,
+        }
+    }
+}
+
+impl core::fmt::Display for Times {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Times::Any => write!(f, "any times"),
+            Times::Exactly(n) => write!(f, "exactly {} time(s)", n),
+            Times::AtLeast(n) => write!(f, "at least {} time(s)", n),
+            Times::AtMost(n) => write!(f, "at most {} time(s)", n),
+        }
+    }
+}
+
+/// Steps through expectations of mocked interfaces in the order they were
+/// added to it via `in_sequence`.
+///
+/// A step may be matched repeatedly before moving to the next one, but
+/// steps can not be skipped or revisited.
+#[derive(Debug, Clone, Default)]
+pub struct Sequence(Rc<RefCell<SequenceState>>);
+
+#[derive(Debug, Default)]
+struct SequenceState {
+    len: usize,
+    /// The step matched most recently.
+    current: Option<usize>,
+}
+
+impl Sequence {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn push(&self) -> usize {
+        let mut state = self.0.borrow_mut();
+        state.len += 1;
+        state.len - 1
+    }
+
+    fn step(&self, position: usize, name: &str) {
+        let mut state = self.0.borrow_mut();
+        let expected = match state.current {
+            None => 0,
+            Some(current) if current == position => current,
+            Some(current) => current + 1,
+        };
+        if position != expected {
+            panic!(
+                "`{}` is called out of sequence, it is step {} but step {} is expected",
+                name, position, expected
+            );
+        }
+        state.current = Some(position);
+    }
+}
+
+/// Tracks the calls matched by an expectation of a mocked interface.
+#[derive(Debug)]
+pub struct CallCounter {
+    times: Times,
+    calls: usize,
+    sequence: Option<(Sequence, usize)>,
+}
+
+impl Default for CallCounter {
+    fn default() -> Self {
+        Self {
+            times: Times::Any,
+            calls: 0,
+            sequence: None,
+        }
+    }
+}
+
+impl CallCounter {
+    pub fn set_times(&mut self, times: Times) {
+        self.times = times;
+    }
+
+    pub fn join_sequence(&mut self, sequence: &Sequence) {
+        self.sequence = Some((sequence.clone(), sequence.push()));
+    }
+
+    /// Returns whether one more call is still allowed.
+    pub fn is_available(&self) -> bool {
+        self.times.allows(self.calls + 1)
+    }
+
+    pub fn record(&mut self, name: &str) {
+        if let Some((sequence, position)) = &self.sequence {
+            sequence.step(*position, name);
+        }
+        self.calls += 1;
+    }
+
+    /// Panics if the recorded calls do not satisfy the expected times.
+    pub fn verify(&self, name: &str) {
+        if !self.times.is_satisfied_by(self.calls) {
+            panic!(
+                "`{}` is expected to be called {}, but it is called {} time(s)",
+                name, self.times, self.calls
+            );
+        }
+    }
+}
+
+thread_local!(
+    static LENIENT_DEPTH: Cell<usize> = Cell::new(0);
+);
+
+/// Makes mocked interfaces return default values for calls that match no
+/// expectation, instead of panicking, until the returned guard is dropped.
+///
+/// Only methods whose return type implements `Default` can be answered in
+/// this way, other methods still panic when no expectation is matched.
+#[must_use = "the lenient mode ends as soon as the guard is dropped"]
+pub fn lenient() -> Lenient {
+    LENIENT_DEPTH.with(|depth| depth.set(depth.get() + 1));
+    Lenient { _private: () }
+}
+
+/// Returns whether mocked interfaces are in lenient mode.
+pub fn is_lenient() -> bool {
+    LENIENT_DEPTH.with(|depth| depth.get() > 0)
+}
+
+/// Guard returned by [`lenient`].
+pub struct Lenient {
+    _private: (),
+}
+
+impl Drop for Lenient {
+    fn drop(&mut self) {
+        LENIENT_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_calls() {
+        let mut counter = CallCounter::default();
+        counter.set_times(Times::Exactly(2));
+        assert!(counter.is_available());
+        counter.record("f");
+        counter.record("f");
+        assert!(!counter.is_available());
+        counter.verify("f");
+    }
+
+    #[test]
+    #[should_panic(expected = "`f` is expected to be called at least 2 time(s), but it \
+                               is called 1 time(s)")]
+    fn too_few_calls() {
+        let mut counter = CallCounter::default();
+        counter.set_times(Times::AtLeast(2));
+        counter.record("f");
+        counter.verify("f");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "`g` is called out of sequence, it is step 1 but step 0 is expected"
+    )]
+    fn out_of_sequence() {
+        let sequence = Sequence::new();
+        let mut first = CallCounter::default();
+        first.join_sequence(&sequence);
+        let mut second = CallCounter::default();
+        second.join_sequence(&sequence);
+
+        second.record("g");
+    }
+
+    #[test]
+    fn in_sequence() {
+        let sequence = Sequence::new();
+        let mut first = CallCounter::default();
+        first.join_sequence(&sequence);
+        let mut second = CallCounter::default();
+        second.join_sequence(&sequence);
+
+        first.record("f");
+        first.record("f");
+        second.record("g");
+    }
+
+    #[test]
+    fn lenient_guard() {
+        assert!(!is_lenient());
+        {
+            let _outer = lenient();
+            {
+                let _inner = lenient();
+                assert!(is_lenient());
+            }
+            assert!(is_lenient());
+        }
+        assert!(!is_lenient());
+    }
+}
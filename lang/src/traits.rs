@@ -11,9 +11,13 @@
 // limitations under the License.
 
 use cfg_if::cfg_if;
+use core::cmp::Ordering;
 use liquid_macro::seq;
-use liquid_prelude::{string::String, vec::Vec};
-use liquid_primitives::{types::*, Selector};
+use liquid_prelude::{collections::BTreeMap, string::String, vec::Vec};
+use liquid_primitives::{
+    types::{address::ADDRESS_LENGTH, hash::HASH_LENGTH, *},
+    Selector,
+};
 
 cfg_if! {
     if #[cfg(feature = "solidity-compatible")] {
@@ -35,6 +39,92 @@ pub trait FnSelector {
     const SELECTOR: Selector;
 }
 
+/// Returns whether `selector` appears in `selectors` from index `start` on.
+/// Contracts use it to reject colliding selectors at compile time.
+#[doc(hidden)]
+pub const fn contains_selector(
+    selectors: &[Selector],
+    start: usize,
+    selector: &Selector,
+) -> bool {
+    let mut i = start;
+    while i < selectors.len() {
+        let other = &selectors[i];
+        if other[0] == selector[0]
+            && other[1] == selector[1]
+            && other[2] == selector[2]
+            && other[3] == selector[3]
+        {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn selector_lt(lhs: &Selector, rhs: &Selector) -> bool {
+    let mut i = 0;
+    while i < lhs.len() {
+        if lhs[i] != rhs[i] {
+            return lhs[i] < rhs[i];
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Pairs `selectors` with their indices and sorts the pairs by selector, so
+/// that the dispatcher finds the method to call via binary search rather than
+/// comparing the selector with every method. Contracts build the table at
+/// compile time.
+#[doc(hidden)]
+pub const fn sort_selectors<const N: usize>(
+    selectors: [Selector; N],
+) -> [(Selector, usize); N] {
+    let mut table = [([0u8; 4], 0usize); N];
+    let mut i = 0;
+    while i < N {
+        table[i] = (selectors[i], i);
+        i += 1;
+    }
+
+    // Insertion sort, as slices can't be sorted in const contexts.
+    let mut i = 1;
+    while i < N {
+        let mut j = i;
+        while j > 0 && selector_lt(&table[j].0, &table[j - 1].0) {
+            let prev = table[j - 1];
+            table[j - 1] = table[j];
+            table[j] = prev;
+            j -= 1;
+        }
+        i += 1;
+    }
+    table
+}
+
+/// Returns the index paired with `selector` in a table built by
+/// `sort_selectors`.
+#[doc(hidden)]
+pub fn search_selector(
+    table: &[(Selector, usize)],
+    selector: &Selector,
+) -> Option<usize> {
+    search_selector_by(table, |other| other.cmp(selector))
+}
+
+/// Same as `search_selector`, with `compare` ordering a selector in the table
+/// against the one searched for.
+fn search_selector_by<F>(table: &[(Selector, usize)], mut compare: F) -> Option<usize>
+where
+    F: FnMut(&Selector) -> Ordering,
+{
+    table
+        .binary_search_by(|(other, _)| compare(other))
+        .ok()
+        .map(|i| table[i].1)
+}
+
 pub trait FnMutability {
     const IS_MUT: bool;
 }
@@ -58,6 +148,12 @@ cfg_if! {
             fn acquire_signers(&self) -> liquid_prelude::collections::BTreeSet<&Address>;
         }
 
+        /// Every contract needs to implement this trait to get parties who
+        /// can look up the contract without being its signers.
+        pub trait AcquireObservers {
+            fn acquire_observers(&self) -> liquid_prelude::collections::BTreeSet<&Address>;
+        }
+
         #[allow(non_camel_case_types)]
         pub trait Parties_Should_Be_Address_Or_Address_Collection<'a>
         {
@@ -97,6 +193,10 @@ cfg_if! {
             type Contract: ContractName;
             type ContractId;
             fn fetch(&self) -> Self::Contract;
+            /// Same as `fetch`, but returns `None` rather than reverting if the
+            /// contract doesn't exist or has been abolished. Fetching a
+            /// contract which the caller can't see still reverts.
+            fn try_fetch(&self) -> Option<Self::Contract>;
             fn sign_new_contract(contract: Self::Contract) -> Self::ContractId;
 
             fn inexistent_error(id: u32) {
@@ -118,6 +218,16 @@ cfg_if! {
                 error_info.push_str("` had been abolished already");
                 crate::env::revert(&error_info);
             }
+
+            fn unauthorized_fetching_error(id: u32) {
+                let mut error_info = String::from("fetching of contract `");
+                error_info.push_str(Self::Contract::CONTRACT_NAME);
+                error_info.push_str("` with id `");
+                use liquid_prelude::string::ToString;
+                error_info.push_str(&id.to_string());
+                error_info.push_str("` is not permitted");
+                crate::env::revert(&error_info);
+            }
         }
     }
 }
@@ -165,14 +275,67 @@ pub trait You_Should_Use_An_Valid_Event_Data_Type: Sized {
 pub trait You_Should_Use_An_Valid_Event_Topic_Type: Sized {
     type T = Self;
 
+    /// Puts the encoded value in a topic as is, padded with leading zeros, if
+    /// it fits in 32 bytes, otherwise puts the hash of it, just like
+    /// Solidity does for values which do not fit in a topic.
     fn topic(&self) -> Hash
     where
         Self: Encode,
     {
-        self.encode().into()
+        let encoded = self.encode();
+        if encoded.len() > HASH_LENGTH {
+            liquid_primitives::hash::hash(&encoded).into()
+        } else {
+            let mut word = [0x00; HASH_LENGTH];
+            word[(HASH_LENGTH - encoded.len())..].copy_from_slice(&encoded);
+            word.into()
+        }
+    }
+
+    /// The encoding of the value as an element of an indexed array, whose
+    /// topic is the hash of the concatenated encodings of its elements. For
+    /// value types it's the same as the topic.
+    fn topic_elem(&self) -> Vec<u8>
+    where
+        Self: Encode,
+    {
+        self.topic().as_bytes().to_vec()
     }
 }
 
+/// Implemented by the generated counterparts of events whose `String` and
+/// `Bytes` fields are borrowed as `&str` and `&[u8]`, see `env::emit_ref`.
+pub trait BorrowedEvent {
+    /// The event owning all its fields.
+    type Owned: 'static;
+
+    fn into_owned_event(self) -> Self::Owned;
+}
+
+/// Structs deriving `InOut` are always put in topics as the hash of their
+/// encoding, even if it fits in 32 bytes, so that logs can be filtered on
+/// composite keys such as `(market_id, side)` regardless of their size.
+#[doc(hidden)]
+pub fn composite_topic<T: Encode>(value: &T) -> Hash {
+    liquid_primitives::hash::hash(&value.encode()).into()
+}
+
+/// Strings and bytes in indexed arrays are put as is, padded to multiples of
+/// 32 bytes, rather than as their hashes.
+fn pad_topic_elem(bytes: &[u8]) -> Vec<u8> {
+    let padding = (HASH_LENGTH - bytes.len() % HASH_LENGTH) % HASH_LENGTH;
+    let mut elem = bytes.to_vec();
+    elem.resize(bytes.len() + padding, 0x00);
+    elem
+}
+
+/// Only scalar types and strings can be used as keys of maps appearing in
+/// inputs, outputs or events, so that keys have a stable order.
+#[allow(non_camel_case_types)]
+pub trait You_Should_Use_An_Valid_Map_Key_Type: Sized + Ord {
+    type T = Self;
+}
+
 macro_rules! gen_basic_type_notations {
     ($t:ty) => {
         cfg_if! {
@@ -194,7 +357,7 @@ macro_rules! gen_type_notations {
     ($t:ty) => {
         gen_basic_type_notations!($t);
 
-        impl You_Should_Use_An_Valid_Event_Topic_Type for $t {}
+        impl You_Should_Use_An_Valid_Map_Key_Type for $t {}
     };
 }
 
@@ -206,23 +369,125 @@ macro_rules! impl_for_primitives {
     };
 }
 
-impl_for_primitives!(
-    u8, u16, u32, u64, u128, u256, i8, i16, i32, i64, i128, i256, bool, Address
-);
+impl_for_primitives!(u256, i256, bool, Address);
+
+// Like integers below, these types are put in topics as their Solidity ABI
+// word.
+impl You_Should_Use_An_Valid_Event_Topic_Type for u256 {
+    fn topic(&self) -> Hash {
+        self.to_be_bytes().into()
+    }
+}
+
+impl You_Should_Use_An_Valid_Event_Topic_Type for i256 {
+    fn topic(&self) -> Hash {
+        self.to_be_bytes().into()
+    }
+}
+
+impl You_Should_Use_An_Valid_Event_Topic_Type for bool {
+    fn topic(&self) -> Hash {
+        let mut word = [0x00; HASH_LENGTH];
+        word[HASH_LENGTH - 1] = *self as u8;
+        word.into()
+    }
+}
+
+impl You_Should_Use_An_Valid_Event_Topic_Type for Address {
+    fn topic(&self) -> Hash {
+        let mut word = [0x00; HASH_LENGTH];
+        word[(HASH_LENGTH - ADDRESS_LENGTH)..].copy_from_slice(&self.0);
+        word.into()
+    }
+}
+
+/// Fixed-width integers are always put in topics as a 32-byte big-endian
+/// word, sign-extended for signed types, which is the same as how they are
+/// encoded by the Solidity ABI. Relying on `Encode` instead would produce
+/// topics of variable length when SCALE codec is in use.
+macro_rules! impl_for_integers {
+    ($($t:ty),*) => {
+        $(
+            gen_basic_type_notations!($t);
+            impl You_Should_Use_An_Valid_Map_Key_Type for $t {}
+
+            impl You_Should_Use_An_Valid_Event_Topic_Type for $t {
+                fn topic(&self) -> Hash {
+                    // unused comparisons will be optimized by compiler
+                    #[allow(unused_comparisons)]
+                    let mut word = if *self >= 0 {
+                        [0x00; HASH_LENGTH]
+                    } else {
+                        [0xff; HASH_LENGTH]
+                    };
+                    let be = self.to_be_bytes();
+                    word[(HASH_LENGTH - be.len())..].copy_from_slice(&be);
+                    word.into()
+                }
+            }
+        )*
+    };
+}
+
+impl_for_integers!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
 
 gen_basic_type_notations!(Bytes);
+impl You_Should_Use_An_Valid_Event_Topic_Type for Bytes {
+    fn topic(&self) -> Hash {
+        liquid_primitives::hash::hash(self).into()
+    }
+
+    fn topic_elem(&self) -> Vec<u8> {
+        pad_topic_elem(self)
+    }
+}
+
 seq!(N in 1..=32 {
     #(
         gen_type_notations!(Bytes#N);
+
+        // Fixed-size bytes are left-aligned in their word.
+        impl You_Should_Use_An_Valid_Event_Topic_Type for Bytes#N {
+            fn topic(&self) -> Hash {
+                let mut word = [0x00; HASH_LENGTH];
+                word[..(N as usize)].copy_from_slice(&self.0);
+                word.into()
+            }
+        }
     )*
 });
 
 gen_basic_type_notations!(String);
+impl You_Should_Use_An_Valid_Map_Key_Type for String {}
 impl You_Should_Use_An_Valid_Event_Topic_Type for String {
     type T = Self;
     fn topic(&self) -> Hash {
         liquid_primitives::hash::hash(self.as_bytes()).into()
     }
+
+    fn topic_elem(&self) -> Vec<u8> {
+        pad_topic_elem(self.as_bytes())
+    }
+}
+
+impl<T, const N: usize> You_Should_Use_An_Valid_Event_Topic_Type for [T; N]
+where
+    T: You_Should_Use_An_Valid_Event_Topic_Type + Encode,
+{
+    fn topic(&self) -> Hash {
+        liquid_primitives::hash::hash(&concat_topic_elems(self)).into()
+    }
+
+    fn topic_elem(&self) -> Vec<u8> {
+        concat_topic_elems(self)
+    }
+}
+
+fn concat_topic_elems<T>(elems: &[T]) -> Vec<u8>
+where
+    T: You_Should_Use_An_Valid_Event_Topic_Type + Encode,
+{
+    elems.iter().flat_map(|elem| elem.topic_elem()).collect()
 }
 
 cfg_if! {
@@ -275,6 +540,31 @@ cfg_if! {
             T: You_Should_Use_An_Valid_Element_Type
         {
         }
+        impl<K, V> You_Should_Use_An_Valid_Element_Type for BTreeMap<K, V> where
+            K: You_Should_Use_An_Valid_Map_Key_Type + You_Should_Use_An_Valid_Element_Type,
+            V: You_Should_Use_An_Valid_Element_Type
+        {
+        }
+        impl<K, V> You_Should_Use_An_Valid_Return_Type for BTreeMap<K, V> where
+            K: You_Should_Use_An_Valid_Map_Key_Type + You_Should_Use_An_Valid_Element_Type,
+            V: You_Should_Use_An_Valid_Element_Type
+        {
+        }
+        impl<K, V> You_Should_Use_An_Valid_Input_Type for BTreeMap<K, V> where
+            K: You_Should_Use_An_Valid_Map_Key_Type + You_Should_Use_An_Valid_Element_Type,
+            V: You_Should_Use_An_Valid_Element_Type
+        {
+        }
+        impl<K, V> You_Should_Use_An_Valid_Event_Data_Type for BTreeMap<K, V> where
+            K: You_Should_Use_An_Valid_Map_Key_Type + You_Should_Use_An_Valid_Element_Type,
+            V: You_Should_Use_An_Valid_Element_Type
+        {
+        }
+        impl<K, V> You_Should_Use_An_Valid_InOut_Type for BTreeMap<K, V> where
+            K: You_Should_Use_An_Valid_Map_Key_Type + You_Should_Use_An_Valid_Element_Type,
+            V: You_Should_Use_An_Valid_Element_Type
+        {
+        }
     } else {
         impl<T> You_Should_Use_An_Valid_Return_Type for Vec<T> where
             T: You_Should_Use_An_Valid_Return_Type
@@ -308,6 +598,26 @@ cfg_if! {
             T: You_Should_Use_An_Valid_Event_Data_Type
         {
         }
+        impl<K, V> You_Should_Use_An_Valid_Return_Type for BTreeMap<K, V> where
+            K: You_Should_Use_An_Valid_Map_Key_Type + You_Should_Use_An_Valid_Return_Type,
+            V: You_Should_Use_An_Valid_Return_Type
+        {
+        }
+        impl<K, V> You_Should_Use_An_Valid_Input_Type for BTreeMap<K, V> where
+            K: You_Should_Use_An_Valid_Map_Key_Type + You_Should_Use_An_Valid_Input_Type,
+            V: You_Should_Use_An_Valid_Input_Type
+        {
+        }
+        impl<K, V> You_Should_Use_An_Valid_Event_Data_Type for BTreeMap<K, V> where
+            K: You_Should_Use_An_Valid_Map_Key_Type + You_Should_Use_An_Valid_Event_Data_Type,
+            V: You_Should_Use_An_Valid_Event_Data_Type
+        {
+        }
+        impl<K, V> You_Should_Use_An_Valid_Field_Type for BTreeMap<K, V> where
+            K: You_Should_Use_An_Valid_Map_Key_Type + You_Should_Use_An_Valid_Field_Type,
+            V: You_Should_Use_An_Valid_Field_Type
+        {
+        }
     }
 }
 
@@ -456,19 +766,263 @@ cfg_if! {
             E: You_Should_Use_An_Valid_Event_Data_Type
         {
         }
+    } else {
+        impl<T> You_Should_Use_An_Valid_Element_Type for Option<T>
+        where
+            T: You_Should_Use_An_Valid_Element_Type + Default
+        {
+        }
+        impl<T> You_Should_Use_An_Valid_InOut_Type for Option<T>
+        where
+            T: You_Should_Use_An_Valid_Element_Type + Default
+        {
+        }
+        impl<T> You_Should_Use_An_Valid_Return_Type for Option<T>
+        where
+            T: You_Should_Use_An_Valid_Element_Type + Default
+        {
+        }
+        impl<T> You_Should_Use_An_Valid_Input_Type for Option<T>
+        where
+            T: You_Should_Use_An_Valid_Element_Type + Default
+        {
+        }
+        impl<T> You_Should_Use_An_Valid_Event_Data_Type for Option<T>
+        where
+            T: You_Should_Use_An_Valid_Element_Type + Default
+        {
+        }
+
+        impl<T, E> You_Should_Use_An_Valid_Element_Type for Result<T, E>
+        where
+            T: You_Should_Use_An_Valid_Element_Type + Default,
+            E: You_Should_Use_An_Valid_Element_Type + Default,
+        {
+        }
+        impl<T, E> You_Should_Use_An_Valid_InOut_Type for Result<T, E>
+        where
+            T: You_Should_Use_An_Valid_Element_Type + Default,
+            E: You_Should_Use_An_Valid_Element_Type + Default,
+        {
+        }
+        impl<T, E> You_Should_Use_An_Valid_Return_Type for Result<T, E>
+        where
+            T: You_Should_Use_An_Valid_Element_Type + Default,
+            E: You_Should_Use_An_Valid_Element_Type + Default,
+        {
+        }
+        impl<T, E> You_Should_Use_An_Valid_Input_Type for Result<T, E>
+        where
+            T: You_Should_Use_An_Valid_Element_Type + Default,
+            E: You_Should_Use_An_Valid_Element_Type + Default,
+        {
+        }
+        impl<T, E> You_Should_Use_An_Valid_Event_Data_Type for Result<T, E>
+        where
+            T: You_Should_Use_An_Valid_Element_Type + Default,
+            E: You_Should_Use_An_Valid_Element_Type + Default,
+        {
+        }
     }
 }
 
 cfg_if! {
-    if #[cfg(feature = "contract")] {
-        #[cfg(feature = "contract-abi-gen")]
+    if #[cfg(all(feature = "contract", feature = "contract-abi-gen"))] {
         pub trait GenerateAbi {
             fn generate_abi() -> liquid_abi_gen::ContractAbi;
         }
-    } else if #[cfg(feature = "collaboration")] {
-        #[cfg(feature = "collaboration-abi-gen")]
+    } else if #[cfg(all(feature = "collaboration", feature = "collaboration-abi-gen"))] {
         pub trait GenerateAbi {
             fn generate_abi() -> liquid_abi_gen::CollaborationAbi;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn topic_of<T: You_Should_Use_An_Valid_Event_Topic_Type + Encode>(t: T) -> Hash {
+        t.topic()
+    }
+
+    #[test]
+    fn find_selectors() {
+        const SELECTORS: [Selector; 3] = [[1, 2, 3, 4], [5, 6, 7, 8], [1, 2, 3, 4]];
+        assert!(contains_selector(&SELECTORS, 0, &[5, 6, 7, 8]));
+        assert!(contains_selector(&SELECTORS, 1, &SELECTORS[0]));
+        assert!(!contains_selector(&SELECTORS, 2, &SELECTORS[1]));
+        assert!(!contains_selector(&SELECTORS, 3, &SELECTORS[0]));
+        assert!(!contains_selector(&[], 0, &SELECTORS[0]));
+    }
+
+    #[test]
+    fn search_sorted_selectors() {
+        const SELECTORS: [Selector; 5] = [
+            [0xff, 0, 0, 0],
+            [0x12, 0x34, 0x56, 0x78],
+            [0, 0, 0, 1],
+            [0x12, 0x34, 0x56, 0x77],
+            [0x80, 0, 0, 0],
+        ];
+        const TABLE: [(Selector, usize); 5] = sort_selectors(SELECTORS);
+        assert!(TABLE.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        // Finds the same method as comparing with every selector does.
+        for (i, selector) in SELECTORS.iter().enumerate() {
+            assert_eq!(search_selector(&TABLE, selector), Some(i));
+        }
+        assert_eq!(search_selector(&TABLE, &[0x12, 0x34, 0x56, 0x79]), None);
+        assert_eq!(search_selector(&sort_selectors([]), &SELECTORS[0]), None);
+    }
+
+    #[test]
+    fn search_selectors_in_logarithmic_comparisons() {
+        const N: usize = 64;
+        let mut selectors = [[0u8; 4]; N];
+        for (i, selector) in selectors.iter_mut().enumerate() {
+            *selector = liquid_primitives::selector(format!("method{}()", i).as_bytes());
+        }
+        let table = sort_selectors(selectors);
+        assert!(table.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        let mut missing = selectors;
+        missing.iter_mut().for_each(|selector| selector[3] ^= 0x01);
+        for len in 1..=N {
+            // Tables of `len` selectors are searched within ceil(log2(len)) + 1
+            // comparisons, i.e., at most 7 for 64 methods.
+            let bound = (0..).find(|bits| 1usize << bits >= len).unwrap() + 1;
+            let table = &table[..len];
+            for selector in selectors.iter().chain(missing.iter()) {
+                let mut comparisons = 0;
+                let found = search_selector_by(table, |other| {
+                    comparisons += 1;
+                    other.cmp(selector)
+                });
+                assert!(comparisons <= bound, "{} > {}", comparisons, bound);
+                assert_eq!(found, search_selector(table, selector));
+                match table.iter().find(|(other, _)| other == selector) {
+                    Some((_, index)) => assert_eq!(found, Some(*index)),
+                    None => assert_eq!(found, None),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn composite_topics_are_hashed() {
+        let key = (7u32, true);
+        assert_eq!(
+            composite_topic(&key),
+            Hash::from(liquid_primitives::hash::hash(&key.encode()))
+        );
+        assert_ne!(composite_topic(&key), composite_topic(&(7u32, false)));
+    }
+
+    #[test]
+    fn signed_integer_topics_are_sign_extended() {
+        assert_eq!(topic_of(-1i8), Hash::from([0xff; HASH_LENGTH]));
+        assert_eq!(topic_of(-1i128), Hash::from([0xff; HASH_LENGTH]));
+
+        let mut expected = [0xff; HASH_LENGTH];
+        expected[HASH_LENGTH - 2..].copy_from_slice(&(-300i16).to_be_bytes());
+        assert_eq!(topic_of(-300i16), Hash::from(expected));
+
+        let mut expected = [0x00; HASH_LENGTH];
+        expected[HASH_LENGTH - 8..].copy_from_slice(&i64::MAX.to_be_bytes());
+        assert_eq!(topic_of(i64::MAX), Hash::from(expected));
+    }
+
+    #[test]
+    fn unsigned_integer_topics_are_zero_extended() {
+        let mut expected = [0x00; HASH_LENGTH];
+        expected[HASH_LENGTH - 1] = 0xff;
+        assert_eq!(topic_of(u8::MAX), Hash::from(expected));
+
+        let mut expected = [0x00; HASH_LENGTH];
+        expected[HASH_LENGTH - 16..].copy_from_slice(&u128::MAX.to_be_bytes());
+        assert_eq!(topic_of(u128::MAX), Hash::from(expected));
+    }
+
+    #[test]
+    fn value_topics_are_words() {
+        let mut expected = [0x00; HASH_LENGTH];
+        expected[HASH_LENGTH - 1] = 1;
+        assert_eq!(topic_of(true), Hash::from(expected));
+        assert_eq!(topic_of(u256::from(1u8)), Hash::from(expected));
+        assert_eq!(topic_of(i256::from(-1)), Hash::from([0xff; HASH_LENGTH]));
+
+        let addr = Address::new([0x11; ADDRESS_LENGTH]);
+        let mut expected = [0x00; HASH_LENGTH];
+        expected[HASH_LENGTH - ADDRESS_LENGTH..].copy_from_slice(&addr.0);
+        assert_eq!(topic_of(addr), Hash::from(expected));
+
+        let mut expected = [0x00; HASH_LENGTH];
+        expected[..4].copy_from_slice(&[1, 2, 3, 4]);
+        assert_eq!(topic_of(Bytes4([1, 2, 3, 4])), Hash::from(expected));
+    }
+
+    #[test]
+    fn dynamic_topics_are_hashed() {
+        let expected = Hash::from(liquid_primitives::hash::hash(&[1, 2, 3]));
+        assert_eq!(topic_of(Bytes::from(&[1, 2, 3])), expected);
+        assert_eq!(
+            topic_of(String::from("liquid")),
+            Hash::from(liquid_primitives::hash::hash(b"liquid"))
+        );
+    }
+
+    #[test]
+    fn array_topics_hash_the_elements() {
+        let mut words = [0x00; 2 * HASH_LENGTH];
+        words[HASH_LENGTH - 1] = 1;
+        words[2 * HASH_LENGTH - 1] = 2;
+        assert_eq!(
+            topic_of([1u8, 2u8]),
+            Hash::from(liquid_primitives::hash::hash(&words))
+        );
+        assert_eq!(
+            topic_of([[1u8], [2u8]]),
+            Hash::from(liquid_primitives::hash::hash(&words))
+        );
+
+        let mut padded = [0x00; 2 * HASH_LENGTH];
+        padded[..2].copy_from_slice(b"ab");
+        padded[HASH_LENGTH..(HASH_LENGTH + 2)].copy_from_slice(b"cd");
+        assert_eq!(
+            topic_of([String::from("ab"), String::from("cd")]),
+            Hash::from(liquid_primitives::hash::hash(&padded))
+        );
+    }
+
+    #[cfg(all(feature = "solidity-compatible", feature = "std"))]
+    #[test]
+    fn static_array_topics_match_abi_encoding() {
+        let value = [u256::from(7u8), u256::from(8u8), u256::from(9u8)];
+        assert_eq!(
+            topic_of(value),
+            Hash::from(liquid_primitives::hash::hash(&Encode::encode(&value)))
+        );
+    }
+
+    #[cfg(feature = "solidity-compatible")]
+    #[test]
+    fn value_topics_match_abi_encoding() {
+        let value = u256::from(u128::MAX);
+        assert_eq!(topic_of(value), Hash::from(Encode::encode(&value)));
+        let value = i256::from(-42);
+        assert_eq!(topic_of(value), Hash::from(Encode::encode(&value)));
+        let value = Address::new([0xab; ADDRESS_LENGTH]);
+        assert_eq!(topic_of(value), Hash::from(Encode::encode(&value)));
+        let value = Bytes8([0xcd; 8]);
+        assert_eq!(topic_of(value), Hash::from(Encode::encode(&value)));
+        assert_eq!(topic_of(false), Hash::from(Encode::encode(&false)));
+    }
+
+    #[cfg(feature = "solidity-compatible")]
+    #[test]
+    fn integer_topics_match_abi_encoding() {
+        assert_eq!(topic_of(-42i32), Hash::from(Encode::encode(&-42i32)));
+        assert_eq!(topic_of(42u64), Hash::from(Encode::encode(&42u64)));
+    }
+}
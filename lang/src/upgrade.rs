@@ -0,0 +1,319 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime support of upgradeable contracts.
+//!
+//! A contract whose storage struct is marked with `#[liquid(upgradeable)]`
+//! can be deployed as a proxy: after the admin calls `upgrade_to` with the
+//! address of a newer deployment, every other call received by the proxy is
+//! forwarded via `delegatecall` to that deployment, which then runs against
+//! the storage of the proxy.
+//!
+//! Storage fields are keyed by their names, so a newer version may add fields
+//! freely but must keep the names and types of existing ones. The keys used
+//! by this module start with `$`, which can never appear in a field name, so
+//! they are reserved and never collide with user fields.
+//!
+//! Other layout changes require increasing `#[liquid(storage_version = N)]`
+//! and providing a `#[liquid(migration)]` method, which transforms the old
+//! state. When `upgrade_to` switches to a new implementation, the proxy asks
+//! the new implementation to migrate its storage, and the migration runs at
+//! most once for every storage version.
+
+use crate::{
+    intrinsics::require,
+    lang_core::env::{
+        self,
+        api::{get_storage, set_storage},
+        calldata::CallData,
+    },
+    DispatchError,
+};
+use liquid_prelude::vec::Vec;
+use liquid_primitives::{types::Address, Selector};
+
+#[cfg(feature = "solidity-compatible")]
+use liquid_abi_codec::{Decode, Encode};
+#[cfg(not(feature = "solidity-compatible"))]
+use scale::{Decode, Encode};
+
+/// Storage key of the address of current implementation.
+pub const IMPLEMENTATION_KEY: &[u8] = b"$liquid.implementation";
+/// Storage key of the account which is allowed to upgrade the contract.
+pub const ADMIN_KEY: &[u8] = b"$liquid.admin";
+/// Storage key of the version of the storage layout which the stored data
+/// conforms to.
+pub const STORAGE_VERSION_KEY: &[u8] = b"$liquid.storage_version";
+
+/// Whether on-chain builds may make the delegate calls which upgradeable
+/// contracts rely on.
+#[doc(hidden)]
+pub const DELEGATE_CALL_ENABLED: bool =
+    cfg!(any(feature = "std", feature = "ext-delegate-call"));
+
+/// Call data forwarded by a proxy is prefixed with this selector, which tells
+/// the implementation to dispatch the call by itself instead of forwarding
+/// it again.
+pub const DELEGATED_SELECTOR: Selector =
+    liquid_primitives::selector(b"$liquid.delegated");
+
+/// Selector of `__liquid_migrate`, which is exposed by every upgradeable
+/// contract to migrate the storage to its storage version.
+pub const MIGRATE_SELECTOR: Selector = {
+    let signature: &[u8] = if cfg!(feature = "solidity-compatible") {
+        b"__liquid_migrate()"
+    } else {
+        b"__liquid_migrate"
+    };
+    liquid_primitives::selector(signature)
+};
+
+/// Records the caller as admin and `storage_version` as the version of the
+/// storage, unless they have been recorded already. The contract macro calls
+/// this at the beginning of the constructor.
+pub fn initialize(storage_version: u32) {
+    if admin().is_none() {
+        set_storage(ADMIN_KEY, &env::get_caller());
+    }
+    if get_storage::<u32>(STORAGE_VERSION_KEY).is_err() {
+        set_storage(STORAGE_VERSION_KEY, &storage_version);
+    }
+}
+
+pub fn admin() -> Option<Address> {
+    get_storage::<Address>(ADMIN_KEY).ok()
+}
+
+pub fn implementation() -> Option<Address> {
+    get_storage::<Address>(IMPLEMENTATION_KEY).ok()
+}
+
+/// Returns the version of the storage layout which the stored data conforms
+/// to. Storage written before versions are recorded is of version 0.
+pub fn storage_version() -> u32 {
+    get_storage::<u32>(STORAGE_VERSION_KEY).unwrap_or(0)
+}
+
+/// Records `storage_version` as the version of the storage, and returns the
+/// version recorded before if the storage needs to be migrated.
+pub fn migrate(storage_version: u32) -> Option<u32> {
+    let from_version = self::storage_version();
+    require(
+        from_version <= storage_version,
+        "Upgradeable: storage version can not be decreased",
+    );
+    if from_version == storage_version {
+        return None;
+    }
+    set_storage(STORAGE_VERSION_KEY, &storage_version);
+    Some(from_version)
+}
+
+fn delegated(call_data: &CallData) -> Vec<u8> {
+    let mut forwarded = DELEGATED_SELECTOR.to_vec();
+    forwarded.extend(call_data.encode());
+    forwarded
+}
+
+/// Points the contract to a new implementation, and then lets the new
+/// implementation migrate the storage. Only the admin can do this.
+pub fn upgrade_to(new_impl: Address) {
+    require(
+        admin() == Some(env::get_caller()),
+        "Upgradeable: caller is not the admin",
+    );
+    require(
+        new_impl != Address::empty(),
+        "Upgradeable: new implementation is the zero address",
+    );
+    set_storage(IMPLEMENTATION_KEY, &new_impl);
+
+    let migrate = CallData {
+        selector: MIGRATE_SELECTOR,
+        data: Vec::new(),
+    };
+    require(
+        env::delegate_call(&new_impl, &delegated(&migrate)).is_ok(),
+        "Upgradeable: failed to migrate the storage",
+    );
+}
+
+/// Decides where a call should be executed.
+///
+/// Returns the call data to be dispatched locally, or `None` if the call has
+/// been forwarded to the implementation and its result has been returned
+/// already. Calls to `upgrade_to` are always handled by the proxy itself.
+pub fn route(
+    call_data: CallData,
+    upgrade_to_selector: Selector,
+) -> Result<Option<CallData>, DispatchError> {
+    if call_data.selector == DELEGATED_SELECTOR {
+        let inner = <CallData as Decode>::decode(&mut call_data.data.as_slice())
+            .map_err(|_| DispatchError::CouldNotReadInput)?;
+        return Ok(Some(inner));
+    }
+
+    if call_data.selector == upgrade_to_selector {
+        return Ok(Some(call_data));
+    }
+
+    match implementation() {
+        Some(implementation) => {
+            let return_data = env::delegate_call(&implementation, &delegated(&call_data))
+                .map_err(|_| DispatchError::CouldNotDelegate)?;
+            env::finish_raw(&return_data);
+            Ok(None)
+        }
+        None => Ok(Some(call_data)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang_core::env::test;
+
+    /// Registers an implementation of `storage_version`, which records the
+    /// version migrated from in the storage of the proxy.
+    fn register_implementation(addr: Address, storage_version: u32) {
+        test::register_delegate(addr, move |data| {
+            let call_data = CallData {
+                selector: [data[0], data[1], data[2], data[3]],
+                data: data[4..].to_vec(),
+            };
+            let call_data = route(call_data, [0xff; 4]).unwrap().unwrap();
+            assert_eq!(call_data.selector, MIGRATE_SELECTOR);
+            if let Some(from_version) = migrate(storage_version) {
+                set_storage(b"migrated_from", &from_version);
+            }
+            Vec::new()
+        });
+    }
+
+    #[test]
+    fn admin_can_upgrade() {
+        test::run_test(|| {
+            initialize(0);
+            let accounts = test::default_accounts();
+            register_implementation(accounts.bob, 0);
+            assert_eq!(admin(), Some(accounts.alice));
+            assert_eq!(implementation(), None);
+
+            upgrade_to(accounts.bob);
+            assert_eq!(implementation(), Some(accounts.bob));
+
+            // Deploying again must not replace the admin.
+            test::set_caller(accounts.charlie);
+            initialize(0);
+            assert_eq!(admin(), Some(accounts.alice));
+        })
+    }
+
+    #[test]
+    fn migrate_once_per_storage_version() {
+        test::run_test(|| {
+            initialize(1);
+            let accounts = test::default_accounts();
+            register_implementation(accounts.bob, 1);
+            register_implementation(accounts.charlie, 3);
+            register_implementation(accounts.david, 3);
+
+            upgrade_to(accounts.bob);
+            assert_eq!(storage_version(), 1);
+            assert!(get_storage::<u32>(b"migrated_from").is_err());
+
+            upgrade_to(accounts.charlie);
+            assert_eq!(storage_version(), 3);
+            assert_eq!(get_storage::<u32>(b"migrated_from").ok(), Some(1));
+
+            set_storage(b"migrated_from", &0u32);
+            upgrade_to(accounts.david);
+            assert_eq!(get_storage::<u32>(b"migrated_from").ok(), Some(0));
+            assert_eq!(migrate(3), None);
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "Upgradeable: storage version can not be decreased")]
+    fn can_not_decrease_storage_version() {
+        test::run_test(|| {
+            initialize(2);
+            migrate(1);
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "Upgradeable: failed to migrate the storage")]
+    fn can_not_upgrade_to_missing_implementation() {
+        test::run_test(|| {
+            initialize(0);
+            upgrade_to(test::default_accounts().bob);
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "Upgradeable: caller is not the admin")]
+    fn non_admin_can_not_upgrade() {
+        test::run_test(|| {
+            initialize(0);
+            let accounts = test::default_accounts();
+            test::set_caller(accounts.bob);
+            upgrade_to(accounts.bob);
+        })
+    }
+
+    #[test]
+    fn delegated_call_is_dispatched_locally() {
+        test::run_test(|| {
+            let accounts = test::default_accounts();
+            initialize(0);
+            register_implementation(accounts.bob, 0);
+            upgrade_to(accounts.bob);
+
+            let inner = CallData {
+                selector: [1, 2, 3, 4],
+                data: vec![5, 6],
+            };
+            let delegated = CallData {
+                selector: DELEGATED_SELECTOR,
+                data: inner.encode(),
+            };
+            let routed = route(delegated, [0xff; 4]).unwrap().unwrap();
+            assert_eq!(routed.selector, [1, 2, 3, 4]);
+            assert_eq!(routed.data, vec![5, 6]);
+
+            let upgrade = CallData {
+                selector: [0xff; 4],
+                data: vec![],
+            };
+            assert!(route(upgrade, [0xff; 4]).unwrap().is_some());
+        })
+    }
+
+    #[test]
+    fn delegate_runs_in_caller_storage() {
+        test::run_test(|| {
+            let accounts = test::default_accounts();
+            test::register_delegate(accounts.bob, |data| {
+                set_storage(b"counter", &(data.len() as u32));
+                vec![1]
+            });
+
+            assert_eq!(
+                env::delegate_call(&accounts.bob, &[0; 3]).ok(),
+                Some(vec![1])
+            );
+            assert_eq!(get_storage::<u32>(b"counter").ok(), Some(3));
+            assert!(env::delegate_call(&accounts.charlie, &[]).is_err());
+        })
+    }
+}
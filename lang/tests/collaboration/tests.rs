@@ -17,8 +17,34 @@ fn compile_tests() {
     t.pass("tests/collaboration/ui/pass/02-right-belongs-to-everyone.rs");
     t.pass("tests/collaboration/ui/pass/03-inherited-signers.rs");
     t.pass("tests/collaboration/ui/pass/04-selector.rs");
+    t.pass("tests/collaboration/ui/pass/05-consumption-markers.rs");
+    t.pass("tests/collaboration/ui/pass/06-expiring-rights.rs");
+    t.pass("tests/collaboration/ui/pass/07-observers.rs");
+    t.pass("tests/collaboration/ui/pass/08-contract-key.rs");
+    t.pass("tests/collaboration/ui/pass/09-ensure.rs");
+    t.pass("tests/collaboration/ui/pass/10-archive-and-active.rs");
+    t.pass("tests/collaboration/ui/pass/11-proposals.rs");
+    t.pass("tests/collaboration/ui/pass/12-by-signer.rs");
+    t.pass("tests/collaboration/ui/pass/13-contract-id-repr.rs");
+    t.pass("tests/collaboration/ui/pass/14-delegation.rs");
+    t.pass("tests/collaboration/ui/pass/15-lifecycle-events.rs");
+    t.pass("tests/collaboration/ui/pass/16-try-fetch.rs");
+    t.pass("tests/collaboration/ui/pass/17-client.rs");
     t.compile_fail("tests/collaboration/ui/fail/01-no-signers.rs");
     t.compile_fail("tests/collaboration/ui/fail/02-no-contract.rs");
     t.compile_fail("tests/collaboration/ui/fail/03-invalid-signers.rs");
-    t.compile_fail("tests/collaboration/ui/fail/04-invalid-signers-syntax.rs")
+    t.compile_fail("tests/collaboration/ui/fail/04-invalid-signers-syntax.rs");
+    t.compile_fail("tests/collaboration/ui/fail/05-multiple-keys.rs");
+    t.compile_fail("tests/collaboration/ui/fail/06-consuming-by-ref.rs");
+    t.compile_fail("tests/collaboration/ui/fail/07-non-consuming-by-value.rs");
+    t.compile_fail("tests/collaboration/ui/fail/08-invalid-ensure.rs");
+    t.compile_fail("tests/collaboration/ui/fail/09-reserved-right-name.rs");
+    t.compile_fail("tests/collaboration/ui/fail/10-invalid-deadline.rs");
+    t.compile_fail("tests/collaboration/ui/fail/11-reserved-query-right-name.rs");
+    t.compile_fail("tests/collaboration/ui/fail/12-reserved-id-right-name.rs");
+    t.compile_fail("tests/collaboration/ui/fail/13-reserved-delegation-right-name.rs");
+    t.compile_fail("tests/collaboration/ui/fail/14-reserved-client-right-name.rs");
+    t.compile_fail("tests/collaboration/ui/fail/15-invalid-observers.rs");
+    t.compile_fail("tests/collaboration/ui/fail/16-duplicated-key.rs");
+    t.compile_fail("tests/collaboration/ui/fail/17-non-literal-ensure.rs")
 }
@@ -0,0 +1,15 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Noop {
+        #[liquid(signers)]
+        #[liquid(key)]
+        addr: address,
+        #[liquid(key)]
+        id: u32,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Noop {
+        #[liquid(signers)]
+        addr: address,
+    }
+
+    #[liquid(rights_belong_to = "addr")]
+    impl Noop {
+        #[liquid(consuming)]
+        pub fn close(&self) {}
+    }
+}
+
+fn main() {}
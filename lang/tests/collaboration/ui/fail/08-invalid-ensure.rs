@@ -0,0 +1,14 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    #[liquid(ensure = "self.id >")]
+    pub struct Noop {
+        #[liquid(signers)]
+        addr: address,
+        id: u32,
+    }
+}
+
+fn main() {}
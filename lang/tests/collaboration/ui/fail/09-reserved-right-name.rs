@@ -0,0 +1,17 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Noop {
+        #[liquid(signers)]
+        addr: address,
+    }
+
+    #[liquid(rights_belong_to = "addr")]
+    impl Noop {
+        pub fn archive(self) {}
+    }
+}
+
+fn main() {}
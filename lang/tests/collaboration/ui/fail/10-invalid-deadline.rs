@@ -0,0 +1,19 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Offer {
+        #[liquid(signers)]
+        addr: address,
+        deadline: timestamp,
+    }
+
+    #[liquid(rights_belong_to = "addr")]
+    impl Offer {
+        #[liquid(expires_after = "self.deadline +")]
+        pub fn take(self) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Noop {
+        #[liquid(signers)]
+        addr: address,
+        #[liquid(observers = everyone)]
+        observer: address,
+    }
+}
+
+fn main() {}
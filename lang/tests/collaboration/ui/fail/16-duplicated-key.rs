@@ -0,0 +1,14 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Noop {
+        #[liquid(signers)]
+        #[liquid(key)]
+        #[liquid(key)]
+        addr: address,
+    }
+}
+
+fn main() {}
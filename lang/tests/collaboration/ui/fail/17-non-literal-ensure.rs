@@ -0,0 +1,14 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    #[liquid(ensure = positive)]
+    pub struct Noop {
+        #[liquid(signers)]
+        addr: address,
+        amount: u64,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Noop {
+        #[liquid(signers)]
+        addr: address,
+    }
+
+    #[liquid(rights_belong_to = "addr")]
+    impl Noop {
+        #[liquid(consuming)]
+        pub fn close(self) {}
+
+        #[liquid(non_consuming)]
+        pub fn peek(&self) -> address {
+            self.addr
+        }
+
+        #[liquid(non_consuming)]
+        pub fn touch(&mut self) {}
+    }
+}
+
+fn main() {}
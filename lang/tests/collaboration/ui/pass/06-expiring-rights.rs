@@ -0,0 +1,22 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Offer {
+        #[liquid(signers)]
+        addr: address,
+        deadline: timestamp,
+    }
+
+    #[liquid(rights_belong_to = "addr")]
+    impl Offer {
+        #[liquid(expires_after = "self.deadline")]
+        pub fn take(self) {}
+
+        #[liquid(expires_after = "deadline")]
+        pub fn take_before(&self, deadline: timestamp) {}
+    }
+}
+
+fn main() {}
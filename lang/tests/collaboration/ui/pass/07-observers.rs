@@ -0,0 +1,40 @@
+use liquid::InOut;
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    use super::*;
+
+    #[derive(InOut)]
+    pub struct Reader {
+        addr: address,
+        subscribed: bool,
+    }
+
+    #[liquid(contract)]
+    pub struct Memo {
+        #[liquid(signers)]
+        author: address,
+        #[liquid(observers)]
+        editor: address,
+        #[liquid(observers = "$[..](?@.subscribed).addr")]
+        readers: Vec<Reader>,
+    }
+
+    #[liquid(contract)]
+    pub struct Digest {
+        #[liquid(signers)]
+        author: address,
+        #[liquid(observers = inherited)]
+        memo: Memo,
+    }
+
+    #[liquid(rights_belong_to = "author")]
+    impl Memo {
+        pub fn count_memos_of_editor(&self) -> u32 {
+            ContractId::<Memo>::contracts_of(&self.editor).len() as u32
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,42 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Account {
+        #[liquid(signers)]
+        owner: address,
+        #[liquid(key)]
+        name: String,
+        balance: u64,
+    }
+
+    #[liquid(rights_belong_to = "owner")]
+    impl Account {
+        pub fn rename(self, name: String) -> ContractId<Account> {
+            sign! { Account =>
+                name,
+                ..self
+            }
+        }
+
+        pub fn exists(&self, name: String) -> bool {
+            Account::lookup_by_key(&name).is_some()
+        }
+
+        pub fn balance_of(&self, name: String) -> u64 {
+            let (_, account) = Account::fetch_by_key(&name);
+            account.balance
+        }
+
+        pub fn rename_other(
+            &self,
+            name: String,
+            new_name: String,
+        ) -> ContractId<Account> {
+            Account::exercise_by_key(&name).rename(new_name)
+        }
+    }
+}
+
+fn main() {}
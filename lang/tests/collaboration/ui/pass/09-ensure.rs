@@ -0,0 +1,17 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    #[liquid(ensure = "self.amount > 0")]
+    #[liquid(ensure = "self.issuer != self.owner")]
+    pub struct Iou {
+        #[liquid(signers)]
+        issuer: address,
+        #[liquid(signers)]
+        owner: address,
+        amount: u64,
+    }
+}
+
+fn main() {}
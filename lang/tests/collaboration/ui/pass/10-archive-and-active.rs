@@ -0,0 +1,23 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Noop {
+        #[liquid(signers)]
+        addr: address,
+    }
+
+    #[liquid(rights_belong_to = "addr")]
+    impl Noop {
+        pub fn count_active(&self, page: u32, size: u32) -> u32 {
+            ContractId::<Noop>::active(page, size).len() as u32
+        }
+
+        pub fn close(&self, other: ContractId<Noop>) {
+            other.archive();
+        }
+    }
+}
+
+fn main() {}
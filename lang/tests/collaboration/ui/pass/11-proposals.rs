@@ -0,0 +1,47 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Iou {
+        #[liquid(signers)]
+        issuer: address,
+        #[liquid(signers)]
+        owner: address,
+        amount: u64,
+    }
+
+    #[liquid(contract)]
+    pub struct IouIssuer {
+        #[liquid(signers)]
+        issuer: address,
+    }
+
+    #[liquid(rights_belong_to = "issuer")]
+    impl IouIssuer {
+        pub fn offer(&self, owner: address, amount: u64) -> ProposalId<Iou> {
+            propose! { Iou =>
+                issuer: self.issuer,
+                owner,
+                amount,
+            }
+        }
+
+        pub fn cancel(&self, proposal_id: ProposalId<Iou>) {
+            proposal_id.withdraw();
+        }
+    }
+
+    #[liquid(rights)]
+    impl IouIssuer {
+        #[liquid(belongs_to = "")]
+        pub fn take(&self, proposal_id: ProposalId<Iou>) -> Option<ContractId<Iou>> {
+            if proposal_id.pending_signers().len() > 1 {
+                return None;
+            }
+            proposal_id.accept()
+        }
+    }
+}
+
+fn main() {}
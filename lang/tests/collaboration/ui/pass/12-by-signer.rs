@@ -0,0 +1,19 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Noop {
+        #[liquid(signers)]
+        addr: address,
+    }
+
+    #[liquid(rights_belong_to = "addr")]
+    impl Noop {
+        pub fn siblings(&self, offset: u32, limit: u32) -> Vec<ContractId<Noop>> {
+            ContractId::<Noop>::by_signer(&self.addr, offset, limit)
+        }
+    }
+}
+
+fn main() {}
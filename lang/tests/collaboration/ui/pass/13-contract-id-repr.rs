@@ -0,0 +1,50 @@
+use liquid::InOut;
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    use super::*;
+
+    #[derive(InOut)]
+    pub struct Link {
+        from: ContractId<Noop>,
+        to: ContractId<Noop>,
+    }
+
+    #[liquid(contract)]
+    pub struct Noop {
+        #[liquid(signers)]
+        addr: address,
+        links: Vec<Link>,
+    }
+
+    #[liquid(rights_belong_to = "addr")]
+    impl Noop {
+        pub fn raw(&self, id: ContractId<Noop>) -> u32 {
+            let raw: u32 = id.into();
+            assert_eq!(raw, id.id());
+            raw
+        }
+
+        pub fn from_number(&self, raw: u32) -> Option<ContractId<Noop>> {
+            ContractId::<Noop>::from_raw(raw)
+        }
+
+        pub fn render(&self, id: ContractId<Noop>) -> String {
+            id.to_string()
+        }
+
+        pub fn parse(&self, repr: String) -> bool {
+            repr.parse::<ContractId<Noop>>().is_ok()
+        }
+
+        pub fn last_link(&self) -> Option<Link> {
+            self.links.last().map(|link| Link {
+                from: link.from,
+                to: link.to,
+            })
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Noop {
+        #[liquid(signers)]
+        addr: address,
+    }
+
+    #[liquid(rights_belong_to = "addr")]
+    impl Noop {
+        pub fn close(self) {}
+
+        pub fn authorize(&self, operator: address) {
+            if !ContractId::<Noop>::is_delegated(&self.addr, &operator, "close") {
+                ContractId::<Noop>::delegate(operator, "close");
+            }
+        }
+
+        pub fn deauthorize(&self, operator: address) {
+            ContractId::<Noop>::revoke(operator, "close");
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+use liquid_lang as liquid;
+use liquid_lang::lifecycle::{LifecycleEvent, Operation};
+use liquid_primitives::Topics;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Noop {
+        #[liquid(signers)]
+        addr: address,
+    }
+
+    #[liquid(rights_belong_to = "addr")]
+    impl Noop {
+        pub fn close(self) {}
+    }
+}
+
+fn main() {
+    let event = LifecycleEvent {
+        operation: Operation::Exercised,
+        template: String::from("Noop"),
+        contract_id: 1,
+        right: String::from("close"),
+        parties: Vec::new(),
+    };
+    let data = scale::Encode::encode(&event);
+    assert_eq!(
+        LifecycleEvent::try_from_log(&event.topics(), &data),
+        Some(event)
+    );
+}
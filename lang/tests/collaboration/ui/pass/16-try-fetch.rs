@@ -0,0 +1,19 @@
+use liquid_lang as liquid;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Noop {
+        #[liquid(signers)]
+        addr: address,
+    }
+
+    #[liquid(rights_belong_to = "addr")]
+    impl Noop {
+        pub fn is_active(&self, other: ContractId<Noop>) -> bool {
+            other.try_fetch().is_some()
+        }
+    }
+}
+
+fn main() {}
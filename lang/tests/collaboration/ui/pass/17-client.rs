@@ -0,0 +1,34 @@
+use liquid_lang as liquid;
+use liquid_primitives::types::Address;
+
+#[liquid::collaboration]
+mod noop {
+    #[liquid(contract)]
+    pub struct Iou {
+        #[liquid(signers)]
+        issuer: address,
+        owner: address,
+        amount: u64,
+    }
+
+    #[liquid(rights_belong_to = "issuer")]
+    impl Iou {
+        pub fn transfer(self, owner: address) -> ContractId<Iou> {
+            sign! { Iou =>
+                owner,
+                ..self
+            }
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn transfer_remotely(remote: Address, issuer: Address, owner: Address) -> Option<()> {
+    let client = noop::IouClient::at(remote);
+    let iou_id = client.sign(issuer, issuer, 100)?;
+    let _iou = client.fetch(iou_id)?;
+    let iou_id = client.transfer(iou_id, owner)?;
+    client.archive(iou_id)
+}
+
+fn main() {}
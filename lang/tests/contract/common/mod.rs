@@ -34,6 +34,44 @@ fn compile_tests() {
     t.pass("tests/contract/common/ui/pass/16-mock-context-getter.rs");
     t.pass("tests/contract/common/ui/pass/17-event.rs");
     t.pass("tests/contract/common/ui/pass/18-array.rs");
+    t.pass("tests/contract/common/ui/pass/19-signed-integers.rs");
+    t.pass("tests/contract/common/ui/pass/20-delegate-interface.rs");
+    t.pass("tests/contract/common/ui/pass/21-immutable-field.rs");
+    t.pass("tests/contract/common/ui/pass/22-error-registry.rs");
+    t.pass("tests/contract/common/ui/pass/23-interface-event.rs");
+    t.pass("tests/contract/common/ui/pass/24-option-result.rs");
+    t.pass("tests/contract/common/ui/pass/25-map.rs");
+    t.pass("tests/contract/common/ui/pass/26-asset-allowance.rs");
+    t.pass("tests/contract/common/ui/pass/27-asset-burn.rs");
+    t.pass("tests/contract/common/ui/pass/28-asset-batch.rs");
+    t.pass("tests/contract/common/ui/pass/29-asset-received.rs");
+    t.pass("tests/contract/common/ui/pass/30-asset-auto-register.rs");
+    t.pass("tests/contract/common/ui/pass/31-asset-events.rs");
+    t.pass("tests/contract/common/ui/pass/32-interface-cns.rs");
+    t.pass("tests/contract/common/ui/pass/33-storage-version.rs");
+    t.pass("tests/contract/common/ui/pass/34-storage-migration.rs");
+    t.pass("tests/contract/common/ui/pass/35-multiple-constructors.rs");
+    t.pass("tests/contract/common/ui/pass/36-internal-helpers.rs");
+    t.pass("tests/contract/common/ui/pass/37-storage-segments.rs");
+    t.pass("tests/contract/common/ui/pass/38-mixin.rs");
+    t.pass("tests/contract/common/ui/pass/39-custom-selector.rs");
+    t.pass("tests/contract/common/ui/pass/40-call-params.rs");
+    t.pass("tests/contract/common/ui/pass/41-chunked-strings.rs");
+    t.pass("tests/contract/common/ui/pass/42-deque-and-heap.rs");
+    t.pass("tests/contract/common/ui/pass/43-set.rs");
+    t.pass("tests/contract/common/ui/pass/44-event-from-log.rs");
+    t.pass("tests/contract/common/ui/pass/45-docs.rs");
+    t.pass("tests/contract/common/ui/pass/46-generic-helpers.rs");
+    t.pass("tests/contract/common/ui/pass/47-fixed-size-arrays.rs");
+    t.pass("tests/contract/common/ui/pass/48-interface-structs.rs");
+    t.pass("tests/contract/common/ui/pass/49-mock-context-getter-template.rs");
+    t.pass("tests/contract/common/ui/pass/50-optional-interface-method.rs");
+    t.pass("tests/contract/common/ui/pass/51-many-methods.rs");
+    t.pass("tests/contract/common/ui/pass/52-contract-version.rs");
+    t.pass("tests/contract/common/ui/pass/53-emit-ref.rs");
+    t.pass("tests/contract/common/ui/pass/54-composite-topic.rs");
+    t.pass("tests/contract/common/ui/pass/55-emit-ref-events.rs");
+    t.pass("tests/contract/common/ui/pass/56-access-control.rs");
     t.compile_fail("tests/contract/common/ui/fail/01-constructor-returns.rs");
     t.compile_fail("tests/contract/common/ui/fail/02-missing-constructor.rs");
     t.compile_fail("tests/contract/common/ui/fail/03-multiple-constructors.rs");
@@ -89,4 +127,30 @@ fn compile_tests() {
     t.compile_fail("tests/contract/common/ui/fail/47-invalid-mock-context-getter-2.rs");
     t.compile_fail("tests/contract/common/ui/fail/48-invalid-mock-context-getter-3.rs");
     t.compile_fail("tests/contract/common/ui/fail/49-invalid-mock-context-getter-4.rs");
+    t.compile_fail("tests/contract/common/ui/fail/50-only-role-without-access-control.rs");
+    t.compile_fail("tests/contract/common/ui/fail/51-unknown-method-attribute.rs");
+    t.compile_fail("tests/contract/common/ui/fail/52-cached-mutable-interface-method.rs");
+    t.compile_fail("tests/contract/common/ui/fail/53-upgradeable-without-storage.rs");
+    t.compile_fail("tests/contract/common/ui/fail/54-modify-immutable-field.rs");
+    t.compile_fail("tests/contract/common/ui/fail/55-error-variant-with-data.rs");
+    t.compile_fail("tests/contract/common/ui/fail/56-invalid-map-key-type.rs");
+    t.compile_fail("tests/contract/common/ui/fail/57-invalid-asset-issuer.rs");
+    t.compile_fail("tests/contract/common/ui/fail/58-public-asset-received-hook.rs");
+    t.compile_fail("tests/contract/common/ui/fail/59-duplicate-asset-received-hook.rs");
+    t.compile_fail("tests/contract/common/ui/fail/60-reserved-asset-event.rs");
+    t.compile_fail("tests/contract/common/ui/fail/61-invalid-storage-version.rs");
+    t.compile_fail("tests/contract/common/ui/fail/62-storage-version-without-storage.rs");
+    t.compile_fail("tests/contract/common/ui/fail/63-migration-without-upgradeable.rs");
+    t.compile_fail("tests/contract/common/ui/fail/64-migration-without-storage-version.rs");
+    t.compile_fail("tests/contract/common/ui/fail/65-constructor-without-new.rs");
+    t.compile_fail("tests/contract/common/ui/fail/66-duplicate-field-in-storage-segments.rs");
+    t.compile_fail("tests/contract/common/ui/fail/67-internal-required-method-in-mixin.rs");
+    t.compile_fail("tests/contract/common/ui/fail/68-selector-collision.rs");
+    t.compile_fail("tests/contract/common/ui/fail/69-invalid-selector.rs");
+    t.compile_fail("tests/contract/common/ui/fail/70-emit-in-immutable-method.rs");
+    t.compile_fail("tests/contract/common/ui/fail/71-where-clause-on-external-method.rs");
+    t.compile_fail("tests/contract/common/ui/fail/72-invalid-mock-context-getter-template.rs");
+    t.compile_fail("tests/contract/common/ui/fail/73-conflicting-mock-context-getters.rs");
+    t.compile_fail("tests/contract/common/ui/fail/74-cached-optional-method.rs");
+    t.compile_fail("tests/contract/common/ui/fail/75-reserved-version-method.rs");
 }
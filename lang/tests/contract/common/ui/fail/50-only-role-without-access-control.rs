@@ -0,0 +1,21 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Noop {
+        value: liquid::storage::Value<u32>,
+    }
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        #[liquid(only_role = "MINTER")]
+        pub fn noop(&self) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        #[liquid(only_admin)]
+        pub fn noop(&self) {}
+    }
+}
+
+fn main() {}
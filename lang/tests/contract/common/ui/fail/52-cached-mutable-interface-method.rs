@@ -0,0 +1,11 @@
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto)]
+mod foo {
+    extern "liquid" {
+        #[liquid(cached)]
+        fn bar(&mut self) -> u32;
+    }
+}
+
+fn main() {}
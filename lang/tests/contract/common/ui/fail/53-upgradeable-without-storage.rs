@@ -0,0 +1,21 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(upgradeable)]
+    struct Foo {}
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop(&self) {}
+    }
+}
+
+fn main() {}
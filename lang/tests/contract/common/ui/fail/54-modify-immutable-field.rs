@@ -0,0 +1,26 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Noop {
+        #[liquid(immutable)]
+        cap: storage::Value<u64>,
+    }
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {
+            self.cap.initialize(0);
+        }
+
+        pub fn set_cap(&mut self, cap: u64) {
+            self.cap.set(cap);
+        }
+    }
+}
+
+fn main() {}
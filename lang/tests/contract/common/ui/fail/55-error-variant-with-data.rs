@@ -0,0 +1,21 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    #[liquid(error)]
+    enum NoopError {
+        TooLarge(u32),
+    }
+
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop(&self) {}
+    }
+}
+
+fn main() {}
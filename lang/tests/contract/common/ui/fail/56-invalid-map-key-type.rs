@@ -0,0 +1,19 @@
+use liquid_lang as liquid;
+use liquid_prelude::collections::BTreeMap;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop(&self, _values: BTreeMap<Vec<u8>, u32>) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        #[liquid(asset_received)]
+        pub fn on_asset_received(&mut self, _asset_name: String, _from: address, _amount: u64) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop(&self) {}
+
+        #[liquid(asset_received)]
+        fn on_asset_received(&mut self, _asset_name: String, _from: address, _amount: u64) {}
+
+        #[liquid(asset_received)]
+        fn on_token_received(&mut self, _asset_name: String, _from: address, _amount: u64) {}
+    }
+}
+
+fn main() {}
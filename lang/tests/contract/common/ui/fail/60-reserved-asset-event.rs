@@ -0,0 +1,29 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(event)]
+    struct AssetIssued {
+        #[liquid(indexed)]
+        to: address,
+    }
+
+    #[liquid(asset(
+        issuer = "0x83309d045a19c44dc3722d15a6abd472f95866ac",
+        total = 1000000000,
+        description = "token"
+    ))]
+    struct Token;
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop(&self) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    #[liquid(storage)]
+    #[liquid(storage_version = 1)]
+    struct Noop {}
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop(&self) {}
+
+        #[liquid(migration)]
+        fn migrate(&mut self, _from_version: u32) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Noop {
+        value: storage::Value<u64>,
+    }
+
+    #[liquid(methods)]
+    impl Noop {
+        #[liquid(constructor)]
+        pub fn with_value(&mut self, value: u64) {
+            self.value.initialize(value);
+        }
+
+        pub fn noop(&self) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Noop {
+        value: storage::Value<u64>,
+    }
+
+    #[liquid(storage)]
+    struct Noop {
+        value: storage::Value<u32>,
+    }
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {
+            self.value.initialize(0);
+        }
+
+        pub fn noop(&self) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use liquid_lang as liquid;
+
+#[liquid::mixin]
+pub trait Pausable {
+    #[liquid(internal)]
+    fn paused_slot(&self) -> &bool;
+
+    fn paused(&self) -> bool {
+        *self.paused_slot()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod selector_collision {
+    use super::*;
+
+    #[liquid(storage)]
+    struct SelectorCollision {
+        value: storage::Value<u32>,
+    }
+
+    #[liquid(methods)]
+    impl SelectorCollision {
+        pub fn new(&mut self) {
+            self.value.initialize(0);
+        }
+
+        #[liquid(selector = "0xdeadbeef")]
+        pub fn get(&self) -> u32 {
+            *self.value
+        }
+
+        #[liquid(selector = "0xdeadbeef")]
+        pub fn set(&mut self, value: u32) {
+            self.value.set(value);
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod invalid_selector {
+    use super::*;
+
+    #[liquid(storage)]
+    struct InvalidSelector {
+        value: storage::Value<u32>,
+    }
+
+    #[liquid(methods)]
+    impl InvalidSelector {
+        pub fn new(&mut self) {
+            self.value.initialize(0);
+        }
+
+        #[liquid(selector = "0xdeadbee")]
+        pub fn get(&self) -> u32 {
+            *self.value
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod emit_in_immutable_method {
+    use super::*;
+
+    #[liquid(storage)]
+    struct EmitInImmutableMethod {
+        value: storage::Value<u32>,
+    }
+
+    #[liquid(event)]
+    struct Queried {
+        value: u32,
+    }
+
+    #[liquid(methods)]
+    impl EmitInImmutableMethod {
+        pub fn new(&mut self) {
+            self.value.initialize(0);
+        }
+
+        pub fn get(&self) -> u32 {
+            let value = *self.value;
+            self.env().emit(Queried { value });
+            value
+        }
+    }
+}
+
+fn main() {}
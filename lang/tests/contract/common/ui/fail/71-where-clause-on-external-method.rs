@@ -0,0 +1,20 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop(&self)
+        where
+            Self: Sized,
+        {
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto, mock_context_getter = "mock")]
+mod foo {
+    extern "liquid" {
+        fn foo(&self);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto, mock_context_getter = "{}_mock")]
+mod foo {
+    extern "liquid" {
+        #[liquid(mock_context_getter = "foo_mock")]
+        fn bar(&self);
+        fn foo(&self);
+    }
+}
+
+fn main() {}
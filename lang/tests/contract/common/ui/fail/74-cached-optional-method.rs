@@ -0,0 +1,12 @@
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto)]
+mod precompiled {
+    extern "liquid" {
+        #[liquid(cached)]
+        #[liquid(optional)]
+        fn get(&self, key: String) -> String;
+    }
+}
+
+fn main() {}
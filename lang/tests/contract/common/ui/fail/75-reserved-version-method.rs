@@ -0,0 +1,18 @@
+use liquid_lang as liquid;
+
+#[liquid::contract(version = "1.2.0")]
+mod noop {
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn version(&self) -> String {
+            String::from("1.2.0")
+        }
+    }
+}
+
+fn main() {}
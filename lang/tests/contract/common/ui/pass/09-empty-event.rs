@@ -12,7 +12,7 @@ mod noop {
     impl Noop {
         pub fn new(&mut self) {}
 
-        pub fn noop(&self) -> () {
+        pub fn noop(&mut self) -> () {
             self.env().emit(TestEvent {});
         }
     }
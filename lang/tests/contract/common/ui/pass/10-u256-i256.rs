@@ -28,7 +28,7 @@ mod noop {
             self.bar.initialize(0.into());
         }
 
-        pub fn noop(&self) -> (u256, i256) {
+        pub fn noop(&mut self) -> (u256, i256) {
             self.env().emit(Nothing {
                 _1: 0.into(),
                 _2: 0.into(),
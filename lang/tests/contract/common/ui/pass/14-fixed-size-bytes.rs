@@ -28,7 +28,7 @@ mod noop {
             self.bar.initialize(Default::default());
         }
 
-        pub fn noop_1(&self) -> (bytes7, bytes8) {
+        pub fn noop_1(&mut self) -> (bytes7, bytes8) {
             self.env().emit(Nothing {
                 _1: Default::default(),
                 _2: Default::default(),
@@ -21,7 +21,7 @@ mod noop {
             self.foo.initialize(Default::default());
         }
 
-        pub fn noop_1(&self) -> bytes {
+        pub fn noop_1(&mut self) -> bytes {
             self.env().emit(Nothing {
                 _1: Default::default(),
             });
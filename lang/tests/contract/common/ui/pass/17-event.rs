@@ -32,7 +32,7 @@ mod noop {
     impl Noop {
         pub fn new(&mut self) {}
 
-        pub fn noop(&self) -> () {
+        pub fn noop(&mut self) -> () {
             self.env().emit(TestEvent {
                 i: 0,
                 b: true,
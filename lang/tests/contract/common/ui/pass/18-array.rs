@@ -38,7 +38,7 @@ mod noop {
             self.bar.initialize(Default::default());
         }
 
-        pub fn noop(&self, a: [String; 2]) -> [Null; 2] {
+        pub fn noop(&mut self, a: [String; 2]) -> [Null; 2] {
             self.env().emit(Nothing {
                 _1: [
                     Null {
@@ -0,0 +1,56 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Noop {
+        pub small: storage::Value<i8>,
+        pub medium: storage::Mapping<i16, i32>,
+        pub large: storage::Mapping<i64, i128>,
+    }
+
+    #[liquid(event)]
+    struct Changed {
+        #[liquid(indexed)]
+        small: i8,
+        #[liquid(indexed)]
+        medium: i16,
+        #[liquid(indexed)]
+        large: i128,
+        delta: i32,
+        old: i64,
+    }
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {
+            self.small.initialize(i8::MIN);
+            self.medium.initialize();
+            self.large.initialize();
+        }
+
+        pub fn set(
+            &mut self,
+            small: i8,
+            medium: i16,
+            large: i128,
+        ) -> (i8, i16, i32, i64, i128) {
+            self.small.set(small);
+            self.medium.insert(&medium, -1);
+            self.large.insert(&-1, large);
+            self.env().emit(Changed {
+                small,
+                medium,
+                large,
+                delta: -1,
+                old: -1,
+            });
+            (small, medium, -1, -1, large)
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto, delegate)]
+mod math_lib {
+    extern "liquid" {
+        fn add(&mut self, delta: u32) -> u32;
+        fn get(&self) -> u32;
+    }
+}
+
+#[liquid::contract]
+mod noop {
+    use super::math_lib::*;
+
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop(&self) -> u32 {
+            let lib = MathLib::at(Default::default());
+            lib.add(1);
+            lib.get().unwrap_or(0)
+        }
+    }
+}
+
+fn main() {}
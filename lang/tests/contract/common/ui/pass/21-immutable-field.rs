@@ -0,0 +1,30 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Noop {
+        #[liquid(immutable)]
+        pub owner: storage::Value<address>,
+        #[liquid(immutable)]
+        cap: storage::Value<u64>,
+    }
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self, cap: u64) {
+            self.owner.initialize(self.env().get_caller());
+            self.cap.initialize(0);
+            *self.cap += cap;
+        }
+
+        pub fn cap(&self) -> u64 {
+            *self.cap
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(error)]
+    enum NoopError {
+        /// the value is too large
+        TooLarge,
+        Zero,
+    }
+
+    #[liquid(storage)]
+    struct Noop {
+        value: storage::Value<u32>,
+    }
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {
+            self.value.initialize(1);
+        }
+
+        pub fn set(&mut self, value: u32) {
+            require!(value != 0, NoopError::Zero);
+            require!(value < 100, "value must be less than 100");
+            self.value.set(value);
+        }
+    }
+}
+
+fn main() {}
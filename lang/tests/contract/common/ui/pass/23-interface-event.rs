@@ -0,0 +1,51 @@
+use liquid_lang as liquid;
+use liquid_primitives::{types::Address, Topics};
+
+#[liquid::interface(name = auto)]
+mod erc20 {
+    #[liquid(event)]
+    struct Transfer {
+        #[liquid(indexed)]
+        from: address,
+        #[liquid(indexed)]
+        to: address,
+        value: u256,
+    }
+
+    extern "solidity" {
+        fn transfer(&mut self, to: address, value: u256) -> bool;
+    }
+}
+
+use erc20::Transfer;
+
+fn main() {
+    assert_eq!(
+        Transfer::SIG_HASH,
+        hex_literal::hex!(
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        )
+    );
+
+    let from: Address = "0x3e9afaa4a062a49d64b8ab057b3cb51892e17ecb"
+        .parse()
+        .unwrap();
+    let to: Address = "0x5e09acd4e82f9fda4f7a2d1ea0f2fbd5a8bf6b0c"
+        .parse()
+        .unwrap();
+    let transfer = Transfer {
+        from,
+        to,
+        value: 100.into(),
+    };
+    let topics = transfer.topics();
+    let data = liquid_abi_codec::Encode::encode(&(transfer.value.clone(),));
+
+    let decoded = Transfer::decode(&topics, &data).unwrap();
+    assert!(decoded.from == from);
+    assert!(decoded.to == to);
+    assert!(decoded.value == 100.into());
+    assert!(Transfer::decode(&topics[..2], &data).is_none());
+    assert!(Transfer::decode(&[topics[0], topics[2], topics[1]], &data).is_some());
+    assert!(Transfer::decode(&[topics[1], topics[0], topics[2]], &data).is_none());
+}
@@ -0,0 +1,61 @@
+use liquid::{storage, InOut};
+use liquid_lang as liquid;
+
+#[derive(InOut)]
+pub struct Lookup {
+    key: String,
+    value: Option<u64>,
+}
+
+#[liquid::contract]
+mod registry {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Registry {
+        entries: storage::Mapping<String, u64>,
+    }
+
+    #[liquid(event)]
+    struct Removed {
+        #[liquid(indexed)]
+        key: String,
+        old: Option<u64>,
+    }
+
+    #[liquid(methods)]
+    impl Registry {
+        pub fn new(&mut self) {
+            self.entries.initialize();
+        }
+
+        pub fn get(&self, key: String) -> Option<u64> {
+            self.entries.get(&key).cloned()
+        }
+
+        pub fn lookup(&self, keys: Vec<String>) -> Vec<Lookup> {
+            keys.into_iter()
+                .map(|key| {
+                    let value = self.get(key.clone());
+                    Lookup { key, value }
+                })
+                .collect()
+        }
+
+        pub fn set(&mut self, key: String, value: Option<u64>) -> Result<u64, String> {
+            match value {
+                Some(value) => {
+                    self.entries.insert(&key, value);
+                    Ok(value)
+                }
+                None => {
+                    let old = self.entries.remove(&key);
+                    self.env().emit(Removed { key, old });
+                    Err(String::from("removed"))
+                }
+            }
+        }
+    }
+}
+
+fn main() {}
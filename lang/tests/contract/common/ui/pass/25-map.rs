@@ -0,0 +1,48 @@
+use liquid::storage;
+use liquid_lang as liquid;
+use liquid_prelude::collections::BTreeMap;
+
+#[liquid::contract]
+mod scores {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Scores {
+        scores: storage::Mapping<address, u32>,
+    }
+
+    #[liquid(event)]
+    struct Updated {
+        scores: BTreeMap<address, u32>,
+    }
+
+    #[liquid(methods)]
+    impl Scores {
+        pub fn new(&mut self) {
+            self.scores.initialize();
+        }
+
+        pub fn update(&mut self, scores: BTreeMap<address, u32>) {
+            for (owner, score) in scores.iter() {
+                self.scores.insert(owner, *score);
+            }
+            self.env().emit(Updated { scores });
+        }
+
+        pub fn query(&self, owners: Vec<address>) -> BTreeMap<address, u32> {
+            owners
+                .into_iter()
+                .filter_map(|owner| {
+                    let score = *self.scores.get(&owner)?;
+                    Some((owner, score))
+                })
+                .collect()
+        }
+
+        pub fn labels(&self) -> BTreeMap<String, Vec<String>> {
+            BTreeMap::new()
+        }
+    }
+}
+
+fn main() {}
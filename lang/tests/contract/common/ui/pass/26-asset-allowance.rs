@@ -0,0 +1,44 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(asset(
+        issuer = "0x83309d045a19c44dc3722d15a6abd472f95866ac",
+        total = 1000000000,
+        description = "token"
+    ))]
+    struct Token;
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn approve(&mut self, spender: address, amount: u64) -> bool {
+            Token::approve(&spender, amount)
+        }
+
+        pub fn allowance(&self, owner: address, spender: address) -> u64 {
+            Token::allowance(&owner, &spender)
+        }
+
+        pub fn transfer_from(
+            &mut self,
+            owner: address,
+            recipient: address,
+            amount: u64,
+        ) -> bool {
+            match Token::withdraw_from(&owner, amount) {
+                Some(token) => {
+                    token.deposit(&recipient);
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+}
+
+fn main() {}
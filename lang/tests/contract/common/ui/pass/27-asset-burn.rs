@@ -0,0 +1,53 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(asset(
+        issuer = "0x83309d045a19c44dc3722d15a6abd472f95866ac",
+        total = 1000000000,
+        description = "token"
+    ))]
+    struct Token;
+
+    #[liquid(asset(
+        issuer = "0x83309d045a19c44dc3722d15a6abd472f95866ac",
+        fungible = false,
+        total = 1000,
+        description = "ticket"
+    ))]
+    struct Ticket;
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn burn_token(&mut self, amount: u64) -> bool {
+            match Token::withdraw_from_caller(amount) {
+                Some(token) => {
+                    token.burn();
+                    true
+                }
+                None => false,
+            }
+        }
+
+        pub fn burn_ticket(&mut self, id: u64) -> bool {
+            match Ticket::withdraw_from_caller(id) {
+                Some(ticket) => {
+                    ticket.burn();
+                    true
+                }
+                None => false,
+            }
+        }
+
+        pub fn circulating_supply(&self) -> (u64, u64) {
+            (Token::circulating_supply(), Ticket::circulating_supply())
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,34 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(asset(
+        issuer = "0x83309d045a19c44dc3722d15a6abd472f95866ac",
+        fungible = false,
+        total = 1000,
+        description = "ticket"
+    ))]
+    struct Ticket;
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn issue(&mut self, to: address) -> Vec<u64> {
+            Ticket::issue_batch_to(&to, &["first", "second"])
+        }
+
+        pub fn transfer(&mut self, to: address, ids: Vec<u64>) {
+            let tickets = ids
+                .into_iter()
+                .map(|id| Ticket::withdraw_from_caller(id).unwrap())
+                .collect();
+            Ticket::transfer_batch(tickets, &to);
+        }
+    }
+}
+
+fn main() {}
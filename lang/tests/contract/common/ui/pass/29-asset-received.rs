@@ -0,0 +1,30 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod vault {
+    use liquid_lang::storage;
+
+    #[liquid(storage)]
+    struct Vault {
+        deposits: storage::Mapping<address, u64>,
+    }
+
+    #[liquid(methods)]
+    impl Vault {
+        pub fn new(&mut self) {
+            self.deposits.initialize();
+        }
+
+        pub fn deposit_of(&self, owner: address) -> u64 {
+            *self.deposits.get(&owner).unwrap_or(&0)
+        }
+
+        #[liquid(asset_received)]
+        fn on_asset_received(&mut self, _asset_name: String, from: address, amount: u64) {
+            let deposit = self.deposit_of(from);
+            self.deposits.insert(&from, deposit + amount);
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(event)]
+    struct Issued {
+        #[liquid(indexed)]
+        to: address,
+    }
+
+    #[liquid(asset(
+        issuer = "0x83309d045a19c44dc3722d15a6abd472f95866ac",
+        total = 1000000000,
+        description = "token"
+    ))]
+    struct Token;
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn issue(&mut self, to: address, amount: u64) -> bool {
+            self.env().emit(Issued { to });
+            Token::issue_to(&to, amount)
+        }
+
+        pub fn transfer(&mut self, to: address, amount: u64) {
+            if let Some(token) = Token::withdraw_from_caller(amount) {
+                token.deposit(&to);
+            }
+        }
+    }
+}
+
+fn main() {}
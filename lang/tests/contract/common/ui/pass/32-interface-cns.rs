@@ -0,0 +1,28 @@
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto)]
+mod hello_world {
+    extern "liquid" {
+        fn get(&self) -> String;
+    }
+}
+
+#[liquid::contract]
+mod noop {
+    use super::hello_world::*;
+
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn greet(&self, version: String) -> String {
+            let hello_world = HelloWorld::at_cns("HelloWorld", &version);
+            hello_world.get().unwrap()
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod upgradeable_counter {
+    use super::*;
+
+    #[liquid(storage)]
+    #[liquid(upgradeable)]
+    #[liquid(storage_version = 2)]
+    struct Counter {
+        count: storage::Value<u64>,
+    }
+
+    #[liquid(methods)]
+    impl Counter {
+        pub fn new(&mut self) {
+            self.count.initialize(0);
+        }
+
+        pub fn get(&self) -> u64 {
+            *self.count
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod upgradeable_counter {
+    use super::*;
+
+    #[liquid(storage)]
+    #[liquid(upgradeable)]
+    #[liquid(storage_version = 2)]
+    struct Counter {
+        count: storage::Value<u64>,
+        wide_count: storage::Value<u128>,
+    }
+
+    #[liquid(methods)]
+    impl Counter {
+        pub fn new(&mut self) {
+            self.count.initialize(0);
+            self.wide_count.initialize(0);
+        }
+
+        pub fn get(&self) -> u128 {
+            *self.wide_count
+        }
+
+        #[liquid(migration)]
+        fn migrate(&mut self, from_version: u32) {
+            if from_version < 2 {
+                self.wide_count.initialize(*self.count as u128);
+            }
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod multiple_constructors {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Registry {
+        value: storage::Value<u64>,
+        name: storage::Value<String>,
+    }
+
+    #[liquid(methods)]
+    impl Registry {
+        pub fn new(&mut self) {
+            self.value.initialize(0);
+            self.name.initialize(String::new());
+        }
+
+        #[liquid(constructor)]
+        pub fn with_value(&mut self, value: u64, name: String) {
+            self.value.initialize(value);
+            self.name.initialize(name);
+        }
+
+        pub fn get(&self) -> (u64, String) {
+            (*self.value, self.name.clone())
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,54 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod bank {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Bank {
+        balances: storage::Mapping<address, u128>,
+        total: storage::Value<u128>,
+    }
+
+    #[liquid(methods)]
+    impl Bank {
+        pub fn new(&mut self) {
+            self.balances.initialize();
+            self.total.initialize(0);
+        }
+
+        pub fn deposit(&mut self, amount: u128) {
+            let caller = self.env().get_caller();
+            let balance = self.balance_mut(caller);
+            *balance = Self::checked_add(*balance, amount);
+            self.add_to_total(amount);
+        }
+
+        pub fn balance_of(&self, owner: address) -> u128 {
+            self.balances.get(&owner).copied().unwrap_or_default()
+        }
+    }
+
+    #[liquid(methods)]
+    impl Bank {
+        fn balance_mut(&mut self, owner: address) -> &mut u128 {
+            if !self.balances.contains_key(&owner) {
+                self.balances.insert(&owner, 0);
+            }
+            self.balances.get_mut(&owner).unwrap()
+        }
+
+        fn add_to_total<T: Into<u128>>(&mut self, amount: T) {
+            *self.total = Self::checked_add(*self.total, amount.into());
+        }
+
+        fn checked_add(a: u128, b: u128) -> u128 {
+            let (sum, overflow) = a.overflowing_add(b);
+            require!(!overflow, "overflow");
+            sum
+        }
+    }
+}
+
+fn main() {}
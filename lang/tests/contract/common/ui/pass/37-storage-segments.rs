@@ -0,0 +1,47 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod token {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Token {
+        #[liquid(immutable)]
+        name: storage::Value<String>,
+        balances: storage::Mapping<address, u64>,
+    }
+
+    #[liquid(methods)]
+    impl Token {
+        pub fn new(&mut self) {
+            self.name.initialize(String::from("token"));
+            self.balances.initialize();
+            self.allowances.initialize();
+            self.paused.initialize(false);
+        }
+
+        pub fn balance_of(&self, owner: address) -> u64 {
+            self.balances.get(&owner).copied().unwrap_or_default()
+        }
+    }
+
+    // The segment of the storage used by the allowance component.
+    #[liquid(storage)]
+    struct Token {
+        allowances: storage::Mapping<(address, address), u64>,
+        pub paused: storage::Value<bool>,
+    }
+
+    #[liquid(methods)]
+    impl Token {
+        pub fn allowance(&self, owner: address, spender: address) -> u64 {
+            self.allowances
+                .get(&(owner, spender))
+                .copied()
+                .unwrap_or_default()
+        }
+    }
+}
+
+fn main() {}
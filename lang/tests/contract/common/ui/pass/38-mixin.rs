@@ -0,0 +1,64 @@
+use liquid::storage;
+use liquid_lang as liquid;
+use liquid_primitives::types::Address;
+
+#[liquid::mixin]
+pub trait Ownable: liquid_lang::Env {
+    fn owner_slot(&self) -> &storage::Value<Address>;
+
+    fn owner_slot_mut(&mut self) -> &mut storage::Value<Address>;
+
+    fn owner(&self) -> Address {
+        *self.owner_slot().get()
+    }
+
+    fn transfer_ownership(&mut self, new_owner: Address) {
+        self.only_owner();
+        self.owner_slot_mut().set(new_owner);
+    }
+
+    #[liquid(internal)]
+    fn only_owner(&self) {
+        liquid_lang::intrinsics::require(
+            self.env().get_caller() == self.owner(),
+            "Ownable: caller is not the owner",
+        );
+    }
+}
+
+#[liquid::contract]
+mod counter {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Counter {
+        owner: storage::Value<address>,
+        count: storage::Value<u64>,
+    }
+
+    #[liquid(methods)]
+    impl Counter {
+        pub fn new(&mut self) {
+            self.owner.initialize(self.env().get_caller());
+            self.count.initialize(0);
+        }
+
+        pub fn increase(&mut self) {
+            self.only_owner();
+            *self.count += 1;
+        }
+    }
+
+    #[liquid(methods)]
+    impl Ownable for Counter {
+        fn owner_slot(&self) -> &storage::Value<address> {
+            &self.owner
+        }
+
+        fn owner_slot_mut(&mut self) -> &mut storage::Value<address> {
+            &mut self.owner
+        }
+    }
+}
+
+fn main() {}
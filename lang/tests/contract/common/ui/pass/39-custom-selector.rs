@@ -0,0 +1,50 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::mixin]
+pub trait Pausable {
+    fn paused_slot_mut(&mut self) -> &mut storage::Value<bool>;
+
+    #[liquid(selector = "0x8456cb59")]
+    fn halt(&mut self) {
+        self.paused_slot_mut().set(true);
+    }
+}
+
+#[liquid::contract]
+mod selectors {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Selectors {
+        paused: storage::Value<bool>,
+        value: storage::Value<u32>,
+    }
+
+    #[liquid(methods)]
+    impl Selectors {
+        pub fn new(&mut self) {
+            self.paused.initialize(false);
+            self.value.initialize(0);
+        }
+
+        #[liquid(selector = "0xdeadbeef")]
+        pub fn get(&self) -> u32 {
+            *self.value
+        }
+
+        #[liquid(selector = "CAFEBABE")]
+        pub fn set(&mut self, value: u32) {
+            self.value.set(value);
+        }
+    }
+
+    #[liquid(methods)]
+    impl Pausable for Selectors {
+        fn paused_slot_mut(&mut self) -> &mut storage::Value<bool> {
+            &mut self.paused
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+#![feature(unboxed_closures, fn_traits)]
+
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto)]
+mod vault {
+    extern "solidity" {
+        fn balance(&self) -> u64;
+
+        #[liquid(cached)]
+        fn owner(&self) -> address;
+
+        fn deposit(&mut self);
+        fn deposit(&mut self, to: address);
+    }
+}
+
+#[liquid::contract]
+mod depositor {
+    use super::vault::*;
+
+    #[liquid(storage)]
+    struct Depositor {}
+
+    #[liquid(methods)]
+    impl Depositor {
+        pub fn new(&mut self) {}
+
+        pub fn deposit(&mut self, vault: address, to: address) -> u64 {
+            let vault = Vault::at(vault).with_gas(100_000).with_value(10);
+            let _ = vault.owner();
+            (vault.deposit)();
+            (vault.deposit)(to);
+            vault.balance().unwrap()
+        }
+
+        pub fn balance(&self, vault: address) -> u64 {
+            Vault::at(vault).with_gas(30_000).balance().unwrap()
+        }
+    }
+}
+
+fn main() {}
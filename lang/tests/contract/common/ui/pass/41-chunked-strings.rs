@@ -0,0 +1,37 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod journal {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Journal {
+        pub log: storage::String,
+        pub blob: storage::Bytes,
+    }
+
+    #[liquid(methods)]
+    impl Journal {
+        pub fn new(&mut self) {
+            self.log.initialize();
+            self.blob.initialize();
+        }
+
+        pub fn write(&mut self, line: String, data: bytes) {
+            self.log.append(&line);
+            self.log.append("\n");
+            self.blob.append(&data);
+        }
+
+        pub fn head(&self, len: u32) -> String {
+            self.log.read(..len).unwrap_or_default()
+        }
+
+        pub fn blob_len(&self) -> u32 {
+            self.blob.len()
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,47 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod matching {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Matching {
+        pub jobs: storage::Deque<u64>,
+        pub bids: storage::BinaryHeap<u128>,
+    }
+
+    #[liquid(methods)]
+    impl Matching {
+        pub fn new(&mut self) {
+            self.jobs.initialize();
+            self.bids.initialize();
+        }
+
+        pub fn submit(&mut self, job: u64, urgent: bool) {
+            if urgent {
+                self.jobs.push_front(job);
+            } else {
+                self.jobs.push_back(job);
+            }
+        }
+
+        pub fn take(&mut self) -> Option<u64> {
+            self.jobs.pop_front()
+        }
+
+        pub fn bid(&mut self, price: u128) {
+            self.bids.push(price);
+        }
+
+        pub fn best_bid(&self) -> Option<u128> {
+            self.bids.peek().copied()
+        }
+
+        pub fn fill(&mut self) -> Option<u128> {
+            self.bids.pop()
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod whitelist {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Whitelist {
+        pub members: storage::Set<address>,
+    }
+
+    #[liquid(methods)]
+    impl Whitelist {
+        pub fn new(&mut self) {
+            self.members.initialize();
+            self.members.insert(self.env().get_caller());
+        }
+
+        pub fn add(&mut self, member: address) -> bool {
+            self.members.insert(member)
+        }
+
+        pub fn remove(&mut self, member: address) -> bool {
+            self.members.remove(&member)
+        }
+
+        pub fn count(&self) -> u32 {
+            self.members.len()
+        }
+
+        pub fn list(&self) -> Vec<address> {
+            self.members.iter().copied().collect()
+        }
+    }
+}
+
+fn main() {}
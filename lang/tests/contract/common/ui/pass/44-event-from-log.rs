@@ -0,0 +1,80 @@
+use liquid::storage;
+use liquid_lang as liquid;
+use liquid_primitives::{types::Address, Topics};
+
+#[liquid::contract]
+mod token {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Token {
+        total: storage::Value<u128>,
+    }
+
+    #[liquid(event)]
+    struct Transfer {
+        #[liquid(indexed)]
+        from: address,
+        #[liquid(indexed)]
+        to: address,
+        value: u128,
+        memo: String,
+    }
+
+    #[liquid(event)]
+    struct Renamed {
+        #[liquid(indexed)]
+        name: String,
+    }
+
+    #[liquid(methods)]
+    impl Token {
+        pub fn new(&mut self) {
+            self.total.initialize(0);
+        }
+
+        pub fn rename(&mut self, name: String) {
+            self.env().emit(Renamed { name });
+        }
+    }
+}
+
+use token::{Event, Renamed, Transfer};
+
+fn main() {
+    let from: Address = "0x3e9afaa4a062a49d64b8ab057b3cb51892e17ecb"
+        .parse()
+        .unwrap();
+    let to: Address = "0x5e09acd4e82f9fda4f7a2d1ea0f2fbd5a8bf6b0c"
+        .parse()
+        .unwrap();
+    let transfer = Transfer {
+        from,
+        to,
+        value: 100,
+        memo: String::from("rent"),
+    };
+    let topics = transfer.topics();
+    let data = liquid_abi_codec::Encode::encode(&transfer);
+    assert_eq!(*topics[0].as_bytes(), Transfer::SIG_HASH);
+
+    let decoded = Transfer::try_from_log(&topics, &data).unwrap();
+    assert!(decoded.from == from);
+    assert!(decoded.to == to);
+    assert_eq!(decoded.value, 100);
+    assert_eq!(decoded.memo, "rent");
+    assert!(Transfer::try_from_log(&topics[..2], &data).is_none());
+    assert!(Renamed::try_from_log(&topics, &data).is_none());
+
+    match Event::try_from_log(&topics, &data) {
+        Some(Event::Transfer(decoded)) => assert_eq!(decoded.memo, "rent"),
+        _ => panic!("expected a `Transfer` event"),
+    }
+
+    // Indexed strings are only kept as their hashes.
+    let renamed = Renamed {
+        name: String::from("liquid"),
+    };
+    let data = liquid_abi_codec::Encode::encode(&renamed);
+    assert!(Renamed::try_from_log(&renamed.topics(), &data).is_none());
+}
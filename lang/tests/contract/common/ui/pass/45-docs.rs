@@ -0,0 +1,46 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+/// A counter.
+///
+/// The count never decreases.
+#[liquid::contract]
+mod counter {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Counter {
+        /// Current count.
+        pub count: storage::Value<u64>,
+    }
+
+    /// Emitted when the count is increased.
+    #[liquid(event)]
+    struct Increased {
+        /// The amount added to the count.
+        #[liquid(indexed)]
+        by: u64,
+    }
+
+    #[liquid(methods)]
+    impl Counter {
+        /// Starts counting from zero.
+        pub fn new(&mut self) {
+            self.count.initialize(0);
+        }
+
+        /// Increases the count.
+        ///
+        /// Overflows are not checked.
+        pub fn increase(
+            &mut self,
+            /// The amount to add.
+            by: u64,
+        ) {
+            *self.count += by;
+            self.env().emit(Increased { by });
+        }
+    }
+}
+
+fn main() {}
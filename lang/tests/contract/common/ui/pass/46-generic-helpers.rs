@@ -0,0 +1,53 @@
+use core::ops::Add;
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod stats {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Stats {
+        values: storage::Vec<u64>,
+    }
+
+    #[liquid(methods)]
+    impl Stats {
+        pub fn new(&mut self) {
+            self.values.initialize();
+        }
+
+        pub fn record(&mut self, value: u64) {
+            self.values.push(value);
+        }
+
+        pub fn total(&self) -> u64 {
+            Self::sum(self.values.iter().copied())
+        }
+
+        pub fn total_of_pairs(&self) -> u128 {
+            let pairs = self.collect_with(|value| u128::from(*value) * 2);
+            Self::sum(pairs)
+        }
+    }
+
+    #[liquid(methods)]
+    impl Stats {
+        fn sum<T, I>(values: I) -> T
+        where
+            T: Add<Output = T> + Default,
+            I: IntoIterator<Item = T>,
+        {
+            values.into_iter().fold(T::default(), |acc, value| acc + value)
+        }
+
+        fn collect_with<F, R>(&self, f: F) -> Vec<R>
+        where
+            F: Fn(&u64) -> R,
+        {
+            self.values.iter().map(f).collect()
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,72 @@
+use liquid::storage;
+use liquid_lang as liquid;
+use liquid_primitives::{hash::hash, types::Address, Topics};
+
+#[liquid::contract]
+mod registry {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Registry {
+        owners: storage::Value<[Address; 3]>,
+    }
+
+    #[liquid(event)]
+    struct OwnersChanged {
+        #[liquid(indexed)]
+        owners: [Address; 3],
+        #[liquid(indexed)]
+        weights: [u8; 3],
+        digest: [u8; 20],
+    }
+
+    #[liquid(methods)]
+    impl Registry {
+        pub fn new(&mut self, owners: [Address; 3]) {
+            self.owners.initialize(owners);
+        }
+
+        pub fn set_owners(&mut self, owners: [Address; 3], weights: [u8; 3]) {
+            self.owners.set(owners);
+            self.env().emit(OwnersChanged {
+                owners,
+                weights,
+                digest: self.digest(),
+            });
+        }
+
+        pub fn owners(&self) -> [Address; 3] {
+            *self.owners
+        }
+
+        pub fn digest(&self) -> [u8; 20] {
+            let mut digest = [0u8; 20];
+            for owner in self.owners.iter() {
+                for (byte, owner_byte) in digest.iter_mut().zip(owner.0.iter()) {
+                    *byte ^= owner_byte;
+                }
+            }
+            digest
+        }
+    }
+}
+
+fn main() {
+    let event = registry::OwnersChanged {
+        owners: [Address::new([0x11; 20]), Address::default(), Address::default()],
+        weights: [1, 2, 3],
+        digest: [0u8; 20],
+    };
+    let topics = event.topics();
+    assert_eq!(topics.len(), 3);
+
+    let mut words = [0u8; 96];
+    words[12..32].copy_from_slice(&[0x11; 20]);
+    assert_eq!(*topics[1].as_bytes(), hash(&words));
+
+    let mut words = [0u8; 96];
+    words[31] = 1;
+    words[63] = 2;
+    words[95] = 3;
+    assert_eq!(*topics[2].as_bytes(), hash(&words));
+}
@@ -0,0 +1,66 @@
+#![feature(unboxed_closures, fn_traits)]
+
+use liquid::{storage, InOut};
+use liquid_lang as liquid;
+
+#[derive(InOut, Clone)]
+pub struct Point {
+    pub x: u32,
+    pub y: u32,
+}
+
+#[liquid::interface(name = auto)]
+mod shapes {
+    use super::*;
+
+    struct Size {
+        width: u32,
+        height: u32,
+    }
+
+    extern "solidity" {
+        fn origin(&self) -> Point;
+        fn size(&self) -> Size;
+    }
+}
+
+#[liquid::interface(name = auto)]
+mod canvas {
+    use super::{shapes::*, *};
+
+    extern "solidity" {
+        fn fit(&self, size: Size, at: Point) -> (bool, Size);
+        fn draw(&mut self, size: Size);
+        fn draw(&mut self, at: Point);
+    }
+}
+
+#[liquid::contract]
+mod painter {
+    use super::{canvas::*, shapes::*, *};
+
+    #[liquid(storage)]
+    struct Painter {
+        shapes: storage::Value<Shapes>,
+        canvas: storage::Value<Canvas>,
+    }
+
+    #[liquid(methods)]
+    impl Painter {
+        pub fn new(&mut self, shapes: address, canvas: address) {
+            self.shapes.initialize(Shapes::at(shapes));
+            self.canvas.initialize(Canvas::at(canvas));
+        }
+
+        pub fn paint(&mut self) -> bool {
+            let origin = self.shapes.origin().unwrap();
+            let size = self.shapes.size().unwrap();
+            (self.canvas.draw)(size.clone());
+            (self.canvas.draw)(origin.clone());
+            let (fitted, fitted_size) = self.canvas.fit(size.clone(), origin).unwrap();
+            fitted && fitted_size == size
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto, mock_context_getter = "mock_{}")]
+mod foo {
+    extern "liquid" {
+        fn foo(&self);
+        #[liquid(mock_context_getter = "another_getter")]
+        fn bar(&self);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use liquid_lang as liquid;
+
+#[liquid::interface(name = auto)]
+mod precompiled {
+    extern "liquid" {
+        fn get(&self, key: String) -> String;
+        #[liquid(optional)]
+        fn get_or_default(&self, key: String, default: String) -> String;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,80 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod many_methods {
+    use super::*;
+
+    #[liquid(storage)]
+    struct ManyMethods {
+        pub values: storage::Vec<u32>,
+        pub flag: storage::Value<bool>,
+    }
+
+    #[liquid(methods)]
+    impl ManyMethods {
+        pub fn new(&mut self) {
+            self.values.initialize();
+            self.flag.initialize(false);
+        }
+
+        pub fn push(&mut self, value: u32) {
+            self.values.push(value);
+        }
+
+        pub fn pop(&mut self) -> Option<u32> {
+            self.values.pop()
+        }
+
+        pub fn len(&self) -> u32 {
+            self.values.len()
+        }
+
+        pub fn get(&self, index: u32) -> Option<u32> {
+            self.values.get(index).cloned()
+        }
+
+        pub fn set(&mut self, index: u32, value: u32) -> bool {
+            match self.values.get_mut(index) {
+                Some(slot) => {
+                    *slot = value;
+                    true
+                }
+                None => false,
+            }
+        }
+
+        pub fn sum(&self) -> u64 {
+            self.values.iter().map(|value| *value as u64).sum()
+        }
+
+        pub fn max(&self) -> Option<u32> {
+            self.values.iter().max().cloned()
+        }
+
+        pub fn min(&self) -> Option<u32> {
+            self.values.iter().min().cloned()
+        }
+
+        pub fn toggle(&mut self) -> bool {
+            let flag = !*self.flag;
+            self.flag.set(flag);
+            flag
+        }
+
+        #[liquid(selector = "0x00000000")]
+        pub fn first(&self) -> Option<u32> {
+            self.values.get(0).cloned()
+        }
+
+        #[liquid(selector = "0xffffffff")]
+        pub fn last(&self) -> Option<u32> {
+            match self.values.len() {
+                0 => None,
+                len => self.values.get(len - 1).cloned(),
+            }
+        }
+    }
+}
+
+fn main() {}
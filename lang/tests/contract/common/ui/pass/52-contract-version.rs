@@ -0,0 +1,25 @@
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract(version = "1.2.0")]
+mod versioned {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Versioned {
+        value: storage::Value<u32>,
+    }
+
+    #[liquid(methods)]
+    impl Versioned {
+        pub fn new(&mut self) {
+            self.value.initialize(0);
+        }
+
+        pub fn get(&self) -> u32 {
+            *self.value
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,52 @@
+use liquid::InOut;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[derive(InOut)]
+    pub struct Log {
+        i: i8,
+        s: String,
+    }
+
+    #[liquid(event)]
+    struct Transfer {
+        #[liquid(indexed)]
+        from: address,
+        #[liquid(indexed)]
+        memo: String,
+        payload: bytes,
+        log: Log,
+    }
+
+    #[liquid(event)]
+    struct Ping {
+        #[liquid(indexed)]
+        value: u32,
+    }
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop(&mut self, memo: String, payload: bytes) {
+            self.env().emit_ref(TransferRef {
+                from: self.env().get_caller(),
+                memo: &memo,
+                payload: &payload,
+                log: Log {
+                    i: 0,
+                    s: String::from("456"),
+                },
+            });
+            self.env().emit(Ping { value: 0 });
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,46 @@
+use liquid::InOut;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod market {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Market {}
+
+    #[derive(InOut)]
+    pub struct OrderKey {
+        market_id: u32,
+        side: bool,
+    }
+
+    #[liquid(event)]
+    struct OrderPlaced {
+        #[liquid(indexed)]
+        key: OrderKey,
+        #[liquid(indexed)]
+        keys: [OrderKey; 2],
+        amount: u64,
+    }
+
+    #[liquid(methods)]
+    impl Market {
+        pub fn new(&mut self) {}
+
+        pub fn place(&mut self, market_id: u32, side: bool, amount: u64) {
+            self.env().emit(OrderPlaced {
+                key: OrderKey { market_id, side },
+                keys: [
+                    OrderKey { market_id, side },
+                    OrderKey {
+                        market_id,
+                        side: !side,
+                    },
+                ],
+                amount,
+            });
+        }
+    }
+}
+
+fn main() {}
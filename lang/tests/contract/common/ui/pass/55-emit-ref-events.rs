@@ -0,0 +1,62 @@
+use liquid::env::test;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(event)]
+    struct Transfer {
+        #[liquid(indexed)]
+        from: address,
+        #[liquid(indexed)]
+        memo: String,
+        payload: bytes,
+        value: u32,
+    }
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop(&self) {}
+    }
+}
+
+use noop::{Event, EventRef, Transfer, TransferRef};
+
+fn main() {
+    test::run_test(|| {
+        let from = test::default_accounts().alice;
+        let memo = String::from("rent");
+        let payload = vec![0x12, 0x34, 0x56];
+
+        liquid::env::emit(Event::from(Transfer {
+            from,
+            memo: memo.clone(),
+            payload: payload.clone().into(),
+            value: 42,
+        }));
+        liquid::env::emit_ref(EventRef::from(TransferRef {
+            from,
+            memo: &memo,
+            payload: &payload,
+            value: 42,
+        }));
+
+        // The borrowing event is logged exactly as the owning one.
+        let events = test::get_events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].topics.len(), 3);
+        assert_eq!(events[0].topics, events[1].topics);
+        assert!(!events[0].data.is_empty());
+        assert_eq!(events[0].data, events[1].data);
+
+        let report = test::gas_report();
+        assert_eq!(report.events, 2);
+        assert_eq!(report.event_bytes, 2 * events[0].data.len() as u64);
+    });
+}
@@ -0,0 +1,51 @@
+use liquid::{
+    acl::{AccessControl, Ownable},
+    storage,
+};
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod vault {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Vault {
+        ownable: Ownable,
+        roles: AccessControl,
+        balance: storage::Value<u128>,
+    }
+
+    #[liquid(methods)]
+    impl Vault {
+        pub fn new(&mut self) {
+            let caller = self.env().get_caller();
+            self.ownable.initialize(caller);
+            self.roles.initialize(caller);
+            self.balance.initialize(0);
+        }
+
+        #[liquid(only_owner)]
+        pub fn grant_minter(&mut self, account: address) {
+            self.roles.grant_role("MINTER", account);
+        }
+
+        #[liquid(only_role = "MINTER")]
+        pub fn mint(&mut self, amount: u128) {
+            self.balance += amount;
+        }
+
+        #[liquid(only_owner)]
+        #[liquid(only_role = "MINTER")]
+        pub fn burn_all(&mut self) -> u128 {
+            let burnt = *self.balance;
+            self.balance.set(0);
+            burnt
+        }
+
+        pub fn balance(&self) -> u128 {
+            *self.balance
+        }
+    }
+}
+
+fn main() {}
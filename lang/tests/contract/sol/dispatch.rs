@@ -0,0 +1,212 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use liquid::storage;
+use liquid_lang as liquid;
+
+#[liquid::contract]
+mod many_methods {
+    use super::*;
+
+    #[liquid(storage)]
+    struct ManyMethods {
+        values: storage::Vec<u32>,
+        flag: storage::Value<bool>,
+    }
+
+    #[liquid(methods)]
+    impl ManyMethods {
+        pub fn new(&mut self) {
+            self.values.initialize();
+            self.flag.initialize(false);
+        }
+
+        pub fn push(&mut self, value: u32) {
+            self.values.push(value);
+        }
+
+        pub fn pop(&mut self) -> u32 {
+            self.values.pop().unwrap_or(0)
+        }
+
+        pub fn len(&self) -> u32 {
+            self.values.len()
+        }
+
+        pub fn get(&self, index: u32) -> u32 {
+            self.values.get(index).cloned().unwrap_or(0)
+        }
+
+        pub fn set(&mut self, index: u32, value: u32) -> bool {
+            match self.values.get_mut(index) {
+                Some(slot) => {
+                    *slot = value;
+                    true
+                }
+                None => false,
+            }
+        }
+
+        pub fn remove(&mut self, index: u32) -> bool {
+            self.values.swap_remove(index).is_some()
+        }
+
+        pub fn contains(&self, value: u32) -> bool {
+            self.values.iter().any(|other| *other == value)
+        }
+
+        pub fn count(&self, value: u32) -> u32 {
+            self.values.iter().filter(|other| **other == value).count() as u32
+        }
+
+        pub fn sum(&self) -> u64 {
+            self.values.iter().map(|value| *value as u64).sum()
+        }
+
+        pub fn max(&self) -> u32 {
+            self.values.iter().max().cloned().unwrap_or(0)
+        }
+
+        pub fn min(&self) -> u32 {
+            self.values.iter().min().cloned().unwrap_or(0)
+        }
+
+        pub fn double_all(&mut self) {
+            for index in 0..self.values.len() {
+                if let Some(value) = self.values.get_mut(index) {
+                    *value *= 2;
+                }
+            }
+        }
+
+        pub fn toggle(&mut self) -> bool {
+            let flag = !*self.flag;
+            self.flag.set(flag);
+            flag
+        }
+
+        pub fn flag(&self) -> bool {
+            *self.flag
+        }
+
+        // Pinned to the ends of the sorted selector table.
+        #[liquid(selector = "0x00000000")]
+        pub fn first(&self) -> u32 {
+            self.get(0)
+        }
+
+        #[liquid(selector = "0xffffffff")]
+        pub fn last(&self) -> u32 {
+            match self.values.len() {
+                0 => 0,
+                len => self.get(len - 1),
+            }
+        }
+    }
+}
+
+use liquid::env::{self, error::Result, test};
+use liquid_abi_codec::{Decode, Encode, TypeInfo};
+use liquid_primitives::{types::Address, Selector};
+use many_methods::ManyMethods;
+
+const CONTRACT: [u8; 20] = [0x42; 20];
+
+/// Calls the method with `selector` through the dispatcher of the deployed
+/// contract, with `input` being the encoded inputs.
+fn call_raw<R>(selector: Selector, input: Vec<u8>) -> Result<R>
+where
+    R: Decode + TypeInfo,
+{
+    let mut data = selector.to_vec();
+    data.extend(input);
+    env::call::<R>(&Address::new(CONTRACT), &data)
+}
+
+fn call<R>(selector: Selector, input: Vec<u8>) -> R
+where
+    R: Decode + TypeInfo,
+{
+    call_raw(selector, input).unwrap()
+}
+
+fn selector(signature: &str) -> Selector {
+    liquid_primitives::selector(signature.as_bytes())
+}
+
+#[liquid::test]
+fn dispatch_every_selector() {
+    test::deploy_at(Address::new(CONTRACT), ManyMethods::new);
+
+    for value in [3u32, 1, 4].iter() {
+        call::<()>(selector("push(uint32)"), (*value,).encode());
+    }
+    assert_eq!(call::<u32>(selector("len()"), vec![]), 3);
+    assert_eq!(call::<u32>(selector("get(uint32)"), (1u32,).encode()), 1);
+    assert_eq!(
+        call::<bool>(selector("set(uint32,uint32)"), (1u32, 5u32).encode()),
+        true
+    );
+    assert_eq!(
+        call::<bool>(selector("set(uint32,uint32)"), (9u32, 5u32).encode()),
+        false
+    );
+    assert_eq!(call::<u64>(selector("sum()"), vec![]), 12);
+    assert_eq!(call::<u32>(selector("max()"), vec![]), 5);
+    assert_eq!(call::<u32>(selector("min()"), vec![]), 3);
+    assert_eq!(
+        call::<bool>(selector("contains(uint32)"), (4u32,).encode()),
+        true
+    );
+    assert_eq!(
+        call::<bool>(selector("contains(uint32)"), (1u32,).encode()),
+        false
+    );
+    assert_eq!(call::<u32>(selector("count(uint32)"), (5u32,).encode()), 1);
+    assert_eq!(call::<bool>(selector("toggle()"), vec![]), true);
+    assert_eq!(call::<bool>(selector("flag()"), vec![]), true);
+
+    call::<()>(selector("double_all()"), vec![]);
+    assert_eq!(call::<u64>(selector("sum()"), vec![]), 24);
+    assert_eq!(
+        call::<bool>(selector("remove(uint32)"), (0u32,).encode()),
+        true
+    );
+    assert_eq!(call::<u32>(selector("len()"), vec![]), 2);
+    assert_eq!(call::<u32>(selector("pop()"), vec![]), 10);
+}
+
+#[liquid::test]
+fn dispatch_selectors_at_both_ends_of_the_table() {
+    test::deploy_at(Address::new(CONTRACT), ManyMethods::new);
+    call::<()>(selector("push(uint32)"), (3u32,).encode());
+    call::<()>(selector("push(uint32)"), (4u32,).encode());
+
+    assert_eq!(call::<u32>([0x00; 4], vec![]), 3);
+    assert_eq!(call::<u32>([0xff; 4], vec![]), 4);
+}
+
+#[liquid::test]
+fn reject_absent_selectors() {
+    test::deploy_at(Address::new(CONTRACT), ManyMethods::new);
+
+    for absent in [
+        selector("unknown()"),
+        [0x00, 0x00, 0x00, 0x01],
+        [0xff, 0xff, 0xff, 0xfe],
+    ]
+    .iter()
+    {
+        let result = env::optional_call(|| call_raw::<()>(*absent, vec![]));
+        assert!(result.is_err());
+    }
+}
@@ -10,6 +10,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod dispatch;
+
 use serial_test::serial;
 
 #[test]
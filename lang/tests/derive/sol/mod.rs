@@ -19,6 +19,7 @@ fn compile_tests() {
 
     t.pass("tests/derive/sol/ui/pass/01-state.rs");
     t.pass("tests/derive/sol/ui/pass/02-nested.rs");
+    t.pass("tests/derive/sol/ui/pass/03-enum.rs");
     t.compile_fail("tests/derive/sol/ui/fail/01-empty-struct.rs");
     t.compile_fail("tests/derive/sol/ui/fail/02-enum.rs");
     t.compile_fail("tests/derive/sol/ui/fail/03-not-public.rs");
@@ -166,4 +167,96 @@ mod codec_tests {
         type Array = Vec<T0>;
         let _ = <Array as TypeInfo>::size_hint();
     }
+
+    #[derive(InOut, PartialEq, Debug, Clone, Copy)]
+    pub enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    #[test]
+    fn test_unit_enum() {
+        assert_eq!(<Color as TypeInfo>::is_dynamic(), false);
+        assert_eq!(<Color as TypeInfo>::size_hint(), 32);
+        assert_eq!(map_to_solidity_type::<Color>(), "uint8");
+
+        let color = Color::Red;
+        test_encode_decode!(
+            Color,
+            color,
+            "0000000000000000000000000000000000000000000000000000000000000000"
+        );
+        let color = Color::Blue;
+        test_encode_decode!(
+            Color,
+            color,
+            "0000000000000000000000000000000000000000000000000000000000000002"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unit_enum_invalid_tag() {
+        let color = Color::Green;
+        test_encode_decode!(
+            Color,
+            color,
+            "0000000000000000000000000000000000000000000000000000000000000003"
+        );
+    }
+
+    #[derive(InOut, PartialEq, Debug, Clone)]
+    pub enum Shape {
+        Empty,
+        Circle(u32),
+        Rect { w: u8, h: u8 },
+    }
+
+    #[test]
+    fn test_static_enum() {
+        assert_eq!(<Shape as TypeInfo>::is_dynamic(), false);
+        assert_eq!(<Shape as TypeInfo>::size_hint(), 128);
+        assert_eq!(
+            map_to_solidity_type::<Shape>(),
+            "(uint8,(uint32),(uint8,uint8))"
+        );
+
+        let shape = Shape::Empty;
+        test_encode_decode!(Shape, shape, "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
+        let shape = Shape::Circle(5);
+        test_encode_decode!(Shape, shape, "0000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
+        let shape = Shape::Rect { w: 2, h: 3 };
+        test_encode_decode!(Shape, shape, "0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_static_enum_invalid_tag() {
+        let shape = Shape::Empty;
+        test_encode_decode!(Shape, shape, "0000000000000000000000000000000000000000000000000000000000000003000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
+    }
+
+    #[derive(InOut, PartialEq, Debug, Clone)]
+    pub enum Message {
+        Ping,
+        Text(String),
+        Pair(u8, bool),
+    }
+
+    #[test]
+    fn test_dynamic_enum() {
+        assert_eq!(<Message as TypeInfo>::is_dynamic(), true);
+        assert_eq!(
+            map_to_solidity_type::<Message>(),
+            "(uint8,(string),(uint8,bool))"
+        );
+
+        let message = Message::Ping;
+        test_encode_decode!(Message, message, "0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000000");
+        let message = Message::Text("hi".to_owned());
+        test_encode_decode!(Message, message, "00000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000026869000000000000000000000000000000000000000000000000000000000000");
+        let message = Message::Pair(7, true);
+        test_encode_decode!(Message, message, "0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000800000000000000000000000000000000000000000000000000000000000000007000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000000");
+    }
 }
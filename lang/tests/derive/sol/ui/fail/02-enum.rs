@@ -1,8 +1,8 @@
-use liquid::{InOut, State};
+use liquid::State;
 use liquid_lang as liquid;
 
-#[derive(InOut, State)]
-enum MyEnum {
+#[derive(State)]
+pub enum MyEnum {
     U32(u32),
     S(String),
 }
@@ -0,0 +1,39 @@
+use liquid::InOut;
+use liquid_lang as liquid;
+
+#[derive(InOut, Default)]
+pub enum Direction {
+    #[default]
+    Up,
+    Down,
+}
+
+#[derive(InOut)]
+pub enum Action {
+    Stop,
+    Move(Direction, u32),
+    Say { words: String },
+}
+
+#[liquid::contract]
+mod noop {
+    use super::*;
+
+    #[liquid(storage)]
+    struct Noop {}
+
+    #[liquid(methods)]
+    impl Noop {
+        pub fn new(&mut self) {}
+
+        pub fn noop_1(&self, _a: Action) {}
+
+        pub fn noop_2(&self, _a: Vec<Action>) {}
+
+        pub fn noop_3(&self) -> (Action, Direction) {
+            (Action::Move(Direction::Up, 1), Direction::Down)
+        }
+    }
+}
+
+fn main() {}
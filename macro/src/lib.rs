@@ -26,7 +26,6 @@ pub fn seq(input: TokenStream) -> TokenStream {
     }
 }
 
-#[cfg(feature = "collaboration")]
 #[proc_macro]
 pub fn sign(input: TokenStream) -> TokenStream {
     match sign::sign_impl(input.into()) {
@@ -34,3 +33,11 @@ pub fn sign(input: TokenStream) -> TokenStream {
         Err(error) => error.into_compile_error().into(),
     }
 }
+
+#[proc_macro]
+pub fn propose(input: TokenStream) -> TokenStream {
+    match sign::propose_impl(input.into()) {
+        Ok(expanded) => expanded.into(),
+        Err(error) => error.into_compile_error().into(),
+    }
+}
@@ -20,6 +20,28 @@ use proc_macro2::{
 use quote::quote;
 
 pub fn sign_impl(input: TokenStream2) -> Result<TokenStream2> {
+    let contract = construct_contract(input)?;
+    Ok(quote! {
+        {
+            #contract
+            <ContractId<T> as liquid_lang::ContractVisitor>::sign_new_contract(contract)
+        }
+    })
+}
+
+pub fn propose_impl(input: TokenStream2) -> Result<TokenStream2> {
+    let contract = construct_contract(input)?;
+    Ok(quote! {
+        {
+            #contract
+            ProposalId::<T>::propose(contract)
+        }
+    })
+}
+
+/// Parses the input in the form of `Contract => fields` and constructs the
+/// contract as a local variable named `contract`.
+fn construct_contract(input: TokenStream2) -> Result<TokenStream2> {
     let mut iter = input.into_iter();
     let ident = expect_ident(&mut iter)?;
     expect_right_arrow(&mut iter)?;
@@ -66,13 +88,10 @@ pub fn sign_impl(input: TokenStream2) -> Result<TokenStream2> {
     };
 
     Ok(quote! {
-        {
-            type T = <#ident as liquid_lang::ContractType>::T;
-            let contract = T {
-                #expr_construct
-            };
-            <ContractId<T> as liquid_lang::ContractVisitor>::sign_new_contract(contract)
-        }
+        type T = <#ident as liquid_lang::ContractType>::T;
+        let contract = T {
+            #expr_construct
+        };
     })
 }
 
@@ -245,4 +245,10 @@ mod tests {
         assert_eq!(hash[2], 0xe6);
         assert_eq!(hash[3], 0x3c);
     }
+
+    #[test]
+    fn const_selector() {
+        const SELECTOR: crate::Selector = crate::selector(b"add(uint32,string)");
+        assert_eq!(SELECTOR, [0x66, 0x89, 0xaa, 0x15]);
+    }
 }
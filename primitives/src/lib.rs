@@ -14,11 +14,6 @@
 #![feature(const_fn)]
 #![feature(const_mut_refs)]
 
-#[macro_use]
-extern crate lazy_static;
-#[macro_use]
-extern crate num_derive;
-
 use cfg_if::cfg_if;
 use liquid_prelude::vec::Vec;
 
@@ -29,6 +24,14 @@ pub mod types;
 pub type Key = &'static str;
 pub type Selector = [u8; 4];
 
+/// Computes the selector of `signature` with the hash algorithm of the chain,
+/// which is the first 4 bytes of its hash. Being a `const fn`, it costs nothing
+/// at runtime when used to initialize a constant.
+pub const fn selector(signature: &[u8]) -> Selector {
+    let hash = hash::hash(signature);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
 #[cfg(feature = "std")]
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct Error(&'static str);
@@ -56,8 +59,20 @@ impl std::fmt::Display for Error {
     }
 }
 
+/// The most topics a log can carry, i.e., the hash of the event signature and
+/// at most 3 indexed fields.
+pub const MAX_TOPICS: usize = 4;
+
 pub trait Topics {
-    fn topics(&self) -> Vec<types::Hash>;
+    /// Writes the topics into the front of `topics` and returns how many
+    /// topics are written, without any heap allocation.
+    fn write_topics(&self, topics: &mut [types::Hash; MAX_TOPICS]) -> usize;
+
+    fn topics(&self) -> Vec<types::Hash> {
+        let mut topics = [types::Hash::default(); MAX_TOPICS];
+        let len = self.write_topics(&mut topics);
+        topics[..len].to_vec()
+    }
 }
 
 cfg_if! {
@@ -30,69 +30,155 @@ impl Address {
     pub const fn empty() -> Self {
         Self([0u8; ADDRESS_LENGTH])
     }
+
+    /// Returns the zero address, which is the same as `Address::empty()`.
+    pub const fn zero() -> Self {
+        Self::empty()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0u8; ADDRESS_LENGTH]
+    }
+
+    /// Derives the address of an account from its uncompressed public key,
+    /// without the leading `0x04` tag, as the last 20 bytes of its hash.
+    pub fn from_pubkey(pubkey: &[u8; 64]) -> Self {
+        let hash = crate::hash::hash(pubkey);
+        let mut addr = [0u8; ADDRESS_LENGTH];
+        addr.copy_from_slice(&hash[32 - ADDRESS_LENGTH..]);
+        Self(addr)
+    }
+
+    /// Computes the address of the contract deployed by `sender` when its
+    /// nonce is `nonce`, as `CREATE` does: the last 20 bytes of the hash of
+    /// the RLP encoding of `[sender, nonce]`.
+    pub fn from_create(sender: &Address, nonce: u64) -> Self {
+        // The payload never exceeds 55 bytes, so both the list and the address
+        // have a single-byte length prefix.
+        let mut rlp = [0u8; 2 + ADDRESS_LENGTH + 1 + 8];
+        rlp[1] = 0x80 + ADDRESS_LENGTH as u8;
+        rlp[2..2 + ADDRESS_LENGTH].copy_from_slice(&sender.0);
+        let nonce_offset = 2 + ADDRESS_LENGTH;
+        let len = match nonce {
+            0 => {
+                rlp[nonce_offset] = 0x80;
+                nonce_offset + 1
+            }
+            1..=0x7f => {
+                rlp[nonce_offset] = nonce as u8;
+                nonce_offset + 1
+            }
+            _ => {
+                let bytes = nonce.to_be_bytes();
+                let leading_zeros = (nonce.leading_zeros() / 8) as usize;
+                let nonce_len = bytes.len() - leading_zeros;
+                rlp[nonce_offset] = 0x80 + nonce_len as u8;
+                rlp[nonce_offset + 1..nonce_offset + 1 + nonce_len]
+                    .copy_from_slice(&bytes[leading_zeros..]);
+                nonce_offset + 1 + nonce_len
+            }
+        };
+        rlp[0] = 0xc0 + (len - 1) as u8;
+
+        let hash = crate::hash::hash(&rlp[..len]);
+        let mut addr = [0u8; ADDRESS_LENGTH];
+        addr.copy_from_slice(&hash[32 - ADDRESS_LENGTH..]);
+        Self(addr)
+    }
+
+    /// Formats the address with the mixed-case checksum of EIP-55, which is
+    /// computed with the hash algorithm of the chain (Keccak-256, or SM3 if
+    /// the `gm` feature is enabled).
+    pub fn to_checksum_string(&self) -> String {
+        let digits = self.checksum_digits();
+        let mut ret = String::with_capacity(ADDRESS_LENGTH * 2 + 2);
+        ret.push_str("0x");
+        ret.extend(digits.iter().map(|digit| *digit as char));
+        ret
+    }
+
+    fn checksum_digits(&self) -> [u8; ADDRESS_LENGTH * 2] {
+        let mut digits = [0u8; ADDRESS_LENGTH * 2];
+        for (i, byte) in self.0.iter().enumerate() {
+            digits[i * 2] = HEX_DIGITS[(byte >> 4) as usize];
+            digits[i * 2 + 1] = HEX_DIGITS[(byte & 0x0f) as usize];
+        }
+
+        // A letter is capitalized if the corresponding nibble in the hash of
+        // the lowercase digits is at least 8.
+        let hash = crate::hash::hash(&digits);
+        for (i, digit) in digits.iter_mut().enumerate() {
+            let nibble = if i % 2 == 0 {
+                hash[i / 2] >> 4
+            } else {
+                hash[i / 2] & 0x0f
+            };
+            if nibble >= 8 {
+                digit.make_ascii_uppercase();
+            }
+        }
+        digits
+    }
 }
 
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
 impl Default for Address {
     fn default() -> Self {
         Self([0; ADDRESS_LENGTH])
     }
 }
 
+/// Renders the address as `0x` followed by 40 lowercase hexadecimal digits,
+/// on the stack rather than into an intermediate `String`.
 impl fmt::Display for Address {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut ret = String::with_capacity(ADDRESS_LENGTH * 2 + 2);
-        ret.push_str("0x");
-
-        for digit in self.0.iter() {
-            let low = digit & 0x0fu8;
-            let high = digit >> 4;
-            ret.push(core::char::from_digit(high.into(), 16).unwrap());
-            ret.push(core::char::from_digit(low.into(), 16).unwrap());
+        let mut rendered = [0u8; ADDRESS_LENGTH * 2 + 2];
+        rendered[..2].copy_from_slice(b"0x");
+        for (pair, byte) in rendered[2..].chunks_mut(2).zip(self.0.iter()) {
+            pair[0] = HEX_DIGITS[(byte >> 4) as usize];
+            pair[1] = HEX_DIGITS[(byte & 0x0f) as usize];
         }
-        write!(f, "{}", ret)
+        // Only ASCII digits are rendered, so the conversion never fails.
+        f.write_str(core::str::from_utf8(&rendered).map_err(|_| fmt::Error)?)
     }
 }
 
+/// Parses an address from at most 40 hexadecimal digits with an optional
+/// `0x` prefix, padding shorter representations with leading zeros. If the
+/// digits are mixed-case, they must carry a valid EIP-55 checksum.
 impl FromStr for Address {
     type Err = Error;
 
-    fn from_str(mut s: &str) -> Result<Self, Self::Err> {
-        if !s.is_ascii() {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s)
+            .as_bytes();
+        if digits.len() > ADDRESS_LENGTH * 2
+            || !digits.iter().all(|digit| digit.is_ascii_hexdigit())
+        {
             return Err("invalid address representation".into());
         }
 
-        if s.starts_with("0x") || s.starts_with("0X") {
-            if s.len() > ADDRESS_LENGTH * 2 + 2 {
-                return Err("invalid address representation".into());
-            }
-            s = &s[2..];
-        } else if s.len() > ADDRESS_LENGTH * 2 {
-            return Err("invalid address representation".into());
-        }
+        let mut padded = [b'0'; ADDRESS_LENGTH * 2];
+        padded[ADDRESS_LENGTH * 2 - digits.len()..].copy_from_slice(digits);
 
         let mut addr = [0u8; ADDRESS_LENGTH];
-        let bytes = s.as_bytes();
-        let padding_len = ADDRESS_LENGTH * 2 - bytes.len();
-        for i in 0..ADDRESS_LENGTH {
-            let (low, high) = if i * 2 + 1 < padding_len {
-                (0, 0)
-            } else {
-                (
-                    (bytes[i * 2 + 1 - padding_len] as char)
-                        .to_digit(16)
-                        .unwrap(),
-                    if i * 2 < padding_len {
-                        0
-                    } else {
-                        (bytes[i * 2 - padding_len] as char).to_digit(16).unwrap()
-                    },
-                )
-            };
+        for (byte, pair) in addr.iter_mut().zip(padded.chunks(2)) {
+            let high = (pair[0] as char).to_digit(16).unwrap();
+            let low = (pair[1] as char).to_digit(16).unwrap();
+            *byte = ((high << 4) + low) as u8;
+        }
+        let addr = Self(addr);
 
-            let digit = (high << 4) + low;
-            addr[i] = digit as u8;
+        let is_mixed_case = digits.iter().any(u8::is_ascii_lowercase)
+            && digits.iter().any(u8::is_ascii_uppercase);
+        if is_mixed_case && padded != addr.checksum_digits() {
+            return Err("invalid address checksum".into());
         }
-        Ok(Self(addr))
+        Ok(addr)
     }
 }
 
@@ -174,6 +260,16 @@ mod tests {
         assert_eq!(addr_str.parse::<Address>().unwrap(), addr);
     }
 
+    #[test]
+    fn render_non_utf8_bytes() {
+        let addr = Address([0xff; ADDRESS_LENGTH]);
+        assert_eq!(
+            addr.to_string(),
+            "0xffffffffffffffffffffffffffffffffffffffff"
+        );
+        assert_eq!(addr.to_string().parse::<Address>().unwrap(), addr);
+    }
+
     #[test]
     fn padding_1() {
         let addr: Address = "0x12".parse().unwrap();
@@ -217,4 +313,72 @@ mod tests {
     fn invalid_addr_str_encode() {
         let _: Address = "羞答答小白虎头李荣浩".parse().unwrap();
     }
+
+    #[test]
+    #[should_panic(expected = "invalid address representation")]
+    fn invalid_addr_digit() {
+        let _: Address = "0x3g".parse().unwrap();
+    }
+
+    #[test]
+    fn zero_addr() {
+        assert!(Address::zero().is_zero());
+        assert_eq!(Address::zero(), Address::default());
+        assert!(!Address(TEST_ADDR).is_zero());
+        assert!("0x".parse::<Address>().unwrap().is_zero());
+    }
+
+    #[cfg(not(feature = "gm"))]
+    #[test]
+    fn checksum() {
+        let checksummed = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        let addr: Address = checksummed.parse().unwrap();
+        assert_eq!(addr.to_checksum_string(), checksummed);
+        assert_eq!(addr.to_string(), checksummed.to_lowercase());
+        assert_eq!(checksummed.to_uppercase()[2..].parse::<Address>(), Ok(addr));
+        assert_eq!(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD".parse::<Address>(),
+            Err("invalid address checksum".into())
+        );
+    }
+
+    #[cfg(not(feature = "gm"))]
+    #[test]
+    fn from_pubkey() {
+        // Public key of the secp256k1 private key `1`
+        let mut pubkey = [0u8; 64];
+        pubkey[..32].copy_from_slice(&[
+            0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce,
+            0x87, 0x0b, 0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2,
+            0x81, 0x5b, 0x16, 0xf8, 0x17, 0x98,
+        ]);
+        pubkey[32..].copy_from_slice(&[
+            0x48, 0x3a, 0xda, 0x77, 0x26, 0xa3, 0xc4, 0x65, 0x5d, 0xa4, 0xfb, 0xfc, 0x0e,
+            0x11, 0x08, 0xa8, 0xfd, 0x17, 0xb4, 0x48, 0xa6, 0x85, 0x54, 0x19, 0x9c, 0x47,
+            0xd0, 0x8f, 0xfb, 0x10, 0xd4, 0xb8,
+        ]);
+        assert_eq!(
+            Address::from_pubkey(&pubkey).to_checksum_string(),
+            "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+        );
+    }
+
+    #[cfg(not(feature = "gm"))]
+    #[test]
+    fn from_create() {
+        let sender: Address = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0"
+            .parse()
+            .unwrap();
+        let created = |nonce| Address::from_create(&sender, nonce).to_string();
+        assert_eq!(created(0), "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d");
+        assert_eq!(created(1), "0x343c43a37d37dff08ae8c4a11544c718abb4fcf8");
+        assert_eq!(created(2), "0xf778b86fa74e846c4f0a1fbd1335fe81c00a0c91");
+        assert_eq!(created(3), "0xfffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c");
+        assert_eq!(created(0x80), "0x08e190dcb7b73f5fcdabb43e102215c83659a76d");
+        assert_eq!(created(0x100), "0x3837c1ae70354f670550c746580199ac6a73cb0a");
+        assert_eq!(
+            created(u64::MAX),
+            "0x9bc924993b60399df164c3763a964301d3db95ca"
+        );
+    }
 }
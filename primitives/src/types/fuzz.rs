@@ -0,0 +1,52 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of arbitrary values of primitive types, which is how
+//! `liquid_lang::fuzz` produces inputs of contract methods.
+
+use crate::types::{address::ADDRESS_LENGTH, *};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use liquid_macro::seq;
+
+impl<'a> Arbitrary<'a> for u256 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self(Arbitrary::arbitrary(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for i256 {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self(Arbitrary::arbitrary(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Address {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self(<[u8; ADDRESS_LENGTH]>::arbitrary(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Bytes {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::from(<&[u8]>::arbitrary(u)?))
+    }
+}
+
+seq!(N in 1..=32 {
+    #(
+        impl<'a> Arbitrary<'a> for Bytes#N {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                Ok(Self(<[u8; N as usize]>::arbitrary(u)?))
+            }
+        }
+    )*
+});
@@ -33,6 +33,10 @@ impl Hash {
     pub fn as_ptr(&self) -> *const [u8; HASH_LENGTH] {
         &self.0 as *const _
     }
+
+    pub fn as_bytes(&self) -> &[u8; HASH_LENGTH] {
+        &self.0
+    }
 }
 
 impl From<[u8; HASH_LENGTH]> for Hash {
@@ -10,79 +10,604 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::types::uint256::u256;
+use crate::types::uint256::{u256, ParseIntError};
 #[allow(unused_imports)]
 use core::{
+    cmp::Ordering,
     fmt,
     ops::{
-        Add, AddAssign, Deref, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub,
+        Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub,
         SubAssign,
     },
     str::FromStr,
 };
-use liquid_prelude::vec::{from_elem, Vec};
-use num::{
-    bigint::{BigInt, ParseBigIntError},
-    pow,
-    traits::{
-        ops::checked::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub},
-        Signed,
-    },
-    Bounded, Num,
+use liquid_prelude::{string::String, vec::Vec};
+use num_traits::{
+    ops::checked::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub},
+    Bounded, Num, One, Signed, Zero,
 };
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Num, One, Zero)]
+/// Signed 256 bit integer, stored as the two's complement of its value.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
 #[allow(non_camel_case_types)]
-pub struct i256(pub BigInt);
+pub struct i256(pub(crate) u256);
 
 impl i256 {
+    const MIN: Self = Self(u256([0, 0, 0, 1 << 63]));
+    const MAX: Self = Self(u256([u64::MAX, u64::MAX, u64::MAX, i64::MAX as u64]));
+
     /// Checked conversion to u256
     pub fn to_uint256(&self) -> Option<u256> {
-        self.0
-            .to_biguint()
-            .map(u256)
-            .filter(|value| *value <= u256::max_value() && *value >= u256::min_value())
+        if self.is_negative() {
+            None
+        } else {
+            Some(self.0)
+        }
     }
 
     pub fn to_be_bytes(&self) -> [u8; 32] {
-        let bytes = self.0.to_signed_bytes_be();
-        let mut res = [0u8; 32];
-        res[32 - bytes.len()..].copy_from_slice(&bytes);
-        res
+        self.0.to_be_bytes()
     }
 
     pub fn from_signed_le_bytes(bytes: &[u8]) -> Self {
-        let value = num_bigint::BigInt::from_signed_bytes_le(bytes);
-        Self(value)
+        let be = bytes.iter().rev().copied().collect::<Vec<_>>();
+        Self::from_signed_be_bytes(&be)
     }
 
+    /// Creates a value from its big-endian two's complement representation,
+    /// sign-extending it if shorter than 32 bytes.
+    ///
+    /// # Panics
+    ///
+    /// If the value is out of the range of `i256`.
     pub fn from_signed_be_bytes(bytes: &[u8]) -> Self {
-        let value = num_bigint::BigInt::from_signed_bytes_be(bytes);
-        Self(value)
+        let fill = match bytes.first() {
+            Some(byte) if byte & 0x80 != 0 => 0xff,
+            _ => 0x00,
+        };
+        let (high, low) = bytes.split_at(bytes.len().saturating_sub(32));
+        if high.iter().any(|byte| *byte != fill)
+            || (!high.is_empty() && (low[0] ^ fill) & 0x80 != 0)
+        {
+            panic!("attempt to convert bytes to i256 with overflow");
+        }
+
+        let mut buf = [fill; 32];
+        buf[32 - low.len()..].copy_from_slice(low);
+        Self(u256::from_be_bytes(&buf))
+    }
+
+    /// Multiplies by a `u64` directly, without converting `rhs` to `i256`
+    /// first.
+    ///
+    /// # Panics
+    ///
+    /// If the product is out of the range of `i256`.
+    pub fn mul_u64(&self, rhs: u64) -> Self {
+        let (prod, overflow) = self.magnitude().overflowing_mul_u64(rhs);
+        match Self::with_sign(self.is_negative(), prod, overflow) {
+            Ok(res) => res,
+            Err(kind) => panic!("attempt to mul_u64 with {}", kind),
+        }
+    }
+
+    /// Divides by a `u64` using single-limb division. The quotient is
+    /// truncated toward zero, as the `/` operator does.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    pub fn div_u64(&self, rhs: u64) -> Self {
+        let quotient = self.magnitude().div_rem_u64(rhs).0;
+        Self::with_sign(self.is_negative(), quotient, false).unwrap()
+    }
+
+    /// Computes the remainder of dividing by a `u64`, which is returned as a
+    /// primitive integer as `u256::rem_u64` does.
+    ///
+    /// The remainder takes the sign of `self` and its magnitude is less than
+    /// `rhs`, as the `%` operator does since the quotient is truncated toward
+    /// zero, e.g. `-7 % 3` is `-1` rather than `2`. Hence the remainder of a
+    /// negative value may be as low as `1 - u64::MAX`, which only fits in an
+    /// `i128`.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    pub fn rem_u64(&self, rhs: u64) -> i128 {
+        let rem = self.magnitude().div_rem_u64(rhs).1 as i128;
+        if self.is_negative() {
+            -rem
+        } else {
+            rem
+        }
+    }
+
+    /// Formats the value in the given radix, without any prefix.
+    ///
+    /// # Panics
+    ///
+    /// If `radix` is not in the range from 2 to 36.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let mut s = self.magnitude().to_str_radix(radix);
+        if self.is_negative() {
+            s.insert(0, '-');
+        }
+        s
+    }
+
+    /// Raises to the power of `exp`.
+    ///
+    /// # Panics
+    ///
+    /// If the result is out of the range of `i256`.
+    pub fn pow(self, exp: u32) -> Self {
+        match self.try_pow(exp) {
+            Ok(res) => res,
+            Err(kind) => panic!("attempt to pow with {}", kind),
+        }
+    }
+
+    /// Returns the integer square root, rounded down.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is negative.
+    pub fn sqrt(self) -> Self {
+        if self.is_negative() {
+            panic!("argument of integer square root cannot be negative");
+        }
+        Self(self.0.sqrt())
+    }
+
+    /// Returns the base 2 logarithm, rounded down.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is not positive.
+    pub fn log2(self) -> u32 {
+        if !self.is_positive() {
+            panic!("argument of integer logarithm must be positive");
+        }
+        self.0.log2()
+    }
+
+    /// Checked addition, returning `None` on overflow or underflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.try_add(rhs).ok()
+    }
+
+    /// Checked subtraction, returning `None` on overflow or underflow.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.try_sub(rhs).ok()
+    }
+
+    /// Checked multiplication, returning `None` on overflow or underflow.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.try_mul(rhs).ok()
+    }
+
+    /// Checked division, returning `None` if `rhs` is zero or the quotient
+    /// overflows, which only happens for `i256::min_value() / -1`.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            None
+        } else {
+            self.try_div(rhs).ok()
+        }
+    }
+
+    /// Checked remainder, returning `None` if `rhs` is zero.
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            None
+        } else {
+            self.try_rem(rhs).ok()
+        }
+    }
+
+    /// Checked negation, returning `None` for `i256::min_value()`.
+    pub fn checked_neg(self) -> Option<Self> {
+        self.try_neg().ok()
+    }
+
+    /// Checked exponentiation, returning `None` on overflow or underflow.
+    pub fn checked_pow(self, exp: u32) -> Option<Self> {
+        self.try_pow(exp).ok()
+    }
+
+    /// Saturating addition, clamping the result at the bounds of `i256`.
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.try_add(rhs).unwrap_or_else(Bound::saturated)
+    }
+
+    /// Saturating subtraction, clamping the result at the bounds of `i256`.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.try_sub(rhs).unwrap_or_else(Bound::saturated)
+    }
+
+    /// Saturating multiplication, clamping the result at the bounds of
+    /// `i256`.
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        self.try_mul(rhs).unwrap_or_else(Bound::saturated)
+    }
+
+    /// Saturating negation, mapping `i256::min_value()` to
+    /// `i256::max_value()`.
+    pub fn saturating_neg(self) -> Self {
+        self.try_neg().unwrap_or_else(Bound::saturated)
+    }
+
+    /// Saturating exponentiation, clamping the result at the bounds of
+    /// `i256`.
+    pub fn saturating_pow(self, exp: u32) -> Self {
+        self.try_pow(exp).unwrap_or_else(Bound::saturated)
+    }
+
+    /// Wrapping (modular) addition.
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        self.overflowing_add(rhs).0
+    }
+
+    /// Wrapping (modular) subtraction.
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        self.overflowing_sub(rhs).0
+    }
+
+    /// Wrapping (modular) multiplication.
+    pub fn wrapping_mul(self, rhs: Self) -> Self {
+        self.overflowing_mul(rhs).0
+    }
+
+    /// Wrapping division, where `i256::min_value() / -1` wraps to
+    /// `i256::min_value()`.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    pub fn wrapping_div(self, rhs: Self) -> Self {
+        self.overflowing_div(rhs).0
+    }
+
+    /// Wrapping remainder, which never wraps and only exists for parity
+    /// with the other wrapping operations.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    pub fn wrapping_rem(self, rhs: Self) -> Self {
+        self.overflowing_rem(rhs).0
+    }
+
+    /// Wrapping negation, where `i256::min_value()` is mapped to itself.
+    pub fn wrapping_neg(self) -> Self {
+        self.overflowing_neg().0
+    }
+
+    /// Wrapping (modular) exponentiation.
+    pub fn wrapping_pow(self, exp: u32) -> Self {
+        self.overflowing_pow(exp).0
+    }
+
+    /// Calculates `self + rhs`, returning the wrapped result along with
+    /// whether an overflow or underflow happened.
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let res = Self(self.0.wrapping_add(rhs.0));
+        let overflow = self.is_negative() == rhs.is_negative()
+            && res.is_negative() != self.is_negative();
+        (res, overflow)
+    }
+
+    /// Calculates `self - rhs`, returning the wrapped result along with
+    /// whether an overflow or underflow happened.
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let res = Self(self.0.wrapping_sub(rhs.0));
+        let overflow = self.is_negative() != rhs.is_negative()
+            && res.is_negative() != self.is_negative();
+        (res, overflow)
+    }
+
+    /// Calculates `self * rhs`, returning the wrapped result along with
+    /// whether an overflow or underflow happened.
+    pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        // The low 256 bits of a product do not depend on the signedness of
+        // the operands.
+        let res = Self(self.0.wrapping_mul(rhs.0));
+        (res, self.try_mul(rhs).is_err())
+    }
+
+    /// Calculates `self / rhs`, returning the wrapped result along with
+    /// whether an overflow happened.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    pub fn overflowing_div(self, rhs: Self) -> (Self, bool) {
+        match self.try_div(rhs) {
+            Ok(res) => (res, false),
+            Err(_) => (Self::MIN, true),
+        }
+    }
+
+    /// Calculates `self % rhs`, which never overflows.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    pub fn overflowing_rem(self, rhs: Self) -> (Self, bool) {
+        let rem = Self(self.magnitude().div_rem(rhs.magnitude()).1);
+        if self.is_negative() {
+            (rem.wrapping_neg(), false)
+        } else {
+            (rem, false)
+        }
+    }
+
+    /// Calculates `-self`, returning the wrapped result along with whether
+    /// an overflow happened.
+    pub fn overflowing_neg(self) -> (Self, bool) {
+        let u256(limbs) = self.0;
+        let inverted = u256([!limbs[0], !limbs[1], !limbs[2], !limbs[3]]);
+        (
+            Self(inverted.wrapping_add(u256::from(1u8))),
+            self == Self::MIN,
+        )
+    }
+
+    /// Calculates `self` to the power of `exp`, returning the wrapped result
+    /// along with whether an overflow or underflow happened.
+    pub fn overflowing_pow(self, exp: u32) -> (Self, bool) {
+        let res = Self(self.0.wrapping_pow(exp));
+        (res, self.try_pow(exp).is_err())
+    }
+
+    /// Euclidean division, which rounds the quotient so that the remainder
+    /// is never negative, i.e. `self.div_euclid(rhs) * rhs +
+    /// self.rem_euclid(rhs) == self`.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero or the quotient overflows, which only happens for
+    /// `i256::min_value().div_euclid(-1)`.
+    pub fn div_euclid(self, rhs: Self) -> Self {
+        match self.try_div_euclid(rhs) {
+            Ok(res) => res,
+            Err(kind) => panic!("attempt to div_euclid with {}", kind),
+        }
+    }
+
+    /// Checked Euclidean division, returning `None` if `rhs` is zero or the
+    /// quotient overflows.
+    pub fn checked_div_euclid(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            None
+        } else {
+            self.try_div_euclid(rhs).ok()
+        }
+    }
+
+    /// The least non-negative remainder of `self` divided by `rhs`, which
+    /// never overflows.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    pub fn rem_euclid(self, rhs: Self) -> Self {
+        let rem = self.overflowing_rem(rhs).0;
+        if rem.is_negative() {
+            // `|rem| < |rhs|`, so the sum is always representable, even if
+            // the magnitude of `rhs` alone is not.
+            rem.wrapping_add(Self(rhs.magnitude()))
+        } else {
+            rem
+        }
+    }
+
+    /// Checked Euclidean remainder, returning `None` if `rhs` is zero.
+    pub fn checked_rem_euclid(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            None
+        } else {
+            Some(self.rem_euclid(rhs))
+        }
+    }
+
+    /// Division with the semantics of the `SDIV` instruction of the EVM,
+    /// i.e. of `/` on `int256` in inline assembly or `unchecked` blocks of
+    /// Solidity. The quotient is truncated toward zero like `/`, but the
+    /// division by zero yields zero and `i256::min_value() / -1` wraps to
+    /// `i256::min_value()`, hence it never panics.
+    pub fn evm_div(self, rhs: Self) -> Self {
+        if rhs.is_zero() {
+            Self::zero()
+        } else {
+            self.wrapping_div(rhs)
+        }
+    }
+
+    /// Remainder with the semantics of the `SMOD` instruction of the EVM.
+    /// The sign of the remainder follows `self` like `%`, but the remainder
+    /// of the division by zero is zero, hence it never panics.
+    pub fn evm_mod(self, rhs: Self) -> Self {
+        if rhs.is_zero() {
+            Self::zero()
+        } else {
+            self.wrapping_rem(rhs)
+        }
+    }
+
+    /// Returns the absolute value as an unsigned integer, which is always
+    /// representable, even for `i256::min_value()`.
+    fn magnitude(&self) -> u256 {
+        if self.is_negative() {
+            self.wrapping_neg().0
+        } else {
+            self.0
+        }
+    }
+
+    /// Applies a sign to a magnitude, failing with the bound that has been
+    /// crossed if the result is not representable.
+    fn with_sign(negative: bool, magnitude: u256, overflow: bool) -> Result<Self, Bound> {
+        if negative {
+            if overflow || magnitude > Self::MIN.0 {
+                Err(Bound::Underflow)
+            } else {
+                Ok(Self(magnitude).wrapping_neg())
+            }
+        } else if overflow || magnitude > Self::MAX.0 {
+            Err(Bound::Overflow)
+        } else {
+            Ok(Self(magnitude))
+        }
+    }
+
+    fn try_add(self, rhs: Self) -> Result<Self, Bound> {
+        match self.overflowing_add(rhs) {
+            (res, false) => Ok(res),
+            // Adding values of the same sign can only cross the bound on
+            // the side of that sign.
+            (_, true) => Err(Bound::toward(self.is_negative())),
+        }
+    }
+
+    fn try_sub(self, rhs: Self) -> Result<Self, Bound> {
+        match self.overflowing_sub(rhs) {
+            (res, false) => Ok(res),
+            (_, true) => Err(Bound::toward(self.is_negative())),
+        }
+    }
+
+    fn try_mul(self, rhs: Self) -> Result<Self, Bound> {
+        let (prod, overflow) = self.magnitude().overflowing_mul(rhs.magnitude());
+        let negative = self.is_negative() != rhs.is_negative() && !prod.is_zero();
+        Self::with_sign(negative, prod, overflow)
+    }
+
+    fn try_div(self, rhs: Self) -> Result<Self, Bound> {
+        let quotient = self.magnitude().div_rem(rhs.magnitude()).0;
+        let negative = self.is_negative() != rhs.is_negative();
+        Self::with_sign(negative, quotient, false)
+    }
+
+    fn try_div_euclid(self, rhs: Self) -> Result<Self, Bound> {
+        let quotient = self.try_div(rhs)?;
+        if !self.overflowing_rem(rhs).0.is_negative() {
+            return Ok(quotient);
+        }
+        // The truncated quotient is off by one, and moving it away from zero
+        // can't overflow, as a remainder exists.
+        if rhs.is_negative() {
+            Ok(quotient.wrapping_add(Self::one()))
+        } else {
+            Ok(quotient.wrapping_sub(Self::one()))
+        }
+    }
+
+    fn try_rem(self, rhs: Self) -> Result<Self, Bound> {
+        Ok(self.overflowing_rem(rhs).0)
+    }
+
+    fn try_neg(self) -> Result<Self, Bound> {
+        match self.overflowing_neg() {
+            (res, false) => Ok(res),
+            (_, true) => Err(Bound::Overflow),
+        }
+    }
+
+    fn try_pow(self, exp: u32) -> Result<Self, Bound> {
+        let (magnitude, overflow) = self.magnitude().overflowing_pow(exp);
+        let negative = self.is_negative() && exp % 2 == 1;
+        Self::with_sign(negative, magnitude, overflow)
+    }
+}
+
+/// The bound of `i256` which the result of an operation has crossed.
+#[derive(Clone, Copy, Debug)]
+enum Bound {
+    Overflow,
+    Underflow,
+}
+
+impl Bound {
+    fn toward(negative: bool) -> Self {
+        if negative {
+            Bound::Underflow
+        } else {
+            Bound::Overflow
+        }
+    }
+
+    fn saturated(self) -> i256 {
+        match self {
+            Bound::Overflow => i256::MAX,
+            Bound::Underflow => i256::MIN,
+        }
+    }
+}
+
+impl fmt::Display for Bound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Bound::Overflow => write!(f, "overflow"),
+            Bound::Underflow => write!(f, "underflow"),
+        }
+    }
+}
+
+impl Ord for i256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            // Two's complement preserves the order among values of the
+            // same sign.
+            _ => self.0.cmp(&other.0),
+        }
+    }
+}
+
+impl PartialOrd for i256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
 impl Bounded for i256 {
     fn min_value() -> Self {
-        lazy_static! {
-            static ref MIN_VALUE: BigInt = -pow(BigInt::from(2), 255);
-        }
         // -2**255
-        i256(MIN_VALUE.clone())
+        Self::MIN
     }
     fn max_value() -> Self {
-        lazy_static! {
-            static ref MAX_VALUE: BigInt = pow(BigInt::from(2), 255) - BigInt::from(1);
-        }
-        i256(MAX_VALUE.clone())
+        Self::MAX
+    }
+}
+
+impl Zero for i256 {
+    fn zero() -> Self {
+        Self(u256::ZERO)
+    }
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl One for i256 {
+    fn one() -> Self {
+        Self::from(1)
     }
 }
 
-impl Deref for i256 {
-    type Target = BigInt;
+impl Num for i256 {
+    type FromStrRadixErr = ParseIntError;
 
-    fn deref(&self) -> &BigInt {
-        &self.0
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        let magnitude = u256::from_str_radix(digits, radix)?;
+        Self::with_sign(negative, magnitude, false).map_err(|_| ParseIntError::Overflow)
     }
 }
 
@@ -91,7 +616,20 @@ macro_rules! impl_from_int {
         impl From<$T> for i256 {
             #[inline]
             fn from(n: $T) -> Self {
-                i256(BigInt::from(n))
+                let n = n as i128;
+                let fill = if n < 0 { u64::MAX } else { 0 };
+                i256(u256([n as u64, (n >> 64) as u64, fill, fill]))
+            }
+        }
+    };
+}
+
+macro_rules! impl_from_uint {
+    ($T:ty) => {
+        impl From<$T> for i256 {
+            #[inline]
+            fn from(n: $T) -> Self {
+                i256(u256::from(n))
             }
         }
     };
@@ -103,32 +641,34 @@ impl_from_int!(i32);
 impl_from_int!(i64);
 impl_from_int!(i128);
 impl_from_int!(isize);
-impl_from_int!(u8);
-impl_from_int!(u16);
-impl_from_int!(u32);
-impl_from_int!(u64);
-impl_from_int!(u128);
-impl_from_int!(usize);
+impl_from_uint!(u8);
+impl_from_uint!(u16);
+impl_from_uint!(u32);
+impl_from_uint!(u64);
+impl_from_uint!(u128);
+impl_from_uint!(usize);
 
 impl<'a> From<&'a i256> for i256 {
     fn from(n: &i256) -> Self {
-        n.clone()
+        *n
     }
 }
 
 impl FromStr for i256 {
-    type Err = ParseBigIntError;
+    type Err = ParseIntError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.to_lowercase();
-        if s.starts_with("0x") || s.starts_with("-0x") {
-            if let Some(sub_str) = s.strip_prefix("0x") {
-                Ok(BigInt::from_str_radix(sub_str, 16).map(Self)?)
-            } else {
-                Ok(-BigInt::from_str_radix(&s[3..], 16).map(Self)?)
-            }
-        } else {
-            Ok(BigInt::from_str_radix(&s, 10).map(Self)?)
-        }
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        let magnitude = match digits
+            .strip_prefix("0x")
+            .or_else(|| digits.strip_prefix("0X"))
+        {
+            Some(hex) => u256::from_str_radix(hex, 16)?,
+            None => u256::from_str_radix(digits, 10)?,
+        };
+        Self::with_sign(negative, magnitude, false).map_err(|_| ParseIntError::Overflow)
     }
 }
 
@@ -142,134 +682,134 @@ impl fmt::Display for i256 {
 #[cfg(feature = "std")]
 impl fmt::Debug for i256 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "i256({})", &self.0.to_str_radix(10))
+        write!(f, "i256({})", &self.to_str_radix(10))
     }
 }
 
 impl Signed for i256 {
     fn abs(&self) -> Self {
-        i256(self.0.abs())
+        match self.try_neg() {
+            Ok(neg) if self.is_negative() => neg,
+            Ok(_) => *self,
+            Err(kind) => panic!("attempt to abs with {}", kind),
+        }
     }
     fn abs_sub(&self, other: &Self) -> Self {
-        i256(self.0.abs_sub(&other.0))
+        if *self <= *other {
+            Self::zero()
+        } else {
+            *self - *other
+        }
     }
     fn signum(&self) -> Self {
-        i256(self.0.signum())
+        if self.is_negative() {
+            Self::from(-1)
+        } else if self.is_zero() {
+            Self::zero()
+        } else {
+            Self::one()
+        }
     }
     fn is_positive(&self) -> bool {
-        self.0.is_positive()
+        !self.is_negative() && !self.is_zero()
     }
     fn is_negative(&self) -> bool {
-        self.0.is_negative()
+        (self.0).0[3] >> 63 == 1
     }
 }
 
-/// A macro that forwards an unary operator trait i.e. Add
-macro_rules! forward_op {
-    (impl $trait_:ident for $type_:ident { fn $method:ident }) => {
+/// A macro that implements a binary operator trait i.e. Add
+macro_rules! impl_op {
+    (impl $trait_:ident for $type_:ident { fn $method:ident => $try_:ident }) => {
         impl $trait_<$type_> for $type_ {
             type Output = $type_;
 
-            fn $method(self, $type_(b): $type_) -> $type_ {
-                let $type_(a) = self;
-                let res = $type_(a.$method(&b));
-                if res > i256::max_value() {
-                    panic!("attempt to {} with overflow", stringify!($method));
-                } else if res < i256::min_value() {
-                    panic!("attempt to {} with underflow", stringify!($method));
-                } else {
-                    res
+            fn $method(self, b: $type_) -> $type_ {
+                match self.$try_(b) {
+                    Ok(res) => res,
+                    Err(kind) => {
+                        panic!("attempt to {} with {}", stringify!($method), kind)
+                    }
                 }
             }
         }
     };
 }
 
-/// A macro that forwards a checked operator i.e. CheckedAdd
+/// A macro that forwards a checked operator i.e. CheckedAdd to the inherent
+/// method of the same name
 macro_rules! forward_checked_op {
     (impl $trait_:ident for $type_:ident { fn $method:ident }) => {
         impl $trait_ for $type_ {
-            fn $method(&self, $type_(b): &$type_) -> Option<$type_> {
-                let $type_(a) = self;
-                a.$method(&b)
-                    .filter(|value| {
-                        value >= &i256::min_value() && value <= &i256::max_value()
-                    })
-                    .map($type_)
-            }
-        }
-    };
-}
-
-/// A macro that forwards a assignment operator i.e. AddAssign
-macro_rules! forward_assign_op {
-    (impl $trait_:ident for $type_:ident { fn $method:ident }) => {
-        impl $trait_ for $type_ {
-            fn $method(&mut self, $type_(b): $type_) {
-                // Borrow happens only inside this scope
-                {
-                    let a = &mut self.0;
-                    a.$method(b);
-                }
-                // Check bounds
-                if *self > i256::max_value() {
-                    panic!("attempt to {} with overflow", stringify!($method));
-                }
-                if *self < i256::min_value() {
-                    panic!("attempt to {} with underflow", stringify!($method));
-                }
+            fn $method(&self, b: &$type_) -> Option<$type_> {
+                $type_::$method(*self, *b)
             }
         }
     };
 }
 
-macro_rules! forward_unary_op {
-    (impl $trait_:ident for $type_:ident { fn $method:ident }) => {
+/// A macro that implements a assignment operator i.e. AddAssign
+macro_rules! impl_assign_op {
+    (impl $trait_:ident for $type_:ident { fn $method:ident => $try_:ident }) => {
         impl $trait_ for $type_ {
-            type Output = $type_;
-
-            fn $method(self) -> $type_ {
-                let $type_(a) = self;
-                let res = $type_(a.$method());
-                // Check bounds
-                if res > i256::max_value() {
-                    panic!("attempt to {} with overflow", stringify!($method));
-                }
-                if res < i256::min_value() {
-                    panic!("attempt to {} with underflow", stringify!($method));
+            fn $method(&mut self, b: $type_) {
+                match self.$try_(b) {
+                    Ok(res) => *self = res,
+                    Err(kind) => {
+                        panic!("attempt to {} with {}", stringify!($method), kind)
+                    }
                 }
-
-                res
             }
         }
     };
 }
 
-forward_op! { impl Add for i256 { fn add } }
+impl_op! { impl Add for i256 { fn add => try_add } }
 forward_checked_op! { impl CheckedAdd for i256 { fn checked_add } }
-forward_assign_op! { impl AddAssign for i256 { fn add_assign } }
+impl_assign_op! { impl AddAssign for i256 { fn add_assign => try_add } }
 
-forward_op! { impl Sub for i256 { fn sub } }
+impl_op! { impl Sub for i256 { fn sub => try_sub } }
 forward_checked_op! { impl CheckedSub for i256 { fn checked_sub } }
-forward_assign_op! { impl SubAssign for i256 { fn sub_assign } }
+impl_assign_op! { impl SubAssign for i256 { fn sub_assign => try_sub } }
 
-forward_op! { impl Mul for i256 { fn mul } }
+impl_op! { impl Mul for i256 { fn mul => try_mul } }
 forward_checked_op! { impl CheckedMul for i256 { fn checked_mul } }
-forward_assign_op! { impl MulAssign for i256 { fn mul_assign } }
+impl_assign_op! { impl MulAssign for i256 { fn mul_assign => try_mul } }
 
-forward_op! { impl Div for i256 { fn div } }
+impl_op! { impl Div for i256 { fn div => try_div } }
 forward_checked_op! { impl CheckedDiv for i256 { fn checked_div } }
-forward_assign_op! { impl DivAssign for i256 { fn div_assign } }
+impl_assign_op! { impl DivAssign for i256 { fn div_assign => try_div } }
+
+impl_op! { impl Rem for i256 { fn rem => try_rem } }
+impl_assign_op! { impl RemAssign for i256 { fn rem_assign => try_rem } }
 
-forward_op! { impl Rem for i256 { fn rem } }
-forward_assign_op! { impl RemAssign for i256 { fn rem_assign } }
+impl Neg for i256 {
+    type Output = i256;
 
-forward_unary_op! { impl Neg for i256 { fn neg } }
+    fn neg(self) -> i256 {
+        match self.try_neg() {
+            Ok(res) => res,
+            Err(kind) => panic!("attempt to neg with {}", kind),
+        }
+    }
+}
 
+/// The SCALE representation is a length byte (the number of bytes following
+/// it plus one) followed by the shortest big-endian two's complement bytes
+/// of the value, hence zero is encoded as a single `0x01`.
 impl scale::Encode for i256 {
     fn size_hint(&self) -> usize {
-        let bits = self.0.bits() as usize;
-        ((bits + 7) >> 3) + 1
+        let bytes = self.to_be_bytes();
+        let start = if self.is_negative() {
+            (0..31)
+                .find(|&i| bytes[i] != 0xff || bytes[i + 1] & 0x80 == 0)
+                .unwrap_or(31)
+        } else {
+            (0..32)
+                .find(|&i| bytes[i] != 0x00 || (i < 31 && bytes[i + 1] & 0x80 != 0))
+                .unwrap_or(32)
+        };
+        33 - start
     }
 
     fn encode(&self) -> Vec<u8> {
@@ -278,18 +818,21 @@ impl scale::Encode for i256 {
 
         let mut buf = Vec::with_capacity(size);
         buf.push(size as u8);
-        buf.extend(self.0.to_signed_bytes_be());
+        buf.extend_from_slice(&self.to_be_bytes()[33 - size..]);
         buf
     }
 }
 
 impl scale::Decode for i256 {
     fn decode<I: scale::Input>(value: &mut I) -> Result<Self, scale::Error> {
-        let size = value.read_byte()?;
-        let mut buf = from_elem(0, (size - 1) as usize);
-        value.read(buf.as_mut_slice())?;
+        let size = value.read_byte()? as usize;
+        if size == 0 || size > 33 {
+            return Err("Invalid length of i256".into());
+        }
 
-        Ok(Self(BigInt::from_signed_bytes_be(&buf)))
+        let mut buf = [0u8; 32];
+        value.read(&mut buf[..size - 1])?;
+        Ok(Self::from_signed_be_bytes(&buf[..size - 1]))
     }
 }
 
@@ -305,10 +848,191 @@ mod tests {
         assert!(origin == decoded);
     }
 
+    #[test]
+    fn small_divisor_ops() {
+        let val = i256::from(-1_000_000_007i64);
+        assert_eq!(val.mul_u64(250).div_u64(10_000), i256::from(-25_000_000i64));
+        assert_eq!(val.div_u64(10_000), i256::from(-100_000i64));
+        assert_eq!(val.rem_u64(10_000), -7);
+        assert_eq!((-val).rem_u64(10_000), 7);
+        assert_eq!(i256::from(-7).rem_u64(3), -1);
+        assert_eq!(i256::from(-1).rem_u64(u64::MAX), -1);
+        assert_eq!(
+            (-i256::from(u64::MAX - 1)).rem_u64(u64::MAX),
+            -((u64::MAX - 1) as i128)
+        );
+        assert_eq!(i256::min_value().rem_u64(2), 0);
+        assert_eq!(i256::min_value().div_u64(1), i256::min_value());
+    }
+
+    #[test]
+    #[should_panic]
+    fn mul_u64_underflow() {
+        let _ = i256::min_value().mul_u64(2);
+    }
+
     #[test]
     fn from_str() {
         let i1: i256 = "42258578699988885".parse().unwrap();
         let i2: i256 = "-0x9621F414202F95".parse().unwrap();
         assert_eq!(-i1, i2);
     }
+
+    #[test]
+    fn codec_lengths() {
+        for (value, len) in &[
+            (i256::zero(), 1),
+            (i256::from(127), 2),
+            (i256::from(128), 3),
+            (i256::from(-128), 2),
+            (i256::from(-129), 3),
+            (i256::min_value(), 33),
+            (i256::max_value(), 33),
+        ] {
+            let encoded = scale::Encode::encode(value);
+            assert_eq!(encoded.len(), *len);
+            let decoded: i256 = scale::Decode::decode(&mut encoded.as_slice()).unwrap();
+            assert_eq!(*value, decoded);
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a: i256 = "-0xfedcba9876543210fedcba9876543210fedcba98"
+            .parse()
+            .unwrap();
+        let b: i256 = "0x123456789abcdef01".parse().unwrap();
+        assert_eq!(a / b * b + a % b, a);
+        assert!((a % b).is_negative());
+        assert_eq!(a * b / b, a);
+        assert_eq!(-a * -b, a * b);
+        assert!(i256::from(-2) < i256::from(-1));
+        assert!(i256::min_value() < i256::from(i128::MIN));
+        assert_eq!(
+            CheckedAdd::checked_add(&i256::max_value(), &i256::one()),
+            None
+        );
+        assert_eq!(
+            CheckedSub::checked_sub(&i256::min_value(), &i256::one()),
+            None
+        );
+        assert_eq!(
+            CheckedDiv::checked_div(&i256::min_value(), &i256::from(-1)),
+            None
+        );
+        assert_eq!(i256::from(-5).to_uint256(), None);
+        assert_eq!(i256::from(-1).to_string(), "-1");
+        assert_eq!(i256::from_signed_be_bytes(&[0xff, 0x7f]), i256::from(-129));
+        assert_eq!(
+            "-57896044618658097711785492504343953926634992332820282019728792003956564819968"
+                .parse::<i256>()
+                .unwrap(),
+            i256::min_value()
+        );
+        assert_eq!(
+            "57896044618658097711785492504343953926634992332820282019728792003956564819968"
+                .parse::<i256>(),
+            Err(ParseIntError::Overflow)
+        );
+    }
+
+    #[test]
+    fn euclid_and_evm_division() {
+        let cases: &[(i64, i64)] =
+            &[(7, 3), (-7, 3), (7, -3), (-7, -3), (6, -3), (-6, 3)];
+        for (a, b) in cases {
+            let (x, y) = (i256::from(*a), i256::from(*b));
+            assert_eq!(x.div_euclid(y), i256::from(a.div_euclid(*b)));
+            assert_eq!(x.rem_euclid(y), i256::from(a.rem_euclid(*b)));
+            assert_eq!(x.evm_div(y), i256::from(a / b));
+            assert_eq!(x.evm_mod(y), i256::from(a % b));
+        }
+
+        let min = i256::min_value();
+        let minus_one = i256::from(-1);
+        assert_eq!(min.checked_div_euclid(minus_one), None);
+        assert_eq!(min.checked_div_euclid(i256::zero()), None);
+        assert_eq!(min.checked_rem_euclid(i256::zero()), None);
+        assert_eq!(min.rem_euclid(minus_one), i256::zero());
+        assert_eq!(minus_one.rem_euclid(min), i256::max_value());
+        assert_eq!(min.div_euclid(i256::from(2)), min / i256::from(2));
+        assert_eq!(min.evm_div(minus_one), min);
+        assert_eq!(min.evm_mod(minus_one), i256::zero());
+        assert_eq!(i256::from(5).evm_div(i256::zero()), i256::zero());
+        assert_eq!(i256::from(-5).evm_mod(i256::zero()), i256::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to div_euclid with overflow")]
+    fn div_euclid_overflow() {
+        let _ = i256::min_value().div_euclid(i256::from(-1));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn add_overflow() {
+        let _ = i256::max_value() + i256::one();
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to neg with overflow")]
+    fn neg_overflow() {
+        let _ = -i256::min_value();
+    }
+
+    #[test]
+    fn checked_saturating_wrapping() {
+        let (min, max) = (i256::min_value(), i256::max_value());
+        let one = i256::one();
+        let minus_one = i256::from(-1);
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(min.checked_sub(one), None);
+        assert_eq!(min.checked_mul(minus_one), None);
+        assert_eq!(min.checked_div(minus_one), None);
+        assert_eq!(min.checked_neg(), None);
+        assert_eq!(one.checked_rem(i256::zero()), None);
+        assert_eq!(i256::from(-7).checked_rem(i256::from(2)), Some(minus_one));
+        assert_eq!(max.saturating_add(one), max);
+        assert_eq!(min.saturating_sub(one), min);
+        assert_eq!(max.saturating_mul(i256::from(-2)), min);
+        assert_eq!(min.saturating_neg(), max);
+        assert_eq!(max.wrapping_add(one), min);
+        assert_eq!(min.wrapping_sub(one), max);
+        assert_eq!(min.wrapping_mul(minus_one), min);
+        assert_eq!(min.wrapping_div(minus_one), min);
+        assert_eq!(min.wrapping_rem(minus_one), i256::zero());
+        assert_eq!(min.wrapping_neg(), min);
+        assert_eq!(i256::from(-3).wrapping_mul(i256::from(5)), i256::from(-15));
+        assert_eq!(max.overflowing_add(one), (min, true));
+        assert_eq!(minus_one.overflowing_sub(one), (i256::from(-2), false));
+    }
+
+    #[test]
+    fn pow_sqrt_log2() {
+        let minus_two = i256::from(-2);
+        assert_eq!(minus_two.pow(255), i256::min_value());
+        assert_eq!(minus_two.pow(3), i256::from(-8));
+        assert_eq!(minus_two.checked_pow(256), None);
+        assert_eq!(i256::from(2).checked_pow(255), None);
+        assert_eq!(minus_two.saturating_pow(256), i256::max_value());
+        assert_eq!(minus_two.saturating_pow(257), i256::min_value());
+        assert_eq!(minus_two.wrapping_pow(256), i256::zero());
+        assert_eq!(minus_two.wrapping_pow(3), i256::from(-8));
+
+        assert_eq!(i256::from(99).sqrt(), i256::from(9));
+        assert_eq!(i256::max_value().log2(), 254);
+        assert_eq!(i256::one().log2(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to pow with underflow")]
+    fn pow_underflow() {
+        let _ = i256::from(-2).pow(257);
+    }
+
+    #[test]
+    #[should_panic(expected = "argument of integer square root cannot be negative")]
+    fn sqrt_of_negative() {
+        let _ = i256::from(-1).sqrt();
+    }
 }
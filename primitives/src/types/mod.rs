@@ -13,6 +13,8 @@
 pub mod address;
 mod bytes;
 mod fixed_size_bytes;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
 pub mod hash;
 mod int256;
 mod uint256;
@@ -22,6 +24,6 @@ pub use bytes::Bytes;
 pub use fixed_size_bytes::*;
 pub use hash::Hash;
 pub use int256::i256;
-pub use uint256::u256;
+pub use uint256::{u256, ParseIntError, TryFromIntError};
 #[allow(non_camel_case_types)]
 pub type timestamp = u64;
@@ -13,69 +13,581 @@
 pub use crate::types::int256::i256;
 #[allow(unused_imports)]
 use core::{
+    cmp::Ordering,
+    convert::TryFrom,
     default::Default,
     fmt,
-    ops::{Add, AddAssign, Deref, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+    ops::{
+        Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign,
+    },
     str::FromStr,
 };
-use liquid_prelude::vec::{from_elem, Vec};
-use num::{
-    bigint::{ParseBigIntError, ToBigInt},
-    pow,
-    traits::ops::checked::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub},
-    BigUint, Bounded, Num, Zero,
+use liquid_prelude::{string::String, vec::Vec};
+use num_traits::{
+    ops::checked::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub},
+    Bounded, One, Zero,
 };
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Zero)]
+const LIMBS: usize = 4;
+
+/// Unsigned 256 bit integer, stored as four little-endian `u64` limbs so
+/// that no arithmetic on it touches the heap.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
 #[allow(non_camel_case_types)]
-pub struct u256(pub BigUint);
+pub struct u256(pub(crate) [u64; LIMBS]);
+
+/// Error returned when parsing a `u256` or an `i256` from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseIntError {
+    /// The string to parse is empty.
+    Empty,
+    /// The string contains a character which is not a digit of the radix.
+    InvalidDigit,
+    /// The number is out of the range of the target type.
+    Overflow,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ParseIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match self {
+            ParseIntError::Empty => "cannot parse integer from empty string",
+            ParseIntError::InvalidDigit => "invalid digit found in string",
+            ParseIntError::Overflow => "number too large to fit in target type",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+/// Error returned when a `u256` is out of the range of the target integer
+/// type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromIntError(());
+
+#[cfg(feature = "std")]
+impl fmt::Display for TryFromIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "out of range integral type conversion attempted")
+    }
+}
 
 impl u256 {
+    pub(crate) const ZERO: Self = Self([0; LIMBS]);
+    pub(crate) const MAX: Self = Self([u64::MAX; LIMBS]);
+
+    /// Creates a value from its little-endian representation. Bytes beyond the
+    /// lowest 32 ones must be zero.
+    ///
+    /// # Panics
+    ///
+    /// If the value does not fit in 256 bits.
     pub fn from_le_bytes(slice: &[u8]) -> Self {
-        Self(BigUint::from_bytes_le(slice))
+        let (low, high) = slice.split_at(slice.len().min(32));
+        if high.iter().any(|byte| *byte != 0) {
+            panic!("attempt to convert bytes to u256 with overflow");
+        }
+
+        let mut buf = [0u8; 32];
+        buf[..low.len()].copy_from_slice(low);
+        let mut limbs = [0u64; LIMBS];
+        for (limb, chunk) in limbs.iter_mut().zip(buf.chunks_exact(8)) {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(chunk);
+            *limb = u64::from_le_bytes(bytes);
+        }
+        Self(limbs)
     }
 
+    /// Creates a value from its big-endian representation. Bytes beyond the
+    /// lowest 32 ones must be zero.
+    ///
+    /// # Panics
+    ///
+    /// If the value does not fit in 256 bits.
     pub fn from_be_bytes(slice: &[u8]) -> Self {
-        Self(BigUint::from_bytes_be(slice))
+        let (high, low) = slice.split_at(slice.len().saturating_sub(32));
+        if high.iter().any(|byte| *byte != 0) {
+            panic!("attempt to convert bytes to u256 with overflow");
+        }
+
+        let mut limbs = [0u64; LIMBS];
+        for (i, byte) in low.iter().rev().enumerate() {
+            limbs[i / 8] |= (*byte as u64) << ((i % 8) * 8);
+        }
+        Self(limbs)
     }
 
     /// Converts value to a signed 256 bit integer
     pub fn to_int256(&self) -> Option<i256> {
-        self.0
-            .to_bigint()
-            .filter(|value| value.bits() <= 255)
-            .map(i256)
+        if self.0[LIMBS - 1] >> 63 == 0 {
+            Some(i256(*self))
+        } else {
+            None
+        }
     }
 
     pub fn to_be_bytes(&self) -> [u8; 32] {
-        let bytes = self.to_bytes_be();
         let mut res = [0u8; 32];
-        res[32 - bytes.len()..].copy_from_slice(&bytes);
+        for (i, limb) in self.0.iter().enumerate() {
+            let end = 32 - i * 8;
+            res[end - 8..end].copy_from_slice(&limb.to_be_bytes());
+        }
+        res
+    }
+
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut res = [0u8; 32];
+        for (i, limb) in self.0.iter().enumerate() {
+            res[i * 8..(i + 1) * 8].copy_from_slice(&limb.to_le_bytes());
+        }
+        res
+    }
+
+    /// Returns the number of bits needed to represent the value.
+    pub fn bits(&self) -> u64 {
+        match self.0.iter().rposition(|limb| *limb != 0) {
+            Some(i) => (i as u64 + 1) * 64 - self.0[i].leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    /// Multiplies by a `u64` directly, without converting `rhs` to `u256`
+    /// first.
+    ///
+    /// # Panics
+    ///
+    /// If the product overflows 256 bits.
+    pub fn mul_u64(&self, rhs: u64) -> Self {
+        let (res, overflow) = self.overflowing_mul_u64(rhs);
+        if overflow {
+            panic!("attempt to mul_u64 with overflow");
+        }
+        res
+    }
+
+    /// Divides by a `u64` using single-limb division, which is considerably
+    /// cheaper than a full 256-bit division.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    pub fn div_u64(&self, rhs: u64) -> Self {
+        self.div_rem_u64(rhs).0
+    }
+
+    /// Computes the remainder of dividing by a `u64`. The result always fits
+    /// in a `u64`.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    pub fn rem_u64(&self, rhs: u64) -> u64 {
+        self.div_rem_u64(rhs).1
+    }
+
+    /// Parses a value in the given radix, without any prefix.
+    ///
+    /// # Panics
+    ///
+    /// If `radix` is not in the range from 2 to 36.
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError> {
+        assert!((2..=36).contains(&radix), "radix must be within 2..=36");
+        if s.is_empty() {
+            return Err(ParseIntError::Empty);
+        }
+
+        s.chars().try_fold(Self::ZERO, |acc, c| {
+            let digit = c.to_digit(radix).ok_or(ParseIntError::InvalidDigit)?;
+            let (acc, mul_overflow) = acc.overflowing_mul_u64(radix as u64);
+            let (acc, add_overflow) = acc.overflowing_add(Self::from(digit));
+            if mul_overflow || add_overflow {
+                Err(ParseIntError::Overflow)
+            } else {
+                Ok(acc)
+            }
+        })
+    }
+
+    /// Formats the value in the given radix, without any prefix.
+    ///
+    /// # Panics
+    ///
+    /// If `radix` is not in the range from 2 to 36.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix must be within 2..=36");
+        if self.is_zero() {
+            return String::from("0");
+        }
+
+        let mut digits = Vec::new();
+        let mut value = *self;
+        while !value.is_zero() {
+            let (quotient, rem) = value.div_rem_u64(radix as u64);
+            digits.push(core::char::from_digit(rem as u32, radix).unwrap());
+            value = quotient;
+        }
+        digits.iter().rev().collect()
+    }
+
+    /// Raises to the power of `exp`.
+    ///
+    /// # Panics
+    ///
+    /// If the result overflows 256 bits.
+    pub fn pow(self, exp: u32) -> Self {
+        let (res, overflow) = self.overflowing_pow(exp);
+        if overflow {
+            panic!("attempt to pow with overflow");
+        }
         res
     }
+
+    /// Returns the integer square root, rounded down.
+    pub fn sqrt(self) -> Self {
+        if self.is_zero() {
+            return self;
+        }
+
+        // Newton's iteration, starting from a power of two which is not less
+        // than the root so that the estimates decrease monotonically.
+        let mut x = Self::from(1u8).shl_bits(((self.bits() + 1) >> 1) as u32);
+        loop {
+            let y = (x + self / x).shr_bits(1);
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    /// Returns the base 2 logarithm, rounded down.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is zero.
+    pub fn log2(self) -> u32 {
+        if self.is_zero() {
+            panic!("argument of integer logarithm must be positive");
+        }
+        self.bits() as u32 - 1
+    }
+
+    /// Checked addition, returning `None` on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_add(rhs) {
+            (res, false) => Some(res),
+            (_, true) => None,
+        }
+    }
+
+    /// Checked subtraction, returning `None` on overflow.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_sub(rhs) {
+            (res, false) => Some(res),
+            (_, true) => None,
+        }
+    }
+
+    /// Checked multiplication, returning `None` on overflow.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_mul(rhs) {
+            (res, false) => Some(res),
+            (_, true) => None,
+        }
+    }
+
+    /// Checked division, returning `None` if `rhs` is zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            None
+        } else {
+            Some(self.div_rem(rhs).0)
+        }
+    }
+
+    /// Checked remainder, returning `None` if `rhs` is zero.
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            None
+        } else {
+            Some(self.div_rem(rhs).1)
+        }
+    }
+
+    /// Checked exponentiation, returning `None` on overflow.
+    pub fn checked_pow(self, exp: u32) -> Option<Self> {
+        match self.overflowing_pow(exp) {
+            (res, false) => Some(res),
+            (_, true) => None,
+        }
+    }
+
+    /// Saturating addition, clamping the result at `u256::max_value()`.
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).unwrap_or(Self::MAX)
+    }
+
+    /// Saturating subtraction, clamping the result at zero.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs).unwrap_or(Self::ZERO)
+    }
+
+    /// Saturating multiplication, clamping the result at
+    /// `u256::max_value()`.
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs).unwrap_or(Self::MAX)
+    }
+
+    /// Saturating exponentiation, clamping the result at
+    /// `u256::max_value()`.
+    pub fn saturating_pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).unwrap_or(Self::MAX)
+    }
+
+    /// Wrapping (modular) addition.
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        self.overflowing_add(rhs).0
+    }
+
+    /// Wrapping (modular) subtraction.
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        self.overflowing_sub(rhs).0
+    }
+
+    /// Wrapping (modular) multiplication.
+    pub fn wrapping_mul(self, rhs: Self) -> Self {
+        self.overflowing_mul(rhs).0
+    }
+
+    /// Wrapping division, which never wraps for unsigned integers and only
+    /// exists for parity with `i256`.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    pub fn wrapping_div(self, rhs: Self) -> Self {
+        self.div_rem(rhs).0
+    }
+
+    /// Wrapping remainder, which never wraps for unsigned integers and only
+    /// exists for parity with `i256`.
+    ///
+    /// # Panics
+    ///
+    /// If `rhs` is zero.
+    pub fn wrapping_rem(self, rhs: Self) -> Self {
+        self.div_rem(rhs).1
+    }
+
+    /// Wrapping (modular) exponentiation.
+    pub fn wrapping_pow(self, exp: u32) -> Self {
+        self.overflowing_pow(exp).0
+    }
+
+    /// Calculates `self + rhs`, returning the wrapped result along with
+    /// whether an overflow happened.
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let mut res = [0u64; LIMBS];
+        let mut carry = false;
+        for (i, limb) in res.iter_mut().enumerate() {
+            let (sum, c1) = self.0[i].overflowing_add(rhs.0[i]);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = c1 || c2;
+        }
+        (Self(res), carry)
+    }
+
+    /// Calculates `self - rhs`, returning the wrapped result along with
+    /// whether an overflow happened.
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let mut res = [0u64; LIMBS];
+        let mut borrow = false;
+        for (i, limb) in res.iter_mut().enumerate() {
+            let (diff, b1) = self.0[i].overflowing_sub(rhs.0[i]);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            *limb = diff;
+            borrow = b1 || b2;
+        }
+        (Self(res), borrow)
+    }
+
+    /// Calculates `self * rhs`, returning the wrapped result along with
+    /// whether an overflow happened.
+    pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        let mut res = [0u64; LIMBS];
+        let mut overflow = false;
+        for i in 0..LIMBS {
+            if self.0[i] == 0 {
+                continue;
+            }
+
+            let mut carry = 0u64;
+            for j in 0..LIMBS {
+                let prod = self.0[i] as u128 * rhs.0[j] as u128 + carry as u128;
+                if i + j < LIMBS {
+                    let sum = prod + res[i + j] as u128;
+                    res[i + j] = sum as u64;
+                    carry = (sum >> 64) as u64;
+                } else {
+                    overflow |= prod != 0;
+                    carry = 0;
+                }
+            }
+            overflow |= carry != 0;
+        }
+        (Self(res), overflow)
+    }
+
+    /// Calculates `self` to the power of `exp` by squaring, returning the
+    /// wrapped result along with whether an overflow happened.
+    pub fn overflowing_pow(self, mut exp: u32) -> (Self, bool) {
+        let mut base = self;
+        let mut res = Self::from(1u8);
+        let mut overflow = false;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                let (prod, o) = res.overflowing_mul(base);
+                res = prod;
+                overflow |= o;
+            }
+            exp >>= 1;
+            // The squared base is only needed if higher bits remain.
+            if exp > 0 {
+                let (square, o) = base.overflowing_mul(base);
+                base = square;
+                overflow |= o;
+            }
+        }
+        (res, overflow)
+    }
+
+    pub(crate) fn overflowing_mul_u64(self, rhs: u64) -> (Self, bool) {
+        let mut res = [0u64; LIMBS];
+        let mut carry = 0u64;
+        for (i, limb) in res.iter_mut().enumerate() {
+            let prod = self.0[i] as u128 * rhs as u128 + carry as u128;
+            *limb = prod as u64;
+            carry = (prod >> 64) as u64;
+        }
+        (Self(res), carry != 0)
+    }
+
+    pub(crate) fn div_rem_u64(self, rhs: u64) -> (Self, u64) {
+        if rhs == 0 {
+            panic!("attempt to divide by zero");
+        }
+
+        let mut res = [0u64; LIMBS];
+        let mut rem = 0u64;
+        for i in (0..LIMBS).rev() {
+            let cur = (rem as u128) << 64 | self.0[i] as u128;
+            res[i] = (cur / rhs as u128) as u64;
+            rem = (cur % rhs as u128) as u64;
+        }
+        (Self(res), rem)
+    }
+
+    pub(crate) fn div_rem(self, rhs: Self) -> (Self, Self) {
+        if rhs.0[1..].iter().all(|limb| *limb == 0) {
+            let (quotient, rem) = self.div_rem_u64(rhs.0[0]);
+            return (quotient, Self::from(rem));
+        }
+        if self < rhs {
+            return (Self::ZERO, self);
+        }
+
+        // Shift-and-subtract long division, starting from the highest bit
+        // at which the divisor still fits in the dividend.
+        let shift = (self.bits() - rhs.bits()) as u32;
+        let mut divisor = rhs.shl_bits(shift);
+        let mut quotient = Self::ZERO;
+        let mut rem = self;
+        for i in (0..=shift).rev() {
+            if rem >= divisor {
+                rem = rem.overflowing_sub(divisor).0;
+                quotient.0[(i / 64) as usize] |= 1 << (i % 64);
+            }
+            divisor = divisor.shr_bits(1);
+        }
+        (quotient, rem)
+    }
+
+    fn shl_bits(self, shift: u32) -> Self {
+        let (limb_shift, bit_shift) = ((shift / 64) as usize, shift % 64);
+        let mut res = [0u64; LIMBS];
+        for (i, limb) in res.iter_mut().enumerate().skip(limb_shift) {
+            *limb = self.0[i - limb_shift] << bit_shift;
+            if bit_shift > 0 && i > limb_shift {
+                *limb |= self.0[i - limb_shift - 1] >> (64 - bit_shift);
+            }
+        }
+        Self(res)
+    }
+
+    fn shr_bits(self, shift: u32) -> Self {
+        let (limb_shift, bit_shift) = ((shift / 64) as usize, shift % 64);
+        let mut res = [0u64; LIMBS];
+        for (i, limb) in res.iter_mut().enumerate().take(LIMBS - limb_shift) {
+            *limb = self.0[i + limb_shift] >> bit_shift;
+            if bit_shift > 0 && i + limb_shift + 1 < LIMBS {
+                *limb |= self.0[i + limb_shift + 1] << (64 - bit_shift);
+            }
+        }
+        Self(res)
+    }
+
+    fn overflowing_div(self, rhs: Self) -> (Self, bool) {
+        (self.div_rem(rhs).0, false)
+    }
+
+    fn overflowing_rem(self, rhs: Self) -> (Self, bool) {
+        (self.div_rem(rhs).1, false)
+    }
+}
+
+impl Ord for u256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for u256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl Bounded for u256 {
     fn min_value() -> Self {
-        // -2**255
-        u256::zero()
+        Self::ZERO
     }
     fn max_value() -> Self {
-        lazy_static! {
-            static ref MAX_VALUE: BigUint =
-                pow(BigUint::from(2u32), 256) - BigUint::from(1u32);
-        }
-        Self(MAX_VALUE.clone())
+        Self::MAX
+    }
+}
+
+impl Zero for u256 {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+    fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+}
+
+impl One for u256 {
+    fn one() -> Self {
+        Self::from(1u8)
     }
 }
 
 impl FromStr for u256 {
-    type Err = ParseBigIntError;
+    type Err = ParseIntError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.to_lowercase();
-        match s.strip_prefix("0x") {
-            Some(sub_str) => Ok(BigUint::from_str_radix(sub_str, 16).map(Self)?),
-            None => Ok(BigUint::from_str_radix(&s, 10).map(Self)?),
+        match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(sub_str) => Self::from_str_radix(sub_str, 16),
+            None => Self::from_str_radix(s, 10),
         }
     }
 }
@@ -83,34 +595,33 @@ impl FromStr for u256 {
 #[cfg(feature = "std")]
 impl fmt::Display for u256 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", &self.0.to_str_radix(10))
+        write!(f, "{}", &self.to_str_radix(10))
     }
 }
 
 #[cfg(feature = "std")]
 impl fmt::Debug for u256 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "u256({})", &self.0.to_str_radix(10))
-    }
-}
-
-impl Deref for u256 {
-    type Target = BigUint;
-
-    fn deref(&self) -> &BigUint {
-        &self.0
+        write!(f, "u256({})", &self.to_str_radix(10))
     }
 }
 
 impl From<[u8; 32]> for u256 {
     fn from(n: [u8; 32]) -> Self {
-        Self(BigUint::from_bytes_be(&n))
+        Self::from_be_bytes(&n)
     }
 }
 
 impl<'a> From<&'a [u8]> for u256 {
     fn from(n: &'a [u8]) -> Self {
-        Self(BigUint::from_bytes_be(n))
+        Self::from_be_bytes(n)
+    }
+}
+
+impl From<u128> for u256 {
+    #[inline]
+    fn from(n: u128) -> Self {
+        u256([n as u64, (n >> 64) as u64, 0, 0])
     }
 }
 
@@ -119,7 +630,7 @@ macro_rules! uint_impl_from_uint {
         impl From<$T> for u256 {
             #[inline]
             fn from(n: $T) -> Self {
-                u256(BigUint::from(n))
+                u256::from(n as u128)
             }
         }
     };
@@ -131,7 +642,7 @@ macro_rules! uint_impl_from_int {
             #[inline]
             fn from(n: $T) -> Self {
                 if n >= 0 {
-                    u256(BigUint::from(n as u128))
+                    u256::from(n as u128)
                 } else {
                     panic!("attempt to convert negative {} to u256", n);
                 }
@@ -140,12 +651,33 @@ macro_rules! uint_impl_from_int {
     };
 }
 
+impl TryFrom<u256> for u128 {
+    type Error = TryFromIntError;
+
+    fn try_from(n: u256) -> Result<Self, Self::Error> {
+        if n.0[2] != 0 || n.0[3] != 0 {
+            return Err(TryFromIntError(()));
+        }
+        Ok(n.0[0] as u128 | ((n.0[1] as u128) << 64))
+    }
+}
+
+impl TryFrom<u256> for u64 {
+    type Error = TryFromIntError;
+
+    fn try_from(n: u256) -> Result<Self, Self::Error> {
+        if n.0[1..].iter().any(|limb| *limb != 0) {
+            return Err(TryFromIntError(()));
+        }
+        Ok(n.0[0])
+    }
+}
+
 // These implementations are pretty much guaranteed to be panic-free.
 uint_impl_from_uint!(u8);
 uint_impl_from_uint!(u16);
 uint_impl_from_uint!(u32);
 uint_impl_from_uint!(u64);
-uint_impl_from_uint!(u128);
 uint_impl_from_uint!(usize);
 uint_impl_from_int!(i8);
 uint_impl_from_int!(i16);
@@ -154,72 +686,75 @@ uint_impl_from_int!(i64);
 uint_impl_from_int!(i128);
 uint_impl_from_int!(isize);
 
-/// A macro that forwards an unary operator trait i.e. Add
-macro_rules! forward_op {
-    (impl $trait_:ident for $type_:ident { fn $method:ident }) => {
+/// A macro that implements a binary operator trait i.e. Add
+macro_rules! impl_op {
+    (impl $trait_:ident for $type_:ident { fn $method:ident => $overflowing:ident }) => {
         impl $trait_<$type_> for $type_ {
             type Output = $type_;
 
-            fn $method(self, $type_(b): $type_) -> $type_ {
-                let $type_(a) = self;
-                let res = a.$method(&b);
-                if res.bits() > 256 {
+            fn $method(self, b: $type_) -> $type_ {
+                let (res, overflow) = self.$overflowing(b);
+                if overflow {
                     panic!("attempt to {} with overflow", stringify!($method));
                 }
-                $type_(res)
+                res
             }
         }
     };
 }
 
-/// A macro that forwards a checked operator i.e. CheckedAdd
+/// A macro that forwards a checked operator i.e. CheckedAdd to the inherent
+/// method of the same name
 macro_rules! forward_checked_op {
     (impl $trait_:ident for $type_:ident { fn $method:ident }) => {
         impl $trait_ for $type_ {
-            fn $method(&self, $type_(b): &$type_) -> Option<$type_> {
-                let $type_(a) = self;
-                a.$method(&b)
-                    .filter(|value| value.bits() <= 256)
-                    .map($type_)
+            fn $method(&self, b: &$type_) -> Option<$type_> {
+                $type_::$method(*self, *b)
             }
         }
     };
 }
 
-/// A macro that forwards a assignment operator i.e. AddAssign
-macro_rules! forward_assign_op {
-    (impl $trait_:ident for $type_:ident { fn $method:ident }) => {
+/// A macro that implements a assignment operator i.e. AddAssign
+macro_rules! impl_assign_op {
+    (impl $trait_:ident for $type_:ident { fn $method:ident => $overflowing:ident }) => {
         impl $trait_ for $type_ {
-            fn $method(&mut self, $type_(b): $type_) {
-                let a = &mut self.0;
-                a.$method(b);
-                if a.bits() > 256 {
+            fn $method(&mut self, b: $type_) {
+                let (res, overflow) = self.$overflowing(b);
+                if overflow {
                     panic!("attempt to {} with overflow", stringify!($method));
                 }
+                *self = res;
             }
         }
     };
 }
 
-forward_op! { impl Add for u256 { fn add } }
+impl_op! { impl Add for u256 { fn add => overflowing_add } }
 forward_checked_op! { impl CheckedAdd for u256 { fn checked_add } }
-forward_assign_op! { impl AddAssign for u256 { fn add_assign } }
+impl_assign_op! { impl AddAssign for u256 { fn add_assign => overflowing_add } }
 
-forward_op! { impl Sub for u256 { fn sub } }
+impl_op! { impl Sub for u256 { fn sub => overflowing_sub } }
 forward_checked_op! { impl CheckedSub for u256 { fn checked_sub } }
-forward_assign_op! { impl SubAssign for u256 { fn sub_assign } }
+impl_assign_op! { impl SubAssign for u256 { fn sub_assign => overflowing_sub } }
 
-forward_op! { impl Mul for u256 { fn mul } }
+impl_op! { impl Mul for u256 { fn mul => overflowing_mul } }
 forward_checked_op! { impl CheckedMul for u256 { fn checked_mul } }
-forward_assign_op! { impl MulAssign for u256 { fn mul_assign } }
+impl_assign_op! { impl MulAssign for u256 { fn mul_assign => overflowing_mul } }
 
-forward_op! { impl Div for u256 { fn div } }
+impl_op! { impl Div for u256 { fn div => overflowing_div } }
 forward_checked_op! { impl CheckedDiv for u256 { fn checked_div } }
-forward_assign_op! { impl DivAssign for u256 { fn div_assign } }
+impl_assign_op! { impl DivAssign for u256 { fn div_assign => overflowing_div } }
 
+impl_op! { impl Rem for u256 { fn rem => overflowing_rem } }
+impl_assign_op! { impl RemAssign for u256 { fn rem_assign => overflowing_rem } }
+
+/// The SCALE representation is a length byte (the number of bytes following
+/// it plus one) followed by the value in big-endian with leading zero bytes
+/// stripped, hence zero is encoded as a single `0x01`.
 impl scale::Encode for u256 {
     fn size_hint(&self) -> usize {
-        let bits = self.0.bits() as usize;
+        let bits = self.bits() as usize;
         ((bits + 7) >> 3) + 1
     }
 
@@ -229,16 +764,20 @@ impl scale::Encode for u256 {
 
         let mut buf = Vec::with_capacity(size);
         buf.push(size as u8);
-        buf.extend(self.0.to_bytes_be());
+        buf.extend_from_slice(&self.to_be_bytes()[33 - size..]);
         buf
     }
 }
 
 impl scale::Decode for u256 {
     fn decode<I: scale::Input>(value: &mut I) -> Result<Self, scale::Error> {
-        let size = value.read_byte()?;
-        let mut buf = from_elem(0, (size - 1) as usize);
-        value.read(buf.as_mut_slice())?;
+        let size = value.read_byte()? as usize;
+        if size == 0 || size > 33 {
+            return Err("Invalid length of u256".into());
+        }
+
+        let mut buf = [0u8; 32];
+        value.read(&mut buf[33 - size..])?;
         Ok(Self::from_be_bytes(&buf))
     }
 }
@@ -271,6 +810,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn le_bytes() {
+        let val = u256::from(0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10u128);
+        let le = val.to_le_bytes();
+        assert_eq!(
+            le[..16],
+            [16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1]
+        );
+        assert_eq!(le[16..], [0u8; 16]);
+        assert_eq!(u256::from_le_bytes(&le), val);
+        assert_eq!(u256::from_le_bytes(&le[..16]), val);
+        let mut padded = le.to_vec();
+        padded.push(0);
+        assert_eq!(u256::from_le_bytes(&padded), val);
+
+        let mut be = val.to_be_bytes();
+        be.reverse();
+        assert_eq!(be, le);
+    }
+
+    #[test]
+    fn try_into_primitives() {
+        let val = u256::from(u64::MAX);
+        assert_eq!(u64::try_from(val), Ok(u64::MAX));
+        assert_eq!(u128::try_from(val), Ok(u64::MAX as u128));
+
+        let val = val + u256::from(1u8);
+        assert_eq!(u64::try_from(val), Err(TryFromIntError(())));
+        assert_eq!(u128::try_from(val), Ok(u64::MAX as u128 + 1));
+
+        assert_eq!(u128::try_from(u256::from(u128::MAX)), Ok(u128::MAX));
+        assert!(u128::try_from(u256::MAX).is_err());
+    }
+
     #[test]
     fn into_array() {
         let val = u256::from(1024u16);
@@ -297,10 +870,153 @@ mod tests {
         );
     }
 
+    #[test]
+    fn small_divisor_ops() {
+        let val = u256::from(1_000_000_007u64);
+        assert_eq!(val.mul_u64(250).div_u64(10_000), u256::from(25_000_000u64));
+        assert_eq!(val.rem_u64(10_000), 7);
+        assert_eq!(u256::max_value().div_u64(1), u256::max_value());
+        assert_eq!(u256::max_value().rem_u64(u64::MAX), 0);
+        assert_eq!(u256::zero().mul_u64(u64::MAX), u256::zero());
+    }
+
+    #[test]
+    #[should_panic]
+    fn mul_u64_overflow() {
+        let _ = u256::max_value().mul_u64(2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_u64_by_zero() {
+        let _ = u256::from(1u8).div_u64(0);
+    }
+
     #[test]
     #[should_panic]
     fn from_negative() {
         let val: u256 = u256::from(-1);
         assert_eq!(val, 1.into())
     }
+
+    #[test]
+    fn zero_codec() {
+        let encoded = scale::Encode::encode(&u256::zero());
+        assert_eq!(encoded, vec![1]);
+        let decoded: u256 = scale::Decode::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded, u256::zero());
+
+        let max = u256::max_value();
+        let encoded = scale::Encode::encode(&max);
+        assert_eq!(encoded.len(), 33);
+        let decoded: u256 = scale::Decode::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded, max);
+
+        assert!(<u256 as scale::Decode>::decode(&mut [0u8].as_ref()).is_err());
+        assert!(<u256 as scale::Decode>::decode(&mut [34u8].as_ref()).is_err());
+    }
+
+    #[test]
+    fn ordering() {
+        let small = u256::from(u64::MAX);
+        let big: u256 = "0x10000000000000000".parse().unwrap();
+        assert!(small < big);
+        assert!(u256::max_value() > big);
+        assert_eq!(big.bits(), 65);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a: u256 = "0xfedcba9876543210fedcba9876543210fedcba98"
+            .parse()
+            .unwrap();
+        let b: u256 = "0x123456789abcdef01".parse().unwrap();
+        let (q, r) = (a / b, a - a / b * b);
+        assert!(r < b);
+        assert_eq!(q * b + r, a);
+        assert_eq!(a * b / b, a);
+        assert_eq!(u256::max_value() / u256::max_value(), u256::one());
+        assert_eq!(
+            "340282366920938463463374607431768211456"
+                .parse::<u256>()
+                .unwrap(),
+            u256::from(u128::MAX) + u256::one()
+        );
+        assert_eq!(
+            CheckedAdd::checked_add(&u256::max_value(), &u256::one()),
+            None
+        );
+        assert_eq!(CheckedSub::checked_sub(&u256::zero(), &u256::one()), None);
+        assert_eq!(
+            CheckedMul::checked_mul(&u256::max_value(), &u256::from(2u8)),
+            None
+        );
+        assert_eq!(CheckedDiv::checked_div(&u256::one(), &u256::zero()), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("".parse::<u256>(), Err(ParseIntError::Empty));
+        assert_eq!("12a".parse::<u256>(), Err(ParseIntError::InvalidDigit));
+        assert_eq!(
+            format!("0x1{}", "0".repeat(64)).parse::<u256>(),
+            Err(ParseIntError::Overflow)
+        );
+        assert_eq!(u256::max_value().to_str_radix(16), "f".repeat(64));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to sub with overflow")]
+    fn sub_overflow() {
+        let _ = u256::zero() - u256::one();
+    }
+
+    #[test]
+    fn checked_saturating_wrapping() {
+        let max = u256::max_value();
+        let one = u256::one();
+        let two = u256::from(2u8);
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(two.checked_sub(one), Some(one));
+        assert_eq!(one.checked_rem(u256::zero()), None);
+        assert_eq!(u256::from(7u8).checked_rem(two), Some(one));
+        assert_eq!(max.saturating_add(one), max);
+        assert_eq!(one.saturating_sub(two), u256::zero());
+        assert_eq!(max.saturating_mul(two), max);
+        assert_eq!(max.wrapping_add(two), one);
+        assert_eq!(u256::zero().wrapping_sub(one), max);
+        assert_eq!(max.wrapping_mul(max), one);
+        assert_eq!(max.overflowing_mul(two), (max - one, true));
+    }
+
+    #[test]
+    fn pow_sqrt_log2() {
+        let two = u256::from(2u8);
+        assert_eq!(two.pow(255).log2(), 255);
+        assert_eq!(two.checked_pow(256), None);
+        assert_eq!(two.saturating_pow(256), u256::max_value());
+        assert_eq!(two.wrapping_pow(256), u256::zero());
+        assert_eq!(u256::from(10u8).pow(0), u256::one());
+        assert_eq!(u256::zero().pow(0), u256::one());
+
+        assert_eq!(u256::zero().sqrt(), u256::zero());
+        assert_eq!(u256::from(15u8).sqrt(), u256::from(3u8));
+        assert_eq!(u256::from(16u8).sqrt(), u256::from(4u8));
+        assert_eq!(u256::max_value().sqrt(), u256::from(u128::MAX),);
+        let big = u256::from(u128::MAX - 4);
+        assert_eq!((big * big).sqrt(), big);
+        assert_eq!((big * big - u256::one()).sqrt(), big - u256::one());
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to pow with overflow")]
+    fn pow_overflow() {
+        let _ = u256::from(2u8).pow(256);
+    }
+
+    #[test]
+    #[should_panic(expected = "argument of integer logarithm must be positive")]
+    fn log2_of_zero() {
+        let _ = u256::zero().log2();
+    }
 }
@@ -0,0 +1,83 @@
+#!/usr/bin/env python3
+"""Checks that a Wasm module only imports host functions listed in a file.
+
+Usage: check_imports.py <allowed imports> <wasm module>...
+"""
+
+import sys
+
+
+def read_leb128(data, pos):
+    result, shift = 0, 0
+    while True:
+        byte = data[pos]
+        pos += 1
+        result |= (byte & 0x7F) << shift
+        shift += 7
+        if byte & 0x80 == 0:
+            return result, pos
+
+
+def read_name(data, pos):
+    length, pos = read_leb128(data, pos)
+    return data[pos:pos + length].decode("utf-8"), pos + length
+
+
+def skip_limits(data, pos):
+    flags = data[pos]
+    _, pos = read_leb128(data, pos + 1)
+    if flags & 1:
+        _, pos = read_leb128(data, pos)
+    return pos
+
+
+def read_imports(data):
+    if data[:4] != b"\0asm":
+        raise ValueError("not a Wasm module")
+    imports = []
+    pos = 8
+    while pos < len(data):
+        section_id = data[pos]
+        size, pos = read_leb128(data, pos + 1)
+        end = pos + size
+        if section_id == 2:
+            count, pos = read_leb128(data, pos)
+            for _ in range(count):
+                module, pos = read_name(data, pos)
+                field, pos = read_name(data, pos)
+                kind = data[pos]
+                pos += 1
+                if kind == 0:
+                    _, pos = read_leb128(data, pos)
+                elif kind == 1:
+                    pos = skip_limits(data, pos + 1)
+                elif kind == 2:
+                    pos = skip_limits(data, pos)
+                else:
+                    pos += 2
+                imports.append("{}.{}".format(module, field))
+        pos = end
+    return imports
+
+
+def main():
+    with open(sys.argv[1]) as f:
+        allowed = {
+            line.strip()
+            for line in f
+            if line.strip() and not line.startswith("#")
+        }
+    ok = True
+    for path in sys.argv[2:]:
+        with open(path, "rb") as f:
+            imports = read_imports(f.read())
+        unexpected = [name for name in imports if name not in allowed]
+        if unexpected:
+            ok = False
+            print("{} imports unexpected host functions: {}".format(
+                path, ", ".join(unexpected)))
+    sys.exit(0 if ok else 1)
+
+
+if __name__ == "__main__":
+    main()
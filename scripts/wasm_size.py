@@ -0,0 +1,36 @@
+#!/usr/bin/env python3
+"""Prints the size of a Wasm module without its custom sections.
+
+Custom sections such as `name` only carry debugging information, which is
+larger than the code of most contracts and varies with the compiler.
+
+Usage: wasm_size.py <wasm module>
+"""
+
+import sys
+
+from check_imports import read_leb128
+
+
+def code_size(data):
+    if data[:4] != b"\0asm":
+        raise ValueError("not a Wasm module")
+    total = 8
+    pos = 8
+    while pos < len(data):
+        section_id = data[pos]
+        size, start = read_leb128(data, pos + 1)
+        end = start + size
+        if section_id != 0:
+            total += end - pos
+        pos = end
+    return total
+
+
+def main():
+    with open(sys.argv[1], "rb") as f:
+        print(code_size(f.read()))
+
+
+if __name__ == "__main__":
+    main()
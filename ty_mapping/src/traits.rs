@@ -11,7 +11,7 @@
 // limitations under the License.
 
 use liquid_macro::seq;
-use liquid_prelude::{string::String, vec::Vec};
+use liquid_prelude::{collections::BTreeMap, string::String, vec::Vec};
 use liquid_primitives::types::*;
 
 pub const MAX_LENGTH_OF_MAPPED_TYPE_NAME: usize = 256;
@@ -207,6 +207,47 @@ where
         append_static_array_suffix::<T>(N);
 }
 
+/// `Option<T>` is represented as a tagged tuple `(bool,T)`, in which the
+/// leading flag tells whether the value is present.
+impl<T> MappingToSolidityType for Option<T>
+where
+    T: MappingToSolidityType,
+{
+    const MAPPED_TYPE_NAME: [u8; MAX_LENGTH_OF_MAPPED_TYPE_NAME] =
+        composite::<(bool, T), MAX_LENGTH_OF_MAPPED_TYPE_NAME>(&[]);
+}
+
+/// `Result<T, E>` is represented as a tagged tuple `(bool,T,E)`, in which
+/// the leading flag tells whether the result is `Ok`.
+impl<T, E> MappingToSolidityType for Result<T, E>
+where
+    T: MappingToSolidityType,
+    E: MappingToSolidityType,
+{
+    const MAPPED_TYPE_NAME: [u8; MAX_LENGTH_OF_MAPPED_TYPE_NAME] =
+        composite::<(bool, T, E), MAX_LENGTH_OF_MAPPED_TYPE_NAME>(&[]);
+}
+
+/// `BTreeMap<K, V>` is represented as a dynamic array of key-value pairs,
+/// i.e., `(K,V)[]`.
+impl<K, V> MappingToSolidityType for BTreeMap<K, V>
+where
+    K: MappingToSolidityType,
+    V: MappingToSolidityType,
+{
+    const MAPPED_TYPE_NAME: [u8; MAX_LENGTH_OF_MAPPED_TYPE_NAME] = {
+        let mut ret = composite::<(K, V), MAX_LENGTH_OF_MAPPED_TYPE_NAME>(&[]);
+        let mut i = 0;
+        while ret[i] != 0 {
+            i += 1;
+        }
+
+        ret[i] = b'[';
+        ret[i + 1] = b']';
+        ret
+    };
+}
+
 macro_rules! impl_type_mapping_for_tuples {
     ($first:tt,) => {
         impl<$first> MappingToSolidityType for ($first,)
@@ -353,6 +394,35 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_option_and_result() {
+        assert_eq!(map_to_solidity_type::<Option<u8>>(), "(bool,uint8)");
+        assert_eq!(
+            map_to_solidity_type::<Vec<Option<String>>>(),
+            "(bool,string)[]"
+        );
+        assert_eq!(
+            map_to_solidity_type::<Result<u32, String>>(),
+            "(bool,uint32,string)"
+        );
+        assert_eq!(
+            map_to_solidity_type::<(Option<Address>, Result<Vec<u8>, bool>)>(),
+            "(bool,address),(bool,uint8[],bool)"
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        assert_eq!(
+            map_to_solidity_type::<BTreeMap<Address, u64>>(),
+            "(address,uint64)[]"
+        );
+        assert_eq!(
+            map_to_solidity_type::<BTreeMap<String, Vec<u8>>>(),
+            "(string,uint8[])[]"
+        );
+    }
+
     #[test]
     fn test_len() {
         assert_eq!(len::<(u8, String)>(), 12);